@@ -5,8 +5,8 @@
 //! This pattern is very useful when a single component can handle many types of input that can
 //! be represented by an enum.
 //!
-//! Currently we don't have a reducer pattern hook. If you'd like to add it,
-//! feel free to make a PR.
+//! `use_reducer` runs every action through one function that turns the old state into the new one, so all the ways
+//! the todo list can change are in one place.
 
 use dioxus::prelude::*;
 
@@ -15,46 +15,72 @@ fn main() {
 }
 
 fn app(cx: Scope) -> Element {
-    let state = use_state(cx, PlayerState::new);
+    let draft = use_state(cx, String::new);
+    let (todos, dispatch) = use_reducer(cx, TodoList::default, TodoList::reduce);
 
     cx.render(rsx!(
         div {
-            h1 {"Select an option"}
-            h3 { "The radio is... ", state.is_playing(), "!" }
-            button { onclick: move |_| state.make_mut().reduce(PlayerAction::Pause),
-                "Pause"
+            h1 { "Todos" }
+            input {
+                value: "{draft}",
+                oninput: move |evt| draft.set(evt.value.clone()),
             }
-            button { onclick: move |_| state.make_mut().reduce(PlayerAction::Play),
-                "Play"
+            button {
+                onclick: move |_| {
+                    dispatch(TodoAction::Add(draft.get().clone()));
+                    draft.set(String::new());
+                },
+                "Add"
+            }
+            button { onclick: move |_| dispatch(TodoAction::ClearCompleted), "Clear completed" }
+            ul {
+                todos.items.iter().enumerate().map(|(idx, todo)| rsx! {
+                    li { key: "{idx}",
+                        input {
+                            r#type: "checkbox",
+                            checked: "{todo.done}",
+                            onclick: move |_| dispatch(TodoAction::Toggle(idx)),
+                        }
+                        "{todo.text}"
+                    }
+                })
             }
         }
     ))
 }
 
-enum PlayerAction {
-    Pause,
-    Play,
+enum TodoAction {
+    Add(String),
+    Toggle(usize),
+    ClearCompleted,
 }
 
 #[derive(Clone)]
-struct PlayerState {
-    is_playing: bool,
+struct Todo {
+    text: String,
+    done: bool,
 }
 
-impl PlayerState {
-    fn new() -> Self {
-        Self { is_playing: false }
-    }
-    fn reduce(&mut self, action: PlayerAction) {
+#[derive(Clone, Default)]
+struct TodoList {
+    items: Vec<Todo>,
+}
+
+impl TodoList {
+    fn reduce(&self, action: TodoAction) -> Self {
+        let mut next = self.clone();
         match action {
-            PlayerAction::Pause => self.is_playing = false,
-            PlayerAction::Play => self.is_playing = true,
-        }
-    }
-    fn is_playing(&self) -> &'static str {
-        match self.is_playing {
-            true => "currently playing!",
-            false => "not currently playing",
+            TodoAction::Add(text) if !text.is_empty() => {
+                next.items.push(Todo { text, done: false })
+            }
+            TodoAction::Add(_) => {}
+            TodoAction::Toggle(idx) => {
+                if let Some(todo) = next.items.get_mut(idx) {
+                    todo.done = !todo.done;
+                }
+            }
+            TodoAction::ClearCompleted => next.items.retain(|todo| !todo.done),
         }
+        next
     }
 }
@@ -214,7 +214,7 @@ impl Writer<'_> {
             ElementAttr::AttrText { name, value } => {
                 write!(self.out, "{name}: {value}", value = ifmt_to_string(value))?;
             }
-            ElementAttr::AttrExpression { name, value } => {
+            ElementAttr::AttrExpression { name, value } | ElementAttr::Bind { name, value } => {
                 let out = prettyplease::unparse_expr(value);
                 let mut lines = out.split('\n').peekable();
                 let first = lines.next().unwrap();
@@ -252,18 +252,24 @@ impl Writer<'_> {
                 write!(self.out, "{}: {}", name.to_token_stream(), out)?;
             }
 
-            ElementAttr::EventTokens { name, tokens } => {
+            ElementAttr::EventTokens {
+                name,
+                modifiers,
+                tokens,
+            } => {
                 let out = self.retrieve_formatted_expr(tokens).to_string();
 
                 let mut lines = out.split('\n').peekable();
                 let first = lines.next().unwrap();
 
+                let modifiers: String = modifiers.iter().map(|m| format!("{m} ")).collect();
+
                 // a one-liner for whatever reason
                 // Does not need a new line
                 if lines.peek().is_none() {
-                    write!(self.out, "{name}: {first}")?;
+                    write!(self.out, "{name}: {modifiers}{first}")?;
                 } else {
-                    writeln!(self.out, "{name}: {first}")?;
+                    writeln!(self.out, "{name}: {modifiers}{first}")?;
 
                     while let Some(line) = lines.next() {
                         self.out.indented_tab()?;
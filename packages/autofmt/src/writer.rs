@@ -150,7 +150,7 @@ impl<'a> Writer<'a> {
                 ElementAttr::AttrText { value, name } => {
                     ifmt_to_string(value).len() + name.span().line_length() + 6
                 }
-                ElementAttr::AttrExpression { name, value } => {
+                ElementAttr::AttrExpression { name, value } | ElementAttr::Bind { name, value } => {
                     value.span().line_length() + name.span().line_length() + 6
                 }
                 ElementAttr::CustomAttrText { value, name } => {
@@ -159,7 +159,11 @@ impl<'a> Writer<'a> {
                 ElementAttr::CustomAttrExpression { name, value } => {
                     name.to_token_stream().to_string().len() + value.span().line_length() + 6
                 }
-                ElementAttr::EventTokens { tokens, name } => {
+                ElementAttr::EventTokens {
+                    tokens,
+                    name,
+                    modifiers,
+                } => {
                     let location = Location::new(tokens.span().start());
 
                     let len = if let std::collections::hash_map::Entry::Vacant(e) =
@@ -177,7 +181,9 @@ impl<'a> Writer<'a> {
                         self.cached_formats[&location].len()
                     };
 
-                    len + name.span().line_length() + 6
+                    let modifiers: usize = modifiers.iter().map(|m| m.to_string().len() + 1).sum();
+
+                    len + modifiers + name.span().line_length() + 6
                 }
             };
         }
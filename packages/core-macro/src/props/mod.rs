@@ -617,7 +617,7 @@ Finally, call `.build()` to create the instance of `{name}`.
                     .extend(predicates.predicates.clone());
             }
 
-            let can_memoize = match are_there_generics {
+            let can_memoize = match are_there_generics || self.builder_attr.no_memo {
                 true => quote! { false  },
                 false => quote! { self == other },
             };
@@ -1079,6 +1079,9 @@ Finally, call `.build()` to create the instance of `{name}`.
         /// specify `doc` instead and a default value will be filled in here.
         pub build_method_doc: Option<syn::Expr>,
 
+        /// Render the component every time its parent renders, even if the props are equal.
+        pub no_memo: bool,
+
         pub field_defaults: FieldBuilderAttr,
     }
 
@@ -1086,7 +1089,10 @@ Finally, call `.build()` to create the instance of `{name}`.
         pub fn new(attrs: &[syn::Attribute]) -> Result<TypeBuilderAttr, Error> {
             let mut result = TypeBuilderAttr::default();
             for attr in attrs {
-                if path_to_single_string(attr.path()).as_deref() != Some("builder") {
+                if !matches!(
+                    path_to_single_string(attr.path()).as_deref(),
+                    Some("builder" | "props")
+                ) {
                     continue;
                 }
 
@@ -1147,6 +1153,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                             self.doc = true;
                             Ok(())
                         }
+                        "no_memo" => {
+                            self.no_memo = true;
+                            Ok(())
+                        }
                         _ => Err(Error::new_spanned(
                             &path,
                             format!("Unknown parameter {name:?}"),
@@ -2,4 +2,9 @@
 fn rsx() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/rsx/trailing-comma-0.rs");
+    t.compile_fail("tests/rsx/inner-html-children.rs");
+    t.compile_fail("tests/rsx/passive-prevent-default.rs");
+    t.compile_fail("tests/rsx/bind-unsupported-element.rs");
+    t.compile_fail("tests/rsx/bind-twice.rs");
+    t.compile_fail("tests/rsx/bind-conflicting-attribute.rs");
 }
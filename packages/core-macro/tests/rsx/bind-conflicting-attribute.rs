@@ -0,0 +1,13 @@
+// Given an element that sets the attribute its binding controls,
+// ensure the error points at that attribute.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        input {
+            bind_value: text,
+            value: "hello",
+        }
+    };
+}
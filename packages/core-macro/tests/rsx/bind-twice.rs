@@ -0,0 +1,14 @@
+// Given an element with two bindings,
+// ensure the error points at the second binding.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        input {
+            r#type: "checkbox",
+            bind_value: text,
+            bind_checked: done,
+        }
+    };
+}
@@ -0,0 +1,12 @@
+// Given a binding on an element that doesn't support it,
+// ensure the error points at the binding.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        div {
+            bind_value: text,
+        }
+    };
+}
@@ -0,0 +1,13 @@
+// Given an element with both `dangerous_inner_html` and children,
+// ensure the error points at the attribute.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        div {
+            dangerous_inner_html: "<p>markdown</p>",
+            "Hello world"
+        }
+    };
+}
@@ -0,0 +1,12 @@
+// Given a listener that is both `passive` and `prevent_default`,
+// ensure the error points at the `passive` modifier.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        div {
+            ontouchmove: prevent_default passive move |_| {},
+        }
+    };
+}
@@ -0,0 +1,118 @@
+//! Screen reader announcements through a live region managed by the VirtualDom
+//!
+//! The live region is a visually hidden `aria-live` element appended to the root of the app the first time something
+//! is announced. Announcing a message sets the text of the region, which screen readers read out. The region is
+//! created before its text is set, because screen readers only announce changes to live regions that already exist.
+
+use crate::{
+    innerlude::{Mutation, SchedulerMsg},
+    ElementId, Template, TemplateAttribute, TemplateNode, VirtualDom,
+};
+
+/// Hides the live region from sight, but not from screen readers
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;";
+
+static LIVE_REGION: Template<'static> = Template {
+    name: "dioxus-core:live-region:0",
+    roots: &[TemplateNode::Element {
+        tag: "div",
+        namespace: None,
+        attrs: &[
+            TemplateAttribute::Static {
+                name: "aria-live",
+                value: "polite",
+                namespace: None,
+            },
+            TemplateAttribute::Static {
+                name: "aria-atomic",
+                value: "true",
+                namespace: None,
+            },
+            TemplateAttribute::Static {
+                name: "style",
+                value: VISUALLY_HIDDEN,
+                namespace: None,
+            },
+        ],
+        children: &[TemplateNode::DynamicText { id: 0 }],
+    }],
+    node_paths: &[&[0, 0]],
+    attr_paths: &[],
+};
+
+/// Announces messages to screen readers, returned by [`crate::ScopeState::use_announcer`]
+///
+/// ```rust, ignore
+/// let announcer = cx.use_announcer();
+///
+/// render! {
+///     button { onclick: move |_| announcer.announce("Saved"), "Save" }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Announcer {
+    pub(crate) sender: futures_channel::mpsc::UnboundedSender<SchedulerMsg>,
+}
+
+impl Announcer {
+    /// Announce a message in the live region
+    ///
+    /// The message is set in the next render. If several messages are announced before then, only the last one is.
+    pub fn announce(&self, message: impl Into<String>) {
+        _ = self
+            .sender
+            .unbounded_send(SchedulerMsg::Announce(message.into()));
+    }
+}
+
+/// The live region once it has been created, and the message it shows
+pub(crate) struct LiveRegion {
+    text: ElementId,
+    message: String,
+}
+
+impl LiveRegion {
+    /// The message the region shows
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl VirtualDom {
+    /// Store the latest announcement in the live region, creating the region if it doesn't exist yet
+    ///
+    /// Returns the id of the region's text if there was an announcement. The text is set by [`VirtualDom::finalize`],
+    /// where the mutations can borrow the message from the VirtualDom.
+    pub(crate) fn flush_announcement(&mut self) -> Option<ElementId> {
+        let message = self.pending_announcement.take()?;
+
+        if self.live_region.is_none() {
+            self.register_template(LIVE_REGION);
+
+            let region = self.next_null();
+            let text = self.next_null();
+            self.mutations.push(Mutation::LoadTemplate {
+                name: LIVE_REGION.name,
+                index: 0,
+                id: region,
+            });
+            self.mutations.push(Mutation::HydrateText {
+                path: &[0],
+                value: "",
+                id: text,
+            });
+            self.mutations.push(Mutation::AppendChildren {
+                id: ElementId(0),
+                m: 1,
+            });
+            self.live_region = Some(LiveRegion {
+                text,
+                message: String::new(),
+            });
+        }
+
+        let region = self.live_region.as_mut().unwrap();
+        region.message = message;
+        Some(region.text)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    innerlude::Scoped,
+    innerlude::{Scoped, TaskPanic},
     nodes::RenderReturn,
     scopes::{Scope, ScopeState},
     Element,
@@ -67,8 +67,15 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
             Ok(Some(e)) => RenderReturn::Ready(e),
             Ok(None) => RenderReturn::default(),
             Err(err) => {
+                let panic = TaskPanic::new(err.as_ref());
                 let component_name = cx.name();
-                log::error!("Error while rendering component `{component_name}`: {err:?}");
+                log::error!(
+                    "Error while rendering component `{component_name}`: {}",
+                    panic.message
+                );
+
+                // The nearest boundary renders its fallback in place of the component
+                cx.throw(panic);
                 RenderReturn::default()
             }
         }
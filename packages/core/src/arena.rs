@@ -105,17 +105,33 @@ impl VirtualDom {
             }
         }
 
+        #[cfg(feature = "diff-stats")]
+        self.render_stats.remove(&id);
+
+        // A scope that is unmounted while suspended will never resolve
+        self.suspended_scopes.remove(&id);
+
         let scope = &mut self.scopes[id.0];
 
         // Drop all the hooks once the children are dropped
-        // this means we'll drop hooks bottom-up
-        scope.hooks.get_mut().clear();
+        // this means we'll drop hooks bottom-up, and in reverse within a scope since later hooks may use earlier ones
+        let hooks = scope.hooks.get_mut();
+        while let Some(hook) = hooks.pop() {
+            drop(hook);
+        }
 
         // Drop all the futures once the hooks are dropped
         for task_id in scope.spawned_tasks.borrow_mut().drain() {
             scope.tasks.remove(task_id);
         }
 
+        // Layout effects of an unmounted scope should never run
+        scope
+            .tasks
+            .layout_effects
+            .borrow_mut()
+            .retain(|(scope, _)| *scope != id);
+
         self.scopes.remove(id.0);
     }
 
@@ -159,7 +175,7 @@ impl VirtualDom {
         listeners.drain(..).for_each(|listener| {
             let listener = unsafe { &*listener };
             match &listener.value {
-                AttributeValue::Listener(l) => {
+                AttributeValue::Listener(l, _) => {
                     _ = l.take();
                 }
                 AttributeValue::Any(a) => {
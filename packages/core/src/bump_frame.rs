@@ -1,10 +1,13 @@
 use crate::nodes::RenderReturn;
 use bumpalo::Bump;
-use std::cell::{Cell, UnsafeCell};
+use std::cell::{Cell, RefCell, UnsafeCell};
 
 pub(crate) struct BumpFrame {
     pub bump: UnsafeCell<Bump>,
     pub node: Cell<*const RenderReturn<'static>>,
+
+    // Owned text that nodes in the bump borrow. The bump doesn't run destructors, so the frame drops them when it resets
+    pub strings: RefCell<Vec<String>>,
 }
 
 impl BumpFrame {
@@ -13,6 +16,7 @@ impl BumpFrame {
         Self {
             bump: UnsafeCell::new(bump),
             node: Cell::new(std::ptr::null()),
+            strings: Default::default(),
         }
     }
 
@@ -31,8 +35,20 @@ impl BumpFrame {
         unsafe { &*self.bump.get() }
     }
 
-    #[allow(clippy::mut_from_ref)]
-    pub(crate) unsafe fn bump_mut(&self) -> &mut Bump {
-        unsafe { &mut *self.bump.get() }
+    /// Keep a String alive until this frame is reset, and borrow it for as long as the nodes in the bump
+    pub(crate) fn keep_string(&self, string: String) -> &str {
+        let mut strings = self.strings.borrow_mut();
+        strings.push(string);
+
+        // The text is on the heap, so it doesn't move when the list grows, and it isn't dropped until the frame resets
+        unsafe { &*(strings.last().unwrap().as_str() as *const str) }
+    }
+
+    /// Free everything allocated for the last render of this frame
+    ///
+    /// Any references into the frame are invalid after this.
+    pub(crate) unsafe fn reset(&self) {
+        unsafe { &mut *self.bump.get() }.reset();
+        self.strings.borrow_mut().clear();
     }
 }
@@ -0,0 +1,176 @@
+//! A compact encoding for templates without any dynamic parts
+//!
+//! `rsx!` encodes fully static templates into a string of bytes at compile time instead of emitting a tree of
+//! [`TemplateNode`]s. The bytes are a fraction of the size of the tree, which adds up in static-heavy apps. They are
+//! decoded into a [`Template`] the first time the template is rendered, and renderers load and clone that template like
+//! any other.
+//!
+//! The bytes are a list of parts, each starting with a marker:
+//! - [`ELEMENT`] `tag` [`SEPARATOR`] `namespace` opens an element
+//! - [`ATTRIBUTE`] `name` [`SEPARATOR`] `namespace` [`SEPARATOR`] `value` adds a static attribute to the open element
+//! - [`TEXT`] `text` adds a text node
+//! - [`CLOSE`] closes the open element
+//!
+//! Missing namespaces are left empty. Text and attribute values can't contain the markers, so `rsx!` falls back to a
+//! regular template for those.
+
+use crate::{Template, TemplateAttribute, TemplateNode};
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+
+/// Opens an element
+pub const ELEMENT: char = '\u{1}';
+
+/// Separates the fields of an element or attribute
+pub const SEPARATOR: char = '\u{2}';
+
+/// Adds a static attribute to the open element
+pub const ATTRIBUTE: char = '\u{3}';
+
+/// Adds a text node
+pub const TEXT: char = '\u{4}';
+
+/// Closes the open element
+pub const CLOSE: char = '\u{5}';
+
+/// The length of the compact template made of `parts`
+///
+/// This is used by `rsx!` to size the bytes of [`compact_template`] at compile time.
+pub const fn compact_template_len(parts: &[&str]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        len += parts[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Join the `parts` of a compact template into its bytes at compile time
+pub const fn compact_template<const N: usize>(parts: &[&str]) -> [u8; N] {
+    let mut bytes = [0; N];
+    let mut len = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let part = parts[i].as_bytes();
+        let mut j = 0;
+        while j < part.len() {
+            bytes[len] = part[j];
+            len += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    bytes
+}
+
+/// The namespace part of a compact template, which is empty if there is no namespace
+pub const fn compact_namespace(namespace: Option<&'static str>) -> &'static str {
+    match namespace {
+        Some(namespace) => namespace,
+        None => "",
+    }
+}
+
+thread_local! {
+    // Decoded templates are leaked, so they are only decoded once per thread
+    static DECODED: RefCell<FxHashMap<&'static str, Template<'static>>> = RefCell::new(FxHashMap::default());
+}
+
+impl Template<'static> {
+    /// Get the template encoded in the bytes of a [`compact_template`]
+    ///
+    /// The template is decoded the first time this is called for its name, and reused after that.
+    pub fn from_compact(name: &'static str, bytes: &'static [u8]) -> Self {
+        DECODED.with(|decoded| {
+            *decoded
+                .borrow_mut()
+                .entry(name)
+                .or_insert_with(|| Template {
+                    name,
+                    roots: decode(bytes),
+                    node_paths: &[],
+                    attr_paths: &[],
+                })
+        })
+    }
+}
+
+struct OpenElement {
+    tag: &'static str,
+    namespace: Option<&'static str>,
+    attrs: Vec<TemplateAttribute<'static>>,
+    children: Vec<TemplateNode<'static>>,
+}
+
+fn decode(bytes: &'static [u8]) -> &'static [TemplateNode<'static>] {
+    let source = std::str::from_utf8(bytes).expect("compact templates are joined from strings");
+
+    let mut roots = Vec::new();
+    let mut open: Vec<OpenElement> = Vec::new();
+
+    let markers = [ELEMENT, ATTRIBUTE, TEXT, CLOSE];
+    let mut rest = source;
+    while let Some(marker) = rest.chars().next() {
+        let part = &rest[marker.len_utf8()..];
+        let end = part.find(&markers[..]).unwrap_or(part.len());
+        let (part, next) = part.split_at(end);
+        rest = next;
+
+        match marker {
+            ELEMENT => {
+                let (tag, namespace) = part.split_once(SEPARATOR).unwrap_or((part, ""));
+                open.push(OpenElement {
+                    tag,
+                    namespace: namespace_of(namespace),
+                    attrs: Vec::new(),
+                    children: Vec::new(),
+                });
+            }
+            ATTRIBUTE => {
+                let mut fields = part.splitn(3, SEPARATOR);
+                let name = fields.next().unwrap_or_default();
+                let namespace = fields.next().unwrap_or_default();
+                let value = fields.next().unwrap_or_default();
+                if let Some(element) = open.last_mut() {
+                    element.attrs.push(TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace: namespace_of(namespace),
+                    });
+                }
+            }
+            TEXT => {
+                let text = TemplateNode::Text { text: part };
+                match open.last_mut() {
+                    Some(element) => element.children.push(text),
+                    None => roots.push(text),
+                }
+            }
+            CLOSE => {
+                if let Some(element) = open.pop() {
+                    let element = TemplateNode::Element {
+                        tag: element.tag,
+                        namespace: element.namespace,
+                        attrs: Box::leak(element.attrs.into_boxed_slice()),
+                        children: Box::leak(element.children.into_boxed_slice()),
+                    };
+                    match open.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => roots.push(element),
+                    }
+                }
+            }
+            _ => panic!("{:?} is not a compact template", source),
+        }
+    }
+
+    Box::leak(roots.into_boxed_slice())
+}
+
+fn namespace_of(namespace: &'static str) -> Option<&'static str> {
+    match namespace {
+        "" => None,
+        namespace => Some(namespace),
+    }
+}
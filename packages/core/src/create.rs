@@ -165,6 +165,44 @@ impl<'b> VirtualDom {
             .sum()
     }
 
+    /// Create a single element root of an already created template and write its mutations
+    ///
+    /// Anything that was mounted under the root needs to be removed first. Returns the new ID of the root, which is left
+    /// on the stack.
+    pub(crate) fn create_template_root(
+        &mut self,
+        node: &'b VNode<'b>,
+        root_idx: usize,
+    ) -> ElementId {
+        let template = node.template.get();
+        let on_root = |path: &[u8]| path.first().copied() == Some(root_idx as u8);
+
+        #[cfg(not(debug_assertions))]
+        let (attrs_sorted, nodes_sorted): (Vec<_>, Vec<_>) = (
+            template.attr_paths.iter().copied().enumerate().collect(),
+            template.node_paths.iter().copied().enumerate().collect(),
+        );
+        #[cfg(debug_assertions)]
+        let (attrs_sorted, nodes_sorted) =
+            (sort_bfs(template.attr_paths), sort_bfs(template.node_paths));
+
+        let mut attrs = attrs_sorted
+            .into_iter()
+            .filter(|(_, path)| on_root(path))
+            .peekable();
+        let mut nodes = nodes_sorted
+            .iter()
+            .copied()
+            .enumerate()
+            .map(|(i, (id, path))| ((id, i), path))
+            .filter(|(_, path)| on_root(path))
+            .peekable();
+
+        self.write_element_root(node, root_idx, &mut attrs, &mut nodes, &nodes_sorted);
+
+        node.root_ids.borrow()[root_idx]
+    }
+
     fn write_static_text_root(&mut self, node: &VNode, idx: usize) -> usize {
         // Simply just load the template root, no modifications needed
         self.load_template_root(node, idx);
@@ -184,7 +222,7 @@ impl<'b> VirtualDom {
                 self.mutations.push(CreatePlaceholder { id });
                 1
             }
-            Text(VText { id, value }) => {
+            Text(VText { id, value, .. }) => {
                 let id = self.set_slot(template, id, idx);
                 self.create_static_text(value, id);
                 1
@@ -302,11 +340,12 @@ impl<'b> VirtualDom {
         let unbounded_name: &str = unsafe { std::mem::transmute(attribute.name) };
 
         match &attribute.value {
-            AttributeValue::Listener(_) => {
+            AttributeValue::Listener(_, flags) => {
                 self.mutations.push(NewEventListener {
                     // all listeners start with "on"
                     name: &unbounded_name[2..],
                     id,
+                    flags: *flags,
                 })
             }
             _ => {
@@ -369,7 +408,9 @@ impl<'b> VirtualDom {
     }
 
     /// Insert a new template into the VirtualDom's template registry
-    pub(crate) fn register_template_first_byte_index(&mut self, mut template: Template<'static>) {
+    pub(crate) fn register_template_first_byte_index(&mut self, template: Template<'static>) {
+        let mut template = template.cascade_namespaces();
+
         // First, make sure we mark the template as seen, regardless if we process it
         let (path, _) = template.name.rsplit_once(':').unwrap();
         if let Some((_, old_template)) = self
@@ -421,6 +462,7 @@ impl<'b> VirtualDom {
                 template = new_template;
             }
 
+            let template = template.cascade_namespaces();
             self.templates
                 .entry(path)
                 .or_default()
@@ -443,11 +485,40 @@ impl<'b> VirtualDom {
         match node {
             Text(text) => self.create_dynamic_text(template, text, idx),
             Placeholder(place) => self.create_placeholder(place, template, idx),
-            Component(component) => self.create_component_node(template, component),
-            Fragment(frag) => frag.iter().map(|child| self.create(child)).sum(),
+            Component(component) => {
+                let parent = self.parent_of_dynamic_node(template, idx);
+                self.create_component_node(template, component, parent)
+            }
+            Fragment(frag) => {
+                let parent = self.parent_of_dynamic_node(template, idx);
+                frag.iter()
+                    .map(|child| {
+                        child.parent.set(parent);
+                        self.create(child)
+                    })
+                    .sum()
+            }
         }
     }
 
+    /// Find the element that events bubble to from the nodes rendered in a dynamic node of this template
+    ///
+    /// This is the deepest element around the dynamic node that has an id, which every element with a listener has.
+    /// If there isn't one in this template, events go on to the parent of the template.
+    pub(crate) fn parent_of_dynamic_node(&self, template: &VNode, idx: usize) -> Option<ElementId> {
+        let node_template = template.template.get();
+        let path = node_template.node_paths[idx];
+
+        node_template
+            .attr_paths
+            .iter()
+            .zip(template.dynamic_attrs.iter())
+            .filter(|(attr_path, _)| attr_path.len() < path.len() && path.starts_with(attr_path))
+            .max_by_key(|(attr_path, _)| attr_path.len())
+            .map(|(_, attr)| attr.mounted_element.get())
+            .or_else(|| template.parent.get())
+    }
+
     fn create_dynamic_text(
         &mut self,
         template: &'b VNode<'b>,
@@ -500,6 +571,7 @@ impl<'b> VirtualDom {
         &mut self,
         template: &'b VNode<'b>,
         component: &'b VComponent<'b>,
+        parent: Option<ElementId>,
     ) -> usize {
         use RenderReturn::*;
 
@@ -507,12 +579,18 @@ impl<'b> VirtualDom {
         let scope = self.load_scope_from_vcomponent(component);
 
         component.scope.set(Some(scope));
+        self.scopes[scope.0].parent_element.set(parent);
 
-        match unsafe { self.run_scope(scope).extend_lifetime_ref() } {
+        let m = match unsafe { self.run_scope(scope).extend_lifetime_ref() } {
             // Create the component's root element
-            Ready(t) => self.create_scope(scope, t),
+            Ready(t) => {
+                t.parent.set(parent);
+                self.create_scope(scope, t)
+            }
             Aborted(t) => self.mount_aborted(template, t),
-        }
+        };
+
+        self.mount_portal(scope, template, m)
     }
 
     /// Load a scope from a vcomponent. If the props don't exist, that means the component is currently "live"
@@ -0,0 +1,163 @@
+//! Reading and editing the hook state of components from developer tools
+//!
+//! Hooks are stored as type-erased values, so the VirtualDom can only show hooks with a type that was registered with
+//! [`VirtualDom::register_dev_inspect`].
+
+use crate::{ScopeId, VirtualDom};
+use std::{
+    any::{Any, TypeId},
+    fmt::{Display, Formatter},
+};
+
+/// A hook value that developer tools can display and edit
+///
+/// ```rust, ignore
+/// impl DevInspect for UseCounter {
+///     fn inspect(&self) -> String {
+///         self.count.get().to_string()
+///     }
+///
+///     fn set_value(&self, value: &str) -> Result<(), String> {
+///         self.count.set(value.parse().map_err(|e| format!("{e}"))?);
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait DevInspect: Any {
+    /// Format the current value of the hook for display
+    fn inspect(&self) -> String;
+
+    /// Replace the value of the hook with one parsed from text
+    ///
+    /// The VirtualDom re-renders the component after the value is set, so implementations don't need to schedule an
+    /// update themselves.
+    fn set_value(&self, value: &str) -> Result<(), String>;
+}
+
+/// A snapshot of one inspectable hook in a component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookStateView {
+    /// The position of the hook in the component, in the order the hooks are called
+    pub index: usize,
+
+    /// The name of the hook's type
+    pub type_name: &'static str,
+
+    /// The value of the hook, formatted with [`DevInspect::inspect`]
+    pub value: String,
+}
+
+/// The reasons setting a hook value from developer tools can fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookStateError {
+    /// There is no mounted scope with this id
+    ScopeNotFound(ScopeId),
+
+    /// The scope has no hook at this index
+    HookNotFound(usize),
+
+    /// The type of the hook at this index was never registered with [`VirtualDom::register_dev_inspect`]
+    NotInspectable(usize),
+
+    /// The hook rejected the new value
+    InvalidValue(String),
+}
+
+impl Display for HookStateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookStateError::ScopeNotFound(id) => write!(f, "No scope with the id {:?}", id),
+            HookStateError::HookNotFound(index) => write!(f, "No hook at index {}", index),
+            HookStateError::NotInspectable(index) => {
+                write!(f, "The hook at index {} can't be inspected", index)
+            }
+            HookStateError::InvalidValue(err) => write!(f, "Invalid hook value: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for HookStateError {}
+
+/// Recovers a [`DevInspect`] trait object from a type-erased hook of a registered type
+#[derive(Clone, Copy)]
+pub(crate) struct Inspector {
+    type_name: &'static str,
+    cast: fn(&dyn Any) -> Option<&dyn DevInspect>,
+}
+
+fn cast<T: DevInspect>(hook: &dyn Any) -> Option<&dyn DevInspect> {
+    hook.downcast_ref::<T>().map(|hook| hook as &dyn DevInspect)
+}
+
+impl VirtualDom {
+    /// Allow developer tools to inspect and edit hooks of this type
+    pub fn register_dev_inspect<T: DevInspect>(&mut self) {
+        self.inspectors.insert(
+            TypeId::of::<T>(),
+            Inspector {
+                type_name: std::any::type_name::<T>(),
+                cast: cast::<T>,
+            },
+        );
+    }
+
+    /// Get the value of every hook in a scope with a type registered with [`VirtualDom::register_dev_inspect`]
+    ///
+    /// Returns an empty list if the scope doesn't exist.
+    pub fn scope_hook_state(&self, id: ScopeId) -> Vec<HookStateView> {
+        let scope = match self.get_scope(id) {
+            Some(scope) => scope,
+            None => return Vec::new(),
+        };
+
+        let hooks = scope.hooks.borrow();
+        hooks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, hook)| {
+                // safety: the component isn't rendering, so nothing else has a reference to its hooks
+                let hook: &dyn Any = unsafe { &*hook.get() };
+                let (type_name, hook) = self.inspect_hook(hook)?;
+                Some(HookStateView {
+                    index,
+                    type_name,
+                    value: hook.inspect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Set the value of the hook at `index` in a scope and re-render the scope
+    pub fn set_hook_value(
+        &mut self,
+        id: ScopeId,
+        index: usize,
+        value: &str,
+    ) -> Result<(), HookStateError> {
+        {
+            let scope = self
+                .get_scope(id)
+                .ok_or(HookStateError::ScopeNotFound(id))?;
+            let hooks = scope.hooks.borrow();
+            let hook = hooks
+                .get(index)
+                .ok_or(HookStateError::HookNotFound(index))?;
+
+            // safety: the component isn't rendering, so nothing else has a reference to its hooks
+            let hook: &dyn Any = unsafe { &*hook.get() };
+            let (_, hook) = self
+                .inspect_hook(hook)
+                .ok_or(HookStateError::NotInspectable(index))?;
+            hook.set_value(value)
+                .map_err(HookStateError::InvalidValue)?;
+        }
+
+        self.mark_dirty(id);
+        Ok(())
+    }
+
+    fn inspect_hook<'a>(&self, hook: &'a dyn Any) -> Option<(&'static str, &'a dyn DevInspect)> {
+        let inspector = self.inspectors.get(&Any::type_id(hook))?;
+        Some((inspector.type_name, (inspector.cast)(hook)?))
+    }
+}
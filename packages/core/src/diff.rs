@@ -7,7 +7,7 @@ use crate::{
     nodes::{DynamicNode, VNode},
     scopes::ScopeId,
     virtual_dom::VirtualDom,
-    Attribute, TemplateNode,
+    Attribute, AttributeValue, TemplateNode,
 };
 
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -15,6 +15,8 @@ use DynamicNode::*;
 
 impl<'b> VirtualDom {
     pub(super) fn diff_scope(&mut self, scope: ScopeId) {
+        #[cfg(feature = "diff-stats")]
+        let edits_before = self.mutations.edits.len();
         let scope_state = &mut self.scopes[scope.0];
 
         self.scope_stack.push(scope);
@@ -32,6 +34,11 @@ impl<'b> VirtualDom {
 
             use RenderReturn::{Aborted, Ready};
 
+            // The new root bubbles its events to wherever the component is rendered
+            if let Ready(r) = new {
+                r.parent.set(scope_state.parent_element.get());
+            }
+
             match (old, new) {
                 // Normal pathway
                 (Ready(l), Ready(r)) => self.diff_node(l, r),
@@ -48,6 +55,14 @@ impl<'b> VirtualDom {
             };
         }
         self.scope_stack.pop();
+
+        self.move_portal(scope);
+
+        #[cfg(feature = "diff-stats")]
+        {
+            self.frame_stats.scopes_rerendered += 1;
+            self.record_render(scope, edits_before);
+        }
     }
 
     fn diff_ok_to_err(&mut self, l: &'b VNode<'b>, p: &'b VPlaceholder) {
@@ -72,7 +87,13 @@ impl<'b> VirtualDom {
         };
     }
 
-    fn diff_node(&mut self, left_template: &'b VNode<'b>, right_template: &'b VNode<'b>) {
+    pub(crate) fn diff_node(
+        &mut self,
+        left_template: &'b VNode<'b>,
+        right_template: &'b VNode<'b>,
+    ) {
+        right_template.parent.set(left_template.parent.get());
+
         // If hot reloading is enabled, we need to make sure we're using the latest template
         #[cfg(debug_assertions)]
         {
@@ -113,22 +134,64 @@ impl<'b> VirtualDom {
                 // We want to make sure anything that gets pulled is valid
                 self.update_template(left_attr.mounted_element.get(), right_template);
 
+                // A `once` listener that already fired stays spent while the same listener is mounted
+                if let (
+                    AttributeValue::Listener(left_cb, left_flags),
+                    AttributeValue::Listener(right_cb, right_flags),
+                ) = (&left_attr.value, &right_attr.value)
+                {
+                    if left_flags.once && left_flags == right_flags && left_cb.borrow().is_none() {
+                        right_cb.borrow_mut().take();
+                    }
+                }
+
                 // If the attributes are different (or volatile), we need to update them
                 if left_attr.value != right_attr.value || left_attr.volatile {
                     self.update_attribute(right_attr, left_attr);
                 }
             });
 
-        // Now diff the dynamic nodes
+        // Now diff the dynamic nodes, holding on to the text updates of this template so they can be sent together
+        let mut text_updates = Vec::new();
         left_template
             .dynamic_nodes
             .iter()
             .zip(right_template.dynamic_nodes.iter())
-            .for_each(|(left_node, right_node)| {
-                self.diff_dynamic_node(left_node, right_node, right_template);
-            });
+            .enumerate()
+            .for_each(
+                |(idx, (left_node, right_node))| match (left_node, right_node) {
+                    (Text(left), Text(right)) => {
+                        text_updates.extend(self.changed_vtext(left, right, right_template))
+                    }
+                    _ => self.diff_dynamic_node(left_node, right_node, right_template, idx),
+                },
+            );
+        self.push_text_updates(text_updates);
+
+        self.transfer_roots(left_template, right_template);
+    }
+
+    /// Send the text updates of one template, merged into a single `SetMultiText` if there is more than one
+    ///
+    /// The values are sorted by id, which is the order the texts were created in. The texts under an element are created
+    /// last to first.
+    fn push_text_updates(&mut self, mut updates: Vec<(ElementId, &'static str)>) {
+        match updates.len() {
+            0 => {}
+            1 => {
+                let (id, value) = updates[0];
+                self.mutations.push(Mutation::SetText { id, value });
+            }
+            _ => {
+                updates.sort_unstable_by_key(|(id, _)| id.0);
+                self.mutations
+                    .push(Mutation::SetMultiText { values: updates });
+            }
+        }
+    }
 
-        // Make sure the roots get transferred over while we're here
+    /// Make sure the roots get transferred over to the new template
+    fn transfer_roots(&mut self, left_template: &'b VNode<'b>, right_template: &'b VNode<'b>) {
         *right_template.root_ids.borrow_mut() = left_template.root_ids.borrow().clone();
 
         let root_ids = right_template.root_ids.borrow();
@@ -146,24 +209,51 @@ impl<'b> VirtualDom {
         left_node: &'b DynamicNode<'b>,
         right_node: &'b DynamicNode<'b>,
         node: &'b VNode<'b>,
+        idx: usize,
     ) {
         match (left_node, right_node) {
             (Text(left), Text(right)) => self.diff_vtext(left, right, node),
             (Fragment(left), Fragment(right)) => self.diff_non_empty_fragment(left, right),
             (Placeholder(left), Placeholder(right)) => right.id.set(left.id.get()),
             (Component(left), Component(right)) => self.diff_vcomponent(left, right, node),
-            (Placeholder(left), Fragment(right)) => self.replace_placeholder(left, *right),
+            (Placeholder(left), Fragment(right)) => {
+                let parent = self.parent_of_dynamic_node(node, idx);
+                right.iter().for_each(|child| child.parent.set(parent));
+                self.replace_placeholder(left, *right)
+            }
             (Fragment(left), Placeholder(right)) => self.node_to_placeholder(left, right),
             _ => todo!("This is an usual custom case for dynamic nodes. We don't know how to handle it yet."),
         };
     }
 
     fn update_attribute(&mut self, right_attr: &'b Attribute<'b>, left_attr: &'b Attribute) {
+        let id = left_attr.mounted_element.get();
+
+        // Listeners with new flags are removed with their old flags and created again, since renderers may have
+        // created a native listener with options that can't change
+        if let AttributeValue::Listener(_, flags) = &left_attr.value {
+            let name: &str = unsafe { std::mem::transmute(left_attr.name) };
+            self.mutations.push(Mutation::RemoveEventListener {
+                name: &name[2..],
+                id,
+                flags: *flags,
+            });
+        }
+        if let AttributeValue::Listener(_, flags) = &right_attr.value {
+            let name: &str = unsafe { std::mem::transmute(right_attr.name) };
+            self.mutations.push(Mutation::NewEventListener {
+                name: &name[2..],
+                id,
+                flags: *flags,
+            });
+            return;
+        }
+
         let name = unsafe { std::mem::transmute(left_attr.name) };
         let value: BorrowedAttributeValue<'b> = (&right_attr.value).into();
         let value = unsafe { std::mem::transmute(value) };
         self.mutations.push(Mutation::SetAttribute {
-            id: left_attr.mounted_element.get(),
+            id,
             ns: right_attr.namespace,
             name,
             value,
@@ -202,6 +292,11 @@ impl<'b> VirtualDom {
             return;
         }
 
+        // The component can also decide for itself that the new props aren't worth a render
+        if !self.should_update(scope_id, Some(new.as_ref())) {
+            return;
+        }
+
         // First, move over the props from the old to the new, dropping old props in the process
         self.scopes[scope_id.0].props = Some(new);
 
@@ -221,7 +316,11 @@ impl<'b> VirtualDom {
         right: &'b VComponent<'b>,
         left: &'b VComponent<'b>,
     ) {
-        let m = self.create_component_node(right_template, right);
+        // The new component takes the place of the old one, so it bubbles to the same element
+        let parent = self.scopes[left.scope.get().unwrap().0]
+            .parent_element
+            .get();
+        let m = self.create_component_node(right_template, right, parent);
 
         let pre_edits = self.mutations.edits.len();
 
@@ -289,6 +388,18 @@ impl<'b> VirtualDom {
     /// This just moves the ID of the old node over to the new node, and then sets the text of the new node if it's
     /// different.
     fn diff_vtext(&mut self, left: &'b VText<'b>, right: &'b VText<'b>, node: &'b VNode<'b>) {
+        if let Some((id, value)) = self.changed_vtext(left, right, node) {
+            self.mutations.push(Mutation::SetText { id, value });
+        }
+    }
+
+    /// Move the id over to the new text, and return the update for it if the text changed
+    fn changed_vtext(
+        &mut self,
+        left: &'b VText<'b>,
+        right: &'b VText<'b>,
+        node: &'b VNode<'b>,
+    ) -> Option<(ElementId, &'static str)> {
         let id = left
             .id
             .get()
@@ -296,24 +407,21 @@ impl<'b> VirtualDom {
 
         right.id.set(Some(id));
         if left.value != right.value {
-            let value = unsafe { std::mem::transmute(right.value) };
-            self.mutations.push(Mutation::SetText { id, value });
+            Some((id, unsafe { std::mem::transmute(right.value) }))
+        } else {
+            None
         }
     }
 
     fn diff_non_empty_fragment(&mut self, old: &'b [VNode<'b>], new: &'b [VNode<'b>]) {
-        let new_is_keyed = new[0].key.is_some();
-        let old_is_keyed = old[0].key.is_some();
-        debug_assert!(
-            new.iter().all(|n| n.key.is_some() == new_is_keyed),
-            "all siblings must be keyed or all siblings must be non-keyed"
-        );
-        debug_assert!(
-            old.iter().all(|o| o.key.is_some() == old_is_keyed),
-            "all siblings must be keyed or all siblings must be non-keyed"
-        );
+        // Every child of a fragment bubbles to the same element, including the ones that are about to be created
+        let parent = old[0].parent.get();
+        new.iter().for_each(|child| child.parent.set(parent));
+
+        let new_is_keyed = new.iter().any(|n| n.key.is_some());
+        let old_is_keyed = old.iter().any(|o| o.key.is_some());
 
-        if new_is_keyed && old_is_keyed {
+        if new_is_keyed && old_is_keyed && keys_are_usable(old) && keys_are_usable(new) {
             self.diff_keyed_children(old, new);
         } else {
             self.diff_non_keyed_children(old, new);
@@ -362,29 +470,8 @@ impl<'b> VirtualDom {
     // https://github.com/infernojs/inferno/blob/36fd96/packages/inferno/src/DOM/patching.ts#L530-L739
     //
     // The stack is empty upon entry.
+    // The caller makes sure every sibling has a key, and that no two siblings share one.
     fn diff_keyed_children(&mut self, old: &'b [VNode<'b>], new: &'b [VNode<'b>]) {
-        if cfg!(debug_assertions) {
-            let mut keys = rustc_hash::FxHashSet::default();
-            let mut assert_unique_keys = |children: &'b [VNode<'b>]| {
-                keys.clear();
-                for child in children {
-                    let key = child.key;
-                    debug_assert!(
-                        key.is_some(),
-                        "if any sibling is keyed, all siblings must be keyed"
-                    );
-                    keys.insert(key);
-                }
-                debug_assert_eq!(
-                    children.len(),
-                    keys.len(),
-                    "keyed siblings must each have a unique key"
-                );
-            };
-            assert_unique_keys(old);
-            assert_unique_keys(new);
-        }
-
         // First up, we diff all the nodes with the same key at the beginning of the
         // children.
         //
@@ -413,17 +500,20 @@ impl<'b> VirtualDom {
         } else if old_middle.is_empty() {
             // there were no old elements, so just create the new elements
             // we need to find the right "foothold" though - we shouldn't use the "append" at all
+            //
+            // The footholds come from the new list: the ends were already diffed, and a node that got replaced
+            // gave its ids back, so the old node might point at ids that now belong to something else
             if left_offset == 0 {
                 // insert at the beginning of the old list
-                let foothold = &old[old.len() - right_offset];
+                let foothold = &new[new.len() - right_offset];
                 self.create_and_insert_before(new_middle, foothold);
             } else if right_offset == 0 {
                 // insert at the end  the old list
-                let foothold = old.last().unwrap();
+                let foothold = &new[left_offset - 1];
                 self.create_and_insert_after(new_middle, foothold);
             } else {
                 // inserting in the middle
-                let foothold = &old[left_offset - 1];
+                let foothold = &new[left_offset - 1];
                 self.create_and_insert_after(new_middle, foothold);
             }
         } else {
@@ -453,9 +543,10 @@ impl<'b> VirtualDom {
         }
 
         // If that was all of the old children, then create and append the remaining
-        // new children and we're finished.
+        // new children and we're finished. The last old child was just diffed into its new
+        // counterpart, which holds the ids that are mounted now.
         if left_offset == old.len() {
-            self.create_and_insert_after(&new[left_offset..], old.last().unwrap());
+            self.create_and_insert_after(&new[left_offset..], &new[left_offset - 1]);
             return None;
         }
 
@@ -548,17 +639,12 @@ impl<'b> VirtualDom {
 
         // If none of the old keys are reused by the new children, then we remove all the remaining old children and
         // create the new children afresh.
+        //
+        // The caller only diffs the middle when both the old and new middles have children, so there is always an old
+        // node to replace.
         if shared_keys.is_empty() {
-            if old.get(0).is_some() {
-                self.remove_nodes(&old[1..]);
-                self.replace(&old[0], new);
-            } else {
-                // I think this is wrong - why are we appending?
-                // only valid of the if there are no trailing elements
-                // self.create_and_append_children(new);
-
-                todo!("we should never be appending - just creating N");
-            }
+            self.remove_nodes(&old[1..]);
+            self.replace(&old[0], new);
             return;
         }
 
@@ -616,10 +702,7 @@ impl<'b> VirtualDom {
 
             let id = self.find_last_element(&new[last]);
             if nodes_created > 0 {
-                self.mutations.push(Mutation::InsertAfter {
-                    id,
-                    m: nodes_created,
-                })
+                self.insert_pushed(id, nodes_created, false);
             }
             nodes_created = 0;
         }
@@ -642,10 +725,7 @@ impl<'b> VirtualDom {
 
                 let id = self.find_first_element(&new[last]);
                 if nodes_created > 0 {
-                    self.mutations.push(Mutation::InsertBefore {
-                        id,
-                        m: nodes_created,
-                    });
+                    self.insert_pushed(id, nodes_created, true);
                 }
 
                 nodes_created = 0;
@@ -668,16 +748,33 @@ impl<'b> VirtualDom {
 
             let id = self.find_first_element(&new[first_lis]);
             if nodes_created > 0 {
-                self.mutations.push(Mutation::InsertBefore {
-                    id,
-                    m: nodes_created,
-                });
+                self.insert_pushed(id, nodes_created, true);
             }
         }
     }
 
+    /// Insert the `m` nodes on the stack before or after `id`
+    ///
+    /// A lone node that was pushed because it moved is moved directly instead, without going through the stack.
+    fn insert_pushed(&mut self, id: ElementId, m: usize, before: bool) {
+        if let (1, Some(&Mutation::PushRoot { id: moved })) = (m, self.mutations.edits.last()) {
+            self.mutations.edits.pop();
+            self.mutations.push(Mutation::MoveNode {
+                id: moved,
+                target: id,
+                before,
+            });
+            return;
+        }
+
+        match before {
+            true => self.mutations.push(Mutation::InsertBefore { id, m }),
+            false => self.mutations.push(Mutation::InsertAfter { id, m }),
+        }
+    }
+
     /// Push all the real nodes on the stack
-    fn push_all_real_nodes(&mut self, node: &'b VNode<'b>) -> usize {
+    pub(crate) fn push_all_real_nodes(&mut self, node: &'b VNode<'b>) -> usize {
         node.template
             .get()
             .roots
@@ -714,6 +811,10 @@ impl<'b> VirtualDom {
 
                     Component(comp) => {
                         let scope = comp.scope.get().unwrap();
+                        if let Some(anchor) = self.portal_anchor(scope) {
+                            self.mutations.push(Mutation::PushRoot { id: anchor });
+                            return 1;
+                        }
                         match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
                             RenderReturn::Ready(node) => self.push_all_real_nodes(node),
                             RenderReturn::Aborted(_node) => todo!(),
@@ -803,7 +904,7 @@ impl<'b> VirtualDom {
             .for_each(|node| self.remove_node(node, true));
     }
 
-    fn remove_node(&mut self, node: &'b VNode<'b>, gen_muts: bool) {
+    pub(crate) fn remove_node(&mut self, node: &'b VNode<'b>, gen_muts: bool) {
         // Clean up any attributes that have claimed a static node as dynamic for mount/unmounta
         // Will not generate mutations!
         self.reclaim_attributes(node);
@@ -878,6 +979,33 @@ impl<'b> VirtualDom {
         }
     }
 
+    /// Remove everything mounted under one root of a template, but not the root itself
+    ///
+    /// Won't generate mutations since the whole root is about to be replaced
+    pub(crate) fn remove_template_root_children(&mut self, node: &'b VNode<'b>, root_idx: usize) {
+        let template = node.template.get();
+        let under_root = |path: &[u8]| path.len() > 1 && path[0] == root_idx as u8;
+
+        let mut id = None;
+        for (attr, path) in node.dynamic_attrs.iter().zip(template.attr_paths) {
+            let next_id = attr.mounted_element.get();
+
+            if !under_root(path) || id == Some(next_id) {
+                continue;
+            }
+
+            id = Some(next_id);
+
+            self.reclaim(next_id);
+        }
+
+        for (dyn_node, path) in node.dynamic_nodes.iter().zip(template.node_paths) {
+            if under_root(path) {
+                self.remove_dynamic_node(dyn_node, false);
+            }
+        }
+    }
+
     fn remove_dynamic_node(&mut self, node: &DynamicNode, gen_muts: bool) {
         match node {
             Component(comp) => self.remove_component_node(comp, gen_muts),
@@ -889,7 +1017,7 @@ impl<'b> VirtualDom {
         };
     }
 
-    fn remove_placeholder(&mut self, t: &VPlaceholder, gen_muts: bool) {
+    pub(crate) fn remove_placeholder(&mut self, t: &VPlaceholder, gen_muts: bool) {
         if let Some(id) = t.id.take() {
             if gen_muts {
                 self.mutations.push(Mutation::Remove { id });
@@ -915,10 +1043,15 @@ impl<'b> VirtualDom {
             .expect("VComponents to always have a scope");
 
         // Remove the component from the dom
-        match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
-            RenderReturn::Ready(t) => self.remove_node(t, gen_muts),
-            RenderReturn::Aborted(placeholder) => self.remove_placeholder(placeholder, gen_muts),
-        };
+        match self.scopes[scope.0].portal_mount.take() {
+            Some(mount) => self.unmount_portal(scope, mount, gen_muts),
+            None => match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
+                RenderReturn::Ready(t) => self.remove_node(t, gen_muts),
+                RenderReturn::Aborted(placeholder) => {
+                    self.remove_placeholder(placeholder, gen_muts)
+                }
+            },
+        }
 
         // Restore the props back to the vcomponent in case it gets rendered again
         let props = self.scopes[scope.0].props.take();
@@ -936,6 +1069,9 @@ impl<'b> VirtualDom {
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
                 let scope = comp.scope.get().unwrap();
+                if let Some(anchor) = self.portal_anchor(scope) {
+                    return anchor;
+                }
                 match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
                     RenderReturn::Ready(t) => self.find_first_element(t),
                     _ => todo!("cannot handle nonstandard nodes"),
@@ -952,6 +1088,9 @@ impl<'b> VirtualDom {
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
                 let scope = comp.scope.get().unwrap();
+                if let Some(anchor) = self.portal_anchor(scope) {
+                    return anchor;
+                }
                 match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
                     RenderReturn::Ready(t) => self.find_last_element(t),
                     _ => todo!("cannot handle nonstandard nodes"),
@@ -1035,3 +1174,29 @@ fn is_dyn_node_only_child(node: &VNode, idx: usize) -> bool {
         _ => false,
     }
 }
+
+/// Check that every sibling has a key and that the keys are unique, so the siblings can be matched up by key
+///
+/// Lists that break either rule are diffed by position instead, with a warning.
+fn keys_are_usable(children: &[VNode]) -> bool {
+    let mut keys = FxHashSet::default();
+    for child in children {
+        let key = match child.key {
+            Some(key) => key,
+            None => {
+                log::warn!(
+                    "Some siblings in a list have a key and some don't, so the list is diffed by position"
+                );
+                return false;
+            }
+        };
+        if !keys.insert(key) {
+            log::warn!(
+                "The key {:?} is used by more than one sibling in a list, so the list is diffed by position",
+                key
+            );
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,179 @@
+//! Diffing two trees of [`VNode`]s directly, without a VirtualDom or components
+//!
+//! This exposes the diffing algorithm of the VirtualDom as a reusable unit for tooling and tests, like fuzzing the diff
+//! by checking that the mutations between two random trees really do turn one into the other.
+
+use crate::{
+    innerlude::{BorrowedAttributeValue, DynamicNode, Mutation, Mutations},
+    ElementId, ScopeId, VNode, VirtualDom,
+};
+
+/// Something that applies [`Mutations`] to a tree of real nodes, like the DOM
+pub trait Renderer {
+    /// Load the templates and apply the edits of a batch of mutations
+    ///
+    /// [`Mutation::SetMultiText`] and [`Mutation::MoveNode`] edits are never part of the batch, they go to
+    /// [`Renderer::set_multi_text`] and [`Renderer::move_node`] instead. Neither are text values of the
+    /// `dangerous_inner_html` attribute, which go to [`Renderer::set_inner_html`].
+    fn apply(&mut self, mutations: Mutations);
+
+    /// Start applying one batch of mutations
+    ///
+    /// The calls for a batch can be split over several methods, like [`Renderer::apply`] and
+    /// [`Renderer::set_inner_html`]. They all happen between `begin_frame` and [`Renderer::flush`], so renderers can
+    /// buffer them and send them on together, like to a remote client. Does nothing by default.
+    fn begin_frame(&mut self) {}
+
+    /// Finish applying the batch of mutations started by [`Renderer::begin_frame`]
+    ///
+    /// The tree is complete at this point, so this is where renderers with a layout can read measurements. Does
+    /// nothing by default.
+    fn flush(&mut self) {}
+
+    /// Set the text of several text nodes at once
+    ///
+    /// Renderers that can batch text updates should override this. By default every text is applied as its own
+    /// [`Mutation::SetText`].
+    fn set_multi_text(&mut self, values: &[(ElementId, &str)]) {
+        self.apply(Mutations {
+            edits: values
+                .iter()
+                .map(|(id, value)| Mutation::SetText {
+                    id: *id,
+                    value: *value,
+                })
+                .collect(),
+            ..Default::default()
+        });
+    }
+
+    /// Move the mounted node `root` before or after the mounted node `target`
+    ///
+    /// Both IDs must be live. `root` keeps its children and listeners, and the stack is left as it was. Renderers with
+    /// a DOM should override this with `insertBefore`. By default the node is pushed onto the stack and inserted with
+    /// [`Mutation::InsertBefore`] or [`Mutation::InsertAfter`].
+    fn move_node(&mut self, root: ElementId, target: ElementId, before: bool) {
+        let insert = match before {
+            true => Mutation::InsertBefore { id: target, m: 1 },
+            false => Mutation::InsertAfter { id: target, m: 1 },
+        };
+        self.apply(Mutations {
+            edits: vec![Mutation::PushRoot { id: root }, insert],
+            ..Default::default()
+        });
+    }
+
+    /// Replace the children of the mounted element `root` with raw HTML
+    ///
+    /// `rsx!` doesn't let elements with `dangerous_inner_html` have children, so nothing else touches the nodes the
+    /// HTML creates. Removing the attribute sets an empty string. Renderers with a DOM should override this with
+    /// `innerHTML`. By default the HTML is applied as a [`Mutation::SetAttribute`] of `dangerous_inner_html`.
+    fn set_inner_html(&mut self, html: &str, root: ElementId) {
+        self.apply(Mutations {
+            edits: vec![Mutation::SetAttribute {
+                name: "dangerous_inner_html",
+                value: BorrowedAttributeValue::Text(html),
+                id: root,
+                ns: None,
+            }],
+            ..Default::default()
+        });
+    }
+}
+
+/// Diff two trees of [`VNode`]s and drive `renderer` with the mutations
+///
+/// The renderer gets two batches of mutations. The first creates `old` and appends it to the root ([`ElementId`] 0).
+/// The second is the result of the diff, which turns `old` into `new`.
+///
+/// The trees are diffed as if they were rendered by the root component of an empty VirtualDom, so they can't contain
+/// components.
+///
+/// ```rust, ignore
+/// let old = rsx!(div { "hello" }).call(cx);
+/// let new = rsx!(div { "world" }).call(cx);
+/// diff_nodes(&old, &new, &mut renderer);
+/// ```
+pub fn diff_nodes<'a>(old: &'a VNode<'a>, new: &'a VNode<'a>, renderer: &mut impl Renderer) {
+    assert!(
+        !has_components(old) && !has_components(new),
+        "diff_nodes can only diff trees without components"
+    );
+
+    let mut dom = VirtualDom::new(|_| None);
+    dom.scope_stack.push(ScopeId(0));
+
+    dom.mutations.begin_scope(ScopeId(0));
+    let m = dom.create(old);
+    dom.mutations.push(Mutation::AppendChildren {
+        id: ElementId(0),
+        m,
+    });
+    apply_mutations(renderer, dom.finalize());
+
+    dom.mutations.begin_scope(ScopeId(0));
+    dom.diff_node(old, new);
+    apply_mutations(renderer, dom.finalize());
+
+    dom.scope_stack.pop();
+}
+
+/// Apply a batch of mutations, sending coalesced text updates, moves, and inner HTML to their own methods of the
+/// [`Renderer`]
+///
+/// The calls are wrapped in [`Renderer::begin_frame`] and [`Renderer::flush`].
+///
+/// ```rust, ignore
+/// let edits = dom.render_immediate();
+/// apply_mutations(&mut renderer, edits);
+/// ```
+pub fn apply_mutations(renderer: &mut impl Renderer, mut mutations: Mutations) {
+    renderer.begin_frame();
+
+    let edits = std::mem::take(&mut mutations.edits);
+    let mut batch = mutations;
+    for edit in edits {
+        match edit {
+            Mutation::SetMultiText { values } => {
+                apply_pending(renderer, &mut batch);
+                renderer.set_multi_text(&values);
+            }
+            Mutation::MoveNode { id, target, before } => {
+                apply_pending(renderer, &mut batch);
+                renderer.move_node(id, target, before);
+            }
+            Mutation::SetAttribute {
+                name: "dangerous_inner_html",
+                value: value @ (BorrowedAttributeValue::Text(_) | BorrowedAttributeValue::None),
+                id,
+                ns: None,
+            } => {
+                apply_pending(renderer, &mut batch);
+                let html = match value {
+                    BorrowedAttributeValue::Text(html) => html,
+                    _ => "",
+                };
+                renderer.set_inner_html(html, id);
+            }
+            edit => batch.edits.push(edit),
+        }
+    }
+    renderer.apply(batch);
+
+    renderer.flush();
+}
+
+/// Apply the edits gathered so far, so the next edit runs after them
+fn apply_pending(renderer: &mut impl Renderer, batch: &mut Mutations) {
+    if !batch.edits.is_empty() || !batch.templates.is_empty() {
+        renderer.apply(std::mem::take(batch));
+    }
+}
+
+fn has_components(node: &VNode) -> bool {
+    node.dynamic_nodes.iter().any(|node| match node {
+        DynamicNode::Component(_) => true,
+        DynamicNode::Fragment(nodes) => nodes.iter().any(has_components),
+        _ => false,
+    })
+}
@@ -0,0 +1,146 @@
+//! Counting the work each render does, to find out what makes a page slow
+//!
+//! Enabled with the `diff-stats` feature. Without it, the VirtualDom doesn't track anything.
+
+use crate::{Mutation, ScopeId, VirtualDom};
+use std::time::Instant;
+
+/// The work done by one call that returns [`crate::Mutations`], like [`VirtualDom::render_immediate`]
+///
+/// ```rust, ignore
+/// let edits = dom.render_immediate();
+/// apply(edits);
+/// let stats = dom.diff_stats();
+/// println!("{} scopes re-rendered in {}ns", stats.scopes_rerendered, stats.nanos);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiffStats {
+    /// How many scopes ran again and were diffed against their last render. Scopes that were created don't count.
+    pub scopes_rerendered: usize,
+
+    /// How many nodes were created: templates loaded, text nodes and placeholders
+    pub creates: usize,
+
+    /// How many nodes were removed or replaced
+    pub removes: usize,
+
+    /// How many attributes were set or removed
+    pub set_attributes: usize,
+
+    /// How many text nodes got new text, including each text of a [`Mutation::SetMultiText`]
+    pub set_texts: usize,
+
+    /// How many mounted nodes were moved
+    pub moves: usize,
+
+    /// How long running and diffing the scopes took, in nanoseconds
+    ///
+    /// This is always zero on `wasm32`, where [`Instant`] isn't available.
+    pub nanos: u64,
+}
+
+impl DiffStats {
+    /// Start timing some work, if the target has a clock
+    pub(crate) fn timer() -> Option<Instant> {
+        match cfg!(target_arch = "wasm32") {
+            true => None,
+            false => Some(Instant::now()),
+        }
+    }
+
+    /// Add the time since the timer started
+    pub(crate) fn record_time(&mut self, timer: Option<Instant>) {
+        if let Some(started) = timer {
+            self.nanos += started.elapsed().as_nanos() as u64;
+        }
+    }
+
+    /// Count the edits of the frame by kind
+    fn count_edits(&mut self, edits: &[Mutation]) {
+        for edit in edits {
+            match edit {
+                Mutation::LoadTemplate { .. }
+                | Mutation::CreatePlaceholder { .. }
+                | Mutation::CreateTextNode { .. } => self.creates += 1,
+                Mutation::Remove { .. } | Mutation::ReplaceWith { .. } => self.removes += 1,
+                Mutation::SetAttribute { .. } => self.set_attributes += 1,
+                Mutation::SetText { .. } => self.set_texts += 1,
+                Mutation::SetMultiText { values } => self.set_texts += values.len(),
+                // Nodes are only pushed back onto the stack to be moved
+                Mutation::MoveNode { .. } | Mutation::PushRoot { .. } => self.moves += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// How often a scope re-rendered, returned by [`VirtualDom::wasted_render_stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderStats {
+    /// The name of the component
+    pub name: &'static str,
+
+    /// How many times the scope re-rendered, not counting the first render
+    pub renders: usize,
+
+    /// How many of those renders produced no mutations
+    pub wasted: usize,
+}
+
+impl RenderStats {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            renders: 0,
+            wasted: 0,
+        }
+    }
+}
+
+impl VirtualDom {
+    /// Get the stats of the last call that returned [`crate::Mutations`]
+    pub fn diff_stats(&self) -> DiffStats {
+        self.diff_stats
+    }
+
+    /// Get how many times each scope re-rendered, and how many of those renders were wasted
+    ///
+    /// A wasted render produced no mutations: the component re-rendered, but its output was the same as before. Scopes
+    /// with many wasted renders are good candidates for memoization. Scopes are sorted with the most wasted renders
+    /// first, and only scopes that re-rendered at least once are included.
+    ///
+    /// ```rust, ignore
+    /// for (id, stats) in dom.wasted_render_stats() {
+    ///     println!("{} ({:?}) wasted {} of {} renders", stats.name, id, stats.wasted, stats.renders);
+    /// }
+    /// ```
+    pub fn wasted_render_stats(&self) -> Vec<(ScopeId, RenderStats)> {
+        let mut stats: Vec<_> = self
+            .render_stats
+            .iter()
+            .map(|(id, stats)| (*id, *stats))
+            .collect();
+        stats.sort_by(|(a_id, a), (b_id, b)| b.wasted.cmp(&a.wasted).then(a_id.cmp(b_id)));
+        stats
+    }
+
+    /// Count a re-render of a scope, which was wasted if the mutations didn't grow
+    pub(crate) fn record_render(&mut self, scope: ScopeId, edits_before: usize) {
+        let name = self.scopes[scope.0].name;
+        let stats = self
+            .render_stats
+            .entry(scope)
+            .or_insert_with(|| RenderStats::new(name));
+        stats.renders += 1;
+        if self.mutations.edits.len() == edits_before {
+            stats.wasted += 1;
+        }
+    }
+
+    /// Close the stats of the frame that is being finalized
+    pub(crate) fn finish_diff_stats(&mut self) {
+        let mut stats = std::mem::take(&mut self.frame_stats);
+        stats.count_edits(&self.mutations.edits);
+        self.diff_stats = stats;
+    }
+}
@@ -1,20 +1,26 @@
 use crate::{ScopeId, ScopeState};
 use std::{
-    any::{Any, TypeId},
-    cell::RefCell,
+    any::Any,
+    cell::{Ref, RefCell},
     fmt::Debug,
+    sync::Arc,
 };
 
 /// A boundary that will capture any errors from child components
+///
+/// Errors thrown with [`ScopeState::throw`] stop at the nearest boundary above the component that threw them, so
+/// boundaries can be nested to keep an error in one widget from taking down the whole page. The VirtualDom provides a
+/// boundary at the root that catches anything that isn't caught further down.
 pub struct ErrorBoundary {
     error: RefCell<Option<CapturedError>>,
-    _id: ScopeId,
+    id: ScopeId,
+    rerun: Option<Arc<dyn Fn()>>,
 }
 
 /// An instance of an error captured by a descendant component.
 pub struct CapturedError {
     /// The error captured by the error boundary
-    pub error: Box<dyn Debug + 'static>,
+    pub error: Box<dyn AnyDebug>,
 
     /// The scope that threw the error
     pub scope: ScopeId,
@@ -23,26 +29,99 @@ pub struct CapturedError {
 impl CapturedError {
     /// Downcast the error type into a concrete error type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
-        if TypeId::of::<T>() == self.error.type_id() {
-            let raw = self.error.as_ref() as *const _ as *const T;
-            Some(unsafe { &*raw })
-        } else {
-            None
-        }
+        // The box is itself an `AnyDebug`, so call through to the error it holds
+        <dyn AnyDebug>::as_any(&*self.error).downcast_ref()
+    }
+}
+
+/// An error that can be printed and downcast back into its concrete type
+pub trait AnyDebug: Debug + 'static {
+    /// Get the error as [`Any`], to downcast it
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Debug + 'static> AnyDebug for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A panic caught while rendering a component or polling a task, like the future of a suspended component
+///
+/// A component that panics renders nothing, and a task that panics is dropped. Either way, the panic is thrown to the
+/// nearest error boundary of the component, which can downcast the [`CapturedError`] to render a fallback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskPanic {
+    /// The message the task panicked with
+    pub message: String,
+}
+
+impl TaskPanic {
+    pub(crate) fn new(payload: &(dyn Any + Send)) -> Self {
+        let message = match payload.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => match payload.downcast_ref::<String>() {
+                Some(message) => message.clone(),
+                None => "the task panicked".to_string(),
+            },
+        };
+        Self { message }
     }
 }
 
 impl ErrorBoundary {
+    /// Create a new boundary for the component with the given [`ScopeId`]
     pub fn new(id: ScopeId) -> Self {
         Self {
             error: RefCell::new(None),
-            _id: id,
+            id,
+            rerun: None,
+        }
+    }
+
+    /// Create a new boundary that re-renders its component whenever it catches an error or is reset
+    pub fn new_with_rerun(id: ScopeId, rerun: Arc<dyn Fn()>) -> Self {
+        Self {
+            error: RefCell::new(None),
+            id,
+            rerun: Some(rerun),
         }
     }
 
+    /// Get the [`ScopeId`] of the component this boundary belongs to
+    pub fn id(&self) -> ScopeId {
+        self.id
+    }
+
     /// Push an error into this Error Boundary
-    pub fn insert_error(&self, scope: ScopeId, error: Box<dyn Debug + 'static>) {
+    pub fn insert_error(&self, scope: ScopeId, error: Box<dyn AnyDebug>) {
         self.error.replace(Some(CapturedError { error, scope }));
+        self.rerun();
+    }
+
+    /// Check if this boundary has caught an error
+    pub fn has_error(&self) -> bool {
+        self.error.borrow().is_some()
+    }
+
+    /// Get the error this boundary has caught, if any
+    pub fn error(&self) -> Option<Ref<'_, CapturedError>> {
+        Ref::filter_map(self.error.borrow(), |error| error.as_ref()).ok()
+    }
+
+    /// Clear the caught error and render the boundary's children again
+    ///
+    /// Children that failed are created from scratch, so they get another chance to render successfully.
+    pub fn reset(&self) {
+        if self.error.take().is_some() {
+            self.rerun();
+        }
+    }
+
+    fn rerun(&self) {
+        if let Some(rerun) = &self.rerun {
+            rerun();
+        }
     }
 }
 
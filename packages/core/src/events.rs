@@ -1,4 +1,5 @@
 use std::{
+    any::Any,
     cell::{Cell, RefCell},
     rc::Rc,
 };
@@ -26,6 +27,32 @@ pub struct Event<T: 'static + ?Sized> {
     pub(crate) propagates: Rc<Cell<bool>>,
 }
 
+impl<T: ?Sized> Event<T> {
+    /// Create a new event that bubbles up the tree from the element it is dispatched to
+    ///
+    /// Renderers usually don't need this since [`crate::VirtualDom::handle_event`] wraps the data for them, but it is
+    /// useful for calling handlers directly.
+    pub fn new(data: Rc<T>) -> Self {
+        Self {
+            data,
+            propagates: Rc::new(Cell::new(true)),
+        }
+    }
+}
+
+impl Event<dyn Any> {
+    /// Get a reference to the data of a type-erased event if it is a `T`
+    ///
+    /// ```rust, ignore
+    /// let event = Event::new(Rc::new(MouseData::default()) as Rc<dyn Any>);
+    /// assert!(event.downcast::<MouseData>().is_some());
+    /// assert!(event.downcast::<KeyboardData>().is_none());
+    /// ```
+    pub fn downcast<T: 'static>(&self) -> Option<&T> {
+        self.data.downcast_ref::<T>()
+    }
+}
+
 impl<T> Event<T> {
     /// Prevent this event from continuing to bubble up the tree to parent elements.
     ///
@@ -1,37 +1,59 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+mod announcer;
 mod any_props;
 mod arena;
 mod bump_frame;
+mod compact_template;
 mod create;
+#[cfg(feature = "devtools")]
+mod devtools;
 mod diff;
+mod diff_nodes;
+#[cfg(feature = "diff-stats")]
+mod diff_stats;
 mod dirty_scope;
 mod error_boundary;
 mod events;
 mod fragment;
 mod lazynodes;
+mod mutation_channel;
 mod mutations;
 mod nodes;
+mod portal;
 mod properties;
+mod render_target;
 mod scheduler;
 mod scope_arena;
 mod scopes;
+mod throttle;
 mod virtual_dom;
 
 pub(crate) mod innerlude {
+    pub use crate::announcer::*;
     pub use crate::arena::*;
+    pub use crate::compact_template::*;
+    #[cfg(feature = "devtools")]
+    pub use crate::devtools::*;
+    pub use crate::diff_nodes::*;
+    #[cfg(feature = "diff-stats")]
+    pub use crate::diff_stats::*;
     pub use crate::dirty_scope::*;
     pub use crate::error_boundary::*;
     pub use crate::events::*;
     pub use crate::fragment::*;
     pub use crate::lazynodes::*;
+    pub use crate::mutation_channel::*;
     pub use crate::mutations::*;
     pub use crate::nodes::RenderReturn;
     pub use crate::nodes::*;
+    pub use crate::portal::*;
     pub use crate::properties::*;
+    pub use crate::render_target::*;
     pub use crate::scheduler::*;
     pub use crate::scopes::*;
+    pub use crate::throttle::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`Scope`] or [`ScopeState`].
@@ -70,20 +92,32 @@ pub(crate) mod innerlude {
 }
 
 pub use crate::innerlude::{
-    fc_to_builder, AnyValue, Attribute, AttributeValue, BorrowedAttributeValue, CapturedError,
-    Component, DynamicNode, Element, ElementId, Event, Fragment, IntoDynNode, LazyNodes, Mutation,
-    Mutations, Properties, RenderReturn, Scope, ScopeId, ScopeState, Scoped, TaskId, Template,
-    TemplateAttribute, TemplateNode, VComponent, VNode, VPlaceholder, VText, VirtualDom,
+    apply_mutations, compact_namespace, compact_template, compact_template_len, current_scope_id,
+    diff_nodes, fc_to_builder, mutation_channel, with_current_scope, Announcer, AnyDebug, AnyValue,
+    Attribute, AttributeValue, BorrowedAttributeValue, CapturedError, Component, DynamicNode,
+    Element, ElementId, ErrorBoundary, Event, EventThrottle, Fragment, HydratedState, IntoDynNode,
+    LazyNodes, ListenerFlags, Mutation, MutationReceiver, MutationSender, Mutations,
+    OwnedAttributeValue, OwnedMutation, OwnedMutations, Portal, PortalMount, Properties,
+    RenderReturn, RenderTarget, Renderer, Scope, ScopeId, ScopeState, Scoped, TaskId, TaskPanic,
+    TaskPriority, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VPlaceholder,
+    VText, VirtualDom, WorkStatus,
 };
 
+#[cfg(feature = "devtools")]
+pub use crate::innerlude::{DevInspect, HookStateError, HookStateView};
+
+#[cfg(feature = "diff-stats")]
+pub use crate::innerlude::{DiffStats, RenderStats};
+
 /// The purpose of this module is to alleviate imports of many common types
 ///
 /// This includes types like [`Scope`], [`Element`], and [`Component`].
 pub mod prelude {
     pub use crate::innerlude::{
         fc_to_builder, AnyValue, Component, Element, Event, EventHandler, Fragment,
-        IntoAttributeValue, LazyNodes, Properties, Scope, ScopeId, ScopeState, Scoped, TaskId,
-        Template, TemplateAttribute, TemplateNode, Throw, VNode, VirtualDom,
+        IntoAttributeValue, LazyNodes, Portal, Properties, RenderTarget, Scope, ScopeId,
+        ScopeState, Scoped, TaskId, TaskPriority, Template, TemplateAttribute, TemplateNode, Throw,
+        VNode, VirtualDom,
     };
 }
 
@@ -0,0 +1,412 @@
+//! Sending [`Mutations`] from a VirtualDom on one thread to a renderer on another
+//!
+//! The VirtualDom itself can't move between threads, but it can be created on a background thread (or a web worker)
+//! so heavy component trees are diffed off of the main thread. [`Mutations`] borrow from the VirtualDom, so they are
+//! copied into an [`OwnedMutations`] batch before they cross over.
+//!
+//! ```rust, ignore
+//! let (sender, mut receiver) = mutation_channel();
+//!
+//! std::thread::spawn(move || {
+//!     let mut dom = VirtualDom::new(app);
+//!     sender.send(dom.rebuild());
+//!     loop {
+//!         block_on(dom.wait_for_work());
+//!         if !sender.send(dom.render_immediate()) {
+//!             break;
+//!         }
+//!     }
+//! });
+//!
+//! while let Some(batch) = receiver.recv().await {
+//!     renderer.load_templates(&batch.templates);
+//!     renderer.apply_edits(batch.edits.iter().map(OwnedMutation::as_mutation).collect());
+//! }
+//! ```
+//!
+//! # Sending mutations over the network
+//!
+//! With the `serialize` feature, [`Mutations`] can be serialized straight out of the VirtualDom and deserialized into
+//! [`OwnedMutations`] on the other side, which is how a server can drive a thin client like LiveView. In JSON, a
+//! batch is an object with the new `templates` and the `edits`. Other fields of [`Mutations`] are ignored by the
+//! receiver.
+//!
+//! - Each edit is an object tagged with its variant name in `type`, like
+//!   `{ "type": "SetText", "value": "hello", "id": 2 }`. The fields are the fields of [`Mutation`].
+//! - An [`ElementId`] is a number.
+//! - A `path` into a template is an array of child indexes, starting at the root of the template.
+//! - An attribute value is a string, a number, a bool, or `null` to remove the attribute. Custom attribute values
+//!   can't leave the VirtualDom, and fail to serialize.
+//! - Listener `flags` may be left out, in which case they are all off.
+//!
+//! For a smaller encoding, any self describing binary format works with the same types. For example, MessagePack
+//! with `rmp_serde::to_vec_named` and `rmp_serde::from_slice`. Formats that aren't self describing, like bincode,
+//! can't read the edits back, because they are tagged with `type` and attribute values are untagged.
+
+use crate::{
+    innerlude::BorrowedAttributeValue, ElementId, ListenerFlags, Mutation, Mutations, Template,
+};
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+
+/// Create a channel to send [`Mutations`] to another thread
+pub fn mutation_channel() -> (MutationSender, MutationReceiver) {
+    let (tx, rx) = futures_channel::mpsc::unbounded();
+    (MutationSender { tx }, MutationReceiver { rx })
+}
+
+/// The sending half of a [`mutation_channel`], used on the thread that owns the VirtualDom
+#[derive(Clone)]
+pub struct MutationSender {
+    tx: UnboundedSender<OwnedMutations>,
+}
+
+impl MutationSender {
+    /// Copy a batch of mutations and send it to the renderer
+    ///
+    /// Returns `false` if the receiver was dropped.
+    pub fn send(&self, mutations: Mutations) -> bool {
+        self.tx
+            .unbounded_send(OwnedMutations::from(&mutations))
+            .is_ok()
+    }
+}
+
+/// The receiving half of a [`mutation_channel`], used on the thread that applies the mutations
+pub struct MutationReceiver {
+    rx: UnboundedReceiver<OwnedMutations>,
+}
+
+impl MutationReceiver {
+    /// Wait for the next batch of mutations
+    ///
+    /// Returns `None` once every sender has been dropped.
+    pub async fn recv(&mut self) -> Option<OwnedMutations> {
+        self.rx.next().await
+    }
+
+    /// Get the next batch of mutations if one has been sent already
+    pub fn try_recv(&mut self) -> Option<OwnedMutations> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// An owned copy of [`Mutations`] that can be sent between threads
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OwnedMutations {
+    /// Any templates encountered while diffing the DOM.
+    ///
+    /// These must be loaded into a cache before applying the edits
+    pub templates: Vec<Template<'static>>,
+
+    /// Any mutations required to patch the renderer to match the layout of the VirtualDom
+    pub edits: Vec<OwnedMutation>,
+}
+
+impl From<&Mutations<'_>> for OwnedMutations {
+    fn from(mutations: &Mutations<'_>) -> Self {
+        Self {
+            templates: mutations.templates.clone(),
+            edits: mutations
+                .edits
+                .iter()
+                .filter_map(OwnedMutation::from_mutation)
+                .collect(),
+        }
+    }
+}
+
+/// An owned copy of a [`Mutation`]. See [`Mutation`] for the meaning of each variant.
+#[allow(missing_docs)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type")
+)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedMutation {
+    AppendChildren {
+        id: ElementId,
+        m: usize,
+    },
+    AssignId {
+        path: Vec<u8>,
+        id: ElementId,
+    },
+    AssignExternalRoot {
+        selector: String,
+        id: ElementId,
+    },
+    CreatePlaceholder {
+        id: ElementId,
+    },
+    CreateTextNode {
+        value: String,
+        id: ElementId,
+    },
+    HydrateText {
+        path: Vec<u8>,
+        value: String,
+        id: ElementId,
+    },
+    LoadTemplate {
+        #[cfg_attr(
+            feature = "serialize",
+            serde(deserialize_with = "crate::nodes::deserialize_string_leaky")
+        )]
+        // Leaked instead of borrowed from the input, like the name of a `Template`
+        name: &'static std::primitive::str,
+        index: usize,
+        id: ElementId,
+    },
+    ReplaceWith {
+        id: ElementId,
+        m: usize,
+    },
+    ReplacePlaceholder {
+        path: Vec<u8>,
+        m: usize,
+    },
+    InsertAfter {
+        id: ElementId,
+        m: usize,
+    },
+    InsertBefore {
+        id: ElementId,
+        m: usize,
+    },
+    MoveNode {
+        id: ElementId,
+        target: ElementId,
+        before: bool,
+    },
+    SetAttribute {
+        name: String,
+        value: OwnedAttributeValue,
+        id: ElementId,
+        ns: Option<String>,
+    },
+    SetText {
+        value: String,
+        id: ElementId,
+    },
+    SetMultiText {
+        values: Vec<(ElementId, String)>,
+    },
+    NewEventListener {
+        name: String,
+        id: ElementId,
+        #[cfg_attr(feature = "serialize", serde(default))]
+        flags: ListenerFlags,
+    },
+    RemoveEventListener {
+        name: String,
+        id: ElementId,
+        #[cfg_attr(feature = "serialize", serde(default))]
+        flags: ListenerFlags,
+    },
+    Remove {
+        id: ElementId,
+    },
+    PushRoot {
+        id: ElementId,
+    },
+}
+
+/// The attribute values that can be sent between threads
+///
+/// Custom values ([`BorrowedAttributeValue::Any`]) can't leave the VirtualDom's thread.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(untagged))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedAttributeValue {
+    /// Text attribute
+    Text(String),
+
+    /// Signed integer
+    ///
+    /// This comes before [`OwnedAttributeValue::Float`] so whole numbers deserialize as integers, not floats
+    Int(i64),
+
+    /// A float
+    Float(f64),
+
+    /// Boolean
+    Bool(bool),
+
+    /// A "none" value, resulting in the removal of an attribute from the dom
+    None,
+}
+
+impl OwnedMutation {
+    /// Copy a mutation so it can be sent to another thread
+    ///
+    /// Returns `None` for attributes with a custom value, which can't leave the VirtualDom's thread.
+    pub fn from_mutation(mutation: &Mutation<'_>) -> Option<Self> {
+        use Mutation::*;
+        Some(match mutation {
+            AppendChildren { id, m } => OwnedMutation::AppendChildren { id: *id, m: *m },
+            AssignId { path, id } => OwnedMutation::AssignId {
+                path: path.to_vec(),
+                id: *id,
+            },
+            AssignExternalRoot { selector, id } => OwnedMutation::AssignExternalRoot {
+                selector: selector.to_string(),
+                id: *id,
+            },
+            CreatePlaceholder { id } => OwnedMutation::CreatePlaceholder { id: *id },
+            CreateTextNode { value, id } => OwnedMutation::CreateTextNode {
+                value: value.to_string(),
+                id: *id,
+            },
+            HydrateText { path, value, id } => OwnedMutation::HydrateText {
+                path: path.to_vec(),
+                value: value.to_string(),
+                id: *id,
+            },
+            LoadTemplate { name, index, id } => OwnedMutation::LoadTemplate {
+                name: *name,
+                index: *index,
+                id: *id,
+            },
+            ReplaceWith { id, m } => OwnedMutation::ReplaceWith { id: *id, m: *m },
+            ReplacePlaceholder { path, m } => OwnedMutation::ReplacePlaceholder {
+                path: path.to_vec(),
+                m: *m,
+            },
+            InsertAfter { id, m } => OwnedMutation::InsertAfter { id: *id, m: *m },
+            InsertBefore { id, m } => OwnedMutation::InsertBefore { id: *id, m: *m },
+            MoveNode { id, target, before } => OwnedMutation::MoveNode {
+                id: *id,
+                target: *target,
+                before: *before,
+            },
+            SetAttribute {
+                name,
+                value,
+                id,
+                ns,
+            } => {
+                let value = match value {
+                    BorrowedAttributeValue::Text(text) => {
+                        OwnedAttributeValue::Text(text.to_string())
+                    }
+                    BorrowedAttributeValue::Float(f) => OwnedAttributeValue::Float(*f),
+                    BorrowedAttributeValue::Int(n) => OwnedAttributeValue::Int(*n),
+                    BorrowedAttributeValue::Bool(b) => OwnedAttributeValue::Bool(*b),
+                    BorrowedAttributeValue::None => OwnedAttributeValue::None,
+                    BorrowedAttributeValue::Any(_) => {
+                        log::warn!(
+                            "The custom value of the attribute `{}` can't be sent to another thread and was skipped",
+                            name
+                        );
+                        return None;
+                    }
+                };
+                OwnedMutation::SetAttribute {
+                    name: name.to_string(),
+                    value,
+                    id: *id,
+                    ns: ns.map(str::to_string),
+                }
+            }
+            SetText { value, id } => OwnedMutation::SetText {
+                value: value.to_string(),
+                id: *id,
+            },
+            SetMultiText { values } => OwnedMutation::SetMultiText {
+                values: values
+                    .iter()
+                    .map(|(id, value)| (*id, value.to_string()))
+                    .collect(),
+            },
+            NewEventListener { name, id, flags } => OwnedMutation::NewEventListener {
+                name: name.to_string(),
+                id: *id,
+                flags: *flags,
+            },
+            RemoveEventListener { name, id, flags } => OwnedMutation::RemoveEventListener {
+                name: name.to_string(),
+                id: *id,
+                flags: *flags,
+            },
+            Remove { id } => OwnedMutation::Remove { id: *id },
+            PushRoot { id } => OwnedMutation::PushRoot { id: *id },
+        })
+    }
+
+    /// Borrow this mutation as a [`Mutation`] so it can be applied by any renderer
+    pub fn as_mutation(&self) -> Mutation<'_> {
+        match self {
+            OwnedMutation::AppendChildren { id, m } => Mutation::AppendChildren { id: *id, m: *m },
+            OwnedMutation::AssignId { path, id } => Mutation::AssignId { path, id: *id },
+            OwnedMutation::AssignExternalRoot { selector, id } => {
+                Mutation::AssignExternalRoot { selector, id: *id }
+            }
+            OwnedMutation::CreatePlaceholder { id } => Mutation::CreatePlaceholder { id: *id },
+            OwnedMutation::CreateTextNode { value, id } => {
+                Mutation::CreateTextNode { value, id: *id }
+            }
+            OwnedMutation::HydrateText { path, value, id } => Mutation::HydrateText {
+                path,
+                value,
+                id: *id,
+            },
+            OwnedMutation::LoadTemplate { name, index, id } => Mutation::LoadTemplate {
+                name: *name,
+                index: *index,
+                id: *id,
+            },
+            OwnedMutation::ReplaceWith { id, m } => Mutation::ReplaceWith { id: *id, m: *m },
+            OwnedMutation::ReplacePlaceholder { path, m } => {
+                Mutation::ReplacePlaceholder { path, m: *m }
+            }
+            OwnedMutation::InsertAfter { id, m } => Mutation::InsertAfter { id: *id, m: *m },
+            OwnedMutation::InsertBefore { id, m } => Mutation::InsertBefore { id: *id, m: *m },
+            OwnedMutation::MoveNode { id, target, before } => Mutation::MoveNode {
+                id: *id,
+                target: *target,
+                before: *before,
+            },
+            OwnedMutation::SetAttribute {
+                name,
+                value,
+                id,
+                ns,
+            } => Mutation::SetAttribute {
+                name,
+                value: match value {
+                    OwnedAttributeValue::Text(text) => BorrowedAttributeValue::Text(text),
+                    OwnedAttributeValue::Float(f) => BorrowedAttributeValue::Float(*f),
+                    OwnedAttributeValue::Int(n) => BorrowedAttributeValue::Int(*n),
+                    OwnedAttributeValue::Bool(b) => BorrowedAttributeValue::Bool(*b),
+                    OwnedAttributeValue::None => BorrowedAttributeValue::None,
+                },
+                id: *id,
+                ns: ns.as_deref(),
+            },
+            OwnedMutation::SetText { value, id } => Mutation::SetText { value, id: *id },
+            OwnedMutation::SetMultiText { values } => Mutation::SetMultiText {
+                values: values
+                    .iter()
+                    .map(|(id, value)| (*id, value.as_str()))
+                    .collect(),
+            },
+            OwnedMutation::NewEventListener { name, id, flags } => Mutation::NewEventListener {
+                name,
+                id: *id,
+                flags: *flags,
+            },
+            OwnedMutation::RemoveEventListener { name, id, flags } => {
+                Mutation::RemoveEventListener {
+                    name,
+                    id: *id,
+                    flags: *flags,
+                }
+            }
+            OwnedMutation::Remove { id } => Mutation::Remove { id: *id },
+            OwnedMutation::PushRoot { id } => Mutation::PushRoot { id: *id },
+        }
+    }
+}
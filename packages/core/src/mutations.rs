@@ -1,6 +1,10 @@
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{arena::ElementId, innerlude::BorrowedAttributeValue, ScopeId, Template};
+use crate::{
+    arena::ElementId,
+    innerlude::{BorrowedAttributeValue, ListenerFlags},
+    ScopeId, Template,
+};
 
 /// A container for all the relevant steps to modify the Real DOM
 ///
@@ -26,10 +30,17 @@ pub struct Mutations<'a> {
     /// Any templates encountered while diffing the DOM.
     ///
     /// These must be loaded into a cache before applying the edits
-    pub templates: Vec<Template<'a>>,
+    pub templates: Vec<Template<'static>>,
 
     /// Any mutations required to patch the renderer to match the layout of the VirtualDom
     pub edits: Vec<Mutation<'a>>,
+
+    /// The scope that each run of edits originated from, as `(scope, start)` pairs in the order they were produced.
+    ///
+    /// A scope owns the edits from `start` up to the `start` of the next pair. Edits caused by components nested inside
+    /// of a scope (like a child that gets created or diffed during its parent's diff) belong to the outer scope, so every
+    /// run is self-contained.
+    pub scopes: Vec<(ScopeId, usize)>,
 }
 
 impl<'a> Mutations<'a> {
@@ -46,10 +57,132 @@ impl<'a> Mutations<'a> {
         self
     }
 
+    /// Split the edits into one batch per originating scope
+    ///
+    /// Batches are returned in the order their scope first produced edits. Each batch carries the templates that it
+    /// loads first, so it can be applied on its own.
+    pub fn split_by_scope(self) -> Vec<(ScopeId, Mutations<'a>)> {
+        let Mutations {
+            subtree,
+            mut templates,
+            edits,
+            scopes,
+            ..
+        } = self;
+
+        let mut batches: Vec<(ScopeId, Mutations<'a>)> = Vec::new();
+        let mut edits = edits.into_iter();
+
+        for (idx, (scope, start)) in scopes.iter().enumerate() {
+            let end = scopes.get(idx + 1).map(|(_, start)| *start);
+            let len = end.unwrap_or(usize::MAX) - start;
+
+            let batch = match batches.iter().position(|(id, _)| id == scope) {
+                Some(existing) => &mut batches[existing].1,
+                None => {
+                    let mut batch = Mutations {
+                        subtree,
+                        ..Default::default()
+                    };
+                    batch.dirty_scopes.insert(*scope);
+                    batch.scopes.push((*scope, 0));
+                    batches.push((*scope, batch));
+                    &mut batches.last_mut().unwrap().1
+                }
+            };
+
+            for edit in edits.by_ref().take(len) {
+                if let Mutation::LoadTemplate { name, .. } = edit {
+                    if let Some(idx) = templates.iter().position(|t| t.name == name) {
+                        batch.templates.push(templates.remove(idx));
+                    }
+                }
+                batch.edits.push(edit);
+            }
+        }
+
+        batches
+    }
+
+    /// Mark that the edits pushed from now on originate from the given scope
+    pub(crate) fn begin_scope(&mut self, scope: ScopeId) {
+        let start = self.edits.len();
+        match self.scopes.last_mut() {
+            Some((last, _)) if *last == scope => {}
+            Some((last, last_start)) if *last_start == start => *last = scope,
+            _ => self.scopes.push((scope, start)),
+        }
+    }
+
     /// Push a new mutation into the dom_edits list
     pub(crate) fn push(&mut self, mutation: Mutation<'static>) {
         self.edits.push(mutation)
     }
+
+    /// Drop attribute and text sets that a later edit in the same batch overwrites
+    ///
+    /// A scope that renders more than once before the edits are handed to the renderer sets the same attributes and
+    /// texts again, and only the last value matters. IDs are reused within a batch, so a set only overwrites the sets
+    /// before it up to the edit that assigned its ID.
+    pub(crate) fn dedup_sets(&mut self) {
+        let mut attributes: FxHashMap<ElementId, Vec<(&'a str, Option<&'a str>)>> =
+            FxHashMap::default();
+        let mut texts: FxHashSet<ElementId> = FxHashSet::default();
+        let mut keep = vec![true; self.edits.len()];
+
+        for (idx, edit) in self.edits.iter_mut().enumerate().rev() {
+            match edit {
+                Mutation::SetAttribute { name, ns, id, .. } => {
+                    let set = attributes.entry(*id).or_default();
+                    if set.contains(&(*name, *ns)) {
+                        keep[idx] = false;
+                    } else {
+                        set.push((*name, *ns));
+                    }
+                }
+                Mutation::SetText { id, .. } => keep[idx] = texts.insert(*id),
+                Mutation::SetMultiText { values } => {
+                    values.retain(|(id, _)| texts.insert(*id));
+                    keep[idx] = !values.is_empty();
+                }
+                Mutation::AssignId { id, .. }
+                | Mutation::AssignExternalRoot { id, .. }
+                | Mutation::CreatePlaceholder { id }
+                | Mutation::CreateTextNode { id, .. }
+                | Mutation::HydrateText { id, .. }
+                | Mutation::LoadTemplate { id, .. } => {
+                    attributes.remove(id);
+                    texts.remove(id);
+                }
+                _ => {}
+            }
+        }
+
+        if keep.iter().all(|keep| *keep) {
+            return;
+        }
+
+        // Move the start of every scope's run of edits back by the number of edits dropped before it
+        let mut kept_before = Vec::with_capacity(keep.len() + 1);
+        kept_before.push(0);
+        for keep in &keep {
+            kept_before.push(kept_before.last().unwrap() + *keep as usize);
+        }
+        for (_, start) in &mut self.scopes {
+            *start = kept_before[*start];
+        }
+        self.scopes.dedup_by(|next, prev| {
+            // A run that lost all of its edits is taken over by the run after it
+            if next.1 == prev.1 {
+                prev.0 = next.0;
+                return true;
+            }
+            false
+        });
+
+        let mut keep = keep.into_iter();
+        self.edits.retain(|_| keep.next().unwrap());
+    }
 }
 
 /// A `Mutation` represents a single instruction for the renderer to use to modify the UI tree to match the state
@@ -77,13 +210,14 @@ pub enum Mutation<'a> {
     /// The path is in the form of a list of indices based on children. Templates cannot have more than 255 children per
     /// element, hence the use of a single byte.
     ///
-    ///
+    /// Paths usually point into a [`crate::Template`], but they only have to live as long as the mutations, so renderers
+    /// must not hold on to them after applying the edits.
     AssignId {
         /// The path of the child of the topmost node on the stack
         ///
         /// A path of `[]` represents the topmost node. A path of `[0]` represents the first child.
         /// `[0,1,2]` represents 1st child's 2nd child's 3rd child.
-        path: &'static [u8],
+        path: &'a [u8],
 
         /// The ID we're assigning to this element/placeholder.
         ///
@@ -91,6 +225,20 @@ pub enum Mutation<'a> {
         id: ElementId,
     },
 
+    /// Assign an ElementId to an element outside of the app, found with a CSS selector like `body` or `#modals`.
+    ///
+    /// This is sent the first time a [`crate::Portal`] mounts its children to the selector, before the
+    /// [`Mutation::AppendChildren`] that mounts them. The ID stays assigned for as long as the VirtualDom lives and is
+    /// reused by every portal with the same selector. Renderers that can't find the element, or that have no document
+    /// to search, should assign the ID to the root of the app instead.
+    AssignExternalRoot {
+        /// The CSS selector of the element
+        selector: &'a str,
+
+        /// The ID we're assigning to the element
+        id: ElementId,
+    },
+
     /// Create an placeholder int he DOM that we will use later.
     ///
     /// Dioxus currently requires the use of placeholders to maintain a re-entrance point for things like list diffing
@@ -120,7 +268,7 @@ pub enum Mutation<'a> {
         ///
         /// A path of `[]` represents the topmost node. A path of `[0]` represents the first child.
         /// `[0,1,2]` represents 1st child's 2nd child's 3rd child.
-        path: &'static [u8],
+        path: &'a [u8],
 
         /// The value of the textnode that we want to set the placeholder with
         value: &'a str,
@@ -165,7 +313,7 @@ pub enum Mutation<'a> {
         ///
         /// A path of `[]` represents the topmost node. A path of `[0]` represents the first child.
         /// `[0,1,2]` represents 1st child's 2nd child's 3rd child.
-        path: &'static [u8],
+        path: &'a [u8],
 
         /// The number of nodes on the stack to replace the target element with
         m: usize,
@@ -189,6 +337,22 @@ pub enum Mutation<'a> {
         m: usize,
     },
 
+    /// Move a node that is already mounted next to another mounted node.
+    ///
+    /// The diff emits this instead of [`Mutation::PushRoot`] followed by an insert when a keyed list moves a single
+    /// node. Both IDs must belong to live nodes. The moved node keeps its children and listeners, and the stack is
+    /// left untouched.
+    MoveNode {
+        /// The ID of the node to move.
+        id: ElementId,
+
+        /// The ID of the node to move it next to.
+        target: ElementId,
+
+        /// Whether to move the node before the target, or after it.
+        before: bool,
+    },
+
     /// Set the value of a node's attribute.
     SetAttribute {
         /// The name of the attribute to set.
@@ -214,6 +378,16 @@ pub enum Mutation<'a> {
         id: ElementId,
     },
 
+    /// Set the textcontent of several text nodes at once.
+    ///
+    /// The diff emits this instead of a run of [`Mutation::SetText`] when more than one text node of a template changes,
+    /// like the cells of a table row. Renderers can apply the texts in one batch.
+    SetMultiText {
+        /// The ID of each text node, and the textcontent to set it to.
+        #[cfg_attr(feature = "serialize", serde(borrow))]
+        values: Vec<(ElementId, &'a str)>,
+    },
+
     /// Create a new Event Listener.
     NewEventListener {
         /// The name of the event to listen for.
@@ -221,6 +395,10 @@ pub enum Mutation<'a> {
 
         /// The ID of the node to attach the listener to.
         id: ElementId,
+
+        /// The modifiers the renderer should apply when dispatching this event.
+        #[cfg_attr(feature = "serialize", serde(default))]
+        flags: ListenerFlags,
     },
 
     /// Remove an existing Event Listener.
@@ -230,9 +408,16 @@ pub enum Mutation<'a> {
 
         /// The ID of the node to remove.
         id: ElementId,
+
+        /// The modifiers the listener was created with, so the renderer can remove the listener it added.
+        #[cfg_attr(feature = "serialize", serde(default))]
+        flags: ListenerFlags,
     },
 
     /// Remove a particular node from the DOM
+    ///
+    /// The whole subtree under the node goes with it, including any listeners. The VirtualDom never sends edits for
+    /// the nodes under a removed node, it only reclaims their IDs.
     Remove {
         /// The ID of the node to remove.
         id: ElementId,
@@ -46,8 +46,11 @@ pub struct VNode<'a> {
     /// In fragments, this is the key of the first child. In other cases, it is the key of the root.
     pub key: Option<&'a str>,
 
-    /// When rendered, this template will be linked to its parent manually
-    pub parent: Option<ElementId>,
+    /// The element this template is rendered under in the VirtualDom, which events bubble up to
+    ///
+    /// This is set when the template is mounted. It follows the component tree instead of the real tree, so the
+    /// children of a [`crate::Portal`] bubble to the element the portal is rendered in.
+    pub parent: Cell<Option<ElementId>>,
 
     /// The static nodes and static descriptor of the template
     pub template: Cell<Template<'static>>,
@@ -68,7 +71,7 @@ impl<'a> VNode<'a> {
     pub fn empty() -> Element<'a> {
         Some(VNode {
             key: None,
-            parent: None,
+            parent: Default::default(),
             root_ids: Default::default(),
             dynamic_nodes: &[],
             dynamic_attrs: &[],
@@ -92,6 +95,22 @@ impl<'a> VNode<'a> {
             }
         }
     }
+
+    /// Get the dynamic nodes in the order the VirtualDom creates them
+    ///
+    /// The roots are created in order, but the dynamic nodes under an element root are created last to first. Portals
+    /// append their children to their targets in this order.
+    pub fn dynamic_nodes_in_creation_order(&self) -> impl Iterator<Item = &'a DynamicNode<'a>> {
+        let paths = self.template.get().node_paths;
+        let dynamic_nodes = self.dynamic_nodes;
+
+        (0..self.template.get().roots.len()).flat_map(move |root| {
+            (0..paths.len())
+                .rev()
+                .filter(move |idx| paths[*idx].first() == Some(&(root as u8)))
+                .map(move |idx| &dynamic_nodes[idx])
+        })
+    }
 }
 
 /// A static layout of a UI tree that describes a set of dynamic and static nodes.
@@ -108,11 +127,13 @@ pub struct Template<'a> {
     /// The name of the template. This must be unique across your entire program for template diffing to work properly
     ///
     /// If two templates have the same name, it's likely that Dioxus will panic when diffing.
+    // Serde assumes a `&str` borrows from the input, which would only let templates be deserialized from `'static`
+    // data. The strings of a template are leaked instead, and spelling out the path of `str` skips that assumption.
     #[cfg_attr(
         feature = "serialize",
         serde(deserialize_with = "deserialize_string_leaky")
     )]
-    pub name: &'a str,
+    pub name: &'a std::primitive::str,
 
     /// The list of template nodes that make up the template
     ///
@@ -142,7 +163,7 @@ pub struct Template<'a> {
 }
 
 #[cfg(feature = "serialize")]
-fn deserialize_string_leaky<'a, 'de, D>(deserializer: D) -> Result<&'a str, D::Error>
+pub(crate) fn deserialize_string_leaky<'a, 'de, D>(deserializer: D) -> Result<&'a str, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -168,7 +189,7 @@ where
 }
 
 #[cfg(feature = "serialize")]
-fn deserialize_leaky<'a, 'de, T: serde::Deserialize<'de>, D>(
+pub(crate) fn deserialize_leaky<'a, 'de, T: serde::Deserialize<'de>, D>(
     deserializer: D,
 ) -> Result<&'a [T], D::Error>
 where
@@ -182,7 +203,7 @@ where
 }
 
 #[cfg(feature = "serialize")]
-fn deserialize_option_leaky<'a, 'de, D>(deserializer: D) -> Result<Option<&'static str>, D::Error>
+fn deserialize_option_leaky<'a, 'de, D>(deserializer: D) -> Result<Option<&'a str>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -204,6 +225,79 @@ impl<'a> Template<'a> {
     }
 }
 
+impl Template<'static> {
+    /// Give the elements without a namespace the namespace of their parent element
+    ///
+    /// Some SVG elements, like `a`, share their name with an HTML element, so the template doesn't know they belong to the
+    /// SVG namespace. Renderers get the cascaded template and can create every element with the namespace it carries.
+    /// The children of a `foreignObject` are HTML again.
+    ///
+    /// Templates that don't need any changes are returned as they are. The others are copied and leaked, which happens
+    /// once per template.
+    pub(crate) fn cascade_namespaces(self) -> Self {
+        if !self.roots.iter().any(|root| needs_cascade(root, None)) {
+            return self;
+        }
+
+        Template {
+            roots: cascade_namespaces(self.roots, None),
+            ..self
+        }
+    }
+}
+
+/// The namespace the children of an element are in
+fn namespace_of_children<'a>(tag: &str, namespace: Option<&'a str>) -> Option<&'a str> {
+    match tag {
+        "foreignObject" => None,
+        _ => namespace,
+    }
+}
+
+fn needs_cascade(node: &TemplateNode, parent: Option<&str>) -> bool {
+    match node {
+        TemplateNode::Element {
+            tag,
+            namespace,
+            children,
+            ..
+        } => {
+            (namespace.is_none() && parent.is_some())
+                || children.iter().any(|child| {
+                    needs_cascade(child, namespace_of_children(tag, namespace.or(parent)))
+                })
+        }
+        _ => false,
+    }
+}
+
+fn cascade_namespaces(
+    nodes: &'static [TemplateNode<'static>],
+    parent: Option<&'static str>,
+) -> &'static [TemplateNode<'static>] {
+    let nodes: Vec<_> = nodes
+        .iter()
+        .map(|node| match *node {
+            TemplateNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                let namespace = namespace.or(parent);
+                TemplateNode::Element {
+                    tag,
+                    namespace,
+                    attrs,
+                    children: cascade_namespaces(children, namespace_of_children(tag, namespace)),
+                }
+            }
+            node => node,
+        })
+        .collect();
+    Box::leak(nodes.into_boxed_slice())
+}
+
 /// A statically known node in a layout.
 ///
 /// This can be created at compile time, saving the VirtualDom time when diffing the tree
@@ -221,7 +315,11 @@ pub enum TemplateNode<'a> {
         /// The name of the element
         ///
         /// IE for a div, it would be the string "div"
-        tag: &'a str,
+        #[cfg_attr(
+            feature = "serialize",
+            serde(deserialize_with = "deserialize_string_leaky")
+        )]
+        tag: &'a std::primitive::str,
 
         /// The namespace of the element
         ///
@@ -231,7 +329,7 @@ pub enum TemplateNode<'a> {
             feature = "serialize",
             serde(deserialize_with = "deserialize_option_leaky")
         )]
-        namespace: Option<&'a str>,
+        namespace: Option<&'a std::primitive::str>,
 
         /// A list of possibly dynamic attribues for this element
         ///
@@ -247,7 +345,11 @@ pub enum TemplateNode<'a> {
     /// This template node is just a piece of static text
     Text {
         /// The actual text
-        text: &'a str,
+        #[cfg_attr(
+            feature = "serialize",
+            serde(deserialize_with = "deserialize_string_leaky")
+        )]
+        text: &'a std::primitive::str,
     },
 
     /// This template node is unknown, and needs to be created at runtime.
@@ -343,6 +445,10 @@ impl<'a> std::fmt::Debug for VComponent<'a> {
 }
 
 /// An instance of some text, mounted to the DOM
+///
+/// The text is borrowed from the scope that rendered it. Text formatted in `rsx!` is written straight into the bump
+/// allocator of the scope, so it is never allocated anywhere else. A `String` is kept by the scope as it is, without
+/// copying it, and is freed along with the nodes that borrow it. See [`ScopeState::owned_text`].
 #[derive(Debug)]
 pub struct VText<'a> {
     /// The actual text itself
@@ -350,6 +456,9 @@ pub struct VText<'a> {
 
     /// The ID of this node in the real DOM
     pub(crate) id: Cell<Option<ElementId>>,
+
+    /// Should renderers that produce markup skip escaping this text?
+    pub(crate) unescaped: bool,
 }
 
 impl<'a> VText<'a> {
@@ -358,9 +467,33 @@ impl<'a> VText<'a> {
         Self {
             value,
             id: Default::default(),
+            unescaped: false,
         }
     }
 
+    /// Create a new VText that renderers producing markup (like SSR) write out without escaping
+    ///
+    /// This is meant for text that has already been escaped, like a pre-rendered string containing HTML entities.
+    /// Unlike `dangerous_inner_html`, it's still a text node and not element markup.
+    ///
+    /// # Safety contract
+    ///
+    /// The value is written into the HTML exactly as given. You are responsible for making sure it is already
+    /// escaped and safe: passing untrusted input here opens you up to XSS attacks. Renderers that create text
+    /// nodes directly (web, desktop, TUI) display the value as-is, entities included.
+    pub fn unescaped(value: &'a str) -> Self {
+        Self {
+            value,
+            id: Default::default(),
+            unescaped: true,
+        }
+    }
+
+    /// Check if this text should be written out without escaping
+    pub fn is_unescaped(&self) -> bool {
+        self.unescaped
+    }
+
     /// Get the mounted ID of this node
     pub fn mounted_element(&self) -> Option<ElementId> {
         self.id.get()
@@ -394,15 +527,27 @@ pub enum TemplateAttribute<'a> {
         /// The name of this attribute.
         ///
         /// For example, the `href` attribute in `href="https://example.com"`, would have the name "href"
-        name: &'a str,
+        #[cfg_attr(
+            feature = "serialize",
+            serde(deserialize_with = "deserialize_string_leaky")
+        )]
+        name: &'a std::primitive::str,
 
         /// The value of this attribute, known at compile time
         ///
         /// Currently this only accepts &str, so values, even if they're known at compile time, are not known
-        value: &'a str,
+        #[cfg_attr(
+            feature = "serialize",
+            serde(deserialize_with = "deserialize_string_leaky")
+        )]
+        value: &'a std::primitive::str,
 
         /// The namespace of this attribute. Does not exist in the HTML spec
-        namespace: Option<&'a str>,
+        #[cfg_attr(
+            feature = "serialize",
+            serde(deserialize_with = "deserialize_option_leaky")
+        )]
+        namespace: Option<&'a std::primitive::str>,
     },
 
     /// The attribute in this position is actually determined dynamically at runtime
@@ -456,6 +601,22 @@ impl<'a> Attribute<'a> {
     pub fn mounted_element(&self) -> ElementId {
         self.mounted_element.get()
     }
+
+    /// Set the [`ListenerFlags`] of this attribute if it is a listener. Other attributes are left unchanged.
+    pub fn with_listener_flags(mut self, flags: ListenerFlags) -> Self {
+        if let AttributeValue::Listener(_, old) = &mut self.value {
+            *old = flags;
+        }
+        self
+    }
+
+    /// Get the [`ListenerFlags`] of this attribute if it is a listener
+    pub fn listener_flags(&self) -> Option<ListenerFlags> {
+        match &self.value {
+            AttributeValue::Listener(_, flags) => Some(*flags),
+            _ => None,
+        }
+    }
 }
 
 /// Any of the built-in values that the Dioxus VirtualDom supports as dynamic attributes on elements
@@ -475,8 +636,8 @@ pub enum AttributeValue<'a> {
     /// Boolean
     Bool(bool),
 
-    /// A listener, like "onclick"
-    Listener(RefCell<Option<ListenerCb<'a>>>),
+    /// A listener, like "onclick", along with the modifiers the renderer should apply when dispatching to it
+    Listener(RefCell<Option<ListenerCb<'a>>>, ListenerFlags),
 
     /// An arbitrary value that implements PartialEq and is static
     Any(RefCell<Option<BumpBox<'a, dyn AnyValue>>>),
@@ -487,6 +648,42 @@ pub enum AttributeValue<'a> {
 
 pub type ListenerCb<'a> = BumpBox<'a, dyn FnMut(Event<dyn Any>) + 'a>;
 
+/// Modifiers attached to a listener that change how its event is dispatched
+///
+/// These are set with rsx modifiers like `onsubmit: prevent_default move |_| {}`. Renderers that have a native event
+/// (like the web) should honor `prevent_default` before the handler runs, and the VirtualDom stops the event from
+/// bubbling after a listener with `stop_propagation` is called. Renderers can use [`crate::EventThrottle`] to honor
+/// `throttle`.
+///
+/// `passive` and `capture` are options for the native listener, and are sent again with the
+/// [`crate::Mutation::RemoveEventListener`] of a listener so renderers can remove the exact listener they added. The
+/// VirtualDom calls `capture` listeners on the way down to the target and handles `once` itself.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ListenerFlags {
+    /// Call `preventDefault` on the native event before the handler runs
+    pub prevent_default: bool,
+
+    /// Stop the event from bubbling to parent listeners after the handler runs
+    pub stop_propagation: bool,
+
+    /// Call the handler at most once every this many milliseconds, with the newest event at the end of each window
+    pub throttle: Option<u32>,
+
+    /// Promise that the handler never prevents the default action, so the renderer can scroll without waiting for it
+    pub passive: bool,
+
+    /// Call the handler while the event travels down to its target, before any listeners it bubbles through
+    pub capture: bool,
+
+    /// Call the handler for the first event only, until the element is rendered again with a different listener
+    pub once: bool,
+}
+
 /// Any of the built-in values that the Dioxus VirtualDom supports as dynamic attributes on elements that are borrowed
 ///
 /// These varients are used to communicate what the value of an attribute is that needs to be updated
@@ -519,6 +716,54 @@ pub enum BorrowedAttributeValue<'a> {
     None,
 }
 
+/// The HTML attributes that are turned on by being there at all, whatever their value is
+///
+/// This matches the `bool_attrs` the interpreters use.
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "allowpaymentrequest",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+    "truespeed",
+    "webkitdirectory",
+];
+
+impl BorrowedAttributeValue<'_> {
+    /// Check if the attribute `name` should be removed instead of set to this value
+    ///
+    /// Like in HTML, a boolean attribute like `disabled` is turned on by being there at all, so `false` removes it
+    /// instead of setting it to "false". Other attributes keep their `false` spelled out, since enumerated attributes
+    /// like `draggable` or `spellcheck` and ARIA attributes read "false" as off.
+    pub fn removes_attribute(&self, name: &str) -> bool {
+        match self {
+            BorrowedAttributeValue::None => true,
+            BorrowedAttributeValue::Bool(false) => BOOLEAN_ATTRIBUTES.contains(&name),
+            _ => false,
+        }
+    }
+}
+
 impl<'a> From<&'a AttributeValue<'a>> for BorrowedAttributeValue<'a> {
     fn from(value: &'a AttributeValue<'a>) -> Self {
         match value {
@@ -526,7 +771,7 @@ impl<'a> From<&'a AttributeValue<'a>> for BorrowedAttributeValue<'a> {
             AttributeValue::Float(value) => BorrowedAttributeValue::Float(*value),
             AttributeValue::Int(value) => BorrowedAttributeValue::Int(*value),
             AttributeValue::Bool(value) => BorrowedAttributeValue::Bool(*value),
-            AttributeValue::Listener(_) => {
+            AttributeValue::Listener(..) => {
                 panic!("A listener cannot be turned into a borrowed value")
             }
             AttributeValue::Any(value) => {
@@ -571,15 +816,21 @@ fn serialize_any_value<S>(_: &std::cell::Ref<'_, dyn AnyValue>, _: S) -> Result<
 where
     S: serde::Serializer,
 {
-    panic!("Any cannot be serialized")
+    Err(serde::ser::Error::custom(
+        "custom attribute values can't be serialized",
+    ))
 }
 
+// This is also tried for every value that isn't text, a number, or a bool, so it must fail without panicking to let
+// `null` reach `None`
 #[cfg(feature = "serialize")]
 fn deserialize_any_value<'de, 'a, D>(_: D) -> Result<std::cell::Ref<'a, dyn AnyValue>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    panic!("Any cannot be deserialized")
+    Err(serde::de::Error::custom(
+        "custom attribute values can't be deserialized",
+    ))
 }
 
 impl<'a> std::fmt::Debug for AttributeValue<'a> {
@@ -589,7 +840,7 @@ impl<'a> std::fmt::Debug for AttributeValue<'a> {
             Self::Float(arg0) => f.debug_tuple("Float").field(arg0).finish(),
             Self::Int(arg0) => f.debug_tuple("Int").field(arg0).finish(),
             Self::Bool(arg0) => f.debug_tuple("Bool").field(arg0).finish(),
-            Self::Listener(_) => f.debug_tuple("Listener").finish(),
+            Self::Listener(_, flags) => f.debug_tuple("Listener").field(flags).finish(),
             Self::Any(_) => f.debug_tuple("Any").finish(),
             Self::None => write!(f, "None"),
         }
@@ -603,7 +854,7 @@ impl<'a> PartialEq for AttributeValue<'a> {
             (Self::Float(l0), Self::Float(r0)) => l0 == r0,
             (Self::Int(l0), Self::Int(r0)) => l0 == r0,
             (Self::Bool(l0), Self::Bool(r0)) => l0 == r0,
-            (Self::Listener(_), Self::Listener(_)) => true,
+            (Self::Listener(_, l0), Self::Listener(_, r0)) => l0 == r0,
             (Self::Any(l0), Self::Any(r0)) => {
                 let l0 = l0.borrow();
                 let r0 = r0.borrow();
@@ -697,19 +948,15 @@ impl<'a, 'b> IntoDynNode<'a> for LazyNodes<'a, 'b> {
 
 impl<'a, 'b> IntoDynNode<'b> for &'a str {
     fn into_vnode(self, cx: &'b ScopeState) -> DynamicNode<'b> {
-        DynamicNode::Text(VText {
-            value: bumpalo::collections::String::from_str_in(self, cx.bump()).into_bump_str(),
-            id: Default::default(),
-        })
+        DynamicNode::Text(VText::new(
+            bumpalo::collections::String::from_str_in(self, cx.bump()).into_bump_str(),
+        ))
     }
 }
 
 impl IntoDynNode<'_> for String {
     fn into_vnode(self, cx: &ScopeState) -> DynamicNode {
-        DynamicNode::Text(VText {
-            value: cx.bump().alloc(self),
-            id: Default::default(),
-        })
+        DynamicNode::Text(VText::new(cx.owned_text(self)))
     }
 }
 
@@ -722,7 +969,7 @@ impl<'b> IntoDynNode<'b> for Arguments<'_> {
 impl<'a> IntoDynNode<'a> for &'a VNode<'a> {
     fn into_vnode(self, _cx: &'a ScopeState) -> DynamicNode<'a> {
         DynamicNode::Fragment(_cx.bump().alloc([VNode {
-            parent: self.parent,
+            parent: Default::default(),
             template: self.template.clone(),
             root_ids: self.root_ids.clone(),
             key: self.key,
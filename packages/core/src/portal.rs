@@ -0,0 +1,221 @@
+use crate::innerlude::*;
+
+/// Render children under another element than the one the portal is rendered in.
+///
+/// ## Details
+///
+/// The children stay where the portal is in the component tree: they share its context, they are diffed with the
+/// portal, and they are removed when the portal is. Only their nodes are appended to the `mount` element instead of
+/// the portal's parent. A placeholder marks the place of the portal among its siblings.
+///
+/// By default, the children are appended to the root of the app, which makes portals a good fit for modals, tooltips
+/// and toasts that have to escape the overflow and stacking context of their parent.
+///
+/// To mount the children outside of the app, like in `document.body`, give the portal a CSS `selector` instead. The
+/// renderer looks the element up the first time a portal uses the selector, with [`Mutation::AssignExternalRoot`].
+/// Renderers without a document mount the children to the root of the app.
+///
+/// Listeners on the children work like anywhere else. Renderers report events with the [`ElementId`] of the element
+/// that got them, and the VirtualDom finds the listeners through its own tree, so moving the nodes doesn't change
+/// which handlers run.
+///
+/// Server side rendering writes the placeholder in place of the portal, like the client does. The children of portals
+/// that mount to the root of the app are written at the start of the app, where the client first creates them, so the
+/// page can be hydrated. The children of portals with a `selector` or another `mount` are left out, and the client
+/// creates them when it renders the app from scratch instead of hydrating it.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// rsx! {
+///     div { class: "card",
+///         Portal {
+///             div { class: "modal", "Are you sure?" }
+///         }
+///         Portal { selector: "body",
+///             div { class: "tooltip", "Deletes the card" }
+///         }
+///     }
+/// }
+/// ```
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn Portal<'a>(cx: Scope<'a, PortalProps<'a>>) -> Element {
+    cx.portal_target.set(Some(cx.props.mount));
+    cx.portal_selector.set(cx.props.selector);
+
+    let children = cx.props.children.as_ref()?;
+    Some(VNode {
+        key: children.key,
+        parent: Default::default(),
+        template: children.template.clone(),
+        root_ids: children.root_ids.clone(),
+        dynamic_nodes: children.dynamic_nodes,
+        dynamic_attrs: children.dynamic_attrs,
+    })
+}
+
+/// The props of a [`Portal`]
+pub struct PortalProps<'a> {
+    mount: ElementId,
+    selector: Option<&'static str>,
+    children: Element<'a>,
+}
+
+/// Builds the [`PortalProps`] of a [`Portal`]
+pub struct PortalBuilder<'a>(PortalProps<'a>);
+impl<'a> PortalBuilder<'a> {
+    /// The element to append the children to, the root of the app by default
+    pub fn mount(mut self, mount: ElementId) -> Self {
+        self.0.mount = mount;
+        self
+    }
+
+    /// A CSS selector of an element outside of the app to append the children to, which takes priority over `mount`
+    pub fn selector(mut self, selector: &'static str) -> Self {
+        self.0.selector = Some(selector);
+        self
+    }
+
+    /// The nodes to append to the mount element
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.0.children = children;
+        self
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> PortalProps<'a> {
+        self.0
+    }
+}
+
+impl<'a> Properties for PortalProps<'a> {
+    type Builder = PortalBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        PortalBuilder(PortalProps {
+            mount: ElementId(0),
+            selector: None,
+            children: None,
+        })
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+/// Where the children of a mounted portal are, and the placeholder that stands in for them
+///
+/// Get it from [`ScopeState::portal_mount`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortalMount {
+    /// The element the children are appended to, which is [`ElementId(0)`](ElementId) for the root of the app
+    pub target: ElementId,
+
+    /// The placeholder in place of the portal among its siblings
+    pub anchor: ElementId,
+}
+
+impl<'b> VirtualDom {
+    /// Move the `m` nodes a scope just created to its portal target, if the scope is a portal
+    ///
+    /// Returns how many nodes are left on the stack for the parent of the scope
+    pub(crate) fn mount_portal(
+        &mut self,
+        scope: ScopeId,
+        parent: &'b VNode<'b>,
+        m: usize,
+    ) -> usize {
+        let target = match self.portal_target(scope) {
+            Some(target) => target,
+            None => return m,
+        };
+
+        self.mutations
+            .push(Mutation::AppendChildren { id: target, m });
+
+        let anchor = self.next_element(parent, &[]);
+        self.mutations
+            .push(Mutation::CreatePlaceholder { id: anchor });
+        self.scopes[scope.0]
+            .portal_mount
+            .set(Some(PortalMount { target, anchor }));
+
+        1
+    }
+
+    /// The element a portal appends its children to, if the scope is a portal
+    fn portal_target(&mut self, scope: ScopeId) -> Option<ElementId> {
+        let scope_state = &self.scopes[scope.0];
+        let target = scope_state.portal_target.get()?;
+        Some(match scope_state.portal_selector.get() {
+            Some(selector) => self.external_root(selector),
+            None => target,
+        })
+    }
+
+    /// The ID of the element outside of the app that a selector finds, asking the renderer for it the first time
+    ///
+    /// The ID is never reclaimed, since the element isn't part of the app
+    fn external_root(&mut self, selector: &'static str) -> ElementId {
+        if let Some(id) = self.external_roots.get(selector) {
+            return *id;
+        }
+
+        let id = self.next_null();
+        self.mutations
+            .push(Mutation::AssignExternalRoot { selector, id });
+        self.external_roots.insert(selector, id);
+        id
+    }
+
+    /// The placeholder that marks the place of a portal among its siblings
+    pub(crate) fn portal_anchor(&self, scope: ScopeId) -> Option<ElementId> {
+        self.scopes[scope.0]
+            .portal_mount
+            .get()
+            .map(|mount| mount.anchor)
+    }
+
+    /// Remove the children of a portal from its target and the placeholder from its parent
+    ///
+    /// The children have to be removed even if the parent is being removed, since they aren't under it
+    pub(crate) fn unmount_portal(&mut self, scope: ScopeId, mount: PortalMount, gen_muts: bool) {
+        match unsafe { self.scopes[scope.0].root_node().extend_lifetime_ref() } {
+            RenderReturn::Ready(t) => self.remove_node(t, true),
+            RenderReturn::Aborted(placeholder) => self.remove_placeholder(placeholder, true),
+        };
+
+        if gen_muts {
+            self.mutations.push(Mutation::Remove { id: mount.anchor });
+        }
+        self.reclaim(mount.anchor);
+    }
+
+    /// Move the children of a portal to its new target if the target changed in the last render
+    pub(crate) fn move_portal(&mut self, scope: ScopeId) {
+        let (mount, target) = match (
+            self.scopes[scope.0].portal_mount.get(),
+            self.portal_target(scope),
+        ) {
+            (Some(mount), Some(target)) if mount.target != target => (mount, target),
+            _ => return,
+        };
+        let scope_state = &self.scopes[scope.0];
+
+        let m = match unsafe { scope_state.root_node().extend_lifetime_ref() } {
+            RenderReturn::Ready(t) => self.push_all_real_nodes(t),
+            RenderReturn::Aborted(placeholder) => {
+                self.mutations.push(Mutation::PushRoot {
+                    id: placeholder.id.get().unwrap(),
+                });
+                1
+            }
+        };
+        self.mutations
+            .push(Mutation::AppendChildren { id: target, m });
+
+        self.scopes[scope.0]
+            .portal_mount
+            .set(Some(PortalMount { target, ..mount }));
+    }
+}
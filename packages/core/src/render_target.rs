@@ -0,0 +1,42 @@
+use crate::{ScopeState, VirtualDom};
+
+/// The medium a [`VirtualDom`] is being rendered for
+///
+/// Renderers provide this as a root context so one component tree can serve both the screen and print-friendly
+/// output. Components read it with [`ScopeState::render_target`].
+///
+/// ```rust, ignore
+/// fn Footer(cx: Scope) -> Element {
+///     match cx.render_target() {
+///         RenderTarget::Screen => render! { button { "Share" } },
+///         RenderTarget::Print => render! { p { "Printed from example.com" } },
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderTarget {
+    /// An interactive display. This is the default if the renderer didn't provide a target.
+    #[default]
+    Screen,
+
+    /// Print media, like a printed page or a PDF export
+    Print,
+}
+
+impl ScopeState {
+    /// Get the [`RenderTarget`] this component is being rendered for
+    ///
+    /// Defaults to [`RenderTarget::Screen`] if no renderer provided one.
+    pub fn render_target(&self) -> RenderTarget {
+        self.consume_context().unwrap_or_default()
+    }
+}
+
+impl VirtualDom {
+    /// Build the virtualdom with the given [`RenderTarget`] provided to every component
+    ///
+    /// This must be called before the first rebuild for components to see the new target.
+    pub fn with_render_target(self, target: RenderTarget) -> Self {
+        self.with_root_context(target)
+    }
+}
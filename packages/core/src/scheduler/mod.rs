@@ -1,5 +1,6 @@
 use crate::ScopeId;
-use slab::Slab;
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
 
 mod task;
 mod wait;
@@ -16,22 +17,43 @@ pub(crate) enum SchedulerMsg {
 
     /// A task has woken and needs to be progressed
     TaskNotified(TaskId),
+
+    /// A message to set in the live region for screen readers
+    Announce(String),
 }
 
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// A layout effect, along with the scope that queued it
+pub(crate) type LayoutEffect = (ScopeId, Box<dyn FnOnce()>);
 
 pub(crate) struct Scheduler {
     pub sender: futures_channel::mpsc::UnboundedSender<SchedulerMsg>,
 
     /// Tasks created with cx.spawn
-    pub tasks: RefCell<Slab<LocalTask>>,
+    pub tasks: RefCell<FxHashMap<TaskId, LocalTask>>,
+
+    /// The ID of the next task, counting up so IDs are never reused
+    pub next_task: Cell<usize>,
+
+    /// Tasks that woke up and are waiting to be polled, with a lane for each [`TaskPriority`]
+    pub woken: RefCell<[VecDeque<TaskId>; TaskPriority::LANES]>,
+
+    /// Layout effects queued while rendering, waiting for the renderer to apply the mutations
+    pub layout_effects: RefCell<Vec<LayoutEffect>>,
 }
 
 impl Scheduler {
     pub fn new(sender: futures_channel::mpsc::UnboundedSender<SchedulerMsg>) -> Rc<Self> {
         Rc::new(Scheduler {
             sender,
-            tasks: RefCell::new(Slab::new()),
+            tasks: RefCell::new(FxHashMap::default()),
+            next_task: Cell::new(0),
+            woken: Default::default(),
+            layout_effects: RefCell::new(Vec::new()),
         })
     }
 }
@@ -11,15 +11,58 @@ use std::task::Waker;
 /// A task's unique identifier.
 ///
 /// `TaskId` is a `usize` that is unique across the entire VirtualDOM and across time. TaskIDs will never be reused
-/// once a Task has been completed.
+/// once a Task has been completed, so a late wakeup for a finished or cancelled task can't resume a newer one.
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct TaskId(pub usize);
 
+/// How urgently a task is polled when it wakes up
+///
+/// Tasks that woke up are polled in lanes: every woken task in a higher lane is polled before any task in a lower lane,
+/// and tasks in the same lane are polled in the order they woke up. This lets tasks that react to input run before
+/// tasks that load data in the background.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum TaskPriority {
+    /// Work that has to happen before anything else, like finishing a transition that is already on screen
+    Immediate,
+
+    /// Work the user is waiting on, like the response to a click or a key press
+    UserBlocking,
+
+    /// Everything else, the priority of tasks spawned with [`crate::ScopeState::spawn`]
+    Normal,
+
+    /// Work that can wait until nothing else is left, like prefetching or logging
+    Idle,
+}
+
+impl TaskPriority {
+    pub(crate) const LANES: usize = 4;
+
+    fn from_lane(lane: usize) -> Self {
+        match lane {
+            0 => Self::Immediate,
+            1 => Self::UserBlocking,
+            2 => Self::Normal,
+            _ => Self::Idle,
+        }
+    }
+}
+
+impl Default for TaskPriority {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
 /// the task itself is the waker
 pub(crate) struct LocalTask {
     pub scope: ScopeId,
+    pub priority: TaskPriority,
     pub(super) task: RefCell<Pin<Box<dyn Future<Output = ()> + 'static>>>,
+
+    /// Built once when the task is spawned and reused for every poll, so wakeups don't allocate
     pub waker: Waker,
 }
 
@@ -33,22 +76,26 @@ impl Scheduler {
     ///
     /// Spawning a future onto the root scope will cause it to be dropped when the root component is dropped - which
     /// will only occur when the VirtuaalDom itself has been dropped.
-    pub fn spawn(&self, scope: ScopeId, task: impl Future<Output = ()> + 'static) -> TaskId {
-        let mut tasks = self.tasks.borrow_mut();
-
-        let entry = tasks.vacant_entry();
-        let task_id = TaskId(entry.key());
+    pub fn spawn(
+        &self,
+        scope: ScopeId,
+        priority: TaskPriority,
+        task: impl Future<Output = ()> + 'static,
+    ) -> TaskId {
+        let task_id = TaskId(self.next_task.get());
+        self.next_task.set(task_id.0 + 1);
 
         let task = LocalTask {
             task: RefCell::new(Box::pin(task)),
             scope,
+            priority,
             waker: futures_util::task::waker(Arc::new(LocalTaskHandle {
                 id: task_id,
                 tx: self.sender.clone(),
             })),
         };
 
-        entry.insert(task);
+        self.tasks.borrow_mut().insert(task_id, task);
 
         self.sender
             .unbounded_send(SchedulerMsg::TaskNotified(task_id))
@@ -57,11 +104,46 @@ impl Scheduler {
         task_id
     }
 
+    /// Queue a task that woke up in the lane for its priority
+    ///
+    /// Wakeups for tasks that are already gone are ignored.
+    pub(crate) fn queue_wakeup(&self, id: TaskId) {
+        let priority = match self.tasks.borrow().get(&id) {
+            Some(task) => task.priority,
+            None => return,
+        };
+        self.woken.borrow_mut()[priority as usize].push_back(id);
+    }
+
+    /// Take the woken task with the highest priority that has waited the longest, skipping lanes below `lowest`
+    pub(crate) fn next_wakeup(&self, lowest: TaskPriority) -> Option<(TaskId, TaskPriority)> {
+        let mut woken = self.woken.borrow_mut();
+        let (lane, id) = woken[..=lowest as usize]
+            .iter_mut()
+            .enumerate()
+            .find_map(|(lane, tasks)| Some((lane, tasks.pop_front()?)))?;
+        Some((id, TaskPriority::from_lane(lane)))
+    }
+
+    /// Check if any task in the given lane woke up and wasn't polled yet
+    pub(crate) fn has_woken(&self, priority: TaskPriority) -> bool {
+        !self.woken.borrow()[priority as usize].is_empty()
+    }
+
     /// Drop the future with the given TaskId
     ///
     /// This does not abort the task, so you'll want to wrap it in an aborthandle if that's important to you
     pub fn remove(&self, id: TaskId) {
-        self.tasks.borrow_mut().try_remove(id.0);
+        self.cancel(id);
+    }
+
+    /// Drop the future with the given TaskId, returning the scope that owned it if it was still running
+    ///
+    /// Wakeups that were already queued for the task are ignored once it's gone.
+    pub(crate) fn cancel(&self, id: TaskId) -> Option<ScopeId> {
+        let task = self.tasks.borrow_mut().remove(&id)?;
+        // The future is dropped after the tasks are released, so dropping it can cancel other tasks
+        Some(task.scope)
     }
 }
 
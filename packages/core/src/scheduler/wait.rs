@@ -1,30 +1,53 @@
-use crate::{TaskId, VirtualDom};
-use std::task::Context;
+use crate::{innerlude::TaskPanic, TaskId, VirtualDom};
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    task::{Context, Poll},
+};
 
 impl VirtualDom {
     /// Handle notifications by tasks inside the scheduler
     ///
     /// This is precise, meaning we won't poll every task, just tasks that have woken up as notified to use by the
     /// queue
+    ///
+    /// A task that panics is dropped, and the panic is thrown to the nearest error boundary of the component that
+    /// spawned it.
     pub(crate) fn handle_task_wakeup(&mut self, id: TaskId) {
         let mut tasks = self.scheduler.tasks.borrow_mut();
 
-        let task = match tasks.get(id.0) {
+        let task = match tasks.get(&id) {
             Some(task) => task,
             // The task was removed from the scheduler, so we can just ignore it
             None => return,
         };
 
+        log::trace!("Polling task {:?} of scope {:?}", id, task.scope);
         let mut cx = Context::from_waker(&task.waker);
 
-        // If the task completes...
-        if task.task.borrow_mut().as_mut().poll(&mut cx).is_ready() {
-            // Remove it from the scope so we dont try to double drop it when the scope dropes
-            let scope = &self.scopes[task.scope.0];
-            scope.spawned_tasks.borrow_mut().remove(&id);
+        let poll = catch_unwind(AssertUnwindSafe(|| {
+            task.task.borrow_mut().as_mut().poll(&mut cx)
+        }));
+
+        // Tasks that are still running stay in the scheduler, finished or panicked tasks are removed
+        if let Ok(Poll::Pending) = poll {
+            return;
+        }
+
+        // Remove it from the scope so we dont try to double drop it when the scope dropes
+        let scope = &self.scopes[task.scope.0];
+        scope.spawned_tasks.borrow_mut().remove(&id);
+
+        // Remove it from the scheduler
+        tasks.remove(&id);
 
-            // Remove it from the scheduler
-            tasks.try_remove(id.0);
+        if let Err(payload) = poll {
+            let panic = TaskPanic::new(payload.as_ref());
+            log::error!(
+                "Task spawned by component `{}` panicked: {}",
+                scope.name(),
+                panic.message
+            );
+            scope.throw(panic);
         }
     }
 }
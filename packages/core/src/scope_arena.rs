@@ -3,7 +3,7 @@ use crate::{
     bump_frame::BumpFrame,
     innerlude::DirtyScope,
     nodes::RenderReturn,
-    scopes::{ScopeId, ScopeState},
+    scopes::{with_rendering_scope, ScopeId, ScopeState},
     virtual_dom::VirtualDom,
 };
 
@@ -17,10 +17,12 @@ impl VirtualDom {
         let entry = self.scopes.vacant_entry();
         let height = unsafe { parent.map(|f| (*f).height + 1).unwrap_or(0) };
         let id = ScopeId(entry.key());
+        self.scopes_created += 1;
 
         entry.insert(Box::new(ScopeState {
             parent,
             id,
+            instance: self.scopes_created,
             height,
             name,
             props: Some(props),
@@ -35,9 +37,29 @@ impl VirtualDom {
             shared_contexts: Default::default(),
             borrowed_props: Default::default(),
             attributes_to_drop: Default::default(),
+            should_update: Default::default(),
+            portal_target: Default::default(),
+            portal_selector: Default::default(),
+            portal_mount: Default::default(),
+            parent_element: Default::default(),
         }))
     }
 
+    /// Check with the predicate from [`crate::Scoped::use_should_update`] if a scope should render with `next` props
+    ///
+    /// Scopes without a predicate always render. Without `next` props, the scope is asked with its current props.
+    pub(crate) fn should_update(&self, scope_id: ScopeId, next: Option<&dyn AnyProps>) -> bool {
+        let scope = &self.scopes[scope_id.0];
+        let should_update = scope.should_update.borrow();
+        match (should_update.as_ref(), scope.props.as_ref()) {
+            (Some(should_update), Some(prev)) => {
+                let prev = prev.props_ptr();
+                should_update(prev, next.map_or(prev, |next| next.props_ptr()))
+            }
+            _ => true,
+        }
+    }
+
     fn acquire_current_scope_raw(&self) -> Option<*const ScopeState> {
         let id = self.scope_stack.last().copied()?;
         let scope = self.scopes.get(id.0)?;
@@ -51,7 +73,7 @@ impl VirtualDom {
         self.ensure_drop_safety(scope_id);
 
         let new_nodes = unsafe {
-            self.scopes[scope_id.0].previous_frame().bump_mut().reset();
+            self.scopes[scope_id.0].previous_frame().reset();
 
             let scope = &self.scopes[scope_id.0];
             scope.suspended.set(false);
@@ -62,7 +84,7 @@ impl VirtualDom {
             let props: &dyn AnyProps = scope.props.as_ref().unwrap().as_ref();
             let props: &dyn AnyProps = std::mem::transmute(props);
 
-            props.render(scope).extend_lifetime()
+            with_rendering_scope(scope, || props.render(scope).extend_lifetime())
         };
 
         let scope = &self.scopes[scope_id.0];
@@ -2,11 +2,11 @@ use crate::{
     any_props::AnyProps,
     any_props::VProps,
     bump_frame::BumpFrame,
-    innerlude::{DynamicNode, EventHandler, VComponent, VText},
-    innerlude::{ErrorBoundary, Scheduler, SchedulerMsg},
+    innerlude::{Announcer, ErrorBoundary, Scheduler, SchedulerMsg, TaskPriority},
+    innerlude::{DynamicNode, EventHandler, PortalMount, VComponent, VText},
     lazynodes::LazyNodes,
-    nodes::{IntoAttributeValue, IntoDynNode, RenderReturn},
-    AnyValue, Attribute, AttributeValue, Element, Event, Properties, TaskId,
+    nodes::{IntoAttributeValue, IntoDynNode, ListenerFlags, RenderReturn},
+    AnyValue, Attribute, AttributeValue, Element, ElementId, Event, Properties, TaskId,
 };
 use bumpalo::{boxed::Box as BumpBox, Bump};
 use rustc_hash::FxHashSet;
@@ -53,6 +53,30 @@ impl<'a, T> std::ops::Deref for Scoped<'a, T> {
     }
 }
 
+impl<'a, T: 'static> Scoped<'a, T> {
+    /// Decide if this component should render, given its previous and next props
+    ///
+    /// When `should_update` returns `false`, the component doesn't render and nothing below it is diffed. It is asked
+    /// when the parent passes new props, and when the component is marked dirty, in which case the previous and next
+    /// props are the same. The predicate from the latest render replaces any earlier ones.
+    ///
+    /// This is an escape hatch for fine control over rendering. Most components are better off with memoized props.
+    ///
+    /// ```rust, ignore
+    /// fn Chart(cx: Scope<ChartProps>) -> Element {
+    ///     cx.use_should_update(|prev, next| prev.points.len() != next.points.len());
+    ///
+    ///     render! { canvas { "{cx.props.points.len()} points" } }
+    /// }
+    /// ```
+    pub fn use_should_update(&self, should_update: impl Fn(&T, &T) -> bool + 'static) {
+        *self.scope.should_update.borrow_mut() = Some(Box::new(move |prev, next| {
+            // Safety: the VirtualDom only asks with the props of this scope, which are always a `T`
+            unsafe { should_update(&*(prev as *const T), &*(next as *const T)) }
+        }));
+    }
+}
+
 /// A component's unique identifier.
 ///
 /// `ScopeId` is a `usize` that acts a key for the internal slab of Scopes. This means that the key is not unqiue across
@@ -62,6 +86,41 @@ impl<'a, T> std::ops::Deref for Scoped<'a, T> {
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
 pub struct ScopeId(pub usize);
 
+thread_local! {
+    // The stack of scopes that are currently running their render function on this thread
+    static RENDERING_SCOPES: RefCell<Vec<*const ScopeState>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Get the [`ScopeId`] of the component that is currently rendering on this thread, if any.
+///
+/// Reactive primitives like signals use this to learn which component read them, so that only that component is
+/// re-rendered when the value changes. Outside of a render (IE in event handlers or tasks) this returns `None`.
+pub fn current_scope_id() -> Option<ScopeId> {
+    with_current_scope(|scope| scope.id)
+}
+
+/// Run a closure with the [`ScopeState`] of the component that is currently rendering on this thread, if any.
+///
+/// This is [`current_scope_id`] for reactive primitives that need more than the id, like a context that cleans up
+/// after the component when it is dropped. Outside of a render this returns `None` without running the closure.
+pub fn with_current_scope<O>(f: impl FnOnce(&ScopeState) -> O) -> Option<O> {
+    let scope = RENDERING_SCOPES.with(|scopes| scopes.borrow().last().copied())?;
+
+    // safety: a scope is only on the stack while it renders, and it can't be dropped in the middle of its render
+    Some(f(unsafe { &*scope }))
+}
+
+/// Run the closure with the given scope marked as the currently rendering scope
+pub(crate) fn with_rendering_scope<O>(scope: &ScopeState, f: impl FnOnce() -> O) -> O {
+    RENDERING_SCOPES.with(|scopes| scopes.borrow_mut().push(scope));
+    let out = f();
+    RENDERING_SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    out
+}
+
+/// Decides from pointers to the previous and next props if a scope should render, set by `use_should_update`
+type ShouldUpdate = Box<dyn Fn(*const (), *const ()) -> bool>;
+
 /// A component's state separate from its props.
 ///
 /// This struct exists to provide a common interface for all scopes without relying on generics.
@@ -74,6 +133,7 @@ pub struct ScopeState {
 
     pub(crate) parent: Option<*const ScopeState>,
     pub(crate) id: ScopeId,
+    pub(crate) instance: usize,
 
     pub(crate) height: u32,
     pub(crate) suspended: Cell<bool>,
@@ -90,6 +150,16 @@ pub struct ScopeState {
     pub(crate) attributes_to_drop: RefCell<Vec<*const Attribute<'static>>>,
 
     pub(crate) props: Option<Box<dyn AnyProps<'static>>>,
+
+    pub(crate) should_update: RefCell<Option<ShouldUpdate>>,
+
+    // The element or selector a `Portal` asked for in its last render, and where its children are mounted
+    pub(crate) portal_target: Cell<Option<ElementId>>,
+    pub(crate) portal_selector: Cell<Option<&'static str>>,
+    pub(crate) portal_mount: Cell<Option<PortalMount>>,
+
+    // The element the component is rendered under, which events bubble to from its root
+    pub(crate) parent_element: Cell<Option<ElementId>>,
 }
 
 impl<'src> ScopeState {
@@ -121,6 +191,21 @@ impl<'src> ScopeState {
         self.render_cnt.get()
     }
 
+    /// Get a number that identifies this instance of the component
+    ///
+    /// Unlike the [`ScopeId`], which is reused once the scope is dropped, no two scopes of a VirtualDom share an
+    /// instance number. This tells apart a scope from a later one that got the same [`ScopeId`].
+    pub fn instance(&self) -> usize {
+        self.instance
+    }
+
+    /// Get where the children of this scope are mounted, if it is a [`crate::Portal`]
+    ///
+    /// Returns `None` for every other scope, and for portals that haven't been mounted yet.
+    pub fn portal_mount(&self) -> Option<PortalMount> {
+        self.portal_mount.get()
+    }
+
     /// Get a handle to the currently active bump arena for this Scope
     ///
     /// This is a bump memory allocator. Be careful using this directly since the contents will be wiped on the next render.
@@ -250,6 +335,16 @@ impl<'src> ScopeState {
             .expect("Scheduler to exist if scope exists");
     }
 
+    /// Get an [`Announcer`] that reads messages out to screen readers
+    ///
+    /// Messages are set in a visually hidden `aria-live` region at the root of the app. The VirtualDom creates the
+    /// region the first time something is announced, and every announcer shares it.
+    pub fn use_announcer(&self) -> &Announcer {
+        self.use_hook(|| Announcer {
+            sender: self.tasks.sender.clone(),
+        })
+    }
+
     /// Return any context of type T if it exists on this scope
     pub fn has_context<T: 'static + Clone>(&self) -> Option<T> {
         self.shared_contexts
@@ -338,15 +433,26 @@ impl<'src> ScopeState {
         // Walk upwards until there is no more parent - and tada we have the root
         while let Some(next_parent) = parent.parent {
             parent = unsafe { &*next_parent };
-            debug_assert_eq!(parent.scope_id(), ScopeId(0));
         }
+        debug_assert_eq!(parent.scope_id(), ScopeId(0));
 
         parent.provide_context(context)
     }
 
     /// Pushes the future onto the poll queue to be polled after the component renders.
     pub fn push_future(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
-        let id = self.tasks.spawn(self.id, fut);
+        self.push_future_with_priority(TaskPriority::Normal, fut)
+    }
+
+    /// Pushes the future onto the poll queue, polling it before or after other tasks depending on its priority
+    ///
+    /// Whenever several tasks have woken up, the ones with a higher [`TaskPriority`] are polled first.
+    pub fn push_future_with_priority(
+        &self,
+        priority: TaskPriority,
+        fut: impl Future<Output = ()> + 'static,
+    ) -> TaskId {
+        let id = self.tasks.spawn(self.id, priority, fut);
         self.spawned_tasks.borrow_mut().insert(id);
         id
     }
@@ -356,12 +462,27 @@ impl<'src> ScopeState {
         self.push_future(fut);
     }
 
+    /// Spawns the future with a [`TaskPriority`] but does not return the [`TaskId`]
+    ///
+    /// ```rust, ignore
+    /// onkeydown: move |evt| cx.spawn_with_priority(TaskPriority::UserBlocking, async move {
+    ///     search.send(evt.key()).await;
+    /// })
+    /// ```
+    pub fn spawn_with_priority(
+        &self,
+        priority: TaskPriority,
+        fut: impl Future<Output = ()> + 'static,
+    ) {
+        self.push_future_with_priority(priority, fut);
+    }
+
     /// Spawn a future that Dioxus won't clean up when this component is unmounted
     ///
     /// This is good for tasks that need to be run after the component has been dropped.
     pub fn spawn_forever(&self, fut: impl Future<Output = ()> + 'static) -> TaskId {
         // The root scope will never be unmounted so we can just add the task at the top of the app
-        let id = self.tasks.spawn(ScopeId(0), fut);
+        let id = self.tasks.spawn(ScopeId(0), TaskPriority::Normal, fut);
 
         // wake up the scheduler if it is sleeping
         self.tasks
@@ -374,11 +495,33 @@ impl<'src> ScopeState {
         id
     }
 
+    /// Queue a callback to run once the renderer has applied the mutations of this render
+    ///
+    /// Layout effects run synchronously when the renderer calls [`crate::VirtualDom::run_layout_effects`], before the
+    /// changes are painted. Effects queued by a component that is unmounted before then never run.
+    pub fn queue_layout_effect(&self, f: impl FnOnce() + 'static) {
+        self.tasks
+            .layout_effects
+            .borrow_mut()
+            .push((self.id, Box::new(f)));
+    }
+
     /// Informs the scheduler that this task is no longer needed and should be removed.
     ///
     /// This drops the task immediately.
     pub fn remove_future(&self, id: TaskId) {
-        self.tasks.remove(id);
+        self.cancel_task(id);
+    }
+
+    /// Cancel a task that was spawned from any component, dropping its future
+    ///
+    /// Use this to stop long-running tasks, like a polling loop that should end when the user navigates away. Any
+    /// wakeup that was already queued for the task is ignored, and cancelling a task that already finished does
+    /// nothing.
+    pub fn cancel_task(&self, id: TaskId) {
+        if self.tasks.cancel(id) == Some(self.id) {
+            self.spawned_tasks.borrow_mut().remove(&id);
+        }
     }
 
     /// Take a lazy [`crate::VNode`] structure and actually build it with the context of the efficient [`bumpalo::Bump`] allocator.
@@ -400,7 +543,7 @@ impl<'src> ScopeState {
         let mut listeners = self.attributes_to_drop.borrow_mut();
         for attr in element.dynamic_attrs {
             match attr.value {
-                AttributeValue::Any(_) | AttributeValue::Listener(_) => {
+                AttributeValue::Any(_) | AttributeValue::Listener(..) => {
                     let unbounded = unsafe { std::mem::transmute(attr as *const Attribute) };
                     listeners.push(unbounded);
                 }
@@ -424,10 +567,28 @@ impl<'src> ScopeState {
 
     /// Create a dynamic text node using [`Arguments`] and the [`ScopeState`]'s internal [`Bump`] allocator
     pub fn text_node(&'src self, args: Arguments) -> DynamicNode<'src> {
-        DynamicNode::Text(VText {
-            value: self.raw_text(args),
-            id: Default::default(),
-        })
+        DynamicNode::Text(VText::new(self.raw_text(args)))
+    }
+
+    /// Create a text node that SSR writes out without escaping
+    ///
+    /// See [`VText::unescaped`] for the safety contract: the text must already be escaped.
+    ///
+    /// ```rust, ignore
+    /// render! {
+    ///     p { cx.unescaped_text_node(format_args!("Fish &amp; Chips")) }
+    /// }
+    /// ```
+    pub fn unescaped_text_node(&'src self, args: Arguments) -> DynamicNode<'src> {
+        DynamicNode::Text(VText::unescaped(self.raw_text(args)))
+    }
+
+    /// Hand a [`String`] over to the [`ScopeState`], and borrow it for as long as the nodes of the current render
+    ///
+    /// Unlike [`Bump::alloc_str`], this doesn't copy the text. The String is freed when the bump of the current render
+    /// is reset.
+    pub fn owned_text(&'src self, text: String) -> &'src str {
+        self.previous_frame().keep_string(text)
     }
 
     /// Allocate some text inside the [`ScopeState`] from [`Arguments`]
@@ -535,7 +696,7 @@ impl<'src> ScopeState {
             }))
         };
 
-        AttributeValue::Listener(RefCell::new(Some(boxed)))
+        AttributeValue::Listener(RefCell::new(Some(boxed)), ListenerFlags::default())
     }
 
     /// Create a new [`AttributeValue`] with a value that implements [`AnyValue`]
@@ -553,8 +714,18 @@ impl<'src> ScopeState {
     ///
     /// The error doesn't need to implement Error or any specific traits since the boundary
     /// itself will downcast the error into a trait object.
+    ///
+    /// A component that is itself an error boundary throws to the boundary above it.
     pub fn throw(&self, error: impl Debug + 'static) -> Option<()> {
-        if let Some(cx) = self.consume_context::<Rc<ErrorBoundary>>() {
+        let boundary = match self.consume_context::<Rc<ErrorBoundary>>() {
+            Some(boundary) if boundary.id() == self.scope_id() => self
+                .parent
+                // safety: all parent pointers are valid thanks to the bump arena
+                .and_then(|parent| unsafe { &*parent }.consume_context::<Rc<ErrorBoundary>>()),
+            boundary => boundary,
+        };
+
+        if let Some(cx) = boundary {
             cx.insert_error(self.scope_id(), Box::new(error));
         }
 
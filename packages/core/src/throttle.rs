@@ -0,0 +1,100 @@
+use crate::ElementId;
+use rustc_hash::FxHashMap;
+
+/// Coalesces bursts of events for listeners with the `throttle` modifier
+///
+/// Renderers pass every event for a throttled listener through [`EventThrottle::offer`] along with when it happened.
+/// The first event in a window is dispatched right away. Later events in the same window are held back, each one
+/// replacing the last, and the newest comes out of [`EventThrottle::flush`] once the window is over. That way the
+/// handler runs at most once per window but still sees where things ended up, like the final scroll position.
+///
+/// Timestamps are in milliseconds and only need to be consistent with each other, so each renderer can use whatever
+/// clock its events come with.
+pub struct EventThrottle<T> {
+    listeners: FxHashMap<(ElementId, String), ThrottledListener<T>>,
+}
+
+struct ThrottledListener<T> {
+    window_start: f64,
+    window: f64,
+    pending: Option<T>,
+}
+
+impl<T> Default for EventThrottle<T> {
+    fn default() -> Self {
+        Self {
+            listeners: FxHashMap::default(),
+        }
+    }
+}
+
+impl<T> EventThrottle<T> {
+    /// Create a new, empty throttle
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Offer an event that happened at `now` for the listener `name` on `id`, throttled to one event every `window_ms`
+    ///
+    /// Returns the event if it should be dispatched right away. Otherwise it's held until the window is over.
+    pub fn offer(
+        &mut self,
+        id: ElementId,
+        name: &str,
+        window_ms: u32,
+        now: f64,
+        event: T,
+    ) -> Option<T> {
+        let key = (id, name.to_string());
+
+        match self.listeners.get_mut(&key) {
+            Some(listener) if now - listener.window_start < listener.window => {
+                listener.pending = Some(event);
+                None
+            }
+            _ => {
+                let listener = ThrottledListener {
+                    window_start: now,
+                    window: window_ms as f64,
+                    pending: None,
+                };
+                self.listeners.insert(key, listener);
+                Some(event)
+            }
+        }
+    }
+
+    /// Take the held back events whose window is over by `now`
+    ///
+    /// Each flushed event starts a new window for its listener.
+    pub fn flush(&mut self, now: f64) -> Vec<(ElementId, String, T)> {
+        let mut ready = Vec::new();
+
+        for ((id, name), listener) in self.listeners.iter_mut() {
+            if now - listener.window_start < listener.window {
+                continue;
+            }
+
+            if let Some(event) = listener.pending.take() {
+                listener.window_start = now;
+                ready.push((*id, name.clone(), event));
+            }
+        }
+
+        ready
+    }
+
+    /// When the earliest held back event will be ready to flush, if there are any
+    pub fn next_deadline(&self) -> Option<f64> {
+        self.listeners
+            .values()
+            .filter(|listener| listener.pending.is_some())
+            .map(|listener| listener.window_start + listener.window)
+            .reduce(f64::min)
+    }
+
+    /// Forget about a listener that was removed, dropping any event held back for it
+    pub fn remove(&mut self, id: ElementId, name: &str) {
+        self.listeners.remove(&(id, name.to_string()));
+    }
+}
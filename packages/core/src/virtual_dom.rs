@@ -4,18 +4,25 @@
 
 use crate::{
     any_props::VProps,
-    arena::{ElementId, ElementRef},
-    innerlude::{DirtyScope, ErrorBoundary, Mutations, Scheduler, SchedulerMsg},
+    arena::{ElementId, ElementPath, ElementRef},
+    innerlude::{DirtyScope, ErrorBoundary, LiveRegion, Mutations, Scheduler, SchedulerMsg},
     mutations::Mutation,
     nodes::RenderReturn,
-    nodes::{Template, TemplateId},
+    nodes::{DynamicNode, ListenerCb, ListenerFlags, Template, TemplateId, VNode},
     scopes::{ScopeId, ScopeState},
-    AttributeValue, Element, Event, Scope,
+    AttributeValue, Element, Event, Scope, TaskId, TaskPriority,
 };
 use futures_util::{pin_mut, StreamExt};
 use rustc_hash::{FxHashMap, FxHashSet};
 use slab::Slab;
-use std::{any::Any, cell::Cell, collections::BTreeSet, future::Future, rc::Rc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::BTreeSet,
+    future::Future,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
 /// A virtual node system that progresses user events and diffs UI trees.
 ///
@@ -177,6 +184,9 @@ pub struct VirtualDom {
     // Maps a template path to a map of byteindexes to templates
     pub(crate) templates: FxHashMap<TemplateId, FxHashMap<usize, Template<'static>>>,
     pub(crate) scopes: Slab<Box<ScopeState>>,
+
+    // How many scopes were ever created, which numbers the instances of scopes
+    pub(crate) scopes_created: usize,
     pub(crate) dirty_scopes: BTreeSet<DirtyScope>,
     pub(crate) scheduler: Rc<Scheduler>,
 
@@ -192,6 +202,36 @@ pub struct VirtualDom {
     pub(crate) rx: futures_channel::mpsc::UnboundedReceiver<SchedulerMsg>,
 
     pub(crate) mutations: Mutations<'static>,
+
+    pub(crate) pending_announcement: Option<String>,
+
+    // Idle tasks are only polled by `process_idle_tasks`
+    pub(crate) defer_idle_tasks: bool,
+
+    // `wait_for_work` already finished for the idle tasks that are waiting
+    pub(crate) idle_tasks_reported: bool,
+
+    // A task in the immediate lane ran since the last render
+    pub(crate) immediate_work: bool,
+
+    pub(crate) live_region: Option<LiveRegion>,
+
+    // The elements outside of the app that portals mount to, by their selector
+    pub(crate) external_roots: FxHashMap<&'static str, ElementId>,
+
+    // The hook types developer tools can inspect
+    #[cfg(feature = "devtools")]
+    pub(crate) inspectors: FxHashMap<std::any::TypeId, crate::devtools::Inspector>,
+
+    // The work done by the frame that is being rendered, and by the last one that was finalized
+    #[cfg(feature = "diff-stats")]
+    pub(crate) frame_stats: crate::diff_stats::DiffStats,
+    #[cfg(feature = "diff-stats")]
+    pub(crate) diff_stats: crate::diff_stats::DiffStats,
+
+    // How often each scope re-rendered, and how many of those renders were wasted
+    #[cfg(feature = "diff-stats")]
+    pub(crate) render_stats: FxHashMap<ScopeId, crate::diff_stats::RenderStats>,
 }
 
 impl VirtualDom {
@@ -219,6 +259,19 @@ impl VirtualDom {
         Self::new_with_props(app, ())
     }
 
+    /// Create a new VirtualDom with initial state that the server embedded in the page
+    ///
+    /// `state_json` is a JSON object that maps ids to values. It is provided to the app as the [`HydratedState`] root
+    /// context, and hooks like `use_hydrated_state` and `use_hydrated_query` take their values from it on the first
+    /// render instead of computing or fetching them again.
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new_with_state(app, r#"{ "user": { "name": "jane" } }"#);
+    /// ```
+    pub fn new_with_state(app: fn(Scope) -> Element, state_json: &str) -> Self {
+        Self::new(app).with_root_context(HydratedState::new(state_json))
+    }
+
     /// Create a new VirtualDom with the given properties for the root component.
     ///
     /// # Description
@@ -256,11 +309,26 @@ impl VirtualDom {
             scheduler: Scheduler::new(tx),
             templates: Default::default(),
             scopes: Default::default(),
+            scopes_created: 0,
             elements: Default::default(),
             scope_stack: Vec::new(),
             dirty_scopes: BTreeSet::new(),
             suspended_scopes: FxHashSet::default(),
             mutations: Mutations::default(),
+            pending_announcement: None,
+            defer_idle_tasks: false,
+            idle_tasks_reported: false,
+            immediate_work: false,
+            live_region: None,
+            external_roots: FxHashMap::default(),
+            #[cfg(feature = "devtools")]
+            inspectors: FxHashMap::default(),
+            #[cfg(feature = "diff-stats")]
+            frame_stats: Default::default(),
+            #[cfg(feature = "diff-stats")]
+            diff_stats: Default::default(),
+            #[cfg(feature = "diff-stats")]
+            render_stats: FxHashMap::default(),
         };
 
         let root = dom.new_scope(
@@ -333,7 +401,12 @@ impl VirtualDom {
         With the target path, we try and move up to the parent until there is no parent.
         Due to how bubbling works, we call the listeners before walking to the parent.
 
-        If we wanted to do capturing, then we would accumulate all the listeners and call them in reverse order.
+        The parent of a template is the element it is rendered under in the VirtualDom, not in the real dom. Events in
+        the children of a portal bubble to the element the portal is rendered in, not to the element they are mounted to.
+
+        Listeners marked with `capture` are called first, from the root down to the target, before the other listeners
+        are called from the target up to the root. If the event doesn't bubble, only the listener on the target is
+        called after the capture listeners, like in the DOM.
         ----------------------
 
         For a visual demonstration, here we present a tree on the left and whether or not a listener is collected on the
@@ -351,81 +424,73 @@ impl VirtualDom {
 
         // We will clone this later. The data itself is wrapped in RC to be used in callbacks if required
         let uievent = Event {
-            propagates: Rc::new(Cell::new(bubbles)),
+            propagates: Rc::new(Cell::new(true)),
             data,
         };
 
-        // If the event bubbles, we traverse through the tree until we find the target element.
-        if bubbles {
-            // Loop through each dynamic attribute (in a depth first order) in this template before moving up to the template's parent.
-            while let Some(el_ref) = parent_path {
-                // safety: we maintain references of all vnodes in the element slab
-                if let Some(template) = el_ref.template {
-                    let template = unsafe { template.as_ref() };
-                    let node_template = template.template.get();
-                    let target_path = el_ref.path;
-
-                    for (idx, attr) in template.dynamic_attrs.iter().enumerate() {
-                        let this_path = node_template.attr_paths[idx];
-
-                        // Remove the "on" prefix if it exists, TODO, we should remove this and settle on one
-                        if attr.name.trim_start_matches("on") == name
-                            && target_path.is_decendant(&this_path)
-                        {
-                            listeners.push(&attr.value);
-
-                            // Break if this is the exact target element.
-                            // This means we won't call two listeners with the same name on the same element. This should be
-                            // documented, or be rejected from the rsx! macro outright
-                            if target_path == this_path {
-                                break;
-                            }
-                        }
-                    }
+        // Collect the listeners from the target up to the root, moving up one template at a time, and remember which
+        // of them are on the target itself
+        let mut in_target_template = true;
+        while let Some(el_ref) = parent_path {
+            // safety: we maintain references of all vnodes in the element slab
+            if let Some(template) = el_ref.template {
+                let template = unsafe { template.as_ref() };
+                let node_template = template.template.get();
+                let target_path = el_ref.path;
+                let start = listeners.len();
+
+                // Loop through each dynamic attribute (in a depth first order) in this template
+                for (idx, attr) in template.dynamic_attrs.iter().enumerate() {
+                    let this_path = node_template.attr_paths[idx];
 
-                    // Now that we've accumulated all the parent attributes for the target element, call them in reverse order
-                    // We check the bubble state between each call to see if the event has been stopped from bubbling
-                    for listener in listeners.drain(..).rev() {
-                        if let AttributeValue::Listener(listener) = listener {
-                            if let Some(cb) = listener.borrow_mut().as_deref_mut() {
-                                cb(uievent.clone());
-                            }
-
-                            if !uievent.propagates.get() {
-                                return;
-                            }
+                    // Remove the "on" prefix if it exists, TODO, we should remove this and settle on one
+                    if attr.name.trim_start_matches("on") == name
+                        && target_path.is_decendant(&this_path)
+                    {
+                        let on_target = in_target_template && target_path == this_path;
+                        listeners.push((&attr.value, on_target));
+
+                        // Break if this is the exact target element.
+                        // This means we won't call two listeners with the same name on the same element. This should be
+                        // documented, or be rejected from the rsx! macro outright
+                        if target_path == this_path {
+                            break;
                         }
                     }
-
-                    parent_path = template.parent.and_then(|id| self.elements.get(id.0));
-                } else {
-                    break;
                 }
+
+                // The attributes of a template are in depth first order, so its deepest listeners come last
+                listeners[start..].reverse();
+
+                in_target_template = false;
+                parent_path = template.parent.get().and_then(|id| self.elements.get(id.0));
+            } else {
+                break;
             }
-        } else {
-            // Otherwise, we just call the listener on the target element
-            if let Some(el_ref) = parent_path {
-                // safety: we maintain references of all vnodes in the element slab
-                if let Some(template) = el_ref.template {
-                    let template = unsafe { template.as_ref() };
-                    let node_template = template.template.get();
-                    let target_path = el_ref.path;
-
-                    for (idx, attr) in template.dynamic_attrs.iter().enumerate() {
-                        let this_path = node_template.attr_paths[idx];
-
-                        // Remove the "on" prefix if it exists, TODO, we should remove this and settle on one
-                        // Only call the listener if this is the exact target element.
-                        if attr.name.trim_start_matches("on") == name && target_path == this_path {
-                            if let AttributeValue::Listener(listener) = &attr.value {
-                                if let Some(cb) = listener.borrow_mut().as_deref_mut() {
-                                    cb(uievent.clone());
-                                }
-
-                                break;
-                            }
-                        }
-                    }
+        }
+
+        // Capture listeners are called on the way down to the target, and the rest as the event bubbles back up. Events
+        // that don't bubble still go through the capture listeners, but only reach the listener on the target after.
+        // We check the bubble state between each call to see if the event has been stopped from propagating
+        let is_capture = |listener: &AttributeValue| match listener {
+            AttributeValue::Listener(_, flags) => flags.capture,
+            _ => false,
+        };
+        let capturing = listeners.iter().rev().filter(|(l, _)| is_capture(l));
+        let bubbling = listeners
+            .iter()
+            .filter(|(l, on_target)| !is_capture(l) && (bubbles || *on_target));
+
+        for (listener, _) in capturing.chain(bubbling) {
+            if let AttributeValue::Listener(listener, flags) = listener {
+                call_listener(listener, flags, &uievent);
+
+                if flags.stop_propagation {
+                    uievent.propagates.set(false);
+                }
+
+                if !uievent.propagates.get() {
+                    return;
                 }
             }
         }
@@ -448,59 +513,159 @@ impl VirtualDom {
     /// let sender = dom.get_scheduler_channel();
     /// ```
     pub async fn wait_for_work(&mut self) {
-        let mut some_msg = None;
-
         loop {
-            match some_msg.take() {
-                // If a bunch of messages are ready in a sequence, try to pop them off synchronously
-                Some(msg) => match msg {
-                    SchedulerMsg::Immediate(id) => self.mark_dirty(id),
-                    SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
-                },
-
-                // If they're not ready, then we should wait for them to be ready
-                None => {
-                    match self.rx.try_next() {
-                        Ok(Some(val)) => some_msg = Some(val),
-                        Ok(None) => return,
-                        Err(_) => {
-                            // If we have any dirty scopes, or finished fiber trees then we should exit
-                            if !self.dirty_scopes.is_empty() || !self.suspended_scopes.is_empty() {
-                                return;
-                            }
-
-                            some_msg = self.rx.next().await
-                        }
-                    }
-                }
+            // Handle every message that is ready in one go, so woken tasks are polled in order of priority
+            self.process_events();
+
+            // If we have any dirty scopes, or finished fiber trees then we should exit
+            if !self.dirty_scopes.is_empty()
+                || !self.suspended_scopes.is_empty()
+                || self.pending_announcement.is_some()
+            {
+                return;
+            }
+
+            // Only finish once for waiting idle tasks, so renderers can wait for other work while they find spare time
+            if self.has_idle_tasks() && !self.idle_tasks_reported {
+                self.idle_tasks_reported = true;
+                return;
+            }
+
+            // If they're not ready, then we should wait for them to be ready
+            match self.rx.next().await {
+                Some(msg) => self.handle_message(msg),
+                None => return,
+            }
+        }
+    }
+
+    /// Cancel a task, dropping its future
+    ///
+    /// This is the same as [`ScopeState::cancel_task`], for code that runs outside of components. Any wakeup that
+    /// was already queued for the task is ignored, and cancelling a task that already finished does nothing.
+    pub fn cancel_task(&mut self, id: TaskId) {
+        if let Some(scope) = self.scheduler.cancel(id) {
+            if let Some(scope) = self.scopes.get(scope.0) {
+                scope.spawned_tasks.borrow_mut().remove(&id);
             }
         }
     }
 
     /// Process all events in the queue until there are no more left
+    ///
+    /// Tasks that woke up are polled by [`crate::TaskPriority`]. Messages are taken from the queue again after each
+    /// poll, so a task woken by another one can still run before the tasks of a lower priority that were already waiting.
+    ///
+    /// If idle tasks are deferred with [`VirtualDom::with_deferred_idle_tasks`], they are left for
+    /// [`VirtualDom::process_idle_tasks`].
     pub fn process_events(&mut self) {
-        while let Ok(Some(msg)) = self.rx.try_next() {
-            match msg {
-                SchedulerMsg::Immediate(id) => self.mark_dirty(id),
-                SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
+        let lowest = match self.defer_idle_tasks {
+            true => TaskPriority::Normal,
+            false => TaskPriority::Idle,
+        };
+        self.poll_woken_tasks(lowest);
+    }
+
+    /// Poll the woken tasks of every lane, including the idle tasks that [`VirtualDom::process_events`] left behind
+    ///
+    /// Renderers that defer idle tasks call this when the platform has some spare time, like in a
+    /// `requestIdleCallback` on the web.
+    pub fn process_idle_tasks(&mut self) {
+        self.idle_tasks_reported = false;
+        self.poll_woken_tasks(TaskPriority::Idle);
+    }
+
+    /// Check if any deferred idle task woke up and is waiting for [`VirtualDom::process_idle_tasks`]
+    pub fn has_idle_tasks(&self) -> bool {
+        self.defer_idle_tasks && self.scheduler.has_woken(TaskPriority::Idle)
+    }
+
+    /// Check if a task in the [`TaskPriority::Immediate`] lane ran since the last render
+    ///
+    /// Renderers that wait for a good time to apply their edits, like the next animation frame, should render
+    /// right away instead.
+    pub fn has_immediate_work(&self) -> bool {
+        self.immediate_work
+    }
+
+    /// Leave woken tasks in the [`TaskPriority::Idle`] lane for [`VirtualDom::process_idle_tasks`]
+    ///
+    /// By default every woken task is polled before rendering. Renderers that can find spare time for low priority
+    /// work turn this on so idle tasks don't delay the next frame. [`VirtualDom::wait_for_work`] finishes once when an
+    /// idle task is waiting, so it can be scheduled, and then only for other work until
+    /// [`VirtualDom::process_idle_tasks`] is called.
+    pub fn with_deferred_idle_tasks(mut self, defer: bool) -> Self {
+        self.defer_idle_tasks = defer;
+        self
+    }
+
+    fn poll_woken_tasks(&mut self, lowest: TaskPriority) {
+        loop {
+            while let Ok(msg) = self.rx.try_recv() {
+                self.handle_message(msg);
+            }
+
+            match self.scheduler.next_wakeup(lowest) {
+                Some((task, priority)) => {
+                    self.immediate_work |= priority == TaskPriority::Immediate;
+                    self.handle_task_wakeup(task);
+                }
+                None => return,
+            }
+        }
+    }
+
+    fn handle_message(&mut self, msg: SchedulerMsg) {
+        match msg {
+            SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+            SchedulerMsg::TaskNotified(task) => self.scheduler.queue_wakeup(task),
+            SchedulerMsg::Announce(message) => self.pending_announcement = Some(message),
+        }
+    }
+
+    /// Run the layout effects queued by the last render
+    ///
+    /// Renderers call this right after applying the mutations from [`VirtualDom::rebuild`] or
+    /// [`VirtualDom::render_immediate`], before the changes are painted, so layout effects can read the real layout
+    /// and make changes that show up in the same frame.
+    ///
+    /// Renderers that apply the mutations in another process (like desktop and liveview) call this once the mutations
+    /// were sent, so anything the effects send afterwards is applied after the mutations. Renderers without a layout
+    /// (like SSR) don't call this. Any layout effects that weren't run are dropped when the next render starts.
+    pub fn run_layout_effects(&mut self) {
+        // Effects can queue more effects, which run in the same pass
+        loop {
+            let effects = std::mem::take(&mut *self.scheduler.layout_effects.borrow_mut());
+            if effects.is_empty() {
+                return;
+            }
+
+            for (_, effect) in effects {
+                effect();
             }
         }
     }
 
+    fn discard_layout_effects(&mut self) {
+        self.scheduler.layout_effects.borrow_mut().clear();
+    }
+
     /// Replace a template at runtime. This will re-render all components that use this template.
     /// This is the primitive that enables hot-reloading.
     ///
     /// The caller must ensure that the template refrences the same dynamic attributes and nodes as the original template.
     ///
     /// This will only replace the the parent template, not any nested templates.
+    ///
+    /// A component renders again if it uses the template anywhere in its own nodes, including in lists and fragments,
+    /// but not in the nodes of its child components.
     pub fn replace_template(&mut self, template: Template<'static>) {
         self.register_template_first_byte_index(template);
+        let path = template.name.rsplit_once(':').unwrap().0;
         // iterating a slab is very inefficient, but this is a rare operation that will only happen during development so it's fine
         for (_, scope) in self.scopes.iter() {
             if let Some(RenderReturn::Ready(sync)) = scope.try_root_node() {
-                if sync.template.get().name.rsplit_once(':').unwrap().0
-                    == template.name.rsplit_once(':').unwrap().0
-                {
+                if uses_template(sync, path) {
                     let height = scope.height;
                     self.dirty_scopes.insert(DirtyScope {
                         height,
@@ -532,6 +697,10 @@ impl VirtualDom {
     /// apply_edits(edits);
     /// ```
     pub fn rebuild(&mut self) -> Mutations {
+        self.discard_layout_effects();
+        #[cfg(feature = "diff-stats")]
+        let timer = crate::diff_stats::DiffStats::timer();
+        self.mutations.begin_scope(ScopeId(0));
         match unsafe { self.run_scope(ScopeId(0)).extend_lifetime_ref() } {
             // Rebuilding implies we append the created elements to the root
             RenderReturn::Ready(node) => {
@@ -550,9 +719,46 @@ impl VirtualDom {
             }
         }
 
+        #[cfg(feature = "diff-stats")]
+        self.frame_stats.record_time(timer);
+
         self.finalize()
     }
 
+    /// Throw away an element root of a template and create it again from scratch
+    ///
+    /// Renderers can use this to repair part of their tree that doesn't match the VirtualDom, like when the HTML from
+    /// the server diverges from the client during hydration. `root` must be the [`ElementId`] of an element that is the
+    /// root of a template rendered by `scope`. Any components under the root are dropped and created again.
+    ///
+    /// The returned mutations replace the node mounted at `root` with the new one. Returns `None` without changing
+    /// anything if `scope` doesn't exist or `root` isn't the root of a template.
+    pub fn replace_template_root(&mut self, scope: ScopeId, root: ElementId) -> Option<Mutations> {
+        self.get_scope(scope)?;
+        let (template, root_idx) = match self.elements.get(root.0) {
+            Some(ElementRef {
+                template: Some(template),
+                path: ElementPath::Root(idx),
+            }) => (*template, *idx),
+            _ => return None,
+        };
+
+        // safety: we maintain references of all vnodes in the element slab
+        let node = unsafe { template.as_ref() };
+
+        self.mutations.begin_scope(scope);
+        self.scope_stack.push(scope);
+        self.remove_template_root_children(node, root_idx);
+        self.create_template_root(node, root_idx);
+        self.scope_stack.pop();
+
+        self.mutations
+            .push(Mutation::ReplaceWith { id: root, m: 1 });
+        self.reclaim(root);
+
+        Some(self.finalize())
+    }
+
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
     /// suspended subtrees.
     pub fn render_immediate(&mut self) -> Mutations {
@@ -574,19 +780,87 @@ impl VirtualDom {
     /// Render the virtual dom, waiting for all suspense to be finished
     ///
     /// The mutations will be thrown out, so it's best to use this method for things like SSR that have async content
+    ///
+    /// Scopes that resolve can render new components that suspend in turn, so this keeps going until
+    /// [`VirtualDom::pending_suspense_count`] is zero, not just until the scopes that were suspended at the start resolve.
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(app);
+    /// _ = dom.rebuild();
+    /// dom.wait_for_suspense().await;
+    /// let html = dioxus_ssr::pre_render(&dom);
+    /// ```
     pub async fn wait_for_suspense(&mut self) {
-        loop {
-            // println!("waiting for suspense {:?}", self.suspended_scopes);
-            if self.suspended_scopes.is_empty() {
-                return;
+        while !self.suspended_scopes.is_empty() {
+            log::trace!("Waiting for suspended scopes {:?}", self.suspended_scopes);
+            let mutations = self.wait_for_suspense_step().await;
+            log::trace!("Rendered while waiting for suspense: {:?}", mutations.edits);
+        }
+
+        log::debug!("All suspended scopes resolved");
+    }
+
+    /// Render the virtual dom, waiting for all suspense to be finished or for `deadline` to resolve, whichever comes
+    /// first
+    ///
+    /// This is [`VirtualDom::wait_for_suspense`] for servers that shouldn't hold a response forever on a slow future.
+    /// When the deadline passes, this only stops waiting: scopes that are still suspended stay suspended, and nothing
+    /// about what they have mounted changes. Returns `true` if every suspended scope resolved in time, so callers can
+    /// decide what to do with the ones that didn't.
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(app);
+    /// _ = dom.rebuild();
+    /// dom.wait_for_suspense_with_deadline(tokio::time::sleep(Duration::from_millis(200))).await;
+    /// let html = dioxus_ssr::pre_render(&dom);
+    /// ```
+    pub async fn wait_for_suspense_with_deadline(
+        &mut self,
+        deadline: impl Future<Output = ()>,
+    ) -> bool {
+        pin_mut!(deadline);
+
+        while !self.suspended_scopes.is_empty() {
+            let timed_out = {
+                let step = self.wait_for_suspense_step();
+                pin_mut!(step);
+
+                use futures_util::future::{select, Either};
+                matches!(select(&mut deadline, step).await, Either::Left(_))
+            };
+
+            if timed_out {
+                log::debug!(
+                    "Gave up waiting for suspended scopes {:?}",
+                    self.suspended_scopes
+                );
+                return false;
             }
+        }
 
-            // println!("waiting for suspense");
-            self.wait_for_work().await;
+        true
+    }
 
-            // println!("Rendered immediately");
-            _ = self.render_immediate();
+    /// Wait for the next message from the scheduler, like a task of a suspended scope waking up, and render what is
+    /// ready
+    ///
+    /// This is one step of [`VirtualDom::wait_for_suspense`], for renderers that show each suspended scope as soon as
+    /// it resolves instead of waiting for all of them. Suspended scopes only resolve once one of their tasks wakes up,
+    /// so this waits for a message instead of rendering in a loop that never yields to the executor.
+    pub async fn wait_for_suspense_step(&mut self) -> Mutations {
+        if let Some(msg) = self.rx.next().await {
+            self.handle_message(msg);
         }
+
+        self.render_immediate()
+    }
+
+    /// Get how many scopes are suspended, waiting on their futures to render
+    ///
+    /// A scope that resolves can render children that suspend in turn, so the count doesn't only go down between
+    /// renders.
+    pub fn pending_suspense_count(&self) -> usize {
+        self.suspended_scopes.len()
     }
 
     /// Render what you can given the timeline and then move on
@@ -597,23 +871,13 @@ impl VirtualDom {
     pub async fn render_with_deadline(&mut self, deadline: impl Future<Output = ()>) -> Mutations {
         pin_mut!(deadline);
 
+        self.discard_layout_effects();
         self.process_events();
 
         loop {
             // Next, diff any dirty scopes
             // We choose not to poll the deadline since we complete pretty quickly anyways
-            if let Some(dirty) = self.dirty_scopes.iter().next().cloned() {
-                self.dirty_scopes.remove(&dirty);
-
-                // If the scope doesn't exist for whatever reason, then we should skip it
-                if !self.scopes.contains(dirty.id.0) {
-                    continue;
-                }
-
-                // Run the scope and get the mutations
-                self.run_scope(dirty.id);
-                self.diff_scope(dirty.id);
-            }
+            self.render_next_dirty_scope();
 
             // If there's more work, then just continue, plenty of work to do
             if !self.dirty_scopes.is_empty() {
@@ -636,9 +900,105 @@ impl VirtualDom {
         }
     }
 
+    /// Render dirty scopes and handle async wakeups until there is no more work or the budget runs out
+    ///
+    /// This is a bounded version of [`VirtualDom::render_immediate`] for custom event loops that can't let a single
+    /// render block them for long. The budget is checked before each dirty scope is rendered, so a scope is never cut
+    /// in half, and the mutations can end up slightly over `max_mutations`. Passing [`Duration::MAX`] skips reading the
+    /// clock, for targets that don't have one.
+    ///
+    /// If the returned status is [`WorkStatus::Remaining`], apply the mutations and call this again to pick up where it
+    /// left off. Suspended scopes aren't counted as remaining work, since they are waiting on their futures.
+    ///
+    /// ```rust, ignore
+    /// loop {
+    ///     let (mutations, status) = dom.process_with_budget(500, Duration::from_millis(4));
+    ///     apply(mutations);
+    ///     if status == WorkStatus::Finished {
+    ///         break;
+    ///     }
+    ///     yield_to_host();
+    /// }
+    /// ```
+    pub fn process_with_budget(
+        &mut self,
+        max_mutations: usize,
+        max_duration: Duration,
+    ) -> (Mutations, WorkStatus) {
+        let started = (max_duration != Duration::MAX).then(Instant::now);
+        let exhausted = |dom: &Self| {
+            dom.mutations.edits.len() >= max_mutations
+                || started.map_or(false, |started| started.elapsed() >= max_duration)
+        };
+
+        self.discard_layout_effects();
+
+        loop {
+            self.process_events();
+
+            if self.dirty_scopes.is_empty() {
+                return (self.finalize(), WorkStatus::Finished);
+            }
+
+            while !self.dirty_scopes.is_empty() {
+                if exhausted(self) {
+                    return (self.finalize(), WorkStatus::Remaining);
+                }
+                self.render_next_dirty_scope();
+            }
+        }
+    }
+
+    /// Run and diff the highest dirty scope, if there is one
+    fn render_next_dirty_scope(&mut self) {
+        let dirty = match self.dirty_scopes.iter().next().cloned() {
+            Some(dirty) => dirty,
+            None => return,
+        };
+        self.dirty_scopes.remove(&dirty);
+
+        // If the scope doesn't exist for whatever reason, then we should skip it
+        if !self.scopes.contains(dirty.id.0) {
+            return;
+        }
+
+        // Components can opt out of rendering
+        if !self.should_update(dirty.id, None) {
+            return;
+        }
+
+        #[cfg(feature = "diff-stats")]
+        let timer = crate::diff_stats::DiffStats::timer();
+
+        // Run the scope and get the mutations
+        self.run_scope(dirty.id);
+        self.mutations.begin_scope(dirty.id);
+        self.diff_scope(dirty.id);
+
+        #[cfg(feature = "diff-stats")]
+        self.frame_stats.record_time(timer);
+    }
+
     /// Swap the current mutations with a new
-    fn finalize(&mut self) -> Mutations {
-        std::mem::take(&mut self.mutations)
+    pub(crate) fn finalize(&mut self) -> Mutations {
+        let announced = self.flush_announcement();
+        self.mutations.dedup_sets();
+
+        #[cfg(feature = "diff-stats")]
+        self.finish_diff_stats();
+
+        self.immediate_work = false;
+        let mut mutations: Mutations = std::mem::take(&mut self.mutations);
+
+        // The mutations borrow the VirtualDom, so the message can't be replaced until they are dropped
+        if let (Some(id), Some(region)) = (announced, &self.live_region) {
+            mutations.edits.push(Mutation::SetText {
+                value: region.message(),
+                id,
+            });
+        }
+
+        mutations
     }
 }
 
@@ -648,3 +1008,59 @@ impl Drop for VirtualDom {
         self.drop_scope(ScopeId(0), true);
     }
 }
+
+/// Call a listener with an event, dropping its handler afterwards if it should only be called once
+fn call_listener(
+    listener: &RefCell<Option<ListenerCb>>,
+    flags: &ListenerFlags,
+    event: &Event<dyn Any>,
+) {
+    let mut listener = listener.borrow_mut();
+    if let Some(cb) = listener.as_deref_mut() {
+        cb(event.clone());
+    }
+    if flags.once {
+        listener.take();
+    }
+}
+
+/// Whether [`VirtualDom::process_with_budget`] finished all of its work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkStatus {
+    /// There are no dirty scopes or queued events left
+    Finished,
+
+    /// The budget ran out before every dirty scope was rendered
+    Remaining,
+}
+
+/// The initial state that the VirtualDom was mounted with in [`VirtualDom::new_with_state`]
+///
+/// This is the raw JSON the server embedded in the page. Hooks parse the values they need out of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HydratedState {
+    json: Rc<str>,
+}
+
+impl HydratedState {
+    /// Wrap the JSON of some initial state
+    pub fn new(json: &str) -> Self {
+        Self { json: json.into() }
+    }
+
+    /// Get the JSON of the initial state
+    pub fn json(&self) -> &str {
+        &self.json
+    }
+}
+
+/// Check if a node or any node in its fragments is an instance of the template at `path`
+fn uses_template(node: &VNode, path: &str) -> bool {
+    node.template.get().name.rsplit_once(':').unwrap().0 == path
+        || node.dynamic_nodes.iter().any(|dynamic| match dynamic {
+            DynamicNode::Fragment(children) => {
+                children.iter().any(|child| uses_template(child, path))
+            }
+            _ => false,
+        })
+}
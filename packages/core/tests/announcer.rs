@@ -0,0 +1,90 @@
+//! Announcements for screen readers go through a live region at the root of the app
+use dioxus::core::{Announcer, ElementId, Mutation};
+use dioxus::prelude::*;
+use std::{cell::RefCell, time::Duration};
+
+thread_local! {
+    static ANNOUNCER: RefCell<Option<Announcer>> = RefCell::new(None);
+}
+
+fn app(cx: Scope) -> Element {
+    let announcer = cx.use_announcer();
+    ANNOUNCER.with(|a| *a.borrow_mut() = Some(announcer.clone()));
+
+    render! { div { "hello" } }
+}
+
+fn announce(message: &str) {
+    ANNOUNCER.with(|a| a.borrow().as_ref().unwrap().announce(message));
+}
+
+#[test]
+fn announce_sets_text_of_live_region() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // The first announcement creates the live region at the root, then sets its text
+    announce("Saved");
+    let edits = dom.render_immediate();
+    assert!(edits
+        .templates
+        .iter()
+        .any(|template| template.name.contains("live-region")));
+
+    assert_eq!(edits.edits.len(), 4);
+    assert!(matches!(edits.edits[0], Mutation::LoadTemplate { .. }));
+    let text = match edits.edits[1] {
+        Mutation::HydrateText { value: "", id, .. } => id,
+        ref other => panic!("the region should start empty, got {:?}", other),
+    };
+    assert_eq!(
+        edits.edits[2],
+        Mutation::AppendChildren { id: ElementId(0), m: 1 }
+    );
+    assert_eq!(
+        edits.edits[3],
+        Mutation::SetText { value: "Saved", id: text }
+    );
+
+    drop(edits);
+
+    // Later announcements reuse the region
+    announce("Saved again");
+    let edits = dom.render_immediate();
+    assert!(edits.templates.is_empty());
+    assert_eq!(
+        edits.edits,
+        [Mutation::SetText { value: "Saved again", id: text }]
+    );
+}
+
+#[test]
+fn only_the_last_announcement_of_a_batch_is_set() {
+    fn app(cx: Scope) -> Element {
+        let announcer = cx.use_announcer();
+        ANNOUNCER.with(|a| *a.borrow_mut() = Some(announcer.clone()));
+
+        // Announce while rendering, so the message arrives after the batch started
+        if cx.generation() > 0 {
+            announcer.announce(format!("Rendered {}", cx.generation()));
+        }
+
+        render! { div { "hello" } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    announce("Saved");
+    dom.mark_dirty(ScopeId(0));
+    let (edits, _) = dom.process_with_budget(usize::MAX, Duration::MAX);
+    let set_text: Vec<_> = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::SetText { value, .. } => Some(*value),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(set_text, ["Rendered 1"]);
+}
@@ -0,0 +1,104 @@
+//! `bind_value` and `bind_checked` keep an input and its state in sync
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*, Mutations};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+fn app(cx: Scope) -> Element {
+    let text = use_state(cx, || "hello".to_string());
+    let flavor = use_state(cx, || "vanilla".to_string());
+    let done = use_state(cx, || false);
+
+    render! {
+        input { bind_value: text }
+        select { bind_value: (flavor.get(), flavor.setter()),
+            option { value: "vanilla", "Vanilla" }
+            option { value: "chocolate", "Chocolate" }
+        }
+        input {
+            r#type: "checkbox",
+            bind_checked: (**done, move |checked| done.modify(|_| checked)),
+        }
+    }
+}
+
+/// The attributes the renderer was asked to set, as text
+fn attribute_writes<'a>(edits: &Mutations<'a>) -> Vec<(&'a str, String)> {
+    edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            SetAttribute { name, value, .. } => Some((
+                *name,
+                match value {
+                    BorrowedAttributeValue::Text(value) => value.to_string(),
+                    BorrowedAttributeValue::Bool(value) => value.to_string(),
+                    _ => return None,
+                },
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn type_into(dom: &mut VirtualDom, id: ElementId, value: &str) {
+    let data = FormData { value: value.to_string(), values: Default::default(), files: None };
+    dom.handle_event("input", Rc::new(data), id, true);
+}
+
+#[test]
+fn bindings_follow_input_events() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+    assert_eq!(
+        attribute_writes(&edits),
+        [
+            ("value", "hello".to_string()),
+            ("value", "vanilla".to_string()),
+            ("checked", "false".to_string()),
+        ]
+    );
+
+    let inputs: Vec<ElementId> = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener { name: "input", id, .. } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let (text, flavor, done) = (inputs[0], inputs[1], inputs[2]);
+    drop(edits);
+
+    // `value` is volatile, so both bound values are sent on every render. Renderers only assign the ones that changed.
+
+    // A plain `UseState`
+    type_into(&mut dom, text, "hello world");
+    assert_eq!(
+        attribute_writes(&dom.render_immediate()),
+        [
+            ("value", "hello world".to_string()),
+            ("value", "vanilla".to_string()),
+        ]
+    );
+
+    // A value and its setter
+    type_into(&mut dom, flavor, "chocolate");
+    assert_eq!(
+        attribute_writes(&dom.render_immediate()),
+        [
+            ("value", "hello world".to_string()),
+            ("value", "chocolate".to_string()),
+        ]
+    );
+
+    // A value and a closure that updates the state
+    type_into(&mut dom, done, "true");
+    assert_eq!(
+        attribute_writes(&dom.render_immediate()),
+        [
+            ("value", "hello world".to_string()),
+            ("value", "chocolate".to_string()),
+            ("checked", "true".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,66 @@
+//! Fully static templates are encoded into bytes at compile time and decoded when they are first rendered
+use dioxus::core::{
+    compact_template, compact_template_len, Template, TemplateAttribute, TemplateNode,
+};
+use dioxus::prelude::*;
+
+const CARD: &[TemplateNode] = &[TemplateNode::Element {
+    tag: "div",
+    namespace: None,
+    attrs: &[TemplateAttribute::Static { name: "class", value: "card", namespace: None }],
+    children: &[
+        TemplateNode::Text { text: "hello" },
+        TemplateNode::Element {
+            tag: "svg",
+            namespace: Some("http://www.w3.org/2000/svg"),
+            attrs: &[],
+            children: &[],
+        },
+    ],
+}];
+
+#[test]
+fn decode_compact_template() {
+    const PARTS: &[&str] = &[
+        "\u{1}",
+        "div",
+        "\u{2}",
+        "",
+        "\u{3}",
+        "class",
+        "\u{2}",
+        "",
+        "\u{2}",
+        "card",
+        "\u{4}",
+        "hello",
+        "\u{1}",
+        "svg",
+        "\u{2}",
+        "http://www.w3.org/2000/svg",
+        "\u{5}",
+        "\u{5}",
+    ];
+    static COMPACT: [u8; compact_template_len(PARTS)] = compact_template(PARTS);
+
+    let template = Template::from_compact("compact_template.rs:1:1:0", &COMPACT);
+    assert_eq!(template.roots, CARD);
+    assert!(template.node_paths.is_empty());
+    assert!(template.attr_paths.is_empty());
+}
+
+#[test]
+fn rsx_compacts_static_templates() {
+    let mut dom = VirtualDom::new(|cx| {
+        render! {
+            div { class: "card",
+                "hello"
+                svg {}
+            }
+        }
+    });
+
+    let edits = dom.rebuild().santize();
+    assert_eq!(edits.templates.len(), 1);
+    assert_eq!(edits.templates[0].roots, CARD);
+}
@@ -1,3 +1,5 @@
+#![allow(non_snake_case)]
+
 use dioxus::core::{ElementId, Mutation::*};
 use dioxus::prelude::*;
 
@@ -49,3 +51,58 @@ fn state_shares() {
         [SetText { value: "Value is 3", id: ElementId(1,) },]
     );
 }
+
+#[test]
+fn nested_providers_shadow_for_their_subtree() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Theme(&'static str);
+
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| cx.provide_context(Theme("light")));
+        cx.render(rsx!(
+            dark_section {}
+            Reader { label: "outside" }
+        ))
+    }
+
+    fn dark_section(cx: Scope) -> Element {
+        cx.use_hook(|| cx.provide_context(Theme("dark")));
+        cx.render(rsx!(Reader { label: "inside" }))
+    }
+
+    #[inline_props]
+    fn Reader(cx: Scope, label: &'static str) -> Element {
+        let theme = cx.consume_context::<Theme>().unwrap();
+        let missing = cx.consume_context::<u8>().is_none();
+        cx.render(rsx!("{label}: {theme.0} {missing}"))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "inside: dark trueoutside: light true"
+    );
+}
+
+#[test]
+fn root_context_from_a_deep_scope() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx!(Child {}))
+    }
+
+    fn Child(cx: Scope) -> Element {
+        cx.render(rsx!(Grandchild {}))
+    }
+
+    fn Grandchild(cx: Scope) -> Element {
+        cx.use_hook(|| cx.provide_root_context(7u8));
+        cx.render(rsx!(()))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dom.base_scope().has_context::<u8>(), Some(7));
+}
@@ -0,0 +1,89 @@
+//! A controlled input only gets its value assigned when the value actually changes
+//!
+//! Writing the same value back to an input that the user is typing in would move their cursor. `value` is volatile,
+//! so the VirtualDom sends it on every render in case the element changed behind its back, and renderers only assign
+//! it when it differs from the element's current value.
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*, Mutations};
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+fn app(cx: Scope) -> Element {
+    let text = use_state(cx, String::new);
+
+    render! {
+        input {
+            value: "{text}",
+            oninput: move |evt| text.set(evt.value.clone()),
+        }
+        button { onclick: move |_| text.set(String::new()), "Clear" }
+    }
+}
+
+/// An input element in a renderer that skips assigning the value it already has, like the web interpreter
+#[derive(Default)]
+struct MockInput {
+    value: String,
+    writes: usize,
+}
+
+impl MockInput {
+    fn apply(&mut self, edits: &Mutations) {
+        for edit in &edits.edits {
+            if let SetAttribute {
+                name: "value", value: BorrowedAttributeValue::Text(value), ..
+            } = edit
+            {
+                if *value != self.value {
+                    self.value = value.to_string();
+                    self.writes += 1;
+                }
+            }
+        }
+    }
+
+    /// The user types, which changes the element before the VirtualDom hears about it
+    fn type_text(&mut self, dom: &mut VirtualDom, id: ElementId, value: &str) {
+        self.value = value.to_string();
+        let data = FormData { value: value.to_string(), values: Default::default(), files: None };
+        dom.handle_event("input", Rc::new(data), id, true);
+    }
+}
+
+fn listener(edits: &Mutations, event: &str) -> ElementId {
+    edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { name, id, .. } if *name == event => Some(*id),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn same_value_is_not_written_again() {
+    let mut dom = VirtualDom::new(app);
+    let mut element = MockInput::default();
+
+    let edits = dom.rebuild().santize();
+    element.apply(&edits);
+    let (input, clear) = (listener(&edits, "input"), listener(&edits, "click"));
+    drop(edits);
+    assert_eq!(element.writes, 0);
+
+    // The state catches up with what the user typed, which the element already shows
+    element.type_text(&mut dom, input, "a");
+    element.apply(&dom.render_immediate());
+    assert_eq!(element.writes, 0);
+
+    // Rendering again for any other reason sends the same value, which isn't assigned either
+    dom.mark_dirty(ScopeId(0));
+    element.apply(&dom.render_immediate());
+    assert_eq!(element.writes, 0);
+
+    // Changing the state from anywhere else writes the new value once
+    dom.handle_event("click", Rc::new(MouseData::default()), clear, true);
+    element.apply(&dom.render_immediate());
+    assert_eq!(element.value, "");
+    assert_eq!(element.writes, 1);
+}
@@ -0,0 +1,49 @@
+#![allow(non_snake_case)]
+
+//! A scope that renders more than once before the edits are handed out only sets each attribute and text once
+use dioxus::core::{BorrowedAttributeValue, Mutation::*};
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static COUNT: Cell<usize> = Cell::new(0);
+    static BUMP: Cell<bool> = Cell::new(false);
+}
+
+fn app(cx: Scope) -> Element {
+    let count = COUNT.with(Cell::get);
+    render! {
+        div { class: "{count}", "{count}", Child {} }
+    }
+}
+
+// Bumps the count while it renders, which renders the app again in the same batch
+fn Child(cx: Scope) -> Element {
+    if BUMP.with(|b| b.replace(false)) {
+        COUNT.with(|c| c.set(c.get() + 1));
+        cx.needs_update_any(ScopeId(0));
+    }
+    None
+}
+
+#[tokio::test]
+async fn later_sets_replace_earlier_ones() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    COUNT.with(|c| c.set(1));
+    BUMP.with(|b| b.set(true));
+    dom.mark_dirty(ScopeId(0));
+    dom.mark_dirty(ScopeId(1));
+
+    // Give the scheduler one chance to pick up the update the child sent before the deadline
+    let edits = dom.render_with_deadline(tokio::task::yield_now()).await;
+
+    assert_eq!(COUNT.with(Cell::get), 2);
+    assert_eq!(edits.edits.len(), 2);
+    assert!(matches!(
+        edits.edits[0],
+        SetAttribute { name: "class", value: BorrowedAttributeValue::Text("2"), .. }
+    ));
+    assert!(matches!(edits.edits[1], SetText { value: "2", .. }));
+}
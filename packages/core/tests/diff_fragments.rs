@@ -0,0 +1,59 @@
+#![allow(non_snake_case)]
+
+//! Components that return several roots keep their siblings in place as the number of roots changes
+use dioxus::prelude::*;
+use dioxus_core::Renderer;
+use dioxus_ssr::MutationRenderer;
+use std::cell::Cell;
+
+thread_local! {
+    static ROWS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        table {
+            tbody {
+                Rows {}
+                tr { td { "footer" } }
+            }
+        }
+    }
+}
+
+/// A header row next to the rows of a list, without an element around them
+fn Rows(cx: Scope) -> Element {
+    let count = ROWS.with(Cell::get);
+
+    render! {
+        tr { td { "header" } }
+        Fragment {
+            (0..count).map(|i| rsx! { tr { key: "{i}", td { "{i}" } } })
+        }
+    }
+}
+
+fn expected(count: usize) -> String {
+    let rows: String = (0..count)
+        .map(|i| format!("<tr><td>{i}</td></tr>"))
+        .collect();
+    format!("<table><tbody><tr><td>header</td></tr>{rows}<tr><td>footer</td></tr></tbody></table>")
+}
+
+#[test]
+fn fragments_grow_and_shrink_between_siblings() {
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MutationRenderer::new();
+    renderer.apply(dom.rebuild());
+    assert_eq!(renderer.to_html(), expected(0));
+
+    // The empty list is a placeholder that the rows are added next to, and that comes back once they are all gone
+    for count in [3, 1, 0, 2, 4, 0] {
+        ROWS.with(|r| r.set(count));
+        dom.mark_dirty(ScopeId(1));
+        renderer.apply(dom.render_immediate());
+
+        assert_eq!(renderer.to_html(), expected(count), "with {count} rows");
+        assert_eq!(renderer.to_html(), dioxus_ssr::render(&dom));
+    }
+}
@@ -0,0 +1,581 @@
+#![cfg(not(miri))]
+
+//! Diff random trees into each other and check the result matches building the new tree from scratch
+//!
+//! Every case is generated from a seed, so a failure can be replayed by running [`check`] with the seed and size it
+//! reports. The size bounds the depth of the trees and the length of their lists. Failing cases are shrunk to the
+//! smallest size that still fails for the same seed before they are reported.
+
+use dioxus::core::{
+    diff_nodes, exports::bumpalo::Bump, Attribute, AttributeValue, BorrowedAttributeValue,
+    DynamicNode, ElementId, Mutation, Mutations, Renderer, Template, TemplateAttribute,
+    TemplateNode, VNode, VText,
+};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+const TAGS: &[&str] = &["div", "p", "span"];
+const ATTRIBUTES: &[&str] = &["class", "id", "title"];
+
+/// A small deterministic random number generator, so cases can be replayed from their seed
+#[derive(Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Spread the seed out so neighbouring seeds don't start with similar states
+        let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Rng((state ^ (state >> 31)) | 1)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+
+    fn chance(&mut self, one_in: usize) -> bool {
+        self.below(one_in) == 0
+    }
+}
+
+/// What can fill a dynamic node of a template
+///
+/// Like in `rsx!`, a slot always holds the same kind of node: text nodes stay text, and lists stay keyed or unkeyed.
+#[derive(Clone, Copy)]
+enum Slot {
+    Text,
+    List { keyed: bool },
+}
+
+/// A random template along with what its dynamic parts can be filled with
+struct PoolTemplate {
+    template: Template<'static>,
+    attrs: Vec<&'static str>,
+    slots: Vec<Slot>,
+}
+
+/// Generates random trees of nodes from a shared pool of templates
+///
+/// Everything scales with `size`, so a smaller size generates smaller trees from the same seed. Values are picked
+/// from small sets so two trees often share templates, keys and values, which exercises the paths of the diff that
+/// keep nodes in place.
+#[derive(Clone)]
+struct Generator {
+    rng: Rng,
+    size: usize,
+}
+
+impl Generator {
+    fn new(seed: u64, size: usize) -> Self {
+        Generator { rng: Rng::new(seed), size }
+    }
+
+    fn pool(&mut self) -> Vec<PoolTemplate> {
+        let count = 2 + self.rng.below(self.size.min(4));
+        (0..count).map(|idx| self.template(idx)).collect()
+    }
+
+    fn template(&mut self, idx: usize) -> PoolTemplate {
+        let mut template = TemplateBuilder::default();
+        let roots: Vec<_> = (0..1 + self.rng.below(2))
+            .map(|root| {
+                template.path.push(root as u8);
+                let node = self.template_node(&mut template, 0);
+                template.path.pop();
+                node
+            })
+            .collect();
+
+        PoolTemplate {
+            template: Template {
+                name: Box::leak(format!("diff_fuzz.rs:{}:1:0", idx + 1).into_boxed_str()),
+                roots: Box::leak(roots.into_boxed_slice()),
+                node_paths: leak_paths(template.node_paths),
+                attr_paths: leak_paths(template.attr_paths),
+            },
+            attrs: template.attrs,
+            slots: template.slots,
+        }
+    }
+
+    fn template_node(
+        &mut self,
+        template: &mut TemplateBuilder,
+        depth: usize,
+    ) -> TemplateNode<'static> {
+        if depth < 2 && self.rng.chance(2) {
+            let mut attrs = Vec::new();
+            for name in ATTRIBUTES.iter().copied() {
+                match self.rng.below(3) {
+                    0 => {}
+                    1 => attrs.push(TemplateAttribute::Static {
+                        name,
+                        value: "static",
+                        namespace: None,
+                    }),
+                    _ => {
+                        attrs.push(TemplateAttribute::Dynamic { id: template.attr_paths.len() });
+                        template.attr_paths.push(template.path.clone());
+                        template.attrs.push(name);
+                    }
+                }
+            }
+
+            let children: Vec<_> = (0..self.rng.below(3))
+                .map(|child| {
+                    template.path.push(child as u8);
+                    let node = self.template_node(template, depth + 1);
+                    template.path.pop();
+                    node
+                })
+                .collect();
+
+            return TemplateNode::Element {
+                tag: TAGS[self.rng.below(TAGS.len())],
+                namespace: None,
+                attrs: Box::leak(attrs.into_boxed_slice()),
+                children: Box::leak(children.into_boxed_slice()),
+            };
+        }
+
+        let id = template.node_paths.len();
+        match self.rng.below(3) {
+            0 => TemplateNode::Text { text: "static" },
+            1 => {
+                template.node_paths.push(template.path.clone());
+                template.slots.push(Slot::Text);
+                TemplateNode::DynamicText { id }
+            }
+            _ => {
+                template.node_paths.push(template.path.clone());
+                template
+                    .slots
+                    .push(Slot::List { keyed: self.rng.chance(2) });
+                TemplateNode::Dynamic { id }
+            }
+        }
+    }
+
+    fn vnode<'a>(
+        &mut self,
+        bump: &'a Bump,
+        pool: &[PoolTemplate],
+        depth: usize,
+        key: Option<&'a str>,
+    ) -> VNode<'a> {
+        let template = &pool[self.rng.below(pool.len())];
+
+        let dynamic_attrs = bump.alloc_slice_fill_iter(
+            template
+                .attrs
+                .iter()
+                .map(|name| Attribute::new(*name, self.attribute_value(bump), None, false))
+                .collect::<Vec<_>>(),
+        );
+
+        let dynamic_nodes = bump.alloc_slice_fill_iter(
+            template
+                .slots
+                .iter()
+                .map(|slot| self.dynamic_node(bump, pool, *slot, depth))
+                .collect::<Vec<_>>(),
+        );
+
+        VNode {
+            key,
+            parent: Default::default(),
+            template: Cell::new(template.template),
+            root_ids: Default::default(),
+            dynamic_nodes,
+            dynamic_attrs,
+        }
+    }
+
+    fn attribute_value<'a>(&mut self, bump: &'a Bump) -> AttributeValue<'a> {
+        match self.rng.below(4) {
+            0 => AttributeValue::Text(bump.alloc_str(&format!("v{}", self.rng.below(3)))),
+            1 => AttributeValue::Int(self.rng.below(3) as i64),
+            2 => AttributeValue::Bool(self.rng.chance(2)),
+            _ => AttributeValue::None,
+        }
+    }
+
+    fn dynamic_node<'a>(
+        &mut self,
+        bump: &'a Bump,
+        pool: &[PoolTemplate],
+        slot: Slot,
+        depth: usize,
+    ) -> DynamicNode<'a> {
+        match slot {
+            Slot::Text => DynamicNode::Text(VText::new(
+                bump.alloc_str(&format!("t{}", self.rng.below(3))),
+            )),
+            Slot::List { .. } if depth >= self.size || self.rng.chance(3) => {
+                DynamicNode::Placeholder(Default::default())
+            }
+            Slot::List { keyed } => {
+                let len = 1 + self.rng.below(self.size);
+
+                // Keys are picked from a few more keys than there are items, so lists both reorder and swap items
+                let mut keys: Vec<usize> = (0..len + 2).collect();
+                for i in (1..keys.len()).rev() {
+                    keys.swap(i, self.rng.below(i + 1));
+                }
+
+                let children: Vec<_> = keys[..len]
+                    .iter()
+                    .map(|key| {
+                        let key = keyed.then(|| &*bump.alloc_str(&format!("k{}", key)));
+                        self.vnode(bump, pool, depth + 1, key)
+                    })
+                    .collect();
+                DynamicNode::Fragment(bump.alloc_slice_fill_iter(children))
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct TemplateBuilder {
+    path: Vec<u8>,
+    node_paths: Vec<Vec<u8>>,
+    attr_paths: Vec<Vec<u8>>,
+    attrs: Vec<&'static str>,
+    slots: Vec<Slot>,
+}
+
+fn leak_paths(paths: Vec<Vec<u8>>) -> &'static [&'static [u8]] {
+    let paths: Vec<&'static [u8]> = paths
+        .into_iter()
+        .map(|path| &*Box::leak(path.into_boxed_slice()))
+        .collect();
+    Box::leak(paths.into_boxed_slice())
+}
+
+/// A model of the DOM that records every mutation it applies
+///
+/// It follows the semantics of the web interpreter, and renders its tree to a string to compare it with other models.
+struct MutationsRecorder {
+    nodes: Vec<ModelNode>,
+    parents: Vec<Option<usize>>,
+    ids: HashMap<ElementId, usize>,
+    stack: Vec<usize>,
+    templates: HashMap<String, Vec<TemplateNode<'static>>>,
+    edits: Vec<String>,
+}
+
+enum ModelNode {
+    Element { tag: String, attrs: BTreeMap<String, String>, children: Vec<usize> },
+    Text(String),
+    Placeholder,
+}
+
+impl MutationsRecorder {
+    fn new() -> Self {
+        let mut recorder = MutationsRecorder {
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            ids: HashMap::new(),
+            stack: Vec::new(),
+            templates: HashMap::new(),
+            edits: Vec::new(),
+        };
+        let root = recorder.insert(ModelNode::Element {
+            tag: "root".to_string(),
+            attrs: BTreeMap::new(),
+            children: Vec::new(),
+        });
+        recorder.ids.insert(ElementId(0), root);
+        recorder
+    }
+
+    fn insert(&mut self, node: ModelNode) -> usize {
+        self.nodes.push(node);
+        self.parents.push(None);
+        self.nodes.len() - 1
+    }
+
+    fn create(&mut self, node: &TemplateNode) -> usize {
+        match node {
+            TemplateNode::Element { tag, attrs, children, .. } => {
+                let attrs = attrs
+                    .iter()
+                    .filter_map(|attr| match attr {
+                        TemplateAttribute::Static { name, value, .. } => {
+                            Some((name.to_string(), value.to_string()))
+                        }
+                        TemplateAttribute::Dynamic { .. } => None,
+                    })
+                    .collect();
+                let children: Vec<_> = children.iter().map(|child| self.create(child)).collect();
+                let element = self.insert(ModelNode::Element {
+                    tag: tag.to_string(),
+                    attrs,
+                    children: children.clone(),
+                });
+                for child in children {
+                    self.parents[child] = Some(element);
+                }
+                element
+            }
+            TemplateNode::Text { text } => self.insert(ModelNode::Text(text.to_string())),
+            TemplateNode::DynamicText { .. } => self.insert(ModelNode::Text(String::new())),
+            TemplateNode::Dynamic { .. } => self.insert(ModelNode::Placeholder),
+        }
+    }
+
+    fn children(&mut self, node: usize) -> &mut Vec<usize> {
+        match &mut self.nodes[node] {
+            ModelNode::Element { children, .. } => children,
+            _ => panic!("only elements have children"),
+        }
+    }
+
+    fn at_path(&self, path: &[u8]) -> usize {
+        path.iter().fold(*self.stack.last().unwrap(), |node, idx| {
+            match &self.nodes[node] {
+                ModelNode::Element { children, .. } => children[*idx as usize],
+                _ => panic!("only elements have children"),
+            }
+        })
+    }
+
+    /// Take `node` out of its parent, returning the parent and the position it was at
+    fn detach(&mut self, node: usize) -> Option<(usize, usize)> {
+        let parent = self.parents[node].take()?;
+        let children = self.children(parent);
+        let idx = children.iter().position(|child| *child == node).unwrap();
+        children.remove(idx);
+        Some((parent, idx))
+    }
+
+    /// Move `nodes` to `idx` in `parent`, which must already be detached like [`Self::take`] leaves them
+    fn insert_at(&mut self, parent: usize, idx: usize, nodes: Vec<usize>) {
+        for (offset, node) in nodes.into_iter().enumerate() {
+            self.children(parent).insert(idx + offset, node);
+            self.parents[node] = Some(parent);
+        }
+    }
+
+    /// Pop `m` nodes off the stack and take them out of the tree, since nodes can be moved by pushing them
+    fn take(&mut self, m: usize) -> Vec<usize> {
+        let nodes = self.stack.split_off(self.stack.len() - m);
+        for node in &nodes {
+            self.detach(*node);
+        }
+        nodes
+    }
+
+    fn position(&self, node: usize) -> (usize, usize) {
+        let parent = self.parents[node].expect("the node should be mounted");
+        match &self.nodes[parent] {
+            ModelNode::Element { children, .. } => {
+                (parent, children.iter().position(|c| *c == node).unwrap())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn render(&self) -> String {
+        self.render_node(self.ids[&ElementId(0)])
+    }
+
+    fn render_node(&self, node: usize) -> String {
+        match &self.nodes[node] {
+            ModelNode::Element { tag, attrs, children } => {
+                let attrs: String = attrs
+                    .iter()
+                    .map(|(name, value)| format!(" {}=\"{}\"", name, value))
+                    .collect();
+                let children: String = children
+                    .iter()
+                    .map(|child| self.render_node(*child))
+                    .collect();
+                format!("<{}{}>{}</{}>", tag, attrs, children, tag)
+            }
+            // Quote text so neighbouring text nodes can't render the same as a single one
+            ModelNode::Text(text) => format!("{:?}", text),
+            ModelNode::Placeholder => "<!--placeholder-->".to_string(),
+        }
+    }
+}
+
+impl Renderer for MutationsRecorder {
+    fn apply(&mut self, mutations: Mutations) {
+        for template in &mutations.templates {
+            self.templates
+                .insert(template.name.to_string(), template.roots.to_vec());
+        }
+
+        for edit in mutations.edits {
+            self.edits.push(format!("{:?}", edit));
+            match edit {
+                Mutation::LoadTemplate { name, index, id } => {
+                    let root = self.templates[name][index];
+                    let node = self.create(&root);
+                    self.ids.insert(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::AssignId { path, id } => {
+                    let node = self.at_path(path);
+                    self.ids.insert(id, node);
+                }
+                Mutation::HydrateText { path, value, id } => {
+                    let node = self.at_path(path);
+                    self.nodes[node] = ModelNode::Text(value.to_string());
+                    self.ids.insert(id, node);
+                }
+                Mutation::SetText { value, id } => {
+                    self.nodes[self.ids[&id]] = ModelNode::Text(value.to_string());
+                }
+                Mutation::SetMultiText { values } => {
+                    for (id, value) in values {
+                        self.nodes[self.ids[&id]] = ModelNode::Text(value.to_string());
+                    }
+                }
+                Mutation::SetAttribute { name, value, id, .. } => {
+                    if let ModelNode::Element { attrs, .. } = &mut self.nodes[self.ids[&id]] {
+                        match value {
+                            BorrowedAttributeValue::None => attrs.remove(name),
+                            value => attrs.insert(name.to_string(), format!("{:?}", value)),
+                        };
+                    }
+                }
+                Mutation::CreateTextNode { value, id } => {
+                    let node = self.insert(ModelNode::Text(value.to_string()));
+                    self.ids.insert(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::CreatePlaceholder { id } => {
+                    let node = self.insert(ModelNode::Placeholder);
+                    self.ids.insert(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::AppendChildren { id, m } => {
+                    let nodes = self.take(m);
+                    let parent = self.ids[&id];
+                    let end = self.children(parent).len();
+                    self.insert_at(parent, end, nodes);
+                }
+                Mutation::ReplaceWith { id, m } => {
+                    let nodes = self.take(m);
+                    let (parent, idx) = self
+                        .detach(self.ids[&id])
+                        .expect("the node should be mounted");
+                    self.insert_at(parent, idx, nodes);
+                }
+                Mutation::ReplacePlaceholder { path, m } => {
+                    let nodes = self.take(m);
+                    let placeholder = self.at_path(path);
+                    let (parent, idx) = self
+                        .detach(placeholder)
+                        .expect("the node should be mounted");
+                    self.insert_at(parent, idx, nodes);
+                }
+                Mutation::InsertAfter { id, m } => {
+                    let nodes = self.take(m);
+                    let (parent, idx) = self.position(self.ids[&id]);
+                    self.insert_at(parent, idx + 1, nodes);
+                }
+                Mutation::InsertBefore { id, m } => {
+                    let nodes = self.take(m);
+                    let (parent, idx) = self.position(self.ids[&id]);
+                    self.insert_at(parent, idx, nodes);
+                }
+                Mutation::MoveNode { id, target, before } => {
+                    let node = self.ids[&id];
+                    self.detach(node);
+                    let (parent, idx) = self.position(self.ids[&target]);
+                    self.insert_at(parent, idx + usize::from(!before), vec![node]);
+                }
+                Mutation::Remove { id } => {
+                    self.detach(self.ids[&id]);
+                }
+                Mutation::PushRoot { id } => self.stack.push(self.ids[&id]),
+                Mutation::AssignExternalRoot { id, .. } => {
+                    self.ids.insert(id, self.ids[&ElementId(0)]);
+                }
+                Mutation::NewEventListener { .. } | Mutation::RemoveEventListener { .. } => {}
+            }
+        }
+    }
+}
+
+/// Diff a random tree into another, and compare the result with building the second tree from scratch
+fn run(seed: u64, size: usize) -> Result<(), String> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut gen = Generator::new(seed, size);
+        let pool = gen.pool();
+        let bump = Bump::new();
+
+        let old = gen.vnode(&bump, &pool, 0, None);
+
+        // Generate the new tree twice from the same state: diffing assigns ids to the nodes it mounts, so each
+        // model gets its own copy
+        let mut fresh_gen = gen.clone();
+        let new = gen.vnode(&bump, &pool, 0, None);
+        let fresh = fresh_gen.vnode(&bump, &pool, 0, None);
+
+        let mut diffed = MutationsRecorder::new();
+        diff_nodes(&old, &new, &mut diffed);
+
+        // Diffing a tree with itself only creates it
+        let mut built = MutationsRecorder::new();
+        diff_nodes(&fresh, &fresh, &mut built);
+
+        (diffed, built)
+    }));
+
+    match result {
+        Ok((diffed, built)) if diffed.render() == built.render() => Ok(()),
+        Ok((diffed, built)) => Err(format!(
+            "the diffed tree\n{}\ndoesn't match the fresh tree\n{}\nafter the edits\n{}",
+            diffed.render(),
+            built.render(),
+            diffed.edits.join("\n")
+        )),
+        Err(panic) => Err(format!(
+            "diffing panicked: {}",
+            panic
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| panic.downcast_ref::<&str>().copied())
+                .unwrap_or("unknown panic")
+        )),
+    }
+}
+
+/// Run a case, shrinking it to the smallest failing size if it fails
+fn check(seed: u64, size: usize) {
+    if let Err(failure) = run(seed, size) {
+        let (size, failure) = (1..size)
+            .find_map(|size| run(seed, size).err().map(|failure| (size, failure)))
+            .unwrap_or((size, failure));
+        panic!("seed {} at size {} failed: {}", seed, size, failure);
+    }
+}
+
+#[test]
+fn seed_cases() {
+    for seed in [0, 1, 2, 42, 1337] {
+        check(seed, 4);
+    }
+
+    // A keyed child that changed templates was replaced, and the children after it were inserted after its old ids
+    check(46, 2);
+}
+
+#[test]
+fn random_cases() {
+    for seed in 0..500 {
+        check(seed, 1 + (seed as usize % 5));
+    }
+}
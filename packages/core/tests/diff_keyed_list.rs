@@ -42,10 +42,7 @@ fn keyed_diffing_out_of_order() {
     dom.mark_dirty(ScopeId(0));
     assert_eq!(
         dom.render_immediate().edits,
-        [
-            PushRoot { id: ElementId(7,) },
-            InsertBefore { id: ElementId(5,), m: 1 },
-        ]
+        [MoveNode { id: ElementId(7), target: ElementId(5), before: true }]
     );
 }
 
@@ -145,10 +142,7 @@ fn keyed_diffing_out_of_order_adds_5() {
     dom.mark_dirty(ScopeId(0));
     assert_eq!(
         dom.render_immediate().edits,
-        [
-            PushRoot { id: ElementId(5,) },
-            InsertBefore { id: ElementId(4,), m: 1 },
-        ]
+        [MoveNode { id: ElementId(5), target: ElementId(4), before: true }]
     );
 }
 
@@ -201,8 +195,7 @@ fn keyed_diffing_additions_and_moves_on_ends() {
             LoadTemplate { name: "template", index: 0, id: ElementId(6) },
             InsertAfter { id: ElementId(3), m: 2 },
             // move 7 to the front
-            PushRoot { id: ElementId(4) },
-            InsertBefore { id: ElementId(1), m: 1 }
+            MoveNode { id: ElementId(4), target: ElementId(1), before: true }
         ]
     );
 }
@@ -235,8 +228,7 @@ fn keyed_diffing_additions_and_moves_in_middle() {
             LoadTemplate { name: "template", index: 0, id: ElementId(8) },
             InsertBefore { id: ElementId(2), m: 2 },
             // move 7
-            PushRoot { id: ElementId(4) },
-            InsertBefore { id: ElementId(1), m: 1 }
+            MoveNode { id: ElementId(4), target: ElementId(1), before: true }
         ]
     );
 }
@@ -263,8 +255,7 @@ fn controlled_keyed_diffing_out_of_order() {
             // remove 7
             Remove { id: ElementId(4,) },
             // move 4 to after 6
-            PushRoot { id: ElementId(1) },
-            InsertAfter { id: ElementId(3,), m: 1 },
+            MoveNode { id: ElementId(1), target: ElementId(3), before: false },
             // create 9 and insert before 6
             LoadTemplate { name: "template", index: 0, id: ElementId(4) },
             InsertBefore { id: ElementId(3,), m: 1 },
@@ -296,8 +287,7 @@ fn controlled_keyed_diffing_out_of_order_max_test() {
             Remove { id: ElementId(5,) },
             LoadTemplate { name: "template", index: 0, id: ElementId(5) },
             InsertBefore { id: ElementId(3,), m: 1 },
-            PushRoot { id: ElementId(4) },
-            InsertBefore { id: ElementId(1,), m: 1 },
+            MoveNode { id: ElementId(4), target: ElementId(1), before: true },
         ]
     );
 }
@@ -356,3 +346,106 @@ fn no_common_keys() {
         ]
     );
 }
+
+#[test]
+fn reversing_a_long_list_only_moves() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: Vec<usize> = match cx.generation() % 2 {
+            0 => (0..100).collect(),
+            1 => (0..100).rev().collect(),
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!(order.iter().map(|i| rsx!(div { key: "{i}", "{i}" }))))
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate().edits;
+
+    // Every node but one moves, so there is a push for each of them plus at most two inserts
+    assert!(edits.len() <= 101);
+    assert!(edits.iter().all(|edit| matches!(
+        edit,
+        PushRoot { .. } | InsertBefore { .. } | InsertAfter { .. } | MoveNode { .. }
+    )));
+}
+
+#[test]
+fn duplicate_keys_diff_by_position() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[1, 1, 2],
+            1 => &[2, 1, 1],
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!(order.iter().map(|i| rsx!(div { key: "{i}", "{i}" }))))
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().edits,
+        [
+            SetText { value: "2", id: ElementId(2) },
+            SetText { value: "1", id: ElementId(6) },
+        ]
+    );
+}
+
+#[test]
+fn mixed_keyed_and_unkeyed_siblings_diff_by_position() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[0, 1, 2],
+            1 => &[0, 2, 1],
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!(order.iter().map(|i| match i {
+            0 => rsx!(div { "{i}" }),
+            _ => rsx!(div { key: "{i}", "{i}" }),
+        })))
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().edits,
+        [
+            SetText { value: "2", id: ElementId(4) },
+            SetText { value: "1", id: ElementId(6) },
+        ]
+    );
+}
+
+/// Reordered nodes keep their ElementIds, so only the removed key is dropped and only the new key is created
+#[test]
+fn keyed_reorder_with_additions_keeps_element_ids() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[0, 1, 2, 3, 4],
+            1 => &[0, 3, 1, 5, 4],
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!(order.iter().map(|i| rsx!(div { key: "{i}" }))))
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            Remove { id: ElementId(3) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+            InsertAfter { id: ElementId(2), m: 1 },
+            MoveNode { id: ElementId(4), target: ElementId(2), before: true },
+        ]
+    );
+}
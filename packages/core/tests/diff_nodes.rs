@@ -0,0 +1,441 @@
+//! Diffing two hand-built trees without a VirtualDom
+use bumpalo::Bump;
+use dioxus::core::{
+    diff_nodes, Attribute, AttributeValue, BorrowedAttributeValue, DynamicNode, ElementId,
+    Mutation, Mutations, Renderer, Template, TemplateAttribute, TemplateNode, VNode, VText,
+};
+use std::{cell::Cell, collections::HashMap};
+
+static CARD: Template = Template {
+    name: "diff_nodes.rs:1:1:0",
+    roots: &[TemplateNode::Element {
+        tag: "div",
+        namespace: None,
+        attrs: &[TemplateAttribute::Dynamic { id: 0 }],
+        children: &[TemplateNode::DynamicText { id: 0 }],
+    }],
+    node_paths: &[&[0, 0]],
+    attr_paths: &[&[0]],
+};
+
+static NOTE: Template = Template {
+    name: "diff_nodes.rs:2:1:0",
+    roots: &[TemplateNode::Element {
+        tag: "p",
+        namespace: None,
+        attrs: &[],
+        children: &[TemplateNode::DynamicText { id: 0 }],
+    }],
+    node_paths: &[&[0, 0]],
+    attr_paths: &[],
+};
+
+static EMBED: Template = Template {
+    name: "diff_nodes.rs:3:1:0",
+    roots: &[TemplateNode::Element {
+        tag: "div",
+        namespace: None,
+        attrs: &[TemplateAttribute::Dynamic { id: 0 }],
+        children: &[],
+    }],
+    node_paths: &[],
+    attr_paths: &[&[0]],
+};
+
+/// Build a node out of one of the templates above
+///
+/// Its dynamic parts are allocated in a [`Bump`], like the ones a component renders, so they aren't dropped while the
+/// node still borrows them.
+fn node<'a>(
+    template: Template<'static>,
+    dynamic_nodes: &'a [DynamicNode<'a>],
+    dynamic_attrs: &'a [Attribute<'a>],
+) -> VNode<'a> {
+    VNode {
+        key: None,
+        parent: Default::default(),
+        template: Cell::new(template),
+        root_ids: Default::default(),
+        dynamic_nodes,
+        dynamic_attrs,
+    }
+}
+
+/// A tree of nodes in memory that applies mutations like a real renderer would
+#[derive(Default)]
+struct MockDom {
+    nodes: Vec<MockNode>,
+    ids: HashMap<ElementId, usize>,
+    stack: Vec<usize>,
+    templates: HashMap<String, Vec<TemplateNode<'static>>>,
+    edits: Vec<String>,
+}
+
+enum MockNode {
+    Element { tag: String, attrs: Vec<(String, String)>, children: Vec<usize> },
+    Text(String),
+    Placeholder,
+}
+
+impl MockDom {
+    fn new() -> Self {
+        let mut dom = MockDom::default();
+        let root = dom.insert(MockNode::Element {
+            tag: "root".to_string(),
+            attrs: Vec::new(),
+            children: Vec::new(),
+        });
+        dom.ids.insert(ElementId(0), root);
+        dom
+    }
+
+    fn insert(&mut self, node: MockNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    fn create(&mut self, node: &TemplateNode) -> usize {
+        match node {
+            TemplateNode::Element { tag, attrs, children, .. } => {
+                let attrs = attrs
+                    .iter()
+                    .filter_map(|attr| match attr {
+                        TemplateAttribute::Static { name, value, .. } => {
+                            Some((name.to_string(), value.to_string()))
+                        }
+                        TemplateAttribute::Dynamic { .. } => None,
+                    })
+                    .collect();
+                let children = children.iter().map(|child| self.create(child)).collect();
+                self.insert(MockNode::Element { tag: tag.to_string(), attrs, children })
+            }
+            TemplateNode::Text { text } => self.insert(MockNode::Text(text.to_string())),
+            TemplateNode::DynamicText { .. } => self.insert(MockNode::Text(String::new())),
+            TemplateNode::Dynamic { .. } => self.insert(MockNode::Placeholder),
+        }
+    }
+
+    fn children(&mut self, node: usize) -> &mut Vec<usize> {
+        match &mut self.nodes[node] {
+            MockNode::Element { children, .. } => children,
+            _ => panic!("only elements have children"),
+        }
+    }
+
+    fn at_path(&self, path: &[u8]) -> usize {
+        path.iter().fold(*self.stack.last().unwrap(), |node, idx| {
+            match &self.nodes[node] {
+                MockNode::Element { children, .. } => children[*idx as usize],
+                _ => panic!("only elements have children"),
+            }
+        })
+    }
+
+    fn parent(&self, child: usize) -> (usize, usize) {
+        self.nodes
+            .iter()
+            .enumerate()
+            .find_map(|(parent, node)| match node {
+                MockNode::Element { children, .. } => children
+                    .iter()
+                    .position(|c| *c == child)
+                    .map(|idx| (parent, idx)),
+                _ => None,
+            })
+            .expect("the node should be mounted")
+    }
+
+    fn pop(&mut self, m: usize) -> Vec<usize> {
+        self.stack.split_off(self.stack.len() - m)
+    }
+
+    fn render(&self, node: usize) -> String {
+        match &self.nodes[node] {
+            MockNode::Element { tag, attrs, children } => {
+                let attrs: String = attrs
+                    .iter()
+                    .map(|(name, value)| format!(" {}=\"{}\"", name, value))
+                    .collect();
+                let children: String = children.iter().map(|child| self.render(*child)).collect();
+                format!("<{}{}>{}</{}>", tag, attrs, children, tag)
+            }
+            MockNode::Text(text) => text.clone(),
+            MockNode::Placeholder => "<!--placeholder-->".to_string(),
+        }
+    }
+}
+
+impl Renderer for MockDom {
+    fn apply(&mut self, mutations: Mutations) {
+        for template in &mutations.templates {
+            self.templates
+                .insert(template.name.to_string(), template.roots.to_vec());
+        }
+
+        for edit in mutations.edits {
+            self.edits
+                .push(format!("{:?}", edit).split(' ').next().unwrap().to_string());
+            match edit {
+                Mutation::LoadTemplate { name, index, id } => {
+                    let root = self.templates[name][index];
+                    let node = self.create(&root);
+                    self.ids.insert(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::AssignId { path, id } => {
+                    let node = self.at_path(path);
+                    self.ids.insert(id, node);
+                }
+                Mutation::HydrateText { path, value, id } => {
+                    let node = self.at_path(path);
+                    self.nodes[node] = MockNode::Text(value.to_string());
+                    self.ids.insert(id, node);
+                }
+                Mutation::SetText { value, id } => {
+                    self.nodes[self.ids[&id]] = MockNode::Text(value.to_string());
+                }
+                Mutation::SetAttribute { name, value, id, .. } => {
+                    if let MockNode::Element { attrs, .. } = &mut self.nodes[self.ids[&id]] {
+                        attrs.retain(|(existing, _)| existing != name);
+                        if let BorrowedAttributeValue::Text(value) = value {
+                            attrs.push((name.to_string(), value.to_string()));
+                        }
+                    }
+                }
+                Mutation::CreateTextNode { value, id } => {
+                    let node = self.insert(MockNode::Text(value.to_string()));
+                    self.ids.insert(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::CreatePlaceholder { id } => {
+                    let node = self.insert(MockNode::Placeholder);
+                    self.ids.insert(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::AppendChildren { id, m } => {
+                    let nodes = self.pop(m);
+                    let parent = self.ids[&id];
+                    self.children(parent).extend(nodes);
+                }
+                Mutation::ReplaceWith { id, m } => {
+                    let nodes = self.pop(m);
+                    let (parent, idx) = self.parent(self.ids[&id]);
+                    let children = self.children(parent);
+                    children.remove(idx);
+                    for (offset, node) in nodes.into_iter().enumerate() {
+                        children.insert(idx + offset, node);
+                    }
+                }
+                Mutation::Remove { id } => {
+                    let (parent, idx) = self.parent(self.ids[&id]);
+                    self.children(parent).remove(idx);
+                }
+                Mutation::PushRoot { id } => self.stack.push(self.ids[&id]),
+                other => panic!("the mock dom doesn't support {:?}", other),
+            }
+        }
+    }
+
+    fn set_inner_html(&mut self, html: &str, root: ElementId) {
+        self.edits.push("set_inner_html".to_string());
+        if let MockNode::Element { children, .. } = &mut self.nodes[self.ids[&root]] {
+            children.clear();
+        }
+        if !html.is_empty() {
+            let text = self.insert(MockNode::Text(html.to_string()));
+            self.children(self.ids[&root]).push(text);
+        }
+    }
+}
+
+#[test]
+fn diff_changes_dynamic_parts_in_place() {
+    let bump = Bump::new();
+    let old_nodes = bump.alloc([DynamicNode::Text(VText::new("hello"))]);
+    let old_attrs = bump.alloc([Attribute::new(
+        "class",
+        AttributeValue::Text("old"),
+        None,
+        false,
+    )]);
+    let old = node(CARD, old_nodes, old_attrs);
+
+    let new_nodes = bump.alloc([DynamicNode::Text(VText::new("world"))]);
+    let new_attrs = bump.alloc([Attribute::new(
+        "class",
+        AttributeValue::Text("new"),
+        None,
+        false,
+    )]);
+    let new = node(CARD, new_nodes, new_attrs);
+
+    let mut dom = MockDom::new();
+    diff_nodes(&old, &new, &mut dom);
+
+    assert_eq!(
+        dom.render(dom.ids[&ElementId(0)]),
+        "<root><div class=\"new\">world</div></root>"
+    );
+
+    // The template is the same, so only the dynamic parts change after the old tree is created
+    let diff = &dom.edits[dom
+        .edits
+        .iter()
+        .position(|e| e == "AppendChildren")
+        .unwrap()
+        + 1..];
+    assert_eq!(diff, ["SetAttribute", "SetText"]);
+}
+
+#[test]
+fn diff_replaces_different_templates() {
+    let bump = Bump::new();
+    let old_nodes = bump.alloc([DynamicNode::Text(VText::new("hello"))]);
+    let old_attrs = bump.alloc([Attribute::new(
+        "class",
+        AttributeValue::Text("card"),
+        None,
+        false,
+    )]);
+    let old = node(CARD, old_nodes, old_attrs);
+
+    let new_nodes = bump.alloc([DynamicNode::Text(VText::new("a note"))]);
+    let new = node(NOTE, new_nodes, &[]);
+
+    let mut dom = MockDom::new();
+    diff_nodes(&old, &new, &mut dom);
+
+    assert_eq!(
+        dom.render(dom.ids[&ElementId(0)]),
+        "<root><p>a note</p></root>"
+    );
+}
+
+/// The edits of the diff, after the old tree was created
+fn diff_edits(dom: &MockDom) -> &[String] {
+    let created = dom
+        .edits
+        .iter()
+        .position(|e| e == "AppendChildren")
+        .unwrap();
+    &dom.edits[created + 1..]
+}
+
+#[test]
+fn diff_swaps_inner_html() {
+    let bump = Bump::new();
+    let old_attrs = bump.alloc([Attribute::new(
+        "dangerous_inner_html",
+        AttributeValue::Text("<p>old</p>"),
+        None,
+        false,
+    )]);
+    let old = node(EMBED, &[], old_attrs);
+
+    let new_attrs = bump.alloc([Attribute::new(
+        "dangerous_inner_html",
+        AttributeValue::Text("<p>new</p>"),
+        None,
+        false,
+    )]);
+    let new = node(EMBED, &[], new_attrs);
+
+    let mut dom = MockDom::new();
+    diff_nodes(&old, &new, &mut dom);
+
+    assert_eq!(diff_edits(&dom), ["set_inner_html"]);
+    assert_eq!(
+        dom.render(dom.ids[&ElementId(0)]),
+        "<root><div><p>new</p></div></root>"
+    );
+}
+
+#[test]
+fn diff_clears_removed_inner_html() {
+    let bump = Bump::new();
+    let old_attrs = bump.alloc([Attribute::new(
+        "dangerous_inner_html",
+        AttributeValue::Text("<p>old</p>"),
+        None,
+        false,
+    )]);
+    let old = node(EMBED, &[], old_attrs);
+
+    let new_attrs = bump.alloc([Attribute::new(
+        "dangerous_inner_html",
+        AttributeValue::None,
+        None,
+        false,
+    )]);
+    let new = node(EMBED, &[], new_attrs);
+
+    let mut dom = MockDom::new();
+    diff_nodes(&old, &new, &mut dom);
+
+    assert_eq!(diff_edits(&dom), ["set_inner_html"]);
+    assert_eq!(
+        dom.render(dom.ids[&ElementId(0)]),
+        "<root><div></div></root>"
+    );
+}
+
+/// Records the calls a renderer gets, without applying them
+#[derive(Default)]
+struct FrameRecorder {
+    calls: Vec<&'static str>,
+}
+
+impl Renderer for FrameRecorder {
+    fn apply(&mut self, _: Mutations) {
+        self.calls.push("apply");
+    }
+
+    fn begin_frame(&mut self) {
+        self.calls.push("begin_frame");
+    }
+
+    fn flush(&mut self) {
+        self.calls.push("flush");
+    }
+
+    fn set_inner_html(&mut self, _: &str, _: ElementId) {
+        self.calls.push("set_inner_html");
+    }
+}
+
+#[test]
+fn each_batch_is_wrapped_in_a_frame() {
+    let bump = Bump::new();
+    let old_attrs = bump.alloc([Attribute::new(
+        "dangerous_inner_html",
+        AttributeValue::Text("<p>old</p>"),
+        None,
+        false,
+    )]);
+    let old = node(EMBED, &[], old_attrs);
+
+    let new_attrs = bump.alloc([Attribute::new(
+        "dangerous_inner_html",
+        AttributeValue::Text("<p>new</p>"),
+        None,
+        false,
+    )]);
+    let new = node(EMBED, &[], new_attrs);
+
+    let mut renderer = FrameRecorder::default();
+    diff_nodes(&old, &new, &mut renderer);
+
+    // One frame creates the old tree, the other applies the diff, even though it is split over several calls
+    let frames: Vec<&[&str]> = renderer
+        .calls
+        .split(|call| *call == "flush")
+        .filter(|frame| !frame.is_empty())
+        .collect();
+    assert_eq!(frames.len(), 2);
+    for frame in &frames {
+        assert_eq!(frame[0], "begin_frame");
+        assert!(!frame[1..].contains(&"begin_frame"));
+    }
+    assert_eq!(frames[1], ["begin_frame", "set_inner_html", "apply"]);
+    assert_eq!(renderer.calls.last(), Some(&"flush"));
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "diff-stats")]
+#![allow(non_snake_case)]
+
+//! The VirtualDom counts the work done by each render
+use dioxus::core::DiffStats;
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let count = cx.generation();
+    render! {
+        ul {
+            (0..3).map(|i| rsx! { li { key: "{i}", "item {i}: {count}" } })
+        }
+        Child {}
+    }
+}
+
+fn Child(cx: Scope) -> Element {
+    render! { p { "static" } }
+}
+
+#[test]
+fn counts_creates_then_set_texts() {
+    let mut dom = VirtualDom::new(app);
+
+    _ = dom.rebuild();
+    let stats = dom.diff_stats();
+    assert_eq!(
+        DiffStats { nanos: 0, ..stats },
+        DiffStats {
+            // The templates of the app, the three items, and the child
+            creates: 5,
+            ..Default::default()
+        }
+    );
+
+    // Only the text of each item changes, and the child is memoized
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    let stats = dom.diff_stats();
+    assert_eq!(
+        DiffStats { nanos: 0, ..stats },
+        DiffStats { scopes_rerendered: 1, set_texts: 3, ..Default::default() }
+    );
+
+    // Stats are for the latest frame only
+    _ = dom.render_immediate();
+    assert_eq!(dom.diff_stats(), DiffStats::default());
+}
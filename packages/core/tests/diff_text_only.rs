@@ -0,0 +1,36 @@
+//! Templates whose only dynamic parts are text only get their text updated
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+
+fn app(cx: Scope) -> Element {
+    let gen = cx.generation();
+
+    render! {
+        div {
+            "Hello, user {gen}!"
+            p { "This never changes" }
+            "Rendered {gen} times"
+        }
+    }
+}
+
+#[test]
+fn text_only_updates_only_set_text() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+
+    assert!(edits.templates.is_empty());
+    assert_eq!(
+        edits.edits,
+        [SetMultiText {
+            values: vec![
+                (ElementId(2), "Rendered 1 times"),
+                (ElementId(3), "Hello, user 1!"),
+            ]
+        }]
+    );
+}
@@ -1,5 +1,6 @@
 #![allow(non_snake_case)]
 
+use dioxus::core::TaskPanic;
 use dioxus::prelude::*;
 
 #[test]
@@ -32,3 +33,27 @@ fn ThrowChild(cx: Scope) -> Element {
         div {}
     })
 }
+
+#[test]
+fn render_panics_reach_the_boundary() {
+    fn app(cx: Scope) -> Element {
+        let boundary = use_error_boundary(cx);
+
+        if let Some(error) = boundary.error() {
+            let message = error.downcast::<TaskPanic>().unwrap().message.clone();
+            return render! { "fallback: {message}" };
+        }
+
+        render! { PanicChild {} }
+    }
+
+    fn PanicChild(_cx: Scope) -> Element {
+        panic!("render failed")
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    _ = dom.render_immediate();
+
+    assert_eq!(dioxus_ssr::render(&dom), "fallback: render failed");
+}
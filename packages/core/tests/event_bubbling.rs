@@ -0,0 +1,87 @@
+#![allow(non_snake_case)]
+//! Events bubble through the component tree, across components, fragments and portals
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+thread_local! {
+    static CLICKS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn clicked(name: &'static str) {
+    CLICKS.with(|c| c.borrow_mut().push(name));
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        div { onclick: move |_| clicked("app"),
+            Middle {}
+            Portal {
+                button { onclick: move |_| clicked("portaled") }
+            }
+        }
+    }
+}
+
+fn Middle(cx: Scope) -> Element {
+    render! {
+        section { onclick: move |_| clicked("middle"),
+            (0..1).map(|_| rsx! {
+                p { button { onclick: move |_| clicked("listed") } }
+            })
+            span { onclick: stop_propagation move |_| clicked("stopper"),
+                Leaf {}
+            }
+        }
+    }
+}
+
+fn Leaf(cx: Scope) -> Element {
+    render! {
+        button { onclick: move |_| clicked("leaf") }
+    }
+}
+
+/// Click every element with a listener, and collect the listeners that ran by the first of them
+fn click_all(dom: &mut VirtualDom, ids: &[ElementId]) -> HashMap<&'static str, Vec<&'static str>> {
+    ids.iter()
+        .map(|id| {
+            dom.handle_event("click", Rc::new(MouseData::default()), *id, true);
+            let clicks = CLICKS.with(|c| c.take());
+            (clicks[0], clicks)
+        })
+        .collect()
+}
+
+#[test]
+fn events_bubble_through_the_component_tree() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    let ids: Vec<ElementId> = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener { name: "click", id, .. } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    drop(edits);
+
+    let clicks = click_all(&mut dom, &ids);
+    assert_eq!(clicks["app"], ["app"]);
+    assert_eq!(clicks["middle"], ["middle", "app"]);
+    assert_eq!(clicks["listed"], ["listed", "middle", "app"]);
+    assert_eq!(clicks["stopper"], ["stopper"]);
+    assert_eq!(clicks["leaf"], ["leaf", "stopper"]);
+
+    // The children of the portal are mounted to the root, but bubble to the element the portal is in
+    assert_eq!(clicks["portaled"], ["portaled", "app"]);
+
+    // The new nodes of the next render bubble the same way
+    for scope in 0..4 {
+        dom.mark_dirty(ScopeId(scope));
+    }
+    _ = dom.render_immediate();
+    assert_eq!(click_all(&mut dom, &ids), clicks);
+}
@@ -0,0 +1,76 @@
+//! Type-erased events can be downcast back into the data renderers put in them
+use dioxus::html::geometry::{ClientPoint, Coordinates, ElementPoint, PagePoint, ScreenPoint};
+use dioxus::html::input_data::keyboard_types::{Code, Key, Location, Modifiers};
+use dioxus::html::input_data::{MouseButton, MouseButtonSet};
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+fn synthetic_click() -> MouseData {
+    MouseData::new(
+        Coordinates::new(
+            ScreenPoint::new(110.0, 220.0),
+            ClientPoint::new(10.0, 20.0),
+            ElementPoint::new(1.0, 2.0),
+            PagePoint::new(10.0, 120.0),
+        ),
+        Some(MouseButton::Primary),
+        MouseButtonSet::only(MouseButton::Primary),
+        Modifiers::CONTROL,
+    )
+}
+
+#[test]
+fn downcasts_to_the_data_type() {
+    let event = Event::new(Rc::new(synthetic_click()) as Rc<dyn Any>);
+
+    let data = event.downcast::<MouseData>().unwrap();
+    assert_eq!(data.client_coordinates(), ClientPoint::new(10.0, 20.0));
+    assert_eq!(data.trigger_button(), Some(MouseButton::Primary));
+    assert!(data.modifiers().contains(Modifiers::CONTROL));
+
+    assert!(event.downcast::<KeyboardData>().is_none());
+}
+
+#[test]
+fn keyboard_events_downcast() {
+    let key = KeyboardData::new(
+        Key::Enter,
+        Code::Enter,
+        Location::Standard,
+        false,
+        Modifiers::CONTROL,
+    );
+    let event = Event::new(Rc::new(key) as Rc<dyn Any>);
+
+    let data = event.downcast::<KeyboardData>().unwrap();
+    assert_eq!(data.key(), Key::Enter);
+    assert_eq!(data.code(), Code::Enter);
+    assert!(data.modifiers().contains(Modifiers::CONTROL));
+
+    assert!(event.downcast::<MouseData>().is_none());
+}
+
+#[test]
+fn handlers_receive_typed_data() {
+    thread_local! {
+        static CLICKS: RefCell<Vec<MouseData>> = RefCell::new(Vec::new());
+    }
+
+    fn app(cx: Scope) -> Element {
+        render! {
+            button {
+                onclick: |evt| CLICKS.with(|c| c.borrow_mut().push(evt.data.as_ref().clone())),
+                onkeydown: |_| -> () { panic!("a mouse event reached a keyboard handler") },
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.handle_event("click", Rc::new(synthetic_click()), ElementId(1), true);
+    dom.handle_event("keydown", Rc::new(synthetic_click()), ElementId(1), true);
+
+    CLICKS.with(|c| assert_eq!(*c.borrow(), [synthetic_click()]));
+}
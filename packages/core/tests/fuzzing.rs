@@ -274,7 +274,7 @@ fn create_random_element(cx: Scope<DepthProps>) -> Element {
             // println!("{template:#?}");
             let node = VNode {
                 key: None,
-                parent: None,
+                parent: Default::default(),
                 template: Cell::new(template),
                 root_ids: Default::default(),
                 dynamic_nodes: {
@@ -341,7 +341,7 @@ fn diff() {
             {
                 let muts = vdom.render_immediate();
                 for mut_ in muts.edits {
-                    if let Mutation::NewEventListener { name, id } = mut_ {
+                    if let Mutation::NewEventListener { name, id, .. } = mut_ {
                         println!("new event listener on {:?} for {:?}", id, name);
                         event_listeners.insert(id);
                     }
@@ -40,7 +40,7 @@ fn dual_stream() {
                 id: ElementId(1),
                 ns: None,
             },
-            NewEventListener { name: "click", id: ElementId(1) },
+            NewEventListener { name: "click", id: ElementId(1), flags: Default::default() },
             HydrateText { path: &[0, 0], value: "123", id: ElementId(2) },
             AppendChildren { id: ElementId(0), m: 1 },
         ]
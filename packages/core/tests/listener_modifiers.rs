@@ -0,0 +1,77 @@
+//! Tests for the `prevent_default` and `stop_propagation` listener modifiers in rsx
+use dioxus::core::{ElementId, ListenerFlags, Mutation::*};
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static CLICKS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn clicked(name: &'static str) {
+    CLICKS.with(|c| c.borrow_mut().push(name));
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div { onclick: move |_| clicked("outer"),
+            button { onclick: stop_propagation move |_| clicked("stopped") }
+            a { onclick: move |_| clicked("bubbles") }
+            form { onsubmit: prevent_default move |_| {} }
+        }
+    })
+}
+
+#[test]
+fn modifiers_set_listener_flags() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    let listeners: Vec<_> = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener { name, flags, .. } => Some((*name, *flags)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        listeners,
+        [
+            ("click", ListenerFlags::default()),
+            (
+                "click",
+                ListenerFlags { stop_propagation: true, ..Default::default() }
+            ),
+            ("click", ListenerFlags::default()),
+            (
+                "submit",
+                ListenerFlags { prevent_default: true, ..Default::default() }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn stop_propagation_modifier_stops_bubbling() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+
+    let ids: Vec<ElementId> = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener { name: "click", id, .. } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    let (stopped, bubbles) = (ids[1], ids[2]);
+    drop(edits);
+
+    dom.handle_event("click", Rc::new(MouseData::default()), stopped, true);
+    assert_eq!(CLICKS.with(|c| c.take()), ["stopped"]);
+
+    dom.handle_event("click", Rc::new(MouseData::default()), bubbles, true);
+    assert_eq!(CLICKS.with(|c| c.take()), ["bubbles", "outer"]);
+}
@@ -0,0 +1,269 @@
+#![allow(non_snake_case)]
+//! Tests for the `passive`, `capture` and `once` listener options
+use dioxus::core::{AttributeValue, ElementId, ListenerFlags, Mutation::*, Mutations, Renderer};
+use dioxus::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    static CLICKS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn clicked(name: &'static str) {
+    CLICKS.with(|c| c.borrow_mut().push(name));
+}
+
+fn take_clicks() -> Vec<&'static str> {
+    CLICKS.with(|c| c.take())
+}
+
+/// A renderer that keeps its native listeners like the DOM does, where removing a listener only matches the capture
+/// option it was added with
+#[derive(Default)]
+struct MockListeners {
+    listeners: Vec<(ElementId, String, ListenerFlags)>,
+}
+
+impl Renderer for MockListeners {
+    fn apply(&mut self, mutations: Mutations) {
+        for edit in mutations.edits {
+            match edit {
+                NewEventListener { name, id, flags } => {
+                    self.listeners.push((id, name.to_string(), flags));
+                }
+                RemoveEventListener { name, id, flags } => {
+                    let added = self
+                        .listeners
+                        .iter()
+                        .position(|(i, n, f)| *i == id && n == name && f.capture == flags.capture);
+                    match added {
+                        Some(idx) => self.listeners.remove(idx),
+                        None => panic!("no {} listener on {:?} to remove", name, id),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn modifiers_set_listener_options() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div { onwheel: passive move |_| {},
+                button { onclick: capture once move |_| {} }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MockListeners::default();
+    renderer.apply(dom.rebuild());
+
+    let mut options: Vec<_> = renderer
+        .listeners
+        .iter()
+        .map(|(_, name, flags)| (name.as_str(), *flags))
+        .collect();
+    options.sort_by_key(|(name, _)| *name);
+
+    assert_eq!(
+        options,
+        [
+            (
+                "click",
+                ListenerFlags { capture: true, once: true, ..Default::default() }
+            ),
+            (
+                "wheel",
+                ListenerFlags { passive: true, ..Default::default() }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn changed_options_remove_the_old_listener() {
+    thread_local! {
+        static FLAGS: Cell<ListenerFlags> = Cell::new(ListenerFlags {
+            passive: true,
+            ..Default::default()
+        });
+    }
+
+    fn app(cx: Scope) -> Element {
+        let flags = FLAGS.with(Cell::get);
+        let listener = match cx.listener(|_: Event<WheelData>| {}) {
+            AttributeValue::Listener(cb, _) => AttributeValue::Listener(cb, flags),
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx! { div { "onwheel": listener } })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MockListeners::default();
+    renderer.apply(dom.rebuild());
+
+    // The same options don't touch the listener
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+    assert!(!edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, NewEventListener { .. } | RemoveEventListener { .. })));
+    renderer.apply(edits);
+
+    // New options remove the listener with the options it was added with, and add it again
+    let capture = ListenerFlags { capture: true, ..Default::default() };
+    FLAGS.with(|f| f.set(capture));
+    dom.mark_dirty(ScopeId(0));
+    renderer.apply(dom.render_immediate());
+
+    assert_eq!(renderer.listeners.len(), 1);
+    assert_eq!(renderer.listeners[0].1, "wheel");
+    assert_eq!(renderer.listeners[0].2, capture);
+}
+
+#[test]
+fn capture_listeners_run_before_bubbling() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div { onclick: capture move |_| clicked("outer capture"),
+                div { onclick: move |_| clicked("middle bubble"),
+                    section { onclick: capture move |_| clicked("inner capture"),
+                        button {
+                            onclick: move |_| clicked("target"),
+                            onkeydown: move |_| {},
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+    let target = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { name: "keydown", id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    drop(edits);
+
+    dom.handle_event("click", Rc::new(MouseData::default()), target, true);
+    assert_eq!(
+        take_clicks(),
+        ["outer capture", "inner capture", "target", "middle bubble"]
+    );
+}
+
+#[test]
+fn capture_listeners_run_across_components() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div { onclick: capture move |_| clicked("outer capture"),
+                div { onclick: move |_| clicked("middle bubble"),
+                    Child {}
+                }
+            }
+        })
+    }
+
+    fn Child(cx: Scope) -> Element {
+        cx.render(rsx! {
+            section { onclick: capture move |_| clicked("inner capture"),
+                button {
+                    onclick: move |_| clicked("target"),
+                    onkeydown: move |_| {},
+                }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+    let target = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { name: "keydown", id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+
+    drop(edits);
+
+    dom.handle_event("click", Rc::new(MouseData::default()), target, true);
+    assert_eq!(
+        take_clicks(),
+        ["outer capture", "inner capture", "target", "middle bubble"]
+    );
+}
+
+#[test]
+fn once_listeners_run_once() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            button { onclick: once move |_| clicked("once") }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+    let button = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { name: "click", id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    drop(edits);
+
+    dom.handle_event("click", Rc::new(MouseData::default()), button, true);
+    dom.handle_event("click", Rc::new(MouseData::default()), button, true);
+    assert_eq!(take_clicks(), ["once"]);
+
+    // Rendering the same listener again doesn't bring it back
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    dom.handle_event("click", Rc::new(MouseData::default()), button, true);
+    assert!(take_clicks().is_empty());
+}
+
+#[test]
+fn capture_listeners_see_events_that_dont_bubble() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div { onfocus: capture move |_| clicked("outer capture"),
+                div { onfocus: move |_| clicked("middle"),
+                    input { onfocus: move |_| clicked("target") }
+                }
+            }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild().santize();
+    let target = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener { name: "focus", id, .. } => Some(*id),
+            _ => None,
+        })
+        .last()
+        .unwrap();
+    drop(edits);
+
+    // The listeners the event would bubble to are skipped, but the capture listeners on the way down are not
+    dom.handle_event("focus", Rc::new(FocusData {}), target, false);
+    assert_eq!(take_clicks(), ["outer capture", "target"]);
+}
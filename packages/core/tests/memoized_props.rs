@@ -0,0 +1,64 @@
+//! Components whose props are equal to the last render are skipped when their parent renders again
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static STATIC_RENDERS: Cell<usize> = Cell::new(0);
+    static COUNT_RENDERS: Cell<usize> = Cell::new(0);
+    static OPTED_OUT_RENDERS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let count = cx.generation();
+    render! {
+        static_child { text: "never changes" }
+        count_child { count: count }
+        opted_out { text: "never changes" }
+    }
+}
+
+#[inline_props]
+fn static_child(cx: Scope, text: &'static str) -> Element {
+    STATIC_RENDERS.with(|r| r.set(r.get() + 1));
+    render! { "{text}" }
+}
+
+#[inline_props]
+fn count_child(cx: Scope, count: usize) -> Element {
+    COUNT_RENDERS.with(|r| r.set(r.get() + 1));
+    render! { "{count}" }
+}
+
+#[derive(Props, PartialEq)]
+#[props(no_memo)]
+struct OptedOutProps {
+    text: &'static str,
+}
+
+fn opted_out(cx: Scope<OptedOutProps>) -> Element {
+    OPTED_OUT_RENDERS.with(|r| r.set(r.get() + 1));
+    render! { "{cx.props.text}" }
+}
+
+#[test]
+fn equal_props_skip_the_render() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+    }
+
+    // The static child rendered once, while the child with new props rendered every time
+    assert_eq!(STATIC_RENDERS.with(Cell::get), 1);
+    assert_eq!(COUNT_RENDERS.with(Cell::get), 4);
+
+    // Components can opt out, and render with their parent even if the props are equal
+    assert_eq!(OPTED_OUT_RENDERS.with(Cell::get), 4);
+
+    // Marking the child itself dirty still renders it
+    dom.mark_dirty(ScopeId(1));
+    _ = dom.render_immediate();
+    assert_eq!(STATIC_RENDERS.with(Cell::get), 2);
+}
@@ -0,0 +1,125 @@
+#![allow(non_snake_case)]
+
+//! The handle from onmounted sends focus, scroll and measure commands to the renderer element it was created for
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*, Mutations};
+use dioxus::html::geometry::euclid::{Point2D, Rect, Size2D};
+use dioxus::html::{MountedResult, RenderedElementBacking, ScrollBehavior};
+use dioxus::prelude::*;
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc};
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Focus(ElementId, bool),
+    ScrollIntoView(ElementId),
+    Measure(ElementId),
+}
+
+thread_local! {
+    static COMMANDS: RefCell<Vec<Command>> = RefCell::new(Vec::new());
+    static RECTS: RefCell<Vec<Rect<f64, f64>>> = RefCell::new(Vec::new());
+}
+
+/// An element of a mock renderer that records the commands it gets, and is laid out in rows 10 pixels tall
+struct MockElement(ElementId);
+
+impl MockElement {
+    fn rect(&self) -> Rect<f64, f64> {
+        Rect::new(
+            Point2D::new(0.0, self.0 .0 as f64 * 10.0),
+            Size2D::new(100.0, 10.0),
+        )
+    }
+}
+
+impl RenderedElementBacking for MockElement {
+    #[allow(clippy::type_complexity)]
+    fn get_client_rect(&self) -> Pin<Box<dyn Future<Output = MountedResult<Rect<f64, f64>>>>> {
+        COMMANDS.with(|c| c.borrow_mut().push(Command::Measure(self.0)));
+        let rect = self.rect();
+        Box::pin(async move { Ok(rect) })
+    }
+
+    fn scroll_to(&self, _: ScrollBehavior) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        COMMANDS.with(|c| c.borrow_mut().push(Command::ScrollIntoView(self.0)));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn set_focus(&self, focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        COMMANDS.with(|c| c.borrow_mut().push(Command::Focus(self.0, focus)));
+        Box::pin(async { Ok(()) })
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        form {
+            Field { name: "name", autofocus: false }
+            Field { name: "email", autofocus: true }
+        }
+    }
+}
+
+#[inline_props]
+fn Field(cx: Scope, name: &'static str, autofocus: bool) -> Element {
+    let autofocus = *autofocus;
+
+    render! {
+        input {
+            name: "{name}",
+            onmounted: move |evt| {
+                let element = evt.data.clone();
+                cx.spawn(async move {
+                    if autofocus {
+                        element.set_focus(true).await.unwrap();
+                        element.scroll_to(ScrollBehavior::Smooth).await.unwrap();
+                        let rect = element.get_client_rect().await.unwrap();
+                        RECTS.with(|r| r.borrow_mut().push(rect));
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// The elements that got a mounted listener, and the element with each `name`
+fn mounted(mutations: Mutations) -> (Vec<ElementId>, Vec<(String, ElementId)>) {
+    let mut mounted = Vec::new();
+    let mut names = Vec::new();
+    for edit in mutations.edits {
+        match edit {
+            NewEventListener { name: "mounted", id, .. } => mounted.push(id),
+            SetAttribute {
+                name: "name", value: BorrowedAttributeValue::Text(value), id, ..
+            } => names.push((value.to_string(), id)),
+            _ => {}
+        }
+    }
+    (mounted, names)
+}
+
+#[test]
+fn commands_reach_the_mounted_element() {
+    let mut dom = VirtualDom::new(app);
+    let (mounted, names) = mounted(dom.rebuild());
+    assert_eq!(mounted.len(), 2);
+
+    // Hand each listener its element once the mutations are applied, like a renderer does
+    for id in mounted {
+        let data = MountedData::new(MockElement(id));
+        dom.handle_event("mounted", Rc::new(data), id, false);
+    }
+    dom.process_events();
+
+    let email = names.iter().find(|(name, _)| name == "email").unwrap().1;
+    COMMANDS.with(|c| {
+        assert_eq!(
+            *c.borrow(),
+            [
+                Command::Focus(email, true),
+                Command::ScrollIntoView(email),
+                Command::Measure(email),
+            ]
+        )
+    });
+    RECTS.with(|r| assert_eq!(*r.borrow(), [MockElement(email).rect()]));
+}
@@ -0,0 +1,87 @@
+#![allow(non_snake_case)]
+
+//! Several text updates in one template are sent as a single mutation
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+
+fn Row(cx: Scope) -> Element {
+    let gen = cx.generation();
+
+    render! {
+        tr {
+            td { "{gen}" }
+            td { "{gen * 2}" }
+            td { "{gen * 3}" }
+        }
+    }
+}
+
+#[test]
+fn changed_cells_coalesce_into_one_edit() {
+    let mut dom = VirtualDom::new(Row);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+
+    // The cells are created last to first, so the last one has the lowest id
+    assert_eq!(
+        edits.edits,
+        [SetMultiText {
+            values: vec![
+                (ElementId(2), "3"),
+                (ElementId(3), "2"),
+                (ElementId(4), "1"),
+            ]
+        }]
+    );
+}
+
+#[test]
+fn child_components_send_their_own_updates() {
+    fn table(cx: Scope) -> Element {
+        let gen = cx.generation();
+        render! {
+            caption { "{gen}" }
+            Row {}
+        }
+    }
+
+    let mut dom = VirtualDom::new(table);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    dom.mark_dirty(ScopeId(1));
+    let edits = dom.render_immediate();
+
+    assert_eq!(
+        edits.edits,
+        [
+            SetText { value: "1", id: ElementId(2) },
+            SetMultiText {
+                values: vec![
+                    (ElementId(4), "3"),
+                    (ElementId(5), "2"),
+                    (ElementId(6), "1"),
+                ]
+            },
+        ]
+    );
+}
+
+#[test]
+fn single_text_update_stays_set_text() {
+    fn one(cx: Scope) -> Element {
+        let gen = cx.generation();
+        render! { td { "{gen}" span { "static" } } }
+    }
+
+    let mut dom = VirtualDom::new(one);
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+
+    assert_eq!(edits.edits, [SetText { value: "1", id: ElementId(2) }]);
+}
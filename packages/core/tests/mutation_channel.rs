@@ -0,0 +1,133 @@
+//! A VirtualDom can diff on a background thread while the main thread applies its mutations
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{mutation_channel, ElementId, OwnedMutation, OwnedMutations};
+
+fn app(cx: Scope) -> Element {
+    let gen = cx.generation();
+
+    render! { div { "Rendered {gen} times" } }
+}
+
+#[test]
+fn diff_on_background_thread() {
+    let (sender, mut receiver) = mutation_channel();
+
+    // The VirtualDom never leaves this thread, only copies of its mutations do
+    std::thread::spawn(move || {
+        let mut dom = VirtualDom::new(app);
+        assert!(sender.send(dom.rebuild()));
+
+        dom.mark_dirty(ScopeId(0));
+        assert!(sender.send(dom.render_immediate()));
+    })
+    .join()
+    .unwrap();
+
+    let rebuild = receiver.try_recv().unwrap();
+    assert_eq!(rebuild.templates.len(), 1);
+    assert_eq!(
+        rebuild
+            .edits
+            .iter()
+            .map(OwnedMutation::as_mutation)
+            .collect::<Vec<_>>(),
+        [
+            LoadTemplate { name: rebuild.templates[0].name, index: 0, id: ElementId(1) },
+            HydrateText { path: &[0], value: "Rendered 0 times", id: ElementId(2) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+
+    let update = receiver.try_recv().unwrap();
+    assert!(update.templates.is_empty());
+    assert_eq!(
+        update
+            .edits
+            .iter()
+            .map(OwnedMutation::as_mutation)
+            .collect::<Vec<_>>(),
+        [SetText { value: "Rendered 1 times", id: ElementId(2) }]
+    );
+
+    // The background thread is gone, so nothing else is coming
+    assert!(receiver.try_recv().is_none());
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn mutations_round_trip_through_json() {
+    fn app(cx: Scope) -> Element {
+        let gen = cx.generation() as i64;
+        let title = (gen == 0).then(|| "first render");
+
+        render! {
+            div { title: title, tabindex: gen, hidden: gen > 0, opacity: 0.5,
+                button { onclick: |_| {}, "Rendered {gen} times" }
+                (gen == 0).then(|| rsx!(p { "only at first" }))
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+
+    let rebuild = dom.rebuild();
+    let json = serde_json::to_string(&rebuild).unwrap();
+    let received: OwnedMutations = serde_json::from_str(&json).unwrap();
+    assert_eq!(received.templates, rebuild.templates);
+    assert_eq!(
+        received
+            .edits
+            .iter()
+            .map(OwnedMutation::as_mutation)
+            .collect::<Vec<_>>(),
+        rebuild.edits
+    );
+    drop(rebuild);
+
+    // Removed attributes are sent as null
+    dom.mark_dirty(ScopeId(0));
+    let update = dom.render_immediate();
+    let json = serde_json::to_string(&update).unwrap();
+    assert!(json.contains(r#"{"type":"SetAttribute","name":"title","value":null"#));
+    let received: OwnedMutations = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        received
+            .edits
+            .iter()
+            .map(OwnedMutation::as_mutation)
+            .collect::<Vec<_>>(),
+        update.edits
+    );
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn mutations_round_trip_through_msgpack() {
+    fn app(cx: Scope) -> Element {
+        let gen = cx.generation();
+
+        render! {
+            div { class: "gen-{gen}", tabindex: 1, opacity: 0.5,
+                button { onclick: |_| {}, "Rendered {gen} times" }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+
+    let rebuild = dom.rebuild();
+    let bytes = rmp_serde::to_vec_named(&rebuild).unwrap();
+    assert!(bytes.len() < serde_json::to_vec(&rebuild).unwrap().len());
+
+    let received: OwnedMutations = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(received.templates, rebuild.templates);
+    assert_eq!(
+        received
+            .edits
+            .iter()
+            .map(OwnedMutation::as_mutation)
+            .collect::<Vec<_>>(),
+        rebuild.edits
+    );
+}
@@ -0,0 +1,93 @@
+#![allow(non_snake_case)]
+
+//! Errors stop at the nearest error boundary, and boundaries can be reset to render their children again
+use dioxus::core::ErrorBoundary;
+use dioxus::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    static SHOULD_THROW: Cell<bool> = Cell::new(true);
+    static RENDERED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    static BOUNDARIES: RefCell<Vec<Rc<ErrorBoundary>>> = RefCell::new(Vec::new());
+}
+
+fn rendered(name: &'static str) {
+    RENDERED.with(|r| r.borrow_mut().push(name));
+}
+
+fn boundaries() -> (Rc<ErrorBoundary>, Rc<ErrorBoundary>) {
+    BOUNDARIES.with(|b| {
+        let b = b.borrow();
+        (b[0].clone(), b[1].clone())
+    })
+}
+
+fn app(cx: Scope) -> Element {
+    let boundary = use_error_boundary(cx);
+    cx.use_hook(|| BOUNDARIES.with(|b| b.borrow_mut().push(boundary.clone())));
+
+    if boundary.has_error() {
+        rendered("outer fallback");
+        return render! { "Something went wrong" };
+    }
+
+    rendered("page");
+    render! {
+        h1 { "Page" }
+        widget_frame {}
+    }
+}
+
+fn widget_frame(cx: Scope) -> Element {
+    let boundary = use_error_boundary(cx);
+    cx.use_hook(|| BOUNDARIES.with(|b| b.borrow_mut().push(boundary.clone())));
+
+    if boundary.has_error() {
+        rendered("inner fallback");
+        return render! { "The widget crashed" };
+    }
+
+    rendered("frame");
+    render! { Widget {} }
+}
+
+fn Widget(cx: Scope) -> Element {
+    if SHOULD_THROW.with(|s| s.get()) {
+        cx.throw("widget broke")?;
+    }
+
+    rendered("widget");
+    render! { "Widget" }
+}
+
+#[test]
+fn inner_boundary_catches_and_resets() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    _ = dom.render_immediate();
+
+    let (outer, inner) = boundaries();
+
+    // The inner boundary caught the error and rendered its fallback, the outer one never saw it
+    assert_eq!(
+        RENDERED.with(|r| r.take()),
+        ["page", "frame", "inner fallback"]
+    );
+    assert!(!outer.has_error());
+    let error = inner.error().unwrap();
+    assert_eq!(format!("{:?}", error.error), "\"widget broke\"");
+    assert_eq!(error.scope, ScopeId(2));
+    drop(error);
+
+    // Resetting the boundary renders its children from scratch
+    SHOULD_THROW.with(|s| s.set(false));
+    inner.reset();
+    _ = dom.render_immediate();
+
+    assert_eq!(RENDERED.with(|r| r.take()), ["frame", "widget"]);
+    assert!(!inner.has_error());
+    assert!(!outer.has_error());
+}
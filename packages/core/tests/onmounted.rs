@@ -0,0 +1,75 @@
+//! The mounted event fires once when an element is created, and again only if the element is created again
+use dioxus::core::{ElementId, Mutation::*, Mutations};
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    static SHOW: Cell<bool> = Cell::new(true);
+    static COUNT: Cell<usize> = Cell::new(0);
+    static MOUNTS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW.with(Cell::get);
+    let count = COUNT.with(Cell::get);
+
+    render! {
+        p { "{count}" }
+        show.then(|| rsx! {
+            div { onmounted: move |_| MOUNTS.with(|m| m.set(m.get() + 1)), "{count}" }
+        })
+    }
+}
+
+/// The elements that got a mounted listener
+fn mounted(mutations: Mutations) -> Vec<ElementId> {
+    mutations
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener { name: "mounted", id, .. } => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Fire the mounted event for every element that got a mounted listener, like the web renderer does once the
+/// mutations are applied
+fn mount(dom: &mut VirtualDom, mounted: Vec<ElementId>) {
+    for id in mounted {
+        dom.handle_event("mounted", Rc::new(MountedData::new(())), id, false);
+    }
+}
+
+fn rerender(dom: &mut VirtualDom) {
+    dom.mark_dirty(ScopeId(0));
+    let ids = mounted(dom.render_immediate());
+    mount(dom, ids);
+}
+
+fn mounts() -> usize {
+    MOUNTS.with(Cell::get)
+}
+
+#[test]
+fn fires_once_per_mount() {
+    let mut dom = VirtualDom::new(app);
+    let ids = mounted(dom.rebuild());
+    mount(&mut dom, ids);
+    assert_eq!(mounts(), 1);
+
+    // Updating the element doesn't mount it again
+    COUNT.with(|c| c.set(1));
+    rerender(&mut dom);
+    rerender(&mut dom);
+    assert_eq!(mounts(), 1);
+
+    // Removing the element doesn't fire anything, creating it again does
+    SHOW.with(|s| s.set(false));
+    rerender(&mut dom);
+    assert_eq!(mounts(), 1);
+
+    SHOW.with(|s| s.set(true));
+    rerender(&mut dom);
+    assert_eq!(mounts(), 2);
+}
@@ -0,0 +1,139 @@
+//! Portals append their children to another element, and remove them from there when they are removed
+use dioxus::core::{BorrowedAttributeValue, ElementId, Mutation::*, Renderer};
+use dioxus::prelude::*;
+use dioxus_ssr::MutationRenderer;
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    static SHOW: Cell<bool> = Cell::new(false);
+    static TARGET: Cell<ElementId> = Cell::new(ElementId(0));
+    static CLICKS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW.with(Cell::get);
+    let target = TARGET.with(Cell::get);
+    let modals = "modals";
+
+    render! {
+        main {
+            "content"
+            show.then(|| rsx! {
+                Portal { mount: target,
+                    button { onclick: move |_| CLICKS.with(|c| c.set(c.get() + 1)), "close" }
+                }
+            })
+            "footer"
+        }
+        aside { id: "{modals}" }
+    }
+}
+
+fn rerender(dom: &mut VirtualDom, renderer: &mut MutationRenderer) -> Vec<ElementId> {
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+    let clicks = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener { name: "click", id, .. } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    renderer.apply(edits);
+    clicks
+}
+
+#[test]
+fn children_are_appended_to_the_target() {
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MutationRenderer::new();
+    let edits = dom.rebuild();
+    let modals = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            SetAttribute {
+                name: "id", value: BorrowedAttributeValue::Text("modals"), id, ..
+            } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    renderer.apply(edits);
+    TARGET.with(|t| t.set(modals));
+
+    SHOW.with(|s| s.set(true));
+    let buttons = rerender(&mut dom, &mut renderer);
+    assert_eq!(
+        renderer.to_html(),
+        r#"<main>contentfooter</main><aside id="modals"><button>close</button></aside>"#
+    );
+
+    // The button is still under the portal in the VirtualDom, so its listener runs
+    assert_eq!(buttons.len(), 1);
+    dom.handle_event("click", Rc::new(MouseData::default()), buttons[0], true);
+    assert_eq!(CLICKS.with(Cell::get), 1);
+
+    // Removing the portal removes the children from the target too
+    SHOW.with(|s| s.set(false));
+    rerender(&mut dom, &mut renderer);
+    assert_eq!(
+        renderer.to_html(),
+        r#"<main>contentfooter</main><aside id="modals"></aside>"#
+    );
+
+    // Moving the target moves the children
+    SHOW.with(|s| s.set(true));
+    rerender(&mut dom, &mut renderer);
+    TARGET.with(|t| t.set(ElementId(0)));
+    rerender(&mut dom, &mut renderer);
+    assert_eq!(
+        renderer.to_html(),
+        r#"<main>contentfooter</main><aside id="modals"></aside><button>close</button>"#
+    );
+}
+
+fn tooltips(cx: Scope) -> Element {
+    render! {
+        main {
+            Portal { selector: "body", p { "first" } }
+            Portal { selector: "body", p { "second" } }
+        }
+    }
+}
+
+#[test]
+fn selectors_are_assigned_an_id_once() {
+    let mut dom = VirtualDom::new(tooltips);
+    let edits = dom.rebuild();
+
+    let assigned: Vec<_> = edits
+        .edits
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, edit)| match edit {
+            AssignExternalRoot { selector, id } => Some((idx, *selector, *id)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(assigned.len(), 1);
+    let (assigned_at, selector, body) = assigned[0];
+    assert_eq!(selector, "body");
+
+    // Both portals append to the element, after the renderer found it
+    let appended: Vec<_> = edits
+        .edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| matches!(edit, AppendChildren { id, .. } if *id == body))
+        .map(|(idx, _)| idx)
+        .collect();
+    assert_eq!(appended.len(), 2);
+    assert!(appended.iter().all(|idx| *idx > assigned_at));
+
+    // Without a document, the children are mounted to the root of the app. Like the other dynamic nodes under an
+    // element, the portals are created last to first.
+    let mut renderer = MutationRenderer::new();
+    renderer.apply(edits);
+    assert_eq!(renderer.to_html(), "<p>second</p><p>first</p><main></main>");
+}
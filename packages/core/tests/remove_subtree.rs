@@ -0,0 +1,69 @@
+//! Removing a subtree is a single mutation on its root, however many nodes and listeners are under it
+use dioxus::core::{ElementId, Mutation::*, Mutations};
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static SHOW: Cell<bool> = Cell::new(true);
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW.with(Cell::get);
+
+    render! {
+        show.then(|| rsx! {
+            div {
+                ul {
+                    (0..1000).map(|i| rsx! {
+                        li { key: "{i}", onclick: move |_| {}, "{i}" }
+                    })
+                }
+            }
+        })
+    }
+}
+
+fn highest_id(mutations: &Mutations) -> ElementId {
+    mutations
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            LoadTemplate { id, .. } | CreatePlaceholder { id } | HydrateText { id, .. } => {
+                Some(*id)
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap()
+}
+
+fn toggle(dom: &mut VirtualDom, show: bool) -> Mutations {
+    SHOW.with(|s| s.set(show));
+    dom.mark_dirty(ScopeId(0));
+    dom.render_immediate()
+}
+
+#[test]
+fn subtree_removal_is_one_mutation() {
+    let mut dom = VirtualDom::new(app);
+    let created = dom.rebuild();
+    let listeners = created
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, NewEventListener { .. }))
+        .count();
+    assert_eq!(listeners, 1000);
+    let highest = highest_id(&created);
+    drop(created);
+
+    // The list goes away with its root, the renderer drops the listeners along with the nodes
+    let removed = toggle(&mut dom, false);
+    assert_eq!(removed.edits.len(), 2);
+    assert!(matches!(removed.edits[0], CreatePlaceholder { .. }));
+    assert!(matches!(removed.edits[1], ReplaceWith { m: 1, .. }));
+    drop(removed);
+
+    // Every ID under the root was reclaimed, so creating the list again doesn't need new ones
+    let recreated = toggle(&mut dom, true);
+    assert!(highest_id(&recreated) <= highest);
+}
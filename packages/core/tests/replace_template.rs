@@ -0,0 +1,48 @@
+//! Replacing a template at runtime renders every component that uses it again, which is what hot reloading relies on
+use dioxus::core::{Mutation::*, Renderer, Template, TemplateNode};
+use dioxus::prelude::*;
+use dioxus_ssr::MutationRenderer;
+
+fn app(cx: Scope) -> Element {
+    render! {
+        ul {
+            (0..2).map(|_| rsx! { li { "item" } })
+        }
+    }
+}
+
+#[test]
+fn templates_in_lists_are_replaced() {
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MutationRenderer::new();
+    let edits = dom.rebuild();
+    let item = *edits
+        .templates
+        .iter()
+        .find(|template| matches!(template.roots, [TemplateNode::Element { tag: "li", .. }]))
+        .unwrap();
+    renderer.apply(edits);
+    assert_eq!(renderer.to_html(), "<ul><li>item</li><li>item</li></ul>");
+
+    dom.replace_template(Template {
+        roots: &[TemplateNode::Element {
+            tag: "li",
+            namespace: None,
+            attrs: &[],
+            children: &[TemplateNode::Text { text: "changed" }],
+        }],
+        ..item
+    });
+
+    // The items are only in the list of the root component, but they are still recreated
+    let edits = dom.render_immediate();
+    assert!(edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, ReplaceWith { .. })));
+    renderer.apply(edits);
+    assert_eq!(
+        renderer.to_html(),
+        "<ul><li>changed</li><li>changed</li></ul>"
+    );
+}
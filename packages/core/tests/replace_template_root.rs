@@ -0,0 +1,67 @@
+#![allow(non_snake_case)]
+
+//! Renderers can recreate a single root of a template when it doesn't match the VirtualDom
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    render! {
+        h1 { "title" }
+        div { Child {} }
+    }
+}
+
+fn Child(cx: Scope) -> Element {
+    render! { p { "child" } }
+}
+
+#[test]
+fn replaces_only_the_given_root() {
+    let mut dom = VirtualDom::new(app);
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            LoadTemplate { name: "template", index: 1, id: ElementId(2) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(3) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            AppendChildren { id: ElementId(0), m: 2 },
+        ]
+    );
+
+    // The child component is dropped and created again under the new root
+    assert_eq!(
+        dom.replace_template_root(ScopeId(0), ElementId(2))
+            .unwrap()
+            .santize()
+            .edits,
+        [
+            LoadTemplate { name: "template", index: 1, id: ElementId(3) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(4) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            ReplaceWith { id: ElementId(2), m: 1 },
+        ]
+    );
+
+    // The rest of the tree keeps diffing normally afterwards
+    dom.mark_dirty(ScopeId(0));
+    assert!(dom.render_immediate().edits.is_empty());
+}
+
+#[test]
+fn ids_that_are_not_template_roots_are_ignored() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // An id that was never given out, and a scope that doesn't exist
+    assert!(dom
+        .replace_template_root(ScopeId(0), ElementId(100))
+        .is_none());
+    assert!(dom
+        .replace_template_root(ScopeId(100), ElementId(2))
+        .is_none());
+
+    dom.mark_dirty(ScopeId(0));
+    assert!(dom.render_immediate().edits.is_empty());
+}
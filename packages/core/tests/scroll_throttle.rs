@@ -0,0 +1,106 @@
+//! Scroll events carry their position, and listeners with the `throttle` modifier get bursts of them coalesced
+use dioxus::core::{ElementId, EventThrottle, ListenerFlags, Mutation::*};
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+thread_local! {
+    static SCROLLS: RefCell<Vec<(f64, f64)>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        div {
+            onscroll: throttle(100) move |evt: ScrollEvent| {
+                SCROLLS.with(|s| s.borrow_mut().push((evt.scroll_top, evt.scroll_left)));
+            }
+        }
+    }
+}
+
+/// A renderer that fires scroll events at made up times, throttling them like a real renderer would
+struct MockRenderer {
+    dom: VirtualDom,
+    listener: (ElementId, ListenerFlags),
+    throttle: EventThrottle<ScrollData>,
+}
+
+impl MockRenderer {
+    fn new() -> Self {
+        let mut dom = VirtualDom::new(app);
+        let listener = dom
+            .rebuild()
+            .edits
+            .iter()
+            .find_map(|edit| match edit {
+                NewEventListener { name: "scroll", id, flags } => Some((*id, *flags)),
+                _ => None,
+            })
+            .unwrap();
+
+        Self { dom, listener, throttle: EventThrottle::new() }
+    }
+
+    fn scroll(&mut self, now: f64, scroll_top: f64) {
+        let (id, flags) = self.listener;
+        let data = ScrollData { scroll_top, scroll_left: 0.0 };
+
+        let ready = match flags.throttle {
+            Some(window) => self.throttle.offer(id, "scroll", window, now, data),
+            None => Some(data),
+        };
+        if let Some(data) = ready {
+            self.dom.handle_event("scroll", Rc::new(data), id, false);
+        }
+    }
+
+    fn tick(&mut self, now: f64) {
+        for (id, name, data) in self.throttle.flush(now) {
+            self.dom.handle_event(&name, Rc::new(data), id, false);
+        }
+    }
+}
+
+#[test]
+fn throttle_modifier_sets_flag() {
+    let renderer = MockRenderer::new();
+    assert_eq!(
+        renderer.listener.1,
+        ListenerFlags { throttle: Some(100), ..Default::default() }
+    );
+}
+
+#[test]
+fn scroll_handler_receives_position() {
+    let mut renderer = MockRenderer::new();
+
+    renderer.scroll(0.0, 42.0);
+
+    assert_eq!(SCROLLS.with(|s| s.take()), [(42.0, 0.0)]);
+}
+
+#[test]
+fn throttle_coalesces_rapid_scrolls() {
+    let mut renderer = MockRenderer::new();
+
+    // A burst of scrolls inside one window only dispatches the first right away
+    for (i, top) in [10.0, 20.0, 30.0, 40.0].iter().copied().enumerate() {
+        renderer.scroll(i as f64 * 10.0, top);
+    }
+    assert_eq!(SCROLLS.with(|s| s.take()), [(10.0, 0.0)]);
+    assert_eq!(renderer.throttle.next_deadline(), Some(100.0));
+
+    // Nothing comes out before the window is over
+    renderer.tick(50.0);
+    assert!(SCROLLS.with(|s| s.take()).is_empty());
+
+    // Then the newest position is delivered
+    renderer.tick(100.0);
+    assert_eq!(SCROLLS.with(|s| s.take()), [(40.0, 0.0)]);
+    assert_eq!(renderer.throttle.next_deadline(), None);
+
+    // The trailing event started a new window
+    renderer.scroll(150.0, 50.0);
+    assert!(SCROLLS.with(|s| s.take()).is_empty());
+    renderer.scroll(200.0, 60.0);
+    assert_eq!(SCROLLS.with(|s| s.take()), [(60.0, 0.0)]);
+}
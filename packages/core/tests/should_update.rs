@@ -0,0 +1,63 @@
+#![allow(non_snake_case)]
+
+//! Components can decide for themselves to skip a render
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let gen = cx.generation();
+    render! { Even { value: gen } }
+}
+
+#[inline_props]
+fn Even(cx: Scope, value: usize) -> Element {
+    RENDERS.with(|r| r.set(r.get() + 1));
+
+    // Odd values aren't worth showing
+    cx.use_should_update(|_, next| next.value % 2 == 0);
+
+    render! { "{value}" }
+}
+
+#[test]
+fn skips_render_when_marked_dirty() {
+    fn frozen(cx: Scope) -> Element {
+        RENDERS.with(|r| r.set(r.get() + 1));
+        cx.use_should_update(|_, _| false);
+        render! { "frozen" }
+    }
+
+    let mut dom = VirtualDom::new(frozen);
+    _ = dom.rebuild();
+    assert_eq!(RENDERS.with(Cell::get), 1);
+
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+    assert_eq!(RENDERS.with(Cell::get), 1);
+    assert!(edits.edits.is_empty());
+}
+
+#[test]
+fn skips_render_with_rejected_props() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(RENDERS.with(Cell::get), 1);
+
+    // The parent passes 1, which the predicate rejects
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+    assert_eq!(RENDERS.with(Cell::get), 1);
+    assert!(edits.edits.is_empty());
+    drop(edits);
+    assert_eq!(dioxus_ssr::render(&dom), "0");
+
+    // The parent passes 2, which renders again
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(RENDERS.with(Cell::get), 2);
+    assert_eq!(dioxus_ssr::render(&dom), "2");
+}
@@ -0,0 +1,63 @@
+#![allow(non_snake_case)]
+
+//! Tests for splitting mutations by the scope that produced them
+use dioxus::core::{Mutation::*, ScopeId};
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static COUNT: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        Child { name: "a" }
+        Child { name: "b" }
+    }
+}
+
+#[inline_props]
+fn Child(cx: Scope, name: &'static str) -> Element {
+    let count = COUNT.with(|c| c.get());
+    render! { div { "{name} {count}" } }
+}
+
+#[test]
+fn nested_creation_belongs_to_the_rebuilt_scope() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+    let (edit_count, template_count) = (edits.edits.len(), edits.templates.len());
+
+    let batches = edits.split_by_scope();
+
+    assert_eq!(batches.len(), 1);
+    let (scope, batch) = &batches[0];
+    assert_eq!(*scope, ScopeId(0));
+    assert_eq!(batch.edits.len(), edit_count);
+    assert_eq!(batch.templates.len(), template_count);
+}
+
+#[test]
+fn rerendered_scopes_split_into_their_own_batches() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    COUNT.with(|c| c.set(1));
+    dom.mark_dirty(ScopeId(1));
+    dom.mark_dirty(ScopeId(2));
+
+    let mut batches = dom.render_immediate().split_by_scope();
+    batches.sort_by_key(|(scope, _)| *scope);
+
+    assert_eq!(batches.len(), 2);
+
+    let (scope, batch) = &batches[0];
+    assert_eq!(*scope, ScopeId(1));
+    assert!(batch.templates.is_empty());
+    assert!(matches!(batch.edits[..], [SetText { value: "a 1", .. }]));
+
+    let (scope, batch) = &batches[1];
+    assert_eq!(*scope, ScopeId(2));
+    assert!(batch.templates.is_empty());
+    assert!(matches!(batch.edits[..], [SetText { value: "b 1", .. }]));
+}
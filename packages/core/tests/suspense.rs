@@ -1,3 +1,5 @@
+#![allow(non_snake_case)]
+
 use dioxus::prelude::*;
 
 #[test]
@@ -41,3 +43,105 @@ fn suspended_child(cx: Scope) -> Element {
 
     render!("child")
 }
+
+mod nested {
+    use dioxus::prelude::*;
+    use futures_channel::oneshot;
+    use futures_util::FutureExt;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static GATES: RefCell<Vec<Option<oneshot::Sender<()>>>> = RefCell::new(Vec::new());
+    }
+
+    /// Spawn a task that waits for its gate to open, returning if it has
+    fn opened(cx: &ScopeState) -> bool {
+        let open = use_state(cx, || false);
+        cx.use_hook(|| {
+            let (tx, rx) = oneshot::channel();
+            GATES.with(|g| g.borrow_mut().push(Some(tx)));
+            let open = open.clone();
+            cx.spawn(async move {
+                if rx.await.is_ok() {
+                    open.set(true);
+                }
+            });
+        });
+        **open
+    }
+
+    fn open_gate(idx: usize) {
+        GATES.with(|g| g.borrow_mut()[idx].take().unwrap().send(()).unwrap());
+    }
+
+    fn app(cx: Scope) -> Element {
+        render! { div { Outer {} } }
+    }
+
+    fn Outer(cx: Scope) -> Element {
+        if !opened(cx) {
+            return cx.suspend()?;
+        }
+        render! { "outer " Inner {} }
+    }
+
+    fn Inner(cx: Scope) -> Element {
+        if !opened(cx) {
+            return cx.suspend()?;
+        }
+        render! { "inner" }
+    }
+
+    #[test]
+    fn settles_after_transitive_suspense() {
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+        assert_eq!(dom.pending_suspense_count(), 1);
+
+        // The outer scope resolves into one that suspends on its own future
+        open_gate(0);
+        assert!(dom.wait_for_suspense().now_or_never().is_none());
+        assert_eq!(GATES.with(|g| g.borrow().len()), 2);
+        assert_eq!(dom.pending_suspense_count(), 1);
+
+        open_gate(1);
+        dom.wait_for_suspense().now_or_never().unwrap();
+        assert_eq!(dom.pending_suspense_count(), 0);
+        assert_eq!(dioxus_ssr::render(&dom), "<div>outer inner</div>");
+    }
+}
+
+#[test]
+fn deadline_renders_placeholders_for_slow_scopes() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx!(
+            div {
+                "Waiting for... "
+                slow_child {}
+            }
+        ))
+    }
+
+    fn slow_child(cx: Scope) -> Element {
+        cx.use_hook(|| cx.spawn(std::future::pending()));
+        cx.suspend()?;
+        render!("child")
+    }
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(app);
+            _ = dom.rebuild();
+
+            let deadline = tokio::time::sleep(std::time::Duration::from_millis(10));
+            assert!(!dom.wait_for_suspense_with_deadline(deadline).await);
+            assert_eq!(dom.pending_suspense_count(), 1);
+            assert_eq!(
+                dioxus_ssr::pre_render(&dom),
+                "<div>Waiting for... <pre></pre></div>"
+            );
+        });
+}
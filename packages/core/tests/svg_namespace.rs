@@ -0,0 +1,73 @@
+//! Elements inside of an svg are created in the svg namespace, even when their name doesn't say so
+use dioxus::core::{Mutation::*, TemplateNode};
+use dioxus::prelude::*;
+
+const SVG: Option<&str> = Some("http://www.w3.org/2000/svg");
+
+/// Every element of the templates with its namespace, depth first
+fn elements<'a>(nodes: &[TemplateNode<'a>], out: &mut Vec<(&'a str, Option<&'a str>)>) {
+    for node in nodes {
+        if let TemplateNode::Element { tag, namespace, children, .. } = node {
+            out.push((*tag, *namespace));
+            elements(children, out);
+        }
+    }
+}
+
+#[test]
+fn svg_children_inherit_the_namespace() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                svg {
+                    g {
+                        path { d: "M 0 0 L 10 10" }
+                        a { href: "#target", title { "link" } }
+                    }
+                    foreignObject {
+                        p { "html again" }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+
+    let mut created = Vec::new();
+    for template in &edits.templates {
+        elements(template.roots, &mut created);
+    }
+    assert_eq!(
+        created,
+        [
+            ("div", None),
+            ("svg", SVG),
+            ("g", SVG),
+            ("path", SVG),
+            ("a", SVG),
+            ("title", SVG),
+            ("foreignObject", SVG),
+            ("p", None),
+        ]
+    );
+}
+
+#[test]
+fn xlink_attributes_have_a_namespace() {
+    fn app(cx: Scope) -> Element {
+        let target = "#target";
+        render! {
+            svg { r#use { xlink_href: "{target}" } }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+
+    assert!(edits.edits.iter().any(|edit| matches!(
+        edit,
+        SetAttribute { name: "xlink:href", ns: Some("http://www.w3.org/1999/xlink"), .. }
+    )));
+}
@@ -44,3 +44,148 @@ fn app(cx: Scope) -> Element {
 
     cx.render(rsx!(()))
 }
+
+#[test]
+fn tasks_reuse_their_waker() {
+    use futures_util::future::poll_fn;
+    use std::{cell::RefCell, task::Poll, task::Waker};
+
+    thread_local! {
+        static WAKERS: RefCell<Vec<(usize, Waker)>> = RefCell::new(Vec::new());
+    }
+
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            for task in 0..2 {
+                cx.spawn(poll_fn(move |cx| {
+                    WAKERS.with(|w| w.borrow_mut().push((task, cx.waker().clone())));
+                    Poll::<()>::Pending
+                }));
+            }
+        });
+
+        cx.render(rsx!(()))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    _ = dom.render_immediate();
+
+    // Wake the first task a few times with the waker it was polled with
+    for _ in 0..3 {
+        let waker = WAKERS.with(|w| w.borrow()[0].1.clone());
+        waker.wake();
+        _ = dom.render_immediate();
+    }
+
+    let wakers = WAKERS.with(|w| w.borrow().clone());
+    let first: Vec<_> = wakers.iter().filter(|(task, _)| *task == 0).collect();
+    let second: Vec<_> = wakers.iter().filter(|(task, _)| *task == 1).collect();
+
+    // Only the woken task was polled again, and always with the same waker
+    assert_eq!(first.len(), 4);
+    assert_eq!(second.len(), 1);
+    assert!(first.iter().all(|(_, waker)| waker.will_wake(&first[0].1)));
+    assert!(!first[0].1.will_wake(&second[0].1));
+}
+
+#[test]
+fn cancelling_a_task_ignores_its_queued_wakeup() {
+    use futures_util::future::poll_fn;
+    use std::{
+        cell::{Cell, RefCell},
+        task::{Poll, Waker},
+    };
+
+    thread_local! {
+        static TASK: Cell<Option<TaskId>> = Cell::new(None);
+        static WAKER: RefCell<Option<Waker>> = RefCell::new(None);
+        static POLLS: Cell<usize> = Cell::new(0);
+        static DROPPED: Cell<bool> = Cell::new(false);
+    }
+
+    struct DropFlag;
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            DROPPED.with(|d| d.set(true));
+        }
+    }
+
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            let flag = DropFlag;
+            let id = cx.push_future(poll_fn(move |cx| {
+                let _flag = &flag;
+                POLLS.with(|p| p.set(p.get() + 1));
+                WAKER.with(|w| *w.borrow_mut() = Some(cx.waker().clone()));
+                Poll::<()>::Pending
+            }));
+            TASK.with(|t| t.set(Some(id)));
+        });
+
+        cx.render(rsx!(()))
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    _ = dom.render_immediate();
+    assert_eq!(POLLS.with(Cell::get), 1);
+
+    // Queue a wakeup, then cancel the task before the wakeup is handled
+    WAKER.with(|w| w.borrow_mut().take().unwrap().wake());
+    dom.cancel_task(TASK.with(Cell::get).unwrap());
+    assert!(DROPPED.with(Cell::get));
+
+    _ = dom.render_immediate();
+    assert_eq!(POLLS.with(Cell::get), 1);
+
+    // Cancelling it again does nothing
+    dom.cancel_task(TASK.with(Cell::get).unwrap());
+}
+
+#[test]
+fn stale_wakeups_dont_resume_newer_tasks() {
+    use futures_util::future::poll_fn;
+    use std::{
+        cell::{Cell, RefCell},
+        task::{Poll, Waker},
+    };
+
+    thread_local! {
+        static WAKER: RefCell<Option<Waker>> = RefCell::new(None);
+        static POLLS: Cell<usize> = Cell::new(0);
+    }
+
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx!(()))
+    }
+
+    fn pending(count: bool) -> impl std::future::Future<Output = ()> {
+        poll_fn(move |cx| {
+            if count {
+                POLLS.with(|p| p.set(p.get() + 1));
+            } else {
+                WAKER.with(|w| *w.borrow_mut() = Some(cx.waker().clone()));
+            }
+            Poll::Pending
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let old = dom.base_scope().push_future(pending(false));
+    _ = dom.render_immediate();
+
+    // Wake the old task after it is gone, then start a new task in its place
+    dom.cancel_task(old);
+    let new = dom.base_scope().push_future(pending(true));
+    assert_ne!(old, new);
+
+    _ = dom.render_immediate();
+    assert_eq!(POLLS.with(Cell::get), 1);
+
+    WAKER.with(|w| w.borrow_mut().take().unwrap().wake());
+    _ = dom.render_immediate();
+    assert_eq!(POLLS.with(Cell::get), 1);
+}
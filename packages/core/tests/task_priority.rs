@@ -0,0 +1,143 @@
+//! Woken tasks are polled by priority, and in the order they woke up within a priority
+use dioxus::prelude::*;
+use futures_util::future::poll_fn;
+use std::{
+    cell::RefCell,
+    task::{Poll, Waker},
+};
+
+thread_local! {
+    static POLLS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    static URGENT: RefCell<Option<Waker>> = RefCell::new(None);
+}
+
+fn polled(name: &'static str) {
+    POLLS.with(|p| p.borrow_mut().push(name));
+}
+
+fn take_polls() -> Vec<&'static str> {
+    POLLS.with(|p| p.take())
+}
+
+#[test]
+fn higher_lanes_drain_first() {
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            let lanes = [
+                ("prefetch", TaskPriority::Idle),
+                ("fetch", TaskPriority::Normal),
+                ("transition", TaskPriority::Immediate),
+                ("input", TaskPriority::UserBlocking),
+                ("another fetch", TaskPriority::Normal),
+            ];
+            for (name, priority) in lanes {
+                cx.spawn_with_priority(priority, async move { polled(name) });
+            }
+            cx.spawn(async { polled("default") });
+        });
+
+        render! { "tasks" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    dom.process_events();
+
+    assert_eq!(
+        take_polls(),
+        [
+            "transition",
+            "input",
+            "fetch",
+            "another fetch",
+            "default",
+            "prefetch"
+        ]
+    );
+}
+
+#[test]
+fn tasks_woken_while_draining_preempt_lower_lanes() {
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            cx.spawn_with_priority(TaskPriority::Idle, async { polled("idle") });
+            cx.spawn(async {
+                polled("first");
+                URGENT.with(|u| u.borrow_mut().take().unwrap().wake());
+            });
+            cx.spawn(async { polled("second") });
+            cx.spawn_with_priority(
+                TaskPriority::UserBlocking,
+                poll_fn(|cx| {
+                    polled("urgent");
+                    URGENT.with(|u| *u.borrow_mut() = Some(cx.waker().clone()));
+                    Poll::<()>::Pending
+                }),
+            );
+        });
+
+        render! { "tasks" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    dom.process_events();
+
+    // The first normal task wakes the urgent one, which runs before the second normal task
+    assert_eq!(
+        take_polls(),
+        ["urgent", "first", "urgent", "second", "idle"]
+    );
+}
+
+#[test]
+fn deferred_idle_tasks_wait_for_spare_time() {
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            cx.spawn_with_priority(TaskPriority::Idle, async { polled("prefetch") });
+            cx.spawn(async { polled("fetch") });
+        });
+
+        render! { "tasks" }
+    }
+
+    let mut dom = VirtualDom::new(app).with_deferred_idle_tasks(true);
+    _ = dom.rebuild();
+    dom.process_events();
+    assert_eq!(take_polls(), ["fetch"]);
+    assert!(dom.has_idle_tasks());
+
+    // Waiting for work finishes right away, so the renderer can schedule the idle task
+    futures_util::FutureExt::now_or_never(dom.wait_for_work()).unwrap();
+    assert_eq!(take_polls(), Vec::<&str>::new());
+
+    // While the renderer waits for spare time, waiting for work only finishes for other work
+    assert!(futures_util::FutureExt::now_or_never(dom.wait_for_work()).is_none());
+
+    dom.process_idle_tasks();
+    assert_eq!(take_polls(), ["prefetch"]);
+    assert!(!dom.has_idle_tasks());
+}
+
+#[test]
+fn immediate_tasks_are_reported_until_the_next_render() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 0);
+        cx.use_hook(|| {
+            let count = count.clone();
+            cx.spawn_with_priority(TaskPriority::Immediate, async move { count.set(1) });
+        });
+
+        render! { "{count}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert!(!dom.has_immediate_work());
+
+    dom.process_events();
+    assert!(dom.has_immediate_work());
+
+    _ = dom.render_immediate();
+    assert!(!dom.has_immediate_work());
+}
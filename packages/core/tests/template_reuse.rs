@@ -0,0 +1,50 @@
+//! A template is only sent to the renderer once, later instances clone it and fill in the dynamic parts
+use dioxus::core::{BorrowedAttributeValue, Mutation};
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static CARDS: Cell<usize> = Cell::new(1);
+}
+
+fn app(cx: Scope) -> Element {
+    let cards = CARDS.with(Cell::get);
+
+    render! {
+        (0..cards).map(|i| rsx! {
+            div { key: "{i}", class: "card-{i}",
+                h1 { "Card" }
+                p { "A body that never changes" }
+            }
+        })
+    }
+}
+
+#[test]
+fn second_instance_loads_the_cached_template() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+    assert_eq!(edits.templates.len(), 1);
+    let card = edits.templates[0].name.to_string();
+    drop(edits);
+
+    CARDS.with(|c| c.set(2));
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+
+    // Nothing is registered again, and the static elements are never created one by one
+    assert!(edits.templates.is_empty());
+    let (load, set_class) = match edits.edits.as_slice() {
+        [Mutation::LoadTemplate { name, index: 0, id }, Mutation::SetAttribute {
+            name: "class",
+            value: BorrowedAttributeValue::Text("card-1"),
+            id: set_class,
+            ns: None,
+        }, Mutation::InsertAfter { m: 1, .. }] => {
+            assert_eq!(*name, card);
+            (*id, *set_class)
+        }
+        edits => panic!("expected the template to be loaded, got {:#?}", edits),
+    };
+    assert_eq!(load, set_class);
+}
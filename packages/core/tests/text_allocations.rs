@@ -0,0 +1,76 @@
+//! Owned text is kept by its scope without a copy, and freed once the nodes borrowing it are gone
+use dioxus::prelude::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static FREES: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        FREES.fetch_add(1, Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn formatted(cx: Scope) -> Element {
+    let gen = cx.generation();
+    render! {
+        ul { (0..1000).map(|i| rsx! { li { key: "{i}", "item {i} of render {gen}" } }) }
+    }
+}
+
+fn owned(cx: Scope) -> Element {
+    let gen = cx.generation();
+    render! {
+        ul { (0..1000).map(|i| rsx! { li { key: "{i}", format!("item {i} of render {gen}") } }) }
+    }
+}
+
+/// Render an app until its allocators are warmed up, then count the allocations and frees of one more render
+fn count_render(app: Component) -> (usize, usize) {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+    }
+
+    dom.mark_dirty(ScopeId(0));
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+    let frees = FREES.load(Ordering::SeqCst);
+    _ = dom.render_immediate();
+    (
+        ALLOCATIONS.load(Ordering::SeqCst) - allocations,
+        FREES.load(Ordering::SeqCst) - frees,
+    )
+}
+
+// Both apps are measured in one test, so no other test allocates at the same time
+#[test]
+fn owned_text_is_freed() {
+    let (formatted_allocations, formatted_frees) = count_render(formatted);
+    let (owned_allocations, owned_frees) = count_render(owned);
+    assert!(owned_allocations >= formatted_allocations + 1000);
+
+    // The scope holds on to each String instead of copying it, and frees the Strings of an older render as the new ones
+    // are made, so they don't add to what a render keeps
+    let formatted_kept = formatted_allocations.saturating_sub(formatted_frees);
+    let owned_kept = owned_allocations.saturating_sub(owned_frees);
+    assert!(
+        owned_kept < formatted_kept + 100,
+        "{} allocations kept with owned text, {} with formatted text",
+        owned_kept,
+        formatted_kept
+    );
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "diff-stats")]
+#![allow(non_snake_case)]
+
+//! Renders that produce no mutations are counted per scope
+use dioxus::core::RenderStats;
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let gen = cx.generation();
+
+    render! {
+        p { "rendered {gen} times" }
+        Label {}
+    }
+}
+
+fn Label(cx: Scope) -> Element {
+    render! { "this never changes" }
+}
+
+#[test]
+fn over_rendering_component_counts_wasted_renders() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // The first render is never wasted
+    assert!(dom.wasted_render_stats().is_empty());
+
+    // The label renders the same thing every time
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId(1));
+        _ = dom.render_immediate();
+    }
+
+    // The app changes its text every time
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+
+    assert_eq!(
+        dom.wasted_render_stats(),
+        [
+            (
+                ScopeId(1),
+                RenderStats { name: "Label", renders: 3, wasted: 3 }
+            ),
+            (
+                ScopeId(0),
+                RenderStats { name: "app", renders: 1, wasted: 0 }
+            ),
+        ]
+    );
+}
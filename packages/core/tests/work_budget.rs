@@ -0,0 +1,63 @@
+#![allow(non_snake_case)]
+
+//! Rendering with a budget stops when it runs out and resumes on the next call
+use dioxus::core::{Mutation::SetText, WorkStatus};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+fn app(cx: Scope) -> Element {
+    render! {
+        Counter {}
+        Counter {}
+        Counter {}
+    }
+}
+
+fn Counter(cx: Scope) -> Element {
+    let gen = cx.generation();
+    render! { p { "{gen}" } }
+}
+
+#[test]
+fn stops_at_the_mutation_budget() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for id in 1..=3 {
+        dom.mark_dirty(ScopeId(id));
+    }
+
+    let (edits, status) = dom.process_with_budget(1, Duration::MAX);
+    assert_eq!(status, WorkStatus::Remaining);
+    assert_eq!(edits.edits.len(), 1);
+    drop(edits);
+
+    let (edits, status) = dom.process_with_budget(usize::MAX, Duration::MAX);
+    assert_eq!(status, WorkStatus::Finished);
+    assert_eq!(edits.edits.len(), 2);
+    assert!(edits
+        .edits
+        .iter()
+        .all(|edit| matches!(edit, SetText { value: "1", .. })));
+    drop(edits);
+
+    let (edits, status) = dom.process_with_budget(usize::MAX, Duration::MAX);
+    assert_eq!(status, WorkStatus::Finished);
+    assert!(edits.edits.is_empty());
+}
+
+#[test]
+fn an_empty_time_budget_renders_nothing() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    dom.mark_dirty(ScopeId(1));
+
+    let (edits, status) = dom.process_with_budget(usize::MAX, Duration::ZERO);
+    assert_eq!(status, WorkStatus::Remaining);
+    assert!(edits.edits.is_empty());
+    drop(edits);
+
+    let (edits, status) = dom.process_with_budget(usize::MAX, Duration::from_secs(60));
+    assert_eq!(status, WorkStatus::Finished);
+    assert_eq!(edits.edits.len(), 1);
+}
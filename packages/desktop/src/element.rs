@@ -4,6 +4,8 @@ use dioxus_html::{geometry::euclid::Rect, MountedResult, RenderedElementBacking}
 use crate::{desktop_context::DesktopContext, query::QueryEngine};
 
 /// A mounted element passed to onmounted events
+///
+/// Get it from the event with [`dioxus_html::MountedData::get_raw_element`] and `downcast_ref`.
 pub struct DesktopElement {
     id: ElementId,
     webview: DesktopContext,
@@ -14,6 +16,11 @@ impl DesktopElement {
     pub(crate) fn new(id: ElementId, webview: DesktopContext, query: QueryEngine) -> Self {
         Self { id, webview, query }
     }
+
+    /// The ID of the element. Scripts running in the webview can find the element with `window.interpreter.nodes[id]`.
+    pub fn id(&self) -> ElementId {
+        self.id
+    }
 }
 
 impl RenderedElementBacking for DesktopElement {
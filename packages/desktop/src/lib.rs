@@ -29,7 +29,7 @@ use desktop_context::{EventData, UserWindowEvent, WebviewQueue, WindowEventHandl
 use dioxus_core::*;
 use dioxus_html::MountedData;
 use dioxus_html::{native_bind::NativeFileEngine, FormData, HtmlEvent};
-use element::DesktopElement;
+pub use element::DesktopElement;
 use eval::init_eval;
 use futures_util::{pin_mut, FutureExt};
 use shortcut::ShortcutRegistry;
@@ -300,6 +300,8 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                     view.dom.handle_event(&name, as_any, element, bubbles);
 
                     send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+
+                    view.dom.run_layout_effects();
                 }
 
                 // When the webview sends a query, we need to send it to the query manager which handles dispatching the data to the correct pending query
@@ -323,6 +325,7 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                 EventData::Ipc(msg) if msg.method() == "initialize" => {
                     let view = webviews.get_mut(&event.1).unwrap();
                     send_edits(view.dom.rebuild(), &view.desktop_context.webview);
+                    view.dom.run_layout_effects();
                 }
 
                 EventData::Ipc(msg) if msg.method() == "browser_open" => {
@@ -361,6 +364,8 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                         }
 
                         send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+
+                        view.dom.run_layout_effects();
                     }
                 }
 
@@ -397,6 +402,9 @@ fn create_new_window(
     // Init eval
     init_eval(cx);
 
+    #[cfg(feature = "tokio_runtime")]
+    cx.provide_context(dioxus_hooks::Timer::new(TokioTimer));
+
     WebviewHandler {
         // We want to poll the virtualdom and the event loop at the same time, so the waker will be connected to both
         waker: waker::tao_waker(proxy, desktop_context.webview.window().id()),
@@ -406,6 +414,20 @@ fn create_new_window(
     }
 }
 
+/// Backs the timeouts of hooks with tokio's timer
+#[cfg(feature = "tokio_runtime")]
+struct TokioTimer;
+
+#[cfg(feature = "tokio_runtime")]
+impl dioxus_hooks::TimerBackend for TokioTimer {
+    fn sleep(
+        &self,
+        duration: std::time::Duration,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()>>> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
 struct WebviewHandler {
     dom: VirtualDom,
     desktop_context: DesktopContext,
@@ -436,6 +458,8 @@ fn poll_vdom(view: &mut WebviewHandler) {
         }
 
         send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+
+        view.dom.run_layout_effects();
     }
 }
 
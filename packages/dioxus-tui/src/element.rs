@@ -19,6 +19,7 @@ pub(crate) fn find_mount_events(mutations: &Mutations) -> Vec<ElementId> {
         if let dioxus_core::Mutation::NewEventListener {
             name: "mounted",
             id,
+            ..
         } = mutation
         {
             mount_events.push(*id);
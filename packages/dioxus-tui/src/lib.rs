@@ -2,9 +2,12 @@ mod element;
 
 use std::{
     any::Any,
+    future::Future,
     ops::Deref,
+    pin::Pin,
     rc::Rc,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use dioxus_core::{Component, ElementId, VirtualDom};
@@ -35,7 +38,8 @@ pub fn launch_cfg_with_props<Props: 'static>(app: Component<Props>, props: Props
             .with_root_context(Query::new(rdom.clone(), taffy.clone()))
             .with_root_context(DioxusElementToNodeId {
                 mapping: dioxus_state.clone(),
-            });
+            })
+            .with_root_context(dioxus_hooks::Timer::new(TokioTimer));
         let muts = vdom.rebuild();
 
         let mut queued_events = Vec::new();
@@ -59,6 +63,9 @@ pub fn launch_cfg_with_props<Props: 'static>(app: Component<Props>, props: Props
             );
         }
 
+        // The real dom is unlocked again, so layout effects can query it
+        vdom.run_layout_effects();
+
         DioxusRenderer {
             vdom,
             dioxus_state,
@@ -77,6 +84,15 @@ pub fn launch_cfg_with_props<Props: 'static>(app: Component<Props>, props: Props
     .unwrap();
 }
 
+/// Backs the timeouts of hooks with tokio's timer
+struct TokioTimer;
+
+impl dioxus_hooks::TimerBackend for TokioTimer {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
 struct DioxusRenderer {
     vdom: VirtualDom,
     dioxus_state: Rc<RwLock<DioxusState>>,
@@ -109,6 +125,8 @@ impl Driver for DioxusRenderer {
                 );
             }
         }
+
+        self.vdom.run_layout_effects();
     }
 
     fn handle_event(
@@ -0,0 +1,42 @@
+//! This benchmark renders a list of 1000 texts, formatted in rsx and as owned Strings.
+//!
+//! Text formatted in rsx is written straight into the bump of the component. Owned Strings are handed to the
+//! component as they are, without being copied into the bump.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use dioxus::prelude::*;
+
+criterion_group!(mbenches, render_texts);
+criterion_main!(mbenches);
+
+fn render_texts(c: &mut Criterion) {
+    fn formatted(cx: Scope) -> Element {
+        let gen = cx.generation();
+        render! {
+            ul { (0..1000).map(|i| rsx! { li { key: "{i}", "item {i} of render {gen}" } }) }
+        }
+    }
+
+    fn owned(cx: Scope) -> Element {
+        let gen = cx.generation();
+        render! {
+            ul { (0..1000).map(|i| rsx! { li { key: "{i}", format!("item {i} of render {gen}") } }) }
+        }
+    }
+
+    for (name, app) in [
+        ("formatted text", formatted as Component),
+        ("owned text", owned),
+    ] {
+        c.bench_function(name, |b| {
+            let mut dom = VirtualDom::new(app);
+            let _ = dom.rebuild();
+
+            b.iter(|| {
+                dom.mark_dirty(ScopeId(0));
+                let g = dom.render_immediate();
+                assert!(g.edits.len() > 1);
+            })
+        });
+    }
+}
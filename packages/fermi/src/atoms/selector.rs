@@ -1 +1,78 @@
+use crate::{AtomId, AtomRoot, Readable};
+use std::rc::Rc;
 
+/// A value derived from atoms and other selectors
+///
+/// ```rust, ignore
+/// static NAME: Atom<&str> = Atom(|_| "Dioxus");
+/// static GREETING: Selector<String> = Selector(|s| format!("Hello, {}!", s.get(&NAME)));
+///
+/// fn Greeting(cx: Scope) -> Element {
+///     let greeting = use_selector(cx, &GREETING);
+///     cx.render(rsx!{ h1 { "{greeting}" } })
+/// }
+/// ```
+///
+/// The value is computed the first time it is read and cached in the [`AtomRoot`]. It is only computed again when one
+/// of the atoms or selectors it read is set, and the components reading it only render again if the new value is
+/// different.
+pub struct Selector<T>(pub fn(&SelectorBuilder) -> T);
+
+/// Reads the atoms and selectors a [`Selector`] is derived from, subscribing the selector to them
+pub struct SelectorBuilder<'a> {
+    root: &'a AtomRoot,
+    id: AtomId,
+}
+
+impl<'a> SelectorBuilder<'a> {
+    pub(crate) fn new(root: &'a AtomRoot, id: AtomId) -> Self {
+        Self { root, id }
+    }
+
+    /// Read an atom, computing the selector again whenever it is set
+    pub fn get<V: 'static>(&self, atom: impl Readable<V>) -> Rc<V> {
+        self.root.add_dependent(atom.unique_id(), self.id);
+        self.root.read(atom)
+    }
+
+    /// Read another selector, computing this selector again whenever its value changes
+    pub fn select<V: PartialEq + 'static>(&self, selector: &'static Selector<V>) -> Rc<V> {
+        self.root.add_dependent(selector.unique_id(), self.id);
+        self.root.read_selector(selector)
+    }
+}
+
+impl<V> Selector<V> {
+    pub fn unique_id(&'static self) -> AtomId {
+        self as *const Selector<V> as *const ()
+    }
+}
+
+#[test]
+fn selectors_only_notify_on_change() {
+    use crate::Atom;
+    use dioxus_core::ScopeId;
+    use std::cell::RefCell;
+
+    static COUNT: Atom<i32> = Atom(|_| 1);
+    static DOUBLED: Selector<i32> = Selector(|s| *s.get(&COUNT) * 2);
+    static IS_BIG: Selector<bool> = Selector(|s| *s.select(&DOUBLED) > 10);
+
+    let updated = Rc::new(RefCell::new(Vec::new()));
+    let root = AtomRoot::new({
+        let updated = updated.clone();
+        std::sync::Arc::new(move |scope: ScopeId| updated.borrow_mut().push(scope))
+    });
+
+    assert_eq!(*root.register_selector(&DOUBLED, ScopeId(1)), 2);
+    assert!(!*root.register_selector(&IS_BIG, ScopeId(2)));
+
+    // Only the selectors whose value changed notify their subscribers
+    root.set((&COUNT).unique_id(), 2);
+    assert_eq!(updated.take(), [ScopeId(1)]);
+    assert_eq!(*root.read_selector(&DOUBLED), 4);
+
+    root.set((&COUNT).unique_id(), 6);
+    assert_eq!(updated.take(), [ScopeId(1), ScopeId(2)]);
+    assert!(*root.read_selector(&IS_BIG));
+}
@@ -1,4 +1,4 @@
-use crate::{use_atom_root, AtomId, AtomRoot, Readable};
+use crate::{use_atom_root, AtomId, AtomRoot, Readable, Selector};
 use dioxus_core::{ScopeId, ScopeState};
 use std::rc::Rc;
 
@@ -9,19 +9,6 @@ pub fn use_read<V: 'static>(cx: &ScopeState, f: impl Readable<V>) -> &V {
 pub fn use_read_rc<V: 'static>(cx: &ScopeState, f: impl Readable<V>) -> &Rc<V> {
     let root = use_atom_root(cx);
 
-    struct UseReadInner<V> {
-        root: Rc<AtomRoot>,
-        id: AtomId,
-        scope_id: ScopeId,
-        value: Option<Rc<V>>,
-    }
-
-    impl<V> Drop for UseReadInner<V> {
-        fn drop(&mut self) {
-            self.root.unsubscribe(self.id, self.scope_id)
-        }
-    }
-
     let inner = cx.use_hook(|| UseReadInner {
         value: None,
         root: root.clone(),
@@ -34,3 +21,36 @@ pub fn use_read_rc<V: 'static>(cx: &ScopeState, f: impl Readable<V>) -> &Rc<V> {
     inner.value = Some(value);
     inner.value.as_ref().unwrap()
 }
+
+/// Read the value of a [`Selector`], rendering the component again whenever it changes
+pub fn use_selector<'a, V: PartialEq + 'static>(
+    cx: &'a ScopeState,
+    selector: &'static Selector<V>,
+) -> &'a V {
+    let root = use_atom_root(cx);
+
+    let inner = cx.use_hook(|| UseReadInner {
+        value: None,
+        root: root.clone(),
+        scope_id: cx.scope_id(),
+        id: selector.unique_id(),
+    });
+
+    let value = inner.root.register_selector(selector, cx.scope_id());
+
+    inner.value = Some(value);
+    inner.value.as_ref().unwrap()
+}
+
+struct UseReadInner<V> {
+    root: Rc<AtomRoot>,
+    id: AtomId,
+    scope_id: ScopeId,
+    value: Option<Rc<V>>,
+}
+
+impl<V> Drop for UseReadInner<V> {
+    fn drop(&mut self) {
+        self.root.unsubscribe(self.id, self.scope_id)
+    }
+}
@@ -3,13 +3,17 @@ use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 use dioxus_core::ScopeId;
 use im_rc::HashSet;
 
-use crate::Readable;
+use crate::{Readable, Selector, SelectorBuilder};
 
 pub type AtomId = *const ();
 
 pub struct AtomRoot {
     pub atoms: RefCell<HashMap<AtomId, Slot>>,
     pub update_any: Arc<dyn Fn(ScopeId)>,
+    /// Compute each selector again, returning whether its value changed
+    selectors: RefCell<HashMap<AtomId, Rc<dyn Fn(&AtomRoot) -> bool>>>,
+    /// The selectors that read each atom or selector
+    dependents: RefCell<HashMap<AtomId, HashSet<AtomId>>>,
 }
 
 pub struct Slot {
@@ -22,6 +26,8 @@ impl AtomRoot {
         Self {
             update_any,
             atoms: RefCell::new(HashMap::new()),
+            selectors: RefCell::new(HashMap::new()),
+            dependents: RefCell::new(HashMap::new()),
         }
     }
 
@@ -62,6 +68,11 @@ impl AtomRoot {
     }
 
     pub fn set<V: 'static>(&self, ptr: AtomId, value: V) {
+        self.set_inner(ptr, value);
+        self.update_dependents(ptr);
+    }
+
+    fn set_inner<V: 'static>(&self, ptr: AtomId, value: V) {
         let mut atoms = self.atoms.borrow_mut();
 
         if let Some(slot) = atoms.get_mut(&ptr) {
@@ -100,6 +111,88 @@ impl AtomRoot {
                 (self.update_any)(*scope);
             }
         }
+        self.update_dependents(ptr);
+    }
+
+    /// Read the value of a selector, subscribing the scope to it
+    pub fn register_selector<V: PartialEq + 'static>(
+        &self,
+        selector: &'static Selector<V>,
+        scope: ScopeId,
+    ) -> Rc<V> {
+        let value = self.read_selector(selector);
+        if let Some(slot) = self.atoms.borrow_mut().get_mut(&selector.unique_id()) {
+            slot.subscribers.insert(scope);
+        }
+        value
+    }
+
+    /// Read the value of a selector, computing it if this is the first time it is read
+    pub fn read_selector<V: PartialEq + 'static>(&self, selector: &'static Selector<V>) -> Rc<V> {
+        let id = selector.unique_id();
+        if let Some(slot) = self.atoms.borrow().get(&id) {
+            return slot.value.clone().downcast().unwrap();
+        }
+
+        let value = Rc::new((selector.0)(&SelectorBuilder::new(self, id)));
+        self.atoms.borrow_mut().insert(
+            id,
+            Slot {
+                value: value.clone(),
+                subscribers: HashSet::new(),
+            },
+        );
+        self.selectors.borrow_mut().insert(
+            id,
+            Rc::new(move |root: &AtomRoot| root.recompute_selector(selector)),
+        );
+        value
+    }
+
+    /// Compute the selector again with the current values of the atoms it reads
+    fn recompute_selector<V: PartialEq + 'static>(&self, selector: &'static Selector<V>) -> bool {
+        let id = selector.unique_id();
+        let value = (selector.0)(&SelectorBuilder::new(self, id));
+
+        let mut atoms = self.atoms.borrow_mut();
+        let slot = match atoms.get_mut(&id) {
+            Some(slot) => slot,
+            None => return false,
+        };
+        if slot.value.downcast_ref::<V>() == Some(&value) {
+            return false;
+        }
+
+        slot.value = Rc::new(value);
+        for scope in slot.subscribers.iter() {
+            (self.update_any)(*scope);
+        }
+        true
+    }
+
+    pub(crate) fn add_dependent(&self, ptr: AtomId, selector: AtomId) {
+        self.dependents
+            .borrow_mut()
+            .entry(ptr)
+            .or_default()
+            .insert(selector);
+    }
+
+    /// Compute the selectors that read an atom again, and the selectors that read those if their value changed
+    fn update_dependents(&self, ptr: AtomId) {
+        let dependents: Vec<AtomId> = match self.dependents.borrow().get(&ptr) {
+            Some(dependents) => dependents.iter().copied().collect(),
+            None => return,
+        };
+
+        for selector in dependents {
+            let recompute = self.selectors.borrow().get(&selector).cloned();
+            if let Some(recompute) = recompute {
+                if recompute(self) {
+                    self.update_dependents(selector);
+                }
+            }
+        }
     }
 
     pub fn read<V: 'static>(&self, f: impl Readable<V>) -> Rc<V> {
@@ -58,6 +58,12 @@ pub use computed::*;
 mod use_on_unmount;
 pub use use_on_unmount::*;
 
+mod use_node_ref;
+pub use use_node_ref::*;
+
+mod use_error_boundary;
+pub use use_error_boundary::*;
+
 mod usecontext;
 pub use usecontext::*;
 
@@ -76,14 +82,62 @@ pub use usecoroutine::*;
 mod usefuture;
 pub use usefuture::*;
 
+mod use_query;
+pub use use_query::*;
+
+#[cfg(feature = "serialize")]
+mod use_hydrated;
+#[cfg(feature = "serialize")]
+pub use use_hydrated::*;
+
+mod use_incremental_list;
+pub use use_incremental_list::*;
+
+mod use_virtual_list;
+pub use use_virtual_list::*;
+
+mod use_deferred;
+pub use use_deferred::*;
+
+mod use_debounce;
+pub use use_debounce::*;
+
+mod use_server_push;
+pub use use_server_push::*;
+
+mod use_navigation;
+pub use use_navigation::*;
+
+mod use_window_focus;
+pub(crate) use use_window_focus::use_refocused;
+pub use use_window_focus::{use_window_focus, WindowFocus};
+
+mod timer;
+pub use timer::*;
+
+mod use_suspense;
+pub use use_suspense::*;
+
+mod suspense_list;
+pub use suspense_list::{RevealOrder, SuspenseList, SuspenseListBuilder, SuspenseListProps};
+
+mod suspense_boundary;
+pub use suspense_boundary::*;
+
 mod useeffect;
 pub use useeffect::*;
 
+mod use_layout_effect;
+pub use use_layout_effect::*;
+
 mod usecallback;
 pub use usecallback::*;
 
 mod usememo;
 pub use usememo::*;
 
+mod use_reducer;
+pub use use_reducer::*;
+
 mod userootcontext;
 pub use userootcontext::*;
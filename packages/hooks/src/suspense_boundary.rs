@@ -0,0 +1,209 @@
+use crate::SuspenseHandle;
+use dioxus_core::{Element, Properties, Scope, ScopeId, ScopeState, VNode};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+/// The state of the future of a suspended component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuspenseState {
+    /// The future is running, and the component is suspended until it resolves
+    Pending,
+
+    /// The future resolved, and the component shows its value
+    Resolved,
+
+    /// The future was cancelled before it resolved, and the component shows nothing but its skeleton until it is
+    /// restarted
+    Cancelled,
+}
+
+/// Control the suspended components inside of it as a group
+///
+/// Every component below the boundary that calls [`crate::use_suspense`] is a leaf of the boundary. The
+/// [`SuspenseContext`] of the boundary can check the state of the leaves and cancel or restart their futures, which is
+/// what a retry button needs. The boundary renders again whenever the state of one of its leaves changes.
+///
+/// ```rust, ignore
+/// fn feed(cx: Scope) -> Element {
+///     render! {
+///         SuspenseBoundary {
+///             posts {}
+///             comments {}
+///             retry_button {}
+///         }
+///     }
+/// }
+///
+/// fn retry_button(cx: Scope) -> Element {
+///     let boundary = use_suspense_boundary(cx)?;
+///     render! {
+///         button { onclick: move |_| boundary.restart_all(), "Retry" }
+///         button { onclick: move |_| boundary.cancel_all(), "Stop loading" }
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn SuspenseBoundary<'a>(cx: Scope<'a, SuspenseBoundaryProps<'a>>) -> Element<'a> {
+    cx.use_hook(|| {
+        cx.provide_context(SuspenseContext {
+            leaves: Default::default(),
+            update: cx.schedule_update(),
+        })
+    });
+
+    let children = cx.props.children.as_ref()?;
+    Some(VNode {
+        key: children.key,
+        parent: Default::default(),
+        template: children.template.clone(),
+        root_ids: children.root_ids.clone(),
+        dynamic_nodes: children.dynamic_nodes,
+        dynamic_attrs: children.dynamic_attrs,
+    })
+}
+
+/// The props of a [`SuspenseBoundary`]
+pub struct SuspenseBoundaryProps<'a> {
+    children: Element<'a>,
+}
+
+/// Builds the props of a [`SuspenseBoundary`]
+pub struct SuspenseBoundaryBuilder<'a> {
+    children: Element<'a>,
+}
+
+impl<'a> SuspenseBoundaryBuilder<'a> {
+    /// Set the children that contain the suspended components
+    pub fn children(self, children: Element<'a>) -> Self {
+        Self { children }
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> SuspenseBoundaryProps<'a> {
+        SuspenseBoundaryProps {
+            children: self.children,
+        }
+    }
+}
+
+impl<'a> Properties for SuspenseBoundaryProps<'a> {
+    type Builder = SuspenseBoundaryBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        SuspenseBoundaryBuilder { children: None }
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+/// Get the [`SuspenseContext`] of the closest [`SuspenseBoundary`] above this component
+pub fn use_suspense_boundary(cx: &ScopeState) -> Option<&SuspenseContext> {
+    cx.use_hook(|| cx.consume_context::<SuspenseContext>())
+        .as_ref()
+}
+
+/// The suspended components of a [`SuspenseBoundary`], shared with everything below it
+#[derive(Clone)]
+pub struct SuspenseContext {
+    leaves: Rc<RefCell<Vec<(ScopeId, SuspenseHandle)>>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl SuspenseContext {
+    /// Get the state of every leaf of the boundary, in the order they were first rendered
+    ///
+    /// A component with more than one suspense hook has a leaf for each of them.
+    pub fn leaves(&self) -> Vec<(ScopeId, SuspenseState)> {
+        self.leaves
+            .borrow()
+            .iter()
+            .map(|(scope, handle)| (*scope, handle.state()))
+            .collect()
+    }
+
+    /// Check if any leaf of the boundary is still waiting on its future
+    pub fn is_pending(&self) -> bool {
+        self.leaves
+            .borrow()
+            .iter()
+            .any(|(_, handle)| handle.state() == SuspenseState::Pending)
+    }
+
+    /// Restart the futures of the leaves rendered by this component
+    pub fn restart(&self, scope: ScopeId) {
+        self.for_each_leaf(|id, handle| {
+            if id == scope {
+                handle.restart();
+            }
+        });
+    }
+
+    /// Restart the future of every leaf that was cancelled
+    ///
+    /// Leaves that are still pending or already resolved are left alone, so this is what a retry button should call.
+    pub fn restart_all(&self) {
+        self.for_each_leaf(|_, handle| {
+            if handle.state() == SuspenseState::Cancelled {
+                handle.restart();
+            }
+        });
+    }
+
+    /// Cancel the futures of the pending leaves rendered by this component
+    pub fn cancel(&self, scope: ScopeId) {
+        self.for_each_leaf(|id, handle| {
+            if id == scope {
+                handle.cancel();
+            }
+        });
+    }
+
+    /// Cancel the future of every pending leaf
+    pub fn cancel_all(&self) {
+        self.for_each_leaf(|_, handle| handle.cancel());
+    }
+
+    // The handles are cloned out first, since they can call back into the boundary
+    fn for_each_leaf(&self, mut f: impl FnMut(ScopeId, &SuspenseHandle)) {
+        let leaves = self.leaves.borrow().clone();
+        for (scope, handle) in &leaves {
+            f(*scope, handle);
+        }
+    }
+}
+
+/// The place of a suspense hook in the closest [`SuspenseBoundary`] above it, which leaves the boundary when dropped
+pub(crate) struct BoundaryLeaf {
+    boundary: SuspenseContext,
+    handle: SuspenseHandle,
+}
+
+impl BoundaryLeaf {
+    /// Join the closest [`SuspenseBoundary`] above this scope, if there is one
+    pub(crate) fn join(cx: &ScopeState, handle: &SuspenseHandle) -> Option<Self> {
+        let boundary = cx.consume_context::<SuspenseContext>()?;
+        boundary
+            .leaves
+            .borrow_mut()
+            .push((cx.scope_id(), handle.clone()));
+        Some(Self {
+            boundary,
+            handle: handle.clone(),
+        })
+    }
+
+    /// Let the boundary know the state of the leaf changed
+    pub(crate) fn state_changed(&self) {
+        (self.boundary.update)();
+    }
+}
+
+impl Drop for BoundaryLeaf {
+    fn drop(&mut self) {
+        self.boundary
+            .leaves
+            .borrow_mut()
+            .retain(|(_, handle)| !handle.same_handle(&self.handle));
+        (self.boundary.update)();
+    }
+}
@@ -0,0 +1,215 @@
+use dioxus_core::{Element, Properties, Scope, ScopeState, VNode};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+/// The order the boundaries in a [`SuspenseList`] show their content in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevealOrder {
+    /// A boundary shows its content once every boundary before it has
+    Forwards,
+
+    /// A boundary shows its content once every boundary after it has
+    Backwards,
+
+    /// Every boundary shows its content at the same time, once they have all resolved
+    Together,
+}
+
+impl Default for RevealOrder {
+    fn default() -> Self {
+        Self::Forwards
+    }
+}
+
+/// Coordinate the order that the suspense boundaries inside of it show their content in
+///
+/// Every component below the list that calls [`crate::use_suspense`] is a boundary of the list, in the order they
+/// were first rendered. A boundary whose future resolves stays suspended, showing its skeleton if it has one, until the
+/// [`RevealOrder`] lets it show its content. This keeps content from popping in all over the page in whatever order
+/// the data happens to arrive.
+///
+/// Once a boundary shows its content it keeps showing it, even if its future runs again.
+///
+/// ```rust, ignore
+/// rsx! {
+///     SuspenseList { reveal_order: RevealOrder::Forwards,
+///         profile {}
+///         posts {}
+///         comments {}
+///     }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn SuspenseList<'a>(cx: Scope<'a, SuspenseListProps<'a>>) -> Element<'a> {
+    let list = cx.use_hook(|| cx.provide_context(SuspenseListContext::default()));
+    list.set_order(cx.props.reveal_order);
+
+    let children = cx.props.children.as_ref()?;
+    Some(VNode {
+        key: children.key,
+        parent: Default::default(),
+        template: children.template.clone(),
+        root_ids: children.root_ids.clone(),
+        dynamic_nodes: children.dynamic_nodes,
+        dynamic_attrs: children.dynamic_attrs,
+    })
+}
+
+/// The props of a [`SuspenseList`]
+pub struct SuspenseListProps<'a> {
+    reveal_order: RevealOrder,
+    children: Element<'a>,
+}
+
+/// Builds the props of a [`SuspenseList`]
+pub struct SuspenseListBuilder<'a> {
+    reveal_order: RevealOrder,
+    children: Element<'a>,
+}
+
+impl<'a> SuspenseListBuilder<'a> {
+    /// Set the order the boundaries show their content in, [`RevealOrder::Forwards`] by default
+    pub fn reveal_order(self, reveal_order: RevealOrder) -> Self {
+        Self {
+            reveal_order,
+            ..self
+        }
+    }
+
+    /// Set the children that contain the boundaries
+    pub fn children(self, children: Element<'a>) -> Self {
+        Self { children, ..self }
+    }
+
+    /// Finish building the props
+    pub fn build(self) -> SuspenseListProps<'a> {
+        SuspenseListProps {
+            reveal_order: self.reveal_order,
+            children: self.children,
+        }
+    }
+}
+
+impl<'a> Properties for SuspenseListProps<'a> {
+    type Builder = SuspenseListBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder() -> Self::Builder {
+        SuspenseListBuilder {
+            reveal_order: RevealOrder::default(),
+            children: None,
+        }
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+/// The state of a [`SuspenseList`], shared with the boundaries below it
+#[derive(Clone, Default)]
+struct SuspenseListContext {
+    inner: Rc<RefCell<SuspenseListInner>>,
+}
+
+#[derive(Default)]
+struct SuspenseListInner {
+    order: RevealOrder,
+    boundaries: Vec<Boundary>,
+}
+
+struct Boundary {
+    resolved: bool,
+    revealed: bool,
+    mounted: bool,
+    update: Arc<dyn Fn()>,
+}
+
+impl SuspenseListContext {
+    fn set_order(&self, order: RevealOrder) {
+        self.inner.borrow_mut().order = order;
+    }
+}
+
+impl SuspenseListInner {
+    fn may_reveal(&self, index: usize) -> bool {
+        let done = |boundary: &Boundary| boundary.resolved || !boundary.mounted;
+        match self.order {
+            RevealOrder::Forwards => self.boundaries[..index].iter().all(done),
+            RevealOrder::Backwards => self.boundaries[index + 1..].iter().all(done),
+            RevealOrder::Together => self.boundaries.iter().all(done),
+        }
+    }
+
+    /// The updates of the other boundaries that resolved but are waiting to reveal
+    fn held_back(&self, except: usize) -> Vec<Arc<dyn Fn()>> {
+        self.boundaries
+            .iter()
+            .enumerate()
+            .filter(|(index, b)| *index != except && b.mounted && b.resolved && !b.revealed)
+            .map(|(_, b)| b.update.clone())
+            .collect()
+    }
+}
+
+/// The place of a suspense boundary in the closest [`SuspenseList`] above it
+pub(crate) struct ListBoundary {
+    list: SuspenseListContext,
+    index: usize,
+}
+
+impl ListBoundary {
+    /// Join the closest [`SuspenseList`] above this scope, if there is one
+    pub(crate) fn join(cx: &ScopeState) -> Option<Self> {
+        let list = cx.consume_context::<SuspenseListContext>()?;
+        let index = {
+            let mut inner = list.inner.borrow_mut();
+            inner.boundaries.push(Boundary {
+                resolved: false,
+                revealed: false,
+                mounted: true,
+                update: cx.schedule_update(),
+            });
+            inner.boundaries.len() - 1
+        };
+        Some(Self { list, index })
+    }
+
+    /// Mark this boundary as resolved, and check if it may show its content
+    ///
+    /// The first time the boundary resolves, every other boundary that is being held back gets a chance to reveal.
+    pub(crate) fn reveal(&self) -> bool {
+        let (revealed, held_back) = {
+            let mut inner = self.list.inner.borrow_mut();
+            let newly_resolved = !inner.boundaries[self.index].resolved;
+            inner.boundaries[self.index].resolved = true;
+
+            let revealed = inner.boundaries[self.index].revealed || inner.may_reveal(self.index);
+            inner.boundaries[self.index].revealed = revealed;
+
+            let held_back = match newly_resolved {
+                true => inner.held_back(self.index),
+                false => Vec::new(),
+            };
+            (revealed, held_back)
+        };
+
+        for update in held_back {
+            update();
+        }
+
+        revealed
+    }
+}
+
+impl Drop for ListBoundary {
+    fn drop(&mut self) {
+        // Unmounted boundaries don't hold anyone back
+        let held_back = {
+            let mut inner = self.list.inner.borrow_mut();
+            inner.boundaries[self.index].mounted = false;
+            inner.held_back(self.index)
+        };
+
+        for update in held_back {
+            update();
+        }
+    }
+}
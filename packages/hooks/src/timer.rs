@@ -0,0 +1,32 @@
+use std::{future::Future, pin::Pin, rc::Rc, time::Duration};
+
+/// The platform clock that [`Timer`] waits on, like `setTimeout` on the web
+pub trait TimerBackend {
+    /// Create a future that resolves once `duration` has passed
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>>;
+}
+
+/// Waits for timeouts, shared through the root context
+///
+/// Renderers provide this at the root of the app with a [`TimerBackend`] for their platform. The web renderer uses
+/// `setTimeout`, and the desktop, liveview and TUI renderers use tokio's timer. The sleeps run as tasks of the
+/// VirtualDom's scheduler, so they work anywhere the renderer's clock does, including wasm. Without a renderer
+/// providing it, like when rendering to a string with SSR, timeouts never fire.
+#[derive(Clone)]
+pub struct Timer {
+    backend: Rc<dyn TimerBackend>,
+}
+
+impl Timer {
+    /// Create a timer that waits on `backend`
+    pub fn new(backend: impl TimerBackend + 'static) -> Self {
+        Self {
+            backend: Rc::new(backend),
+        }
+    }
+
+    /// Create a future that resolves once `duration` has passed
+    pub fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        self.backend.sleep(duration)
+    }
+}
@@ -0,0 +1,198 @@
+use crate::Timer;
+use dioxus_core::{ScopeState, TaskId};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+/// Run a callback once calls to it stop for `interval`
+///
+/// Every [`UseDebounce::call`] cancels the call that was waiting and starts waiting again, so only the last of a quick
+/// burst of calls runs. This is what you want for search-as-you-type:
+///
+/// ```rust, ignore
+/// fn search(cx: Scope) -> Element {
+///     let results = use_state(cx, Vec::new);
+///     let search = use_debounce(cx, Duration::from_millis(300), {
+///         to_owned![results];
+///         move |query: String| results.set(find(&query))
+///     });
+///
+///     render! {
+///         input { oninput: move |evt| search.call(evt.value.clone()) }
+///     }
+/// }
+/// ```
+///
+/// The callback is replaced on every render, so it always sees the latest values it captures. The wait is a task of
+/// this component that sleeps on the [`Timer`] the renderer provides, and it is cancelled when the component is
+/// dropped. Calling it panics if the renderer doesn't provide a timer.
+pub fn use_debounce<T: 'static>(
+    cx: &ScopeState,
+    interval: Duration,
+    callback: impl FnMut(T) + 'static,
+) -> UseDebounce<T> {
+    let state = cx.use_hook(|| RateLimit::new(cx));
+    state.interval = interval;
+    *state.callback.borrow_mut() = Some(Box::new(callback));
+
+    UseDebounce { cx, state }
+}
+
+/// Run a callback at most once every `interval`
+///
+/// The first [`UseThrottle::call`] runs right away. Calls made while the interval hasn't passed yet are held back, and
+/// the last of them runs once it has. This keeps handlers for events that fire quickly, like resizing or scrolling, from
+/// running on every event while still seeing the final one.
+///
+/// ```rust, ignore
+/// let save_size = use_throttle(cx, Duration::from_millis(100), move |size: (f64, f64)| width.set(size.0));
+/// ```
+///
+/// Like [`use_debounce`], the callback is replaced on every render and calling it panics if the renderer doesn't provide
+/// a [`Timer`].
+pub fn use_throttle<T: 'static>(
+    cx: &ScopeState,
+    interval: Duration,
+    callback: impl FnMut(T) + 'static,
+) -> UseThrottle<T> {
+    let state = cx.use_hook(|| RateLimit::new(cx));
+    state.interval = interval;
+    *state.callback.borrow_mut() = Some(Box::new(callback));
+
+    UseThrottle { cx, state }
+}
+
+/// A callback that only runs once calls to it stop, created with [`use_debounce`]
+pub struct UseDebounce<'a, T> {
+    cx: &'a ScopeState,
+    state: &'a RateLimit<T>,
+}
+
+impl<T: 'static> UseDebounce<'_, T> {
+    /// Run the callback with `arg` after the interval, unless it is called again before then
+    pub fn call(&self, arg: T) {
+        self.cancel();
+
+        let sleep = self.state.timer().sleep(self.state.interval);
+        let callback = self.state.callback.clone();
+        let task = self.state.task.clone();
+        self.state.task.set(Some(self.cx.push_future(async move {
+            sleep.await;
+            task.set(None);
+            run_callback(&callback, arg);
+        })));
+    }
+
+    /// Drop the call that is waiting to run, if there is one
+    pub fn cancel(&self) {
+        if let Some(task) = self.state.task.take() {
+            self.cx.cancel_task(task);
+        }
+    }
+
+    /// Check if a call is waiting to run
+    pub fn is_pending(&self) -> bool {
+        self.state.task.get().is_some()
+    }
+}
+
+/// A callback that runs at most once per interval, created with [`use_throttle`]
+pub struct UseThrottle<'a, T> {
+    cx: &'a ScopeState,
+    state: &'a RateLimit<T>,
+}
+
+impl<T: 'static> UseThrottle<'_, T> {
+    /// Run the callback with `arg` now, or once the interval since the last run has passed
+    pub fn call(&self, arg: T) {
+        if self.state.task.get().is_some() {
+            *self.state.held.borrow_mut() = Some(arg);
+            return;
+        }
+
+        // Start the interval first, so calls made by the callback itself are held back
+        let timer = self.state.timer().clone();
+        let interval = self.state.interval;
+        let callback = self.state.callback.clone();
+        let held = self.state.held.clone();
+        let task = self.state.task.clone();
+        self.state.task.set(Some(self.cx.push_future(async move {
+            // Keep running the held back calls until an interval passes without any
+            loop {
+                timer.sleep(interval).await;
+                let arg = held.borrow_mut().take();
+                match arg {
+                    Some(arg) => run_callback(&callback, arg),
+                    None => break,
+                }
+            }
+            task.set(None);
+        })));
+
+        run_callback(&self.state.callback, arg);
+    }
+
+    /// Drop the call that is held back, if there is one
+    pub fn cancel(&self) {
+        self.state.held.borrow_mut().take();
+    }
+}
+
+impl<T> Clone for UseDebounce<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UseDebounce<'_, T> {}
+
+impl<T> Clone for UseThrottle<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UseThrottle<'_, T> {}
+
+/// The state shared by [`use_debounce`] and [`use_throttle`]
+struct RateLimit<T> {
+    interval: Duration,
+    callback: Rc<RefCell<Option<Box<dyn FnMut(T)>>>>,
+    task: Rc<Cell<Option<TaskId>>>,
+    held: Rc<RefCell<Option<T>>>,
+    timer: Option<Timer>,
+}
+
+impl<T> RateLimit<T> {
+    fn new(cx: &ScopeState) -> Self {
+        Self {
+            interval: Duration::ZERO,
+            callback: Rc::new(RefCell::new(None)),
+            task: Rc::new(Cell::new(None)),
+            held: Rc::new(RefCell::new(None)),
+            timer: cx.consume_context::<Timer>(),
+        }
+    }
+
+    fn timer(&self) -> &Timer {
+        self.timer
+            .as_ref()
+            .expect("use_debounce and use_throttle need the Timer that renderers provide at the root of the app")
+    }
+}
+
+/// Run the callback without holding its borrow, so it can call the same debounce or throttle again
+fn run_callback<T>(callback: &RefCell<Option<Box<dyn FnMut(T)>>>, arg: T) {
+    let taken = callback.borrow_mut().take();
+    if let Some(mut run) = taken {
+        run(arg);
+
+        // A render while it ran put in a newer callback, which should be kept
+        let mut slot = callback.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(run);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use dioxus_core::{ScopeState, TaskId};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Get a copy of a value that lags behind it while it changes quickly
+///
+/// Each render returns the last value the deferred copy caught up to. When `value` changes, the copy is updated in a
+/// later pass of the scheduler, after the render with the new value is finished. If `value` changes again before then,
+/// the copy skips straight to the newest value. Once `value` settles, the copy catches up with it.
+///
+/// This keeps fast inputs responsive while expensive parts of the UI render with a slightly older value:
+///
+/// ```rust, ignore
+/// fn search(cx: Scope) -> Element {
+///     let query = use_state(cx, String::new);
+///     let deferred_query = use_deferred(cx, query.get().clone());
+///
+///     render! {
+///         input { value: "{query}", oninput: move |evt| query.set(evt.value.clone()) }
+///         // The results only re-render once typing slows down
+///         search_results { query: deferred_query }
+///     }
+/// }
+/// ```
+pub fn use_deferred<T: Clone + PartialEq + 'static>(cx: &ScopeState, value: T) -> T {
+    let deferred = cx.use_hook(|| Deferred {
+        current: Rc::new(RefCell::new(value.clone())),
+        pending: None,
+        task: Rc::new(Cell::new(None)),
+        update: cx.schedule_update(),
+    });
+
+    let caught_up = *deferred.current.borrow() == value;
+    let scheduled = deferred.task.get().is_some() && deferred.pending.as_ref() == Some(&value);
+
+    if caught_up || !scheduled {
+        // Whatever update was scheduled is out of date now
+        if let Some(task) = deferred.task.take() {
+            cx.remove_future(task);
+        }
+        deferred.pending = None;
+
+        if !caught_up {
+            let current = deferred.current.clone();
+            let task = deferred.task.clone();
+            let update = deferred.update.clone();
+            let new = value.clone();
+            deferred.pending = Some(value);
+            deferred.task.set(Some(cx.push_future(async move {
+                *current.borrow_mut() = new;
+                task.take();
+                update();
+            })));
+        }
+    }
+
+    let current = deferred.current.borrow();
+    current.clone()
+}
+
+struct Deferred<T> {
+    current: Rc<RefCell<T>>,
+    pending: Option<T>,
+    task: Rc<Cell<Option<TaskId>>>,
+    update: Arc<dyn Fn()>,
+}
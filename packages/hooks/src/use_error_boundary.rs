@@ -0,0 +1,33 @@
+use dioxus_core::{ErrorBoundary, ScopeState};
+use std::rc::Rc;
+
+/// Make this component an error boundary for its descendants
+///
+/// Any error thrown below this component (with `cx.throw` or the `Throw` trait) is caught here instead of bubbling up
+/// to a boundary further up the tree. So are panics in the futures of components below it, like the future of
+/// [`crate::use_suspense`], which are caught as a [`dioxus_core::TaskPanic`]. The component re-renders when it catches
+/// an error, so it can show a fallback instead of its children. Calling [`ErrorBoundary::reset`] clears the error and
+/// renders the children again.
+///
+/// ```rust, ignore
+/// fn widget_frame(cx: Scope) -> Element {
+///     let boundary = use_error_boundary(cx);
+///
+///     if let Some(error) = boundary.error() {
+///         return render! {
+///             "The widget crashed: {error.error:?}"
+///             button { onclick: move |_| boundary.reset(), "Try again" }
+///         };
+///     }
+///
+///     render! { widget {} }
+/// }
+/// ```
+pub fn use_error_boundary(cx: &ScopeState) -> &Rc<ErrorBoundary> {
+    cx.use_hook(|| {
+        cx.provide_context(Rc::new(ErrorBoundary::new_with_rerun(
+            cx.scope_id(),
+            cx.schedule_update(),
+        )))
+    })
+}
@@ -0,0 +1,84 @@
+use crate::{use_query, use_query::hydrate_query, use_state, UseQuery, UseState};
+use dioxus_core::{HydratedState, ScopeState};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::{fmt::Display, future::Future, hash::Hash, rc::Rc};
+
+/// [`use_state`] that starts with the value the server embedded under `id`, if there is one
+///
+/// The server's values come from the JSON the VirtualDom was created with in
+/// [`VirtualDom::new_with_state`](dioxus_core::VirtualDom::new_with_state). If it doesn't have a value for `id`, or the
+/// value doesn't deserialize, the state starts with `initial_state_fn` like a regular [`use_state`].
+///
+/// ```rust, ignore
+/// let count = use_hydrated_state(cx, "count", || 0);
+/// ```
+pub fn use_hydrated_state<'a, T: DeserializeOwned + 'static>(
+    cx: &'a ScopeState,
+    id: &str,
+    initial_state_fn: impl FnOnce() -> T,
+) -> &'a UseState<T> {
+    use_state(cx, || {
+        hydrated_value(cx, id).unwrap_or_else(initial_state_fn)
+    })
+}
+
+/// [`use_query`] that starts with the value the server embedded under the key, if there is one
+///
+/// The key is looked up in the server's values by its [`Display`] form. A hydrated value is shown on the first render
+/// and counts as fresh, so the fetcher doesn't run until the query is revalidated.
+///
+/// ```rust, ignore
+/// let user = use_hydrated_query(cx, cx.props.id, |id| async move { fetch_user(id).await });
+/// ```
+pub fn use_hydrated_query<K, T, F>(
+    cx: &ScopeState,
+    key: K,
+    fetcher: impl Fn(K) -> F + 'static,
+) -> &UseQuery<K, T>
+where
+    K: Hash + Eq + Clone + Display + 'static,
+    T: Clone + DeserializeOwned + 'static,
+    F: Future<Output = T> + 'static,
+{
+    cx.use_hook(|| {
+        if let Some(value) = hydrated_value::<T>(cx, &key.to_string()) {
+            hydrate_query(cx, &key, value);
+        }
+    });
+
+    use_query(cx, key, fetcher)
+}
+
+/// The values in the [`HydratedState`], parsed once and shared by every hook
+#[derive(Clone)]
+struct HydratedValues(Rc<Map<String, Value>>);
+
+fn hydrated_value<T: DeserializeOwned>(cx: &ScopeState, id: &str) -> Option<T> {
+    let values = match cx.consume_context::<HydratedValues>() {
+        Some(values) => values,
+        None => {
+            let state = cx.consume_context::<HydratedState>()?;
+            let values = match serde_json::from_str(state.json()) {
+                Ok(Value::Object(values)) => values,
+                _ => {
+                    log::warn!("The hydrated state is not a JSON object, so it will be ignored");
+                    Map::new()
+                }
+            };
+            cx.provide_root_context(HydratedValues(Rc::new(values)))
+        }
+    };
+
+    match serde_json::from_value(values.0.get(id)?.clone()) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            log::warn!(
+                "The hydrated value for {:?} doesn't deserialize: {}",
+                id,
+                err
+            );
+            None
+        }
+    }
+}
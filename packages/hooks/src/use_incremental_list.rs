@@ -0,0 +1,56 @@
+use dioxus_core::ScopeState;
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+/// Mount a long list a chunk at a time so rendering it doesn't block the UI
+///
+/// Returns how many of the `len` items to render right now. The first render mounts the first `chunk_size` items, and
+/// every following chunk is mounted in a later pass of the scheduler, so events and other work can run in between.
+/// Once every chunk is mounted, the rendered list is the same as rendering all of the items at once.
+///
+/// If the list grows, the new items are mounted in chunks as well. If it shrinks, the count shrinks with it.
+///
+/// ```rust, ignore
+/// fn rows(cx: Scope<RowsProps>) -> Element {
+///     let mounted = use_incremental_list(cx, cx.props.rows.len(), 100);
+///
+///     render! {
+///         for row in &cx.props.rows[..mounted] {
+///             li { key: "{row.id}", "{row.name}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_incremental_list(cx: &ScopeState, len: usize, chunk_size: usize) -> usize {
+    let chunk_size = chunk_size.max(1);
+
+    let list = cx.use_hook(|| IncrementalList {
+        mounted: Rc::new(Cell::new(chunk_size)),
+        scheduled: Rc::new(Cell::new(false)),
+        update: cx.schedule_update(),
+    });
+
+    // Keep the count in bounds if the list shrank, but always show the first chunk right away
+    let mounted = list.mounted.get().min(len).max(chunk_size.min(len));
+    list.mounted.set(mounted);
+
+    // Mount the next chunk in a later pass of the scheduler
+    if mounted < len && !list.scheduled.get() {
+        list.scheduled.set(true);
+        let next = list.mounted.clone();
+        let scheduled = list.scheduled.clone();
+        let update = list.update.clone();
+        cx.spawn(async move {
+            next.set(next.get() + chunk_size);
+            scheduled.set(false);
+            update();
+        });
+    }
+
+    mounted
+}
+
+struct IncrementalList {
+    mounted: Rc<Cell<usize>>,
+    scheduled: Rc<Cell<bool>>,
+    update: Arc<dyn Fn()>,
+}
@@ -0,0 +1,57 @@
+use dioxus_core::ScopeState;
+use std::any::Any;
+
+use crate::UseFutureDep;
+
+/// A hook that runs a callback synchronously once the renderer has applied the changes of this render
+///
+/// Unlike [`crate::use_effect`], which runs as a task after the render, layout effects run right after the mutations are
+/// applied and before the changes are painted. This makes them the place to read the layout of the real DOM (like the
+/// size of an element) and make changes that depend on it without a flicker. Keep them short, since they block the
+/// frame from being painted. On desktop and liveview, where the DOM lives in a webview or a browser, they run once the
+/// changes were sent to it instead.
+///
+/// Whenever the dependencies change, the callback is queued again.
+///
+/// - dependencies: a tuple of references to values that are `PartialEq` + `Clone`
+///
+/// ## Examples
+///
+/// ```rust, ignore
+/// fn tooltip(cx: Scope) -> Element {
+///     let anchor = use_node_ref(cx);
+///     let above = use_state(cx, || false);
+///
+///     use_layout_effect(cx, (), {
+///         to_owned![anchor, above];
+///         move |_| above.set(anchor.bottom() > window_height())
+///     });
+///
+///     render!(div { class: if *above.get() { "above" } else { "below" }, "Tooltip" })
+/// }
+/// ```
+pub fn use_layout_effect<D: UseFutureDep>(
+    cx: &ScopeState,
+    dependencies: D,
+    effect: impl FnOnce(D::Out) + 'static,
+) where
+    D::Out: 'static,
+{
+    struct UseLayoutEffect {
+        needs_regen: bool,
+        dependencies: Vec<Box<dyn Any>>,
+    }
+
+    let state = cx.use_hook(move || UseLayoutEffect {
+        needs_regen: true,
+        dependencies: Vec::new(),
+    });
+
+    if dependencies.clone().apply(&mut state.dependencies) || state.needs_regen {
+        // We don't need regen anymore
+        state.needs_regen = false;
+
+        let dependencies = dependencies.out();
+        cx.queue_layout_effect(move || effect(dependencies));
+    }
+}
@@ -0,0 +1,218 @@
+use dioxus_core::{ScopeId, ScopeState};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+
+/// The platform history that [`Navigation`] keeps in sync with, like `window.history` on the web
+pub trait NavigationBackend {
+    /// Add a new entry to the history
+    fn push(&self, path: &str);
+
+    /// Replace the current entry of the history
+    fn replace(&self, path: &str);
+
+    /// Go back one entry
+    ///
+    /// The backend should call [`Navigation::popped`] once the history has moved back.
+    fn back(&self);
+
+    /// Start calling [`Navigation::popped`] when the history moves outside of the app
+    ///
+    /// This is called once, when the first component uses the navigation.
+    fn listen(&self, _navigation: &Navigation) {}
+}
+
+/// The location of the app, shared through the root context
+///
+/// Renderers with a platform history provide this at the root of the app with a [`NavigationBackend`], and call
+/// [`Navigation::popped`] when the user navigates with the platform's controls (like the browser's back button) once
+/// [`NavigationBackend::listen`] was called.
+/// Without a renderer providing it, navigation is kept in memory.
+#[derive(Clone)]
+pub struct Navigation {
+    inner: Rc<RefCell<NavigationInner>>,
+}
+
+struct NavigationInner {
+    // Only the last entry is used when a backend keeps the history
+    entries: Vec<String>,
+    backend: Option<Rc<dyn NavigationBackend>>,
+    subscribers: HashMap<ScopeId, Arc<dyn Fn()>>,
+    listening: bool,
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Self::new("/", None)
+    }
+}
+
+impl Navigation {
+    /// Create a navigation context that starts at `location`
+    pub fn new(location: impl Into<String>, backend: Option<Rc<dyn NavigationBackend>>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(NavigationInner {
+                entries: vec![location.into()],
+                backend,
+                subscribers: HashMap::new(),
+                listening: false,
+            })),
+        }
+    }
+
+    /// Get the current location
+    pub fn location(&self) -> String {
+        self.inner
+            .borrow()
+            .entries
+            .last()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Navigate to a new location, adding it to the history
+    pub fn push(&self, path: impl Into<String>) {
+        let path = path.into();
+        let backend = self.inner.borrow().backend.clone();
+        match backend {
+            Some(backend) => {
+                backend.push(&path);
+                self.inner.borrow_mut().entries = vec![path];
+            }
+            None => self.inner.borrow_mut().entries.push(path),
+        }
+        self.notify();
+    }
+
+    /// Navigate to a new location, replacing the current entry of the history
+    pub fn replace(&self, path: impl Into<String>) {
+        let path = path.into();
+        let backend = self.inner.borrow().backend.clone();
+        if let Some(backend) = backend {
+            backend.replace(&path);
+        }
+        let mut inner = self.inner.borrow_mut();
+        inner.entries.pop();
+        inner.entries.push(path);
+        drop(inner);
+        self.notify();
+    }
+
+    /// Go back to the previous location
+    pub fn back(&self) {
+        let backend = self.inner.borrow().backend.clone();
+        match backend {
+            // The backend lets us know where we ended up with `popped`
+            Some(backend) => backend.back(),
+            None => {
+                let mut inner = self.inner.borrow_mut();
+                if inner.entries.len() > 1 {
+                    inner.entries.pop();
+                    drop(inner);
+                    self.notify();
+                }
+            }
+        }
+    }
+
+    /// Let every component using the location know the platform history moved to `path`
+    ///
+    /// Renderers call this when the history changes outside of the app, like on `popstate` in the browser.
+    pub fn popped(&self, path: impl Into<String>) {
+        self.inner.borrow_mut().entries = vec![path.into()];
+        self.notify();
+    }
+
+    fn subscribe(&self, scope: ScopeId, update: Arc<dyn Fn()>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.subscribers.insert(scope, update);
+
+        // The backend only has to follow the platform history once something shows the location
+        let backend = match inner.listening {
+            true => None,
+            false => inner.backend.clone(),
+        };
+        inner.listening = true;
+        drop(inner);
+        if let Some(backend) = backend {
+            backend.listen(self);
+        }
+    }
+
+    fn unsubscribe(&self, scope: ScopeId) {
+        self.inner.borrow_mut().subscribers.remove(&scope);
+    }
+
+    fn notify(&self) {
+        let subscribers: Vec<_> = self.inner.borrow().subscribers.values().cloned().collect();
+        for update in subscribers {
+            update();
+        }
+    }
+}
+
+/// Read and change the location of the app
+///
+/// The component re-renders whenever the location changes, whether that's from a call to [`UseNavigation::push`]
+/// anywhere in the app or from the user navigating with the platform's controls. On the web, this is kept in sync with
+/// `window.history`.
+///
+/// This is the primitive the router is built on, for apps that need their own navigation logic.
+///
+/// ```rust, ignore
+/// fn breadcrumbs(cx: Scope) -> Element {
+///     let navigation = use_navigation(cx);
+///     let location = navigation.location();
+///
+///     render! {
+///         "You are at {location}"
+///         button { onclick: move |_| navigation.back(), "Back" }
+///         button { onclick: move |_| navigation.push("/"), "Home" }
+///     }
+/// }
+/// ```
+pub fn use_navigation(cx: &ScopeState) -> &UseNavigation {
+    cx.use_hook(|| {
+        let navigation = match cx.consume_context::<Navigation>() {
+            Some(navigation) => navigation,
+            None => cx.provide_root_context(Navigation::default()),
+        };
+        navigation.subscribe(cx.scope_id(), cx.schedule_update());
+        UseNavigation {
+            navigation,
+            scope: cx.scope_id(),
+        }
+    })
+}
+
+/// A handle to the app's [`Navigation`] created with [`use_navigation`]
+pub struct UseNavigation {
+    navigation: Navigation,
+    scope: ScopeId,
+}
+
+impl UseNavigation {
+    /// Get the current location
+    pub fn location(&self) -> String {
+        self.navigation.location()
+    }
+
+    /// Navigate to a new location, adding it to the history
+    pub fn push(&self, path: impl Into<String>) {
+        self.navigation.push(path);
+    }
+
+    /// Navigate to a new location, replacing the current entry of the history
+    pub fn replace(&self, path: impl Into<String>) {
+        self.navigation.replace(path);
+    }
+
+    /// Go back to the previous location
+    pub fn back(&self) {
+        self.navigation.back();
+    }
+}
+
+impl Drop for UseNavigation {
+    fn drop(&mut self) {
+        self.navigation.unsubscribe(self.scope);
+    }
+}
@@ -0,0 +1,93 @@
+use dioxus_core::{Event, ScopeState};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+/// Get a reference to a node rendered by this component or any of its children
+///
+/// The reference starts out empty. Attach it to an element with the `onmounted` event and it will be filled in with
+/// the element's mounted data (like `MountedData` from `dioxus-html`) once the renderer mounts the element. The
+/// component that created the reference re-renders when it's filled in.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let input = use_node_ref::<MountedData>(cx);
+///
+///     render! {
+///         input { onmounted: input.attach() }
+///         button {
+///             onclick: move |_| if let Some(node) = input.get() {
+///                 node.set_focus(true);
+///             },
+///             "Focus"
+///         }
+///     }
+/// }
+/// ```
+///
+/// ## Forwarding
+///
+/// [`NodeRef`] is cheap to clone and compares by identity, so it can be passed down as a prop. A wrapper component can
+/// attach a reference it received to one of its own elements, which lets the parent reach the wrapper's inner node:
+///
+/// ```rust, ignore
+/// #[inline_props]
+/// fn fancy_input(cx: Scope, node_ref: NodeRef<MountedData>) -> Element {
+///     render! {
+///         div { class: "fancy",
+///             input { onmounted: node_ref.attach() }
+///         }
+///     }
+/// }
+///
+/// fn app(cx: Scope) -> Element {
+///     let input = use_node_ref(cx);
+///     render! { fancy_input { node_ref: input.clone() } }
+/// }
+/// ```
+pub fn use_node_ref<T: 'static>(cx: &ScopeState) -> &NodeRef<T> {
+    cx.use_hook(|| NodeRef {
+        node: Rc::new(RefCell::new(None)),
+        update: cx.schedule_update(),
+    })
+}
+
+/// A reference to a mounted node, created with [`use_node_ref`]
+pub struct NodeRef<T> {
+    node: Rc<RefCell<Option<Rc<T>>>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+            update: self.update.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for NodeRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.node, &other.node)
+    }
+}
+
+impl<T: 'static> NodeRef<T> {
+    /// Get the node, if it has been mounted
+    pub fn get(&self) -> Option<Rc<T>> {
+        self.node.borrow().clone()
+    }
+
+    /// Set the node and re-render the component that owns this reference
+    pub fn set(&self, node: Rc<T>) {
+        *self.node.borrow_mut() = Some(node);
+        (self.update)();
+    }
+
+    /// Create an event handler that fills in this reference with the data of the event
+    ///
+    /// Pass it to the `onmounted` event of the element you want to reference.
+    pub fn attach(&self) -> impl FnMut(Event<T>) + 'static {
+        let node_ref = self.clone();
+        move |evt| node_ref.set(evt.inner().clone())
+    }
+}
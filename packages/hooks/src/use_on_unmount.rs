@@ -1,5 +1,8 @@
 /// Creats a callback that will be run before the component is removed. This can be used to clean up side effects from the component (created with use_effect)
 ///
+/// The callback runs once, after the children of the component were removed. Callbacks of the same component run in
+/// the reverse order they were registered, before the hooks that were created ahead of them are dropped.
+///
 /// Example:
 /// ```rust
 /// use dioxus::prelude::*;
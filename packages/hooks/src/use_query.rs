@@ -0,0 +1,339 @@
+use crate::use_refocused;
+use dioxus_core::{ScopeId, ScopeState};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Fetch some data by key, sharing the result with every other component that asks for the same key
+///
+/// Results are kept in a cache at the root of the app, one per key and value type. While a fetch for a key is in
+/// flight, other components asking for the same key wait for it instead of starting their own, so the fetcher runs
+/// once no matter how many components use the key.
+///
+/// Cached values follow "stale while revalidate": a component that starts using a key that already has a value gets
+/// that value right away, while the data is fetched again in the background. Every component using the key re-renders
+/// when the new value comes in. Call [`UseQuery::revalidate`] to fetch again on demand.
+///
+/// The fetch belongs to the component that started it. If that component unmounts before the fetch finishes, the fetch
+/// is cancelled and another component using the key starts it again.
+///
+/// ```rust, ignore
+/// fn user_name(cx: Scope<UserProps>) -> Element {
+///     let user = use_query(cx, cx.props.id, |id| async move { fetch_user(id).await });
+///
+///     match user.value() {
+///         Some(user) => render! { "{user.name}" },
+///         None => render! { "Loading..." },
+///     }
+/// }
+/// ```
+pub fn use_query<K, T, F>(
+    cx: &ScopeState,
+    key: K,
+    fetcher: impl Fn(K) -> F + 'static,
+) -> &UseQuery<K, T>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+    F: Future<Output = T> + 'static,
+{
+    use_query_with_options(cx, key, QueryOptions::default(), fetcher)
+}
+
+/// Options for [`use_query_with_options`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// Fetch the value again when the window regains focus
+    ///
+    /// This needs a renderer that provides [`crate::WindowFocus`], like the web renderer.
+    pub revalidate_on_focus: bool,
+
+    /// Ignore focus events that come in less than this many milliseconds after the previous one
+    pub focus_debounce_ms: u32,
+}
+
+/// [`use_query`] with extra options
+///
+/// ```rust, ignore
+/// let user = use_query_with_options(
+///     cx,
+///     cx.props.id,
+///     QueryOptions { revalidate_on_focus: true, focus_debounce_ms: 500 },
+///     |id| async move { fetch_user(id).await },
+/// );
+/// ```
+pub fn use_query_with_options<K, T, F>(
+    cx: &ScopeState,
+    key: K,
+    options: QueryOptions,
+    fetcher: impl Fn(K) -> F + 'static,
+) -> &UseQuery<K, T>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+    F: Future<Output = T> + 'static,
+{
+    let focus_debounce = match options.revalidate_on_focus {
+        true => Some(options.focus_debounce_ms),
+        false => None,
+    };
+    let refocused = use_refocused(cx, focus_debounce);
+
+    let query = cx.use_hook(|| {
+        let query = UseQuery {
+            cache: QueryCache::root(cx),
+            key: RefCell::new(key.clone()),
+            scope: cx.scope_id(),
+            update: cx.schedule_update(),
+        };
+        query.subscribe();
+        query
+    });
+
+    // Follow the new key if it changed
+    if *query.key.borrow() != key {
+        query.unsubscribe();
+        *query.key.borrow_mut() = key.clone();
+        query.subscribe();
+    }
+
+    if refocused {
+        query.cache.with_entry(&key, |entry| entry.stale.set(true));
+    }
+
+    // Start a fetch if the value is missing or stale and nobody else is fetching it
+    if query.cache.start_fetch(&key) {
+        let fetch = Fetch {
+            cache: query.cache.clone(),
+            key: Some(key.clone()),
+            scope: cx.scope_id(),
+        };
+        let fut = fetcher(key);
+        cx.spawn(async move {
+            let value = fut.await;
+            fetch.finish(value);
+        });
+    }
+
+    query
+}
+
+/// A handle to a query created with [`use_query`]
+pub struct UseQuery<K: Hash + Eq + Clone + 'static, T: 'static> {
+    cache: QueryCache<K, T>,
+    key: RefCell<K>,
+    scope: ScopeId,
+    update: Arc<dyn Fn()>,
+}
+
+impl<K: Hash + Eq + Clone + 'static, T: Clone + 'static> UseQuery<K, T> {
+    /// Get the latest value for the key, if one has been fetched
+    ///
+    /// This might be stale while a newer value is being fetched.
+    pub fn value(&self) -> Option<T> {
+        self.cache
+            .with_entry(&self.key.borrow(), |entry| entry.value.clone())
+    }
+
+    /// Check if a fetch for the key is in flight
+    pub fn is_fetching(&self) -> bool {
+        self.cache
+            .with_entry(&self.key.borrow(), |entry| entry.fetching)
+    }
+
+    /// Mark the cached value as stale and fetch it again in the background
+    ///
+    /// The current value stays available until the new one comes in.
+    pub fn revalidate(&self) {
+        self.cache
+            .with_entry(&self.key.borrow(), |entry| entry.stale.set(true));
+        (self.update)();
+    }
+
+    fn subscribe(&self) {
+        self.cache
+            .subscribe(&self.key.borrow(), self.scope, self.update.clone());
+    }
+
+    fn unsubscribe(&self) {
+        self.cache.unsubscribe(&self.key.borrow(), self.scope);
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, T: 'static> Drop for UseQuery<K, T> {
+    fn drop(&mut self) {
+        self.cache.unsubscribe(&self.key.borrow(), self.scope);
+    }
+}
+
+/// A fetch in flight, which hands the key over to the other components using it if it is dropped before it finishes
+struct Fetch<K: Hash + Eq + Clone, T> {
+    cache: QueryCache<K, T>,
+    key: Option<K>,
+    scope: ScopeId,
+}
+
+impl<K: Hash + Eq + Clone, T> Fetch<K, T> {
+    fn finish(mut self, value: T) {
+        if let Some(key) = self.key.take() {
+            self.cache.finish_fetch(&key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, T> Drop for Fetch<K, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.cache.cancel_fetch(&key, self.scope);
+        }
+    }
+}
+
+/// The shared cache behind [`use_query`], one per key and value type
+struct QueryCache<K, T> {
+    entries: Rc<RefCell<HashMap<K, QueryEntry<T>>>>,
+}
+
+struct QueryEntry<T> {
+    value: Option<T>,
+    hydrated: bool,
+    fetching: bool,
+    stale: Cell<bool>,
+    subscribers: HashMap<ScopeId, Arc<dyn Fn()>>,
+}
+
+impl<T> Default for QueryEntry<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            hydrated: false,
+            fetching: false,
+            stale: Cell::new(false),
+            subscribers: HashMap::new(),
+        }
+    }
+}
+
+impl<K, T> Clone for QueryCache<K, T> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<K, T> Default for QueryCache<K, T> {
+    fn default() -> Self {
+        Self {
+            entries: Default::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, T: 'static> QueryCache<K, T> {
+    fn root(cx: &ScopeState) -> Self {
+        match cx.consume_context::<QueryCache<K, T>>() {
+            Some(cache) => cache,
+            None => cx.provide_root_context(QueryCache::default()),
+        }
+    }
+}
+
+/// Fill the cache for a key with a value the server already fetched, if it doesn't have a value yet
+///
+/// The value counts as fresh, so components that start using the key don't fetch it again until it is revalidated.
+#[cfg(feature = "serialize")]
+pub(crate) fn hydrate_query<K, T>(cx: &ScopeState, key: &K, value: T)
+where
+    K: Hash + Eq + Clone + 'static,
+    T: 'static,
+{
+    let cache = QueryCache::<K, T>::root(cx);
+    let mut entries = cache.entries.borrow_mut();
+    let entry = entries.entry(key.clone()).or_default();
+    if entry.value.is_none() {
+        entry.value = Some(value);
+        entry.hydrated = true;
+    }
+}
+
+impl<K: Hash + Eq + Clone, T> QueryCache<K, T> {
+    fn with_entry<O: Default>(&self, key: &K, f: impl FnOnce(&QueryEntry<T>) -> O) -> O {
+        self.entries.borrow().get(key).map(f).unwrap_or_default()
+    }
+
+    fn subscribe(&self, key: &K, scope: ScopeId, update: Arc<dyn Fn()>) {
+        let mut entries = self.entries.borrow_mut();
+        let entry = entries.entry(key.clone()).or_default();
+
+        // A new component using a cached value gets it right away, but we fetch a fresh one in the background. Hydrated
+        // values were just fetched by the server, so they stay fresh until something revalidates them.
+        if entry.value.is_some() && !entry.fetching && !entry.hydrated {
+            entry.stale.set(true);
+        }
+
+        entry.subscribers.insert(scope, update);
+    }
+
+    fn unsubscribe(&self, key: &K, scope: ScopeId) {
+        if let Some(entry) = self.entries.borrow_mut().get_mut(key) {
+            entry.subscribers.remove(&scope);
+        }
+    }
+
+    /// Claim the fetch for a key if it needs one and nobody else is running it
+    fn start_fetch(&self, key: &K) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        let entry = entries.entry(key.clone()).or_default();
+
+        if entry.fetching || (entry.value.is_some() && !entry.stale.get()) {
+            return false;
+        }
+
+        entry.fetching = true;
+        entry.stale.set(false);
+        true
+    }
+
+    /// Release the fetch for a key that the component that started it didn't finish, so another component starts it
+    fn cancel_fetch(&self, key: &K, owner: ScopeId) {
+        let subscribers: Vec<_> = {
+            let mut entries = self.entries.borrow_mut();
+            let entry = match entries.get_mut(key) {
+                Some(entry) => entry,
+                None => return,
+            };
+            entry.fetching = false;
+            entry
+                .subscribers
+                .iter()
+                .filter(|(scope, _)| **scope != owner)
+                .map(|(_, update)| update.clone())
+                .collect()
+        };
+
+        for update in subscribers {
+            update();
+        }
+    }
+
+    fn finish_fetch(&self, key: &K, value: T) {
+        let subscribers: Vec<_> = {
+            let mut entries = self.entries.borrow_mut();
+            let entry = entries.entry(key.clone()).or_default();
+            entry.value = Some(value);
+            entry.hydrated = false;
+            entry.fetching = false;
+            entry.subscribers.values().cloned().collect()
+        };
+
+        for update in subscribers {
+            update();
+        }
+    }
+}
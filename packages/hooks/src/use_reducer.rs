@@ -0,0 +1,65 @@
+use dioxus_core::ScopeState;
+use std::{cell::RefCell, rc::Rc};
+
+/// Store state that changes through actions, like a state machine
+///
+/// Dispatching an action runs `reducer` with the latest state and the action, stores the state it returns, and
+/// re-renders the component. Actions dispatched in the same frame build on each other in order, and the component
+/// renders once with the result.
+///
+/// Returns the state as of this render, and the function to dispatch actions with.
+///
+/// ```rust, ignore
+/// enum Action {
+///     Increment,
+///     Reset,
+/// }
+///
+/// fn counter(cx: Scope) -> Element {
+///     let (count, dispatch) = use_reducer(cx, || 0, |count, action| match action {
+///         Action::Increment => count + 1,
+///         Action::Reset => 0,
+///     });
+///
+///     render! {
+///         p { "{count}" }
+///         button { onclick: move |_| dispatch(Action::Increment), "+1" }
+///         button { onclick: move |_| dispatch(Action::Reset), "Reset" }
+///     }
+/// }
+/// ```
+pub fn use_reducer<S: 'static, A: 'static>(
+    cx: &ScopeState,
+    initial_state_fn: impl FnOnce() -> S,
+    reducer: impl Fn(&S, A) -> S + 'static,
+) -> (&S, &Rc<dyn Fn(A)>) {
+    struct UseReducer<S, A> {
+        current: Rc<S>,
+        slot: Rc<RefCell<Rc<S>>>,
+        dispatch: Rc<dyn Fn(A)>,
+    }
+
+    let hook = cx.use_hook(|| {
+        let current = Rc::new(initial_state_fn());
+        let slot = Rc::new(RefCell::new(current.clone()));
+        let update = cx.schedule_update();
+        let dispatch: Rc<dyn Fn(A)> = Rc::new({
+            let slot = slot.clone();
+            move |action| {
+                let next = reducer(&slot.borrow(), action);
+                *slot.borrow_mut() = Rc::new(next);
+                update();
+            }
+        });
+
+        UseReducer {
+            current,
+            slot,
+            dispatch,
+        }
+    });
+
+    hook.current = hook.slot.borrow().clone();
+
+    (&hook.current, &hook.dispatch)
+}
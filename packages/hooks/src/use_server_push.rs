@@ -0,0 +1,453 @@
+use dioxus_core::{
+    Attribute, AttributeValue, DynamicNode, Element, ScopeId, ScopeState, Template,
+    TemplateAttribute, TemplateNode, VNode, VText,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// A node of a subtree pushed from the server
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushedNode {
+    /// An element with its attributes and children
+    Element {
+        /// The tag of the element, like `div`
+        tag: String,
+
+        /// The namespace of the element, like `svg`
+        #[cfg_attr(feature = "serialize", serde(default))]
+        namespace: Option<String>,
+
+        /// The attributes of the element as `(name, value)` pairs
+        #[cfg_attr(feature = "serialize", serde(default))]
+        attrs: Vec<(String, String)>,
+
+        /// The children of the element
+        #[cfg_attr(feature = "serialize", serde(default))]
+        children: Vec<PushedNode>,
+    },
+
+    /// A text node
+    Text(String),
+}
+
+/// The new contents of a target, pushed from the server
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushedUpdate {
+    /// The target rendered with [`use_server_push`] that this update replaces the contents of
+    pub target: String,
+
+    /// The version of the contents. Updates with a version that isn't newer than the current one are dropped.
+    pub version: u64,
+
+    /// The new contents of the target
+    pub nodes: Vec<PushedNode>,
+}
+
+/// The most structures of pushed contents that get a template
+///
+/// Templates live for the rest of the app, so a server that pushes a new structure with every update would otherwise
+/// grow the memory of the app without bound.
+const MAX_TEMPLATES: usize = 1024;
+
+/// Why [`ServerPush::push`] rejected an update
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PushError {
+    /// The target already has contents that are as new as the update
+    #[error("the target already has contents with version {current}")]
+    Outdated {
+        /// The version of the contents the target has
+        current: u64,
+    },
+
+    /// The update, or one of its elements, has more than 256 children, which is all template paths can index
+    #[error("pushed nodes can have at most 256 children, but {count} were pushed")]
+    TooManyChildren {
+        /// How many children were pushed
+        count: usize,
+    },
+
+    /// The update has a structure that wasn't pushed before, and every template for pushed structures is taken
+    #[error(
+        "the update has a new structure, but all 1024 templates for pushed structures are taken"
+    )]
+    TooManyTemplates,
+}
+
+/// Subtrees pushed from the server, shared through the root context
+///
+/// The server owns the contents of each target and the client owns everything else, so pushed updates merge into the
+/// live tree like this:
+///
+/// - An update replaces the contents of its target and re-renders only the components rendering that target. The
+///   rest of the tree, and the state of every component, is left alone.
+/// - Contents with the same structure share a template, so an update that only changes text or attribute values is
+///   diffed down to just those changes.
+/// - When a component re-renders for a local reason, it renders the last contents pushed to its targets. Local renders
+///   never overwrite pushed contents.
+/// - Updates can arrive out of order. Only updates with a newer version than the current contents are applied.
+/// - Updates for a target that isn't rendered yet are kept until a component renders it.
+/// - Every element, and the update itself, can have at most 256 children. Each structure gets a template that lives for
+///   the rest of the app, and at most 1024 structures are accepted. Updates past these limits are rejected.
+///
+/// The app receives updates however it talks to the server (like a websocket) and hands them to [`ServerPush::push`].
+#[derive(Clone, Default)]
+pub struct ServerPush {
+    inner: Rc<RefCell<ServerPushInner>>,
+}
+
+#[derive(Default)]
+struct ServerPushInner {
+    targets: HashMap<String, (u64, Rc<[PushedNode]>)>,
+    subscribers: HashMap<(ScopeId, String), Arc<dyn Fn()>>,
+
+    // Templates and names are leaked, so they are only built once per structure, up to `MAX_TEMPLATES`
+    templates: HashMap<Vec<Shape>, Template<'static>>,
+    names: HashSet<&'static str>,
+}
+
+/// The structure of a [`PushedNode`] without its text and attribute values
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Shape {
+    Element {
+        tag: String,
+        namespace: Option<String>,
+        attrs: Vec<String>,
+        children: Vec<Shape>,
+    },
+    Text,
+}
+
+impl ServerPush {
+    /// Merge an update from the server into the live tree
+    ///
+    /// Returns an error if the update was dropped, because the target already has contents that are as new or the
+    /// contents don't fit in a template.
+    pub fn push(&self, update: PushedUpdate) -> Result<(), PushError> {
+        let PushedUpdate {
+            target,
+            version,
+            nodes,
+        } = update;
+
+        let mut inner = self.inner.borrow_mut();
+        if let Some((current, _)) = inner.targets.get(&target) {
+            if *current >= version {
+                return Err(PushError::Outdated { current: *current });
+            }
+        }
+
+        // Build the template for the structure now, so contents that can't be rendered are never applied
+        let mut walk = Walk::default();
+        let shape = walk.nodes(&nodes)?;
+        if !inner.templates.contains_key(&shape) {
+            if inner.templates.len() >= MAX_TEMPLATES {
+                return Err(PushError::TooManyTemplates);
+            }
+            let template = walk.template(&mut inner, &shape);
+            inner.templates.insert(shape, template);
+        }
+
+        let subscribers: Vec<_> = inner
+            .subscribers
+            .iter()
+            .filter(|((_, subscribed), _)| *subscribed == target)
+            .map(|(_, update)| update.clone())
+            .collect();
+        inner.targets.insert(target, (version, nodes.into()));
+        drop(inner);
+
+        for update in subscribers {
+            update();
+        }
+        Ok(())
+    }
+
+    /// Get the version of the contents of a target, if anything was pushed to it yet
+    pub fn version(&self, target: &str) -> Option<u64> {
+        self.inner
+            .borrow()
+            .targets
+            .get(target)
+            .map(|(version, _)| *version)
+    }
+
+    fn contents(&self, target: &str) -> Option<Rc<[PushedNode]>> {
+        self.inner
+            .borrow()
+            .targets
+            .get(target)
+            .map(|(_, nodes)| nodes.clone())
+    }
+
+    fn subscribe(&self, scope: ScopeId, target: String, update: Arc<dyn Fn()>) {
+        self.inner
+            .borrow_mut()
+            .subscribers
+            .insert((scope, target), update);
+    }
+
+    fn unsubscribe(&self, scope: ScopeId, target: String) {
+        self.inner.borrow_mut().subscribers.remove(&(scope, target));
+    }
+
+    fn render<'a>(&self, cx: &'a ScopeState, nodes: &[PushedNode]) -> Element<'a> {
+        if nodes.is_empty() {
+            return None;
+        }
+
+        // Contents are only applied once their template was built by `push`
+        let mut walk = Walk::default();
+        let shape = walk.nodes(nodes).ok()?;
+        let template = *self.inner.borrow().templates.get(&shape)?;
+
+        let dynamic_nodes = walk
+            .texts
+            .into_iter()
+            .map(|text| DynamicNode::Text(VText::new(cx.bump().alloc_str(text))));
+        let dynamic_attrs = walk.attrs.into_iter().map(|(name, value, namespace)| {
+            Attribute::new(
+                cx.bump().alloc_str(name),
+                AttributeValue::Text(cx.bump().alloc_str(value)),
+                namespace,
+                false,
+            )
+        });
+
+        Some(VNode {
+            key: None,
+            parent: Default::default(),
+            template: Cell::new(template),
+            root_ids: Default::default(),
+            dynamic_nodes: cx.bump().alloc_slice_fill_iter(dynamic_nodes),
+            dynamic_attrs: cx.bump().alloc_slice_fill_iter(dynamic_attrs),
+        })
+    }
+}
+
+impl ServerPushInner {
+    fn intern(&mut self, name: &str) -> &'static str {
+        match self.names.get(name) {
+            Some(name) => *name,
+            None => {
+                let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+                self.names.insert(name);
+                name
+            }
+        }
+    }
+}
+
+/// The text and attribute values of pushed nodes, in the order of the dynamic parts of their template
+#[derive(Default)]
+struct Walk<'n> {
+    texts: Vec<&'n str>,
+    attrs: Vec<(&'n str, &'n str, Option<&'static str>)>,
+    node_paths: Vec<Vec<u8>>,
+    attr_paths: Vec<Vec<u8>>,
+}
+
+impl<'n> Walk<'n> {
+    fn nodes(&mut self, nodes: &'n [PushedNode]) -> Result<Vec<Shape>, PushError> {
+        nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| self.node(node, child_path(&[], idx, nodes.len())?))
+            .collect()
+    }
+
+    fn node(&mut self, node: &'n PushedNode, path: Vec<u8>) -> Result<Shape, PushError> {
+        match node {
+            PushedNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                for (name, value) in attrs {
+                    self.attrs.push((name, value, attribute_namespace(name)));
+                    self.attr_paths.push(path.clone());
+                }
+
+                let children = children
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, child)| self.node(child, child_path(&path, idx, children.len())?))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Shape::Element {
+                    tag: tag.clone(),
+                    namespace: namespace.clone(),
+                    attrs: attrs.iter().map(|(name, _)| name.clone()).collect(),
+                    children,
+                })
+            }
+            PushedNode::Text(text) => {
+                self.texts.push(text);
+                self.node_paths.push(path);
+                Ok(Shape::Text)
+            }
+        }
+    }
+
+    fn template(&self, inner: &mut ServerPushInner, shape: &[Shape]) -> Template<'static> {
+        let mut ids = (0, 0);
+        let roots = leak_nodes(inner, shape, &mut ids);
+        let node_paths: Vec<&'static [u8]> = self
+            .node_paths
+            .iter()
+            .map(|path| &*Box::leak(path.clone().into_boxed_slice()))
+            .collect();
+        let attr_paths: Vec<&'static [u8]> = self
+            .attr_paths
+            .iter()
+            .map(|path| &*Box::leak(path.clone().into_boxed_slice()))
+            .collect();
+        let name = format!("dioxus-server-push:{}:0", inner.templates.len());
+
+        Template {
+            name: Box::leak(name.into_boxed_str()),
+            roots,
+            node_paths: Box::leak(node_paths.into_boxed_slice()),
+            attr_paths: Box::leak(attr_paths.into_boxed_slice()),
+        }
+    }
+}
+
+/// The path of the child at `idx` of the node at `path`, which has `count` children
+fn child_path(path: &[u8], idx: usize, count: usize) -> Result<Vec<u8>, PushError> {
+    let idx = u8::try_from(idx).map_err(|_| PushError::TooManyChildren { count })?;
+    let mut path = path.to_vec();
+    path.push(idx);
+    Ok(path)
+}
+
+// Dynamic ids are handed out depth first, in the same order as the walk collects the values
+fn leak_nodes(
+    inner: &mut ServerPushInner,
+    shape: &[Shape],
+    ids: &mut (usize, usize),
+) -> &'static [TemplateNode<'static>] {
+    let nodes: Vec<_> = shape
+        .iter()
+        .map(|shape| match shape {
+            Shape::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                let attrs: Vec<_> = attrs
+                    .iter()
+                    .map(|_| {
+                        ids.1 += 1;
+                        TemplateAttribute::Dynamic { id: ids.1 - 1 }
+                    })
+                    .collect();
+                TemplateNode::Element {
+                    tag: inner.intern(tag),
+                    namespace: namespace
+                        .as_deref()
+                        .map(|namespace| inner.intern(namespace)),
+                    attrs: Box::leak(attrs.into_boxed_slice()),
+                    children: leak_nodes(inner, children, ids),
+                }
+            }
+            Shape::Text => {
+                ids.0 += 1;
+                TemplateNode::DynamicText { id: ids.0 - 1 }
+            }
+        })
+        .collect();
+    Box::leak(nodes.into_boxed_slice())
+}
+
+// Pushed attributes are plain strings, so style properties can't be told apart from attributes by their name alone.
+// Everything is set as an attribute, except for the namespaced attributes of svg.
+fn attribute_namespace(name: &str) -> Option<&'static str> {
+    match name.split_once(':') {
+        Some(("xlink", _)) => Some("http://www.w3.org/1999/xlink"),
+        Some(("xml", _)) => Some("http://www.w3.org/XML/1998/namespace"),
+        _ => None,
+    }
+}
+
+/// Render the contents the server pushed to a target
+///
+/// The server pushes [`PushedUpdate`]s to named targets through [`ServerPush`]. This hook renders the latest contents
+/// of `target`, and re-renders the component whenever new contents are pushed. Until something is pushed, it renders
+/// nothing. See [`ServerPush`] for how pushed contents merge with the rest of the tree.
+///
+/// ```rust, ignore
+/// fn dashboard(cx: Scope) -> Element {
+///     let push = use_root_context(cx, ServerPush::default).clone();
+///     use_effect(cx, (), |_| async move {
+///         let mut socket = connect("/live").await;
+///         while let Some(update) = socket.next::<PushedUpdate>().await {
+///             push.push(update);
+///         }
+///     });
+///
+///     let collapsed = use_state(cx, || false);
+///     let stats = use_server_push(cx, "stats");
+///
+///     render! {
+///         button { onclick: move |_| collapsed.set(!collapsed.get()), "Toggle" }
+///         if !*collapsed.get() {
+///             rsx! { div { stats } }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_server_push<'a>(cx: &'a ScopeState, target: &str) -> Element<'a> {
+    let subscription = cx.use_hook(|| PushSubscription {
+        push: match cx.consume_context::<ServerPush>() {
+            Some(push) => push,
+            None => cx.provide_root_context(ServerPush::default()),
+        },
+        scope: cx.scope_id(),
+        target: None,
+        update: cx.schedule_update(),
+    });
+
+    // The target can change between renders, so follow whichever one is rendered now
+    if subscription.target.as_deref() != Some(target) {
+        subscription.unsubscribe();
+        subscription.push.subscribe(
+            subscription.scope,
+            target.to_string(),
+            subscription.update.clone(),
+        );
+        subscription.target = Some(target.to_string());
+    }
+
+    let nodes = subscription.push.contents(target)?;
+    subscription.push.render(cx, &nodes)
+}
+
+struct PushSubscription {
+    push: ServerPush,
+    scope: ScopeId,
+    target: Option<String>,
+    update: Arc<dyn Fn()>,
+}
+
+impl PushSubscription {
+    fn unsubscribe(&mut self) {
+        if let Some(target) = self.target.take() {
+            self.push.unsubscribe(self.scope, target);
+        }
+    }
+}
+
+impl Drop for PushSubscription {
+    fn drop(&mut self) {
+        self.unsubscribe();
+    }
+}
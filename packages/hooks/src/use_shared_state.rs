@@ -284,6 +284,35 @@ impl<T> UseSharedState<T> {
     }
 }
 
+impl<T: PartialEq> UseSharedState<T> {
+    /// Replace the shared value, re-rendering the consumers only if it changed
+    ///
+    /// Consumers that read the state are subscribed to it, so setting the value they already see doesn't render them
+    /// again. Use [`UseSharedState::write`] to update the value in place instead.
+    #[cfg_attr(debug_assertions, track_caller)]
+    #[cfg_attr(debug_assertions, inline(never))]
+    pub fn set(&self, value: T) {
+        let mut inner = match self.inner.try_borrow_mut() {
+            Ok(inner) => inner,
+            Err(source) => {
+                let message = UseSharedStateError::AlreadyBorrowed {
+                    source,
+                    type_name: std::any::type_name::<Self>(),
+                };
+                panic!(
+                    "Setting the shared state failed: {}\n({:?})",
+                    message, message
+                )
+            }
+        };
+
+        if inner.value != value {
+            inner.value = value;
+            inner.notify_consumers();
+        }
+    }
+}
+
 impl<T> Clone for UseSharedState<T> {
     fn clone(&self) -> Self {
         Self {
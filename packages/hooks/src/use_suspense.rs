@@ -0,0 +1,489 @@
+use dioxus_core::{Element, ScopeState, TaskId, Template, TemplateAttribute, TemplateNode, VNode};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    panic::Location,
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{
+    suspense_boundary::BoundaryLeaf, suspense_list::ListBoundary, use_future, use_refocused,
+    SuspenseState, Timer, UseFutureDep,
+};
+
+/// The style given to every element of a skeleton
+pub const SKELETON_STYLE: &str =
+    "background-color: #e0e0e0; color: transparent; border-color: transparent; border-radius: 4px;";
+
+/// Options for [`use_suspense`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SuspenseOptions {
+    /// While the future is loading, render gray boxes in the shape of the content it resolved to last time
+    ///
+    /// The shape is only known once the content has loaded once, so the first load still suspends without rendering
+    /// anything.
+    pub skeleton: bool,
+
+    /// Run the future again when the window regains focus
+    ///
+    /// The resolved content stays on screen while the new future runs. This needs a renderer that provides
+    /// [`crate::WindowFocus`], like the web renderer.
+    pub revalidate_on_focus: bool,
+
+    /// Ignore focus events that come in less than this many milliseconds after the previous one
+    pub focus_debounce_ms: u32,
+}
+
+/// Suspend this component until a future resolves, then render its value
+///
+/// The future is started on the first render. While it is running the component is suspended, so
+/// [`dioxus_core::VirtualDom::wait_for_suspense`] waits for it.
+///
+/// With [`SuspenseOptions::skeleton`] enabled, the shape of the rendered content is remembered for every call site of
+/// this hook. Later loads render a skeleton in that shape while the future runs, which keeps the layout from shifting
+/// when the content comes in.
+///
+/// Inside a [`crate::SuspenseList`], the content stays suspended after the future resolves until the list's
+/// [`crate::RevealOrder`] lets it show.
+///
+/// ```rust, ignore
+/// fn profile(cx: Scope) -> Element {
+///     use_suspense(
+///         cx,
+///         SuspenseOptions { skeleton: true, ..Default::default() },
+///         || fetch_profile(),
+///         |profile| render! {
+///             div { class: "card",
+///                 h1 { "{profile.name}" }
+///                 p { "{profile.bio}" }
+///             }
+///         },
+///     )
+/// }
+/// ```
+#[track_caller]
+pub fn use_suspense<'a, T, F>(
+    cx: &'a ScopeState,
+    options: SuspenseOptions,
+    create_future: impl FnOnce() -> F,
+    render: impl FnOnce(&'a T) -> Element<'a>,
+) -> Element<'a>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    use_suspense_with_handle(cx, options, create_future, render).0
+}
+
+/// [`use_suspense`] that also returns a [`SuspenseHandle`] to start the future over
+///
+/// ```rust, ignore
+/// fn search(cx: Scope) -> Element {
+///     let query = use_state(cx, String::new);
+///     let (results, search) = use_suspense_with_handle(
+///         cx,
+///         SuspenseOptions::default(),
+///         || fetch_results(query.get().clone()),
+///         |results| render! { ul { results.iter().map(|r| rsx!(li { "{r}" })) } },
+///     );
+///
+///     render! {
+///         input {
+///             oninput: move |evt| {
+///                 query.set(evt.value.clone());
+///                 search.restart();
+///             }
+///         }
+///         results
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_suspense_with_handle<'a, T, F>(
+    cx: &'a ScopeState,
+    options: SuspenseOptions,
+    create_future: impl FnOnce() -> F,
+    render: impl FnOnce(&'a T) -> Element<'a>,
+) -> (Element<'a>, &'a SuspenseHandle)
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    suspend_on(
+        cx,
+        options,
+        Location::caller(),
+        false,
+        create_future,
+        render,
+    )
+}
+
+/// [`use_suspense`] that starts the future over when its dependencies change
+///
+/// This is how a component loads its data and renders it: the future is given the dependencies, and the component is
+/// suspended until it resolves. When the component renders with new dependencies, like a new prop, the future that is
+/// running is dropped and a new one starts, so a slow response for old props is never shown.
+///
+/// The future is `'static`, so it can't borrow the component or call hooks. Read everything it needs from hooks before
+/// the future is created and pass it in through the dependencies. All the hooks of the component run on every render,
+/// in the same order, whether or not the future has resolved.
+///
+/// ```rust, ignore
+/// #[inline_props]
+/// fn user_card(cx: Scope, id: u32) -> Element {
+///     use_suspense_with_deps(
+///         cx,
+///         (id,),
+///         |(id,)| fetch_user(id),
+///         |user| render! { h2 { "{user.name}" } },
+///     )
+/// }
+/// ```
+#[track_caller]
+pub fn use_suspense_with_deps<'a, T, F, D>(
+    cx: &'a ScopeState,
+    dependencies: D,
+    create_future: impl FnOnce(D::Out) -> F,
+    render: impl FnOnce(&'a T) -> Element<'a>,
+) -> Element<'a>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+    D: UseFutureDep,
+{
+    let location = Location::caller();
+    let state = cx.use_hook(Vec::new);
+    let first_render = state.is_empty();
+    let out = dependencies.out();
+    let changed = dependencies.apply(state) && !first_render;
+
+    suspend_on(
+        cx,
+        SuspenseOptions::default(),
+        location,
+        changed,
+        move || create_future(out),
+        render,
+    )
+    .0
+}
+
+/// The body of the suspense hooks, which bumps the restart count before the future is checked if `restart` is set
+fn suspend_on<'a, T, F>(
+    cx: &'a ScopeState,
+    options: SuspenseOptions,
+    location: &'static Location<'static>,
+    restart: bool,
+    create_future: impl FnOnce() -> F,
+    render: impl FnOnce(&'a T) -> Element<'a>,
+) -> (Element<'a>, &'a SuspenseHandle)
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    let skeletons = cx.use_hook(|| match cx.consume_context::<SkeletonCache>() {
+        Some(cache) => cache,
+        None => cx.provide_root_context(SkeletonCache::default()),
+    });
+    let focus_debounce = match options.revalidate_on_focus {
+        true => Some(options.focus_debounce_ms),
+        false => None,
+    };
+    let refocused = use_refocused(cx, focus_debounce);
+    let boundary = cx.use_hook(|| ListBoundary::join(cx));
+    let handle = cx.use_hook(|| SuspenseHandle {
+        restarts: Default::default(),
+        cancelled: Default::default(),
+        state: Rc::new(Cell::new(SuspenseState::Pending)),
+        update: cx.schedule_update(),
+    });
+    let leaf = cx.use_hook(|| BoundaryLeaf::join(cx, handle));
+
+    // Bumping either count restarts the future. The value is tagged with the restart it belongs to, so a value from
+    // before the last restart is never shown, while revalidating keeps the old value on screen.
+    let refocuses = cx.use_hook(|| 0u32);
+    if refocused {
+        *refocuses += 1;
+    }
+    if restart {
+        handle.restarts.set(handle.restarts.get() + 1);
+    }
+    let restarts = handle.restarts.get();
+    let value = use_future(cx, (&*refocuses, &restarts), |(_, restarts)| {
+        let future = create_future();
+        async move { (restarts, future.await) }
+    });
+
+    // A cancelled future stays dropped until the next restart
+    let cancelled = handle.cancelled.get();
+    if cancelled {
+        value.cancel(cx);
+    }
+
+    // A resolved future can still be held back by the suspense list around it
+    let value = value
+        .value()
+        .filter(|(restart, _)| *restart == restarts && !cancelled)
+        .map(|(_, value)| value)
+        .filter(|_| boundary.as_ref().map_or(true, ListBoundary::reveal));
+
+    let state = match (value.is_some(), cancelled) {
+        (true, _) => SuspenseState::Resolved,
+        (false, true) => SuspenseState::Cancelled,
+        (false, false) => SuspenseState::Pending,
+    };
+    if handle.state.replace(state) != state {
+        if let Some(leaf) = leaf {
+            leaf.state_changed();
+        }
+    }
+
+    let element = match value {
+        Some(value) => {
+            let element = render(value);
+            if let Some(node) = element.as_ref().filter(|_| options.skeleton) {
+                skeletons.remember(location, &node.template.get());
+            }
+            element
+        }
+        None => {
+            // A cancelled future will never resolve, so it doesn't hold up the suspense the renderer waits on
+            if !cancelled {
+                cx.suspend();
+            }
+            match options.skeleton {
+                true => skeletons.get(location).map(|template| VNode {
+                    key: None,
+                    parent: Default::default(),
+                    template: Cell::new(template),
+                    root_ids: Default::default(),
+                    dynamic_nodes: &[],
+                    dynamic_attrs: &[],
+                }),
+                false => None,
+            }
+        }
+    };
+
+    (element, handle)
+}
+
+/// [`use_suspense`] that renders a fallback if the future takes longer than `timeout`
+///
+/// Until the timeout the component is suspended without rendering anything. After it, the component stays suspended
+/// but renders `fallback`, like a "still loading" message, until the future resolves and `render` takes over. If the
+/// future resolves first, the timeout is cancelled.
+///
+/// The timeout waits on the [`Timer`] the renderer provides. Without one, the fallback never shows.
+///
+/// ```rust, ignore
+/// use_suspense_with_timeout(
+///     cx,
+///     Duration::from_millis(500),
+///     || fetch_report(),
+///     || render! { p { "Still loading..." } },
+///     |report| render! { pre { "{report}" } },
+/// )
+/// ```
+#[track_caller]
+pub fn use_suspense_with_timeout<'a, T, F>(
+    cx: &'a ScopeState,
+    timeout: Duration,
+    create_future: impl FnOnce() -> F,
+    fallback: impl FnOnce() -> Element<'a>,
+    render: impl FnOnce(&'a T) -> Element<'a>,
+) -> Element<'a>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    let state = cx.use_hook(|| SuspenseTimeout {
+        timer: cx.consume_context::<Timer>(),
+        task: Default::default(),
+        timed_out: Rc::new(Cell::new(false)),
+    });
+
+    let resolved = Cell::new(false);
+    let element = use_suspense(cx, SuspenseOptions::default(), create_future, |value| {
+        resolved.set(true);
+        render(value)
+    });
+
+    if resolved.get() {
+        if let Some(task) = state.task.take() {
+            cx.remove_future(task);
+        }
+        state.timed_out.set(false);
+        return element;
+    }
+
+    if state.timed_out.get() {
+        return fallback();
+    }
+
+    if let (None, Some(timer)) = (state.task.get(), &state.timer) {
+        let sleep = timer.sleep(timeout);
+        let timed_out = state.timed_out.clone();
+        let task = state.task.clone();
+        let update = cx.schedule_update();
+        state.task.set(Some(cx.push_future(async move {
+            sleep.await;
+            task.take();
+            timed_out.set(true);
+            update();
+        })));
+    }
+
+    element
+}
+
+struct SuspenseTimeout {
+    timer: Option<Timer>,
+    // Cleared by the timeout task when it finishes, so a finished task is never removed
+    task: Rc<Cell<Option<TaskId>>>,
+    timed_out: Rc<Cell<bool>>,
+}
+
+/// Restarts or cancels the future of a [`use_suspense_with_handle`]
+#[derive(Clone)]
+pub struct SuspenseHandle {
+    restarts: Rc<Cell<u32>>,
+    cancelled: Rc<Cell<bool>>,
+    state: Rc<Cell<SuspenseState>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl SuspenseHandle {
+    /// Drop the future that is running and start a new one
+    ///
+    /// The component goes back to being suspended, showing its skeleton if it has one, until the new future resolves.
+    /// The old future is cancelled, so neither its value nor a late wakeup ever reaches the component.
+    pub fn restart(&self) {
+        self.cancelled.set(false);
+        self.restarts.set(self.restarts.get() + 1);
+        (self.update)();
+    }
+
+    /// Drop the future that is running without starting a new one
+    ///
+    /// The component keeps showing its skeleton, or nothing, until [`SuspenseHandle::restart`] is called. It is no
+    /// longer suspended, so [`dioxus_core::VirtualDom::wait_for_suspense`] doesn't wait for it. This does nothing once
+    /// the future has resolved.
+    pub fn cancel(&self) {
+        if self.state.get() == SuspenseState::Pending {
+            self.cancelled.set(true);
+            (self.update)();
+        }
+    }
+
+    /// Get the state of the future as of the last render of the component
+    pub fn state(&self) -> SuspenseState {
+        self.state.get()
+    }
+
+    pub(crate) fn same_handle(&self, other: &SuspenseHandle) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+/// The skeletons of every call site of [`use_suspense`] that resolved, shared through the root context
+#[derive(Clone, Default)]
+struct SkeletonCache {
+    inner: Rc<RefCell<SkeletonCacheInner>>,
+}
+
+#[derive(Default)]
+struct SkeletonCacheInner {
+    // The name of the template each call site resolved to last
+    resolved: HashMap<&'static Location<'static>, &'static str>,
+
+    // Skeletons are leaked, so they are only built once per template
+    skeletons: HashMap<&'static str, Template<'static>>,
+}
+
+impl SkeletonCache {
+    fn remember(&self, location: &'static Location<'static>, template: &Template<'static>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.resolved.insert(location, template.name);
+        inner
+            .skeletons
+            .entry(template.name)
+            .or_insert_with(|| skeleton_template(template));
+    }
+
+    fn get(&self, location: &'static Location<'static>) -> Option<Template<'static>> {
+        let inner = self.inner.borrow();
+        let name = inner.resolved.get(location)?;
+        inner.skeletons.get(name).copied()
+    }
+}
+
+/// Build a skeleton of a template: the same elements with [`SKELETON_STYLE`] and none of the dynamic content
+///
+/// Dynamic text is replaced with a non-breaking space so text elements keep the height of a line. Components and
+/// other dynamic nodes are left out because their shape isn't part of the template.
+///
+/// The new template is leaked, so this should only be called once per template.
+pub fn skeleton_template(template: &Template<'static>) -> Template<'static> {
+    // Keep the trailing byte index the VirtualDom parses from template names
+    let name = match template.name.rsplit_once(':') {
+        Some((path, index)) => format!("{}:skeleton:{}", path, index),
+        None => format!("{}:skeleton:0", template.name),
+    };
+    Template {
+        name: Box::leak(name.into_boxed_str()),
+        roots: skeleton_nodes(template.roots),
+        node_paths: &[],
+        attr_paths: &[],
+    }
+}
+
+fn skeleton_nodes(nodes: &'static [TemplateNode<'static>]) -> &'static [TemplateNode<'static>] {
+    let nodes: Vec<_> = nodes.iter().filter_map(skeleton_node).collect();
+    Box::leak(nodes.into_boxed_slice())
+}
+
+fn skeleton_node(node: &'static TemplateNode<'static>) -> Option<TemplateNode<'static>> {
+    match node {
+        TemplateNode::Element {
+            tag,
+            namespace,
+            attrs,
+            children,
+        } => {
+            let attrs: Vec<_> = attrs
+                .iter()
+                .filter_map(|attr| match attr {
+                    TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace,
+                    } if *name != "style" => Some(TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace: *namespace,
+                    }),
+                    _ => None,
+                })
+                .chain(std::iter::once(TemplateAttribute::Static {
+                    name: "style",
+                    value: SKELETON_STYLE,
+                    namespace: None,
+                }))
+                .collect();
+            Some(TemplateNode::Element {
+                tag: *tag,
+                namespace: *namespace,
+                attrs: Box::leak(attrs.into_boxed_slice()),
+                children: skeleton_nodes(children),
+            })
+        }
+        TemplateNode::Text { text } => Some(TemplateNode::Text { text: *text }),
+        TemplateNode::DynamicText { .. } => Some(TemplateNode::Text { text: "\u{a0}" }),
+        TemplateNode::Dynamic { .. } => None,
+    }
+}
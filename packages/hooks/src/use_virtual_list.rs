@@ -0,0 +1,108 @@
+use dioxus_core::ScopeState;
+use std::{cell::Cell, ops::Range, sync::Arc};
+
+/// How many items are rendered past each edge of the viewport, so fast scrolling doesn't show empty space
+const OVERSCAN: usize = 3;
+
+/// Render only the items of a long list that are scrolled into view
+///
+/// Every item is assumed to be `item_height` pixels tall, and the list is shown in a scrolling element that is
+/// `viewport_height` pixels tall. Render the items in [`VirtualList::range`] between two spacers that stand in for the
+/// items above and below them, and report the scroll position of the element with [`VirtualList::set_scroll_top`]:
+///
+/// ```rust, ignore
+/// fn table(cx: Scope<TableProps>) -> Element {
+///     let list = use_virtual_list(cx, cx.props.rows.len(), 24.0, 600.0);
+///
+///     render! {
+///         div {
+///             style: "height: 600px; overflow-y: auto;",
+///             onscroll: move |evt| list.set_scroll_top(evt.scroll_top),
+///             div { style: "height: {list.offset_top()}px;" }
+///             for row in &cx.props.rows[list.range()] {
+///                 div { key: "{row.id}", style: "height: 24px;", "{row.name}" }
+///             }
+///             div { style: "height: {list.offset_bottom()}px;" }
+///         }
+///     }
+/// }
+/// ```
+///
+/// The component only renders again when the scroll position brings other items into view. Keyed items that stay in
+/// view keep their nodes, so moving the window only creates and removes the items at its edges.
+pub fn use_virtual_list(
+    cx: &ScopeState,
+    len: usize,
+    item_height: f64,
+    viewport_height: f64,
+) -> &VirtualList {
+    let list = cx.use_hook(|| VirtualList {
+        len: 0,
+        item_height: 1.0,
+        viewport_height: 0.0,
+        scroll_top: Cell::new(0.0),
+        window: Cell::new((0, 0)),
+        update: cx.schedule_update(),
+    });
+    list.len = len;
+    list.item_height = item_height.max(1.0);
+    list.viewport_height = viewport_height.max(0.0);
+    list.window.set(list.window_at(list.scroll_top.get()));
+
+    list
+}
+
+/// The items of a long list to render, created with [`use_virtual_list`]
+pub struct VirtualList {
+    len: usize,
+    item_height: f64,
+    viewport_height: f64,
+    scroll_top: Cell<f64>,
+    window: Cell<(usize, usize)>,
+    update: Arc<dyn Fn()>,
+}
+
+impl VirtualList {
+    /// The indices of the items to render
+    pub fn range(&self) -> Range<usize> {
+        let (start, end) = self.window.get();
+        start..end
+    }
+
+    /// The height of the items above the range, which a spacer before the items should take up
+    pub fn offset_top(&self) -> f64 {
+        self.window.get().0 as f64 * self.item_height
+    }
+
+    /// The height of the items below the range, which a spacer after the items should take up
+    pub fn offset_bottom(&self) -> f64 {
+        (self.len - self.window.get().1) as f64 * self.item_height
+    }
+
+    /// The height of every item together
+    pub fn total_height(&self) -> f64 {
+        self.len as f64 * self.item_height
+    }
+
+    /// Set how far the list is scrolled from the top, in pixels
+    ///
+    /// The component renders again if other items scrolled into view.
+    pub fn set_scroll_top(&self, scroll_top: f64) {
+        let scroll_top = scroll_top.max(0.0);
+        self.scroll_top.set(scroll_top);
+        if self.window_at(scroll_top) != self.window.get() {
+            (self.update)();
+        }
+    }
+
+    fn window_at(&self, scroll_top: f64) -> (usize, usize) {
+        // Elements can't scroll past their content, but keep the window full if the reported position is stale
+        let scroll_top = scroll_top.min((self.total_height() - self.viewport_height).max(0.0));
+        let first = (scroll_top / self.item_height).floor() as usize;
+        let last = ((scroll_top + self.viewport_height) / self.item_height).ceil() as usize;
+
+        let end = last.saturating_add(OVERSCAN).min(self.len);
+        let start = first.saturating_sub(OVERSCAN).min(end);
+        (start, end)
+    }
+}
@@ -0,0 +1,151 @@
+use dioxus_core::ScopeState;
+use slab::Slab;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// Focus changes of the window the app runs in
+///
+/// Renderers that know when the window regains focus provide this at the root of the app with [`WindowFocus::new`],
+/// and call [`WindowFocus::focused`]. Without a renderer providing it, focus hooks never fire.
+#[derive(Clone, Default)]
+pub struct WindowFocus {
+    listeners: Rc<RefCell<Slab<Rc<dyn Fn(f64)>>>>,
+    listen: Rc<RefCell<Option<Box<dyn FnOnce(WindowFocus)>>>>,
+}
+
+impl WindowFocus {
+    /// Create the focus changes of a window, calling `listen` when the first hook starts listening for them
+    ///
+    /// Renderers start forwarding the focus events of the window in `listen`, so apps that never use a focus hook
+    /// don't listen for them at all.
+    pub fn new(listen: impl FnOnce(WindowFocus) + 'static) -> Self {
+        Self {
+            listeners: Default::default(),
+            listen: Rc::new(RefCell::new(Some(Box::new(listen)))),
+        }
+    }
+
+    /// Let every listener know the window regained focus at `timestamp`, in milliseconds
+    pub fn focused(&self, timestamp: f64) {
+        // Listeners can subscribe and unsubscribe while we call them
+        let listeners: Vec<_> = self
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        for listener in listeners {
+            listener(timestamp);
+        }
+    }
+
+    fn subscribe(&self, listener: Rc<dyn Fn(f64)>) -> usize {
+        let key = self.listeners.borrow_mut().insert(listener);
+        let listen = self.listen.borrow_mut().take();
+        if let Some(listen) = listen {
+            listen(self.clone());
+        }
+        key
+    }
+
+    fn unsubscribe(&self, key: usize) {
+        let mut listeners = self.listeners.borrow_mut();
+        if listeners.contains(key) {
+            listeners.remove(key);
+        }
+    }
+}
+
+/// Re-render the component when the window regains focus
+///
+/// Returns `true` on the render caused by the window regaining focus. Focus events that come in less than
+/// `debounce_ms` milliseconds after the previous one are ignored, so rapidly switching focus doesn't cause a re-render
+/// for every switch.
+///
+/// ```rust, ignore
+/// fn clock(cx: Scope) -> Element {
+///     let now = use_state(cx, current_time);
+///
+///     if use_window_focus(cx, 1000) {
+///         now.set(current_time());
+///     }
+///
+///     render! { "{now}" }
+/// }
+/// ```
+pub fn use_window_focus(cx: &ScopeState, debounce_ms: u32) -> bool {
+    use_refocused(cx, Some(debounce_ms))
+}
+
+/// Like [`use_window_focus`], but only listens for focus changes while `debounce_ms` is `Some`
+pub(crate) fn use_refocused(cx: &ScopeState, debounce_ms: Option<u32>) -> bool {
+    let listener = cx.use_hook(|| {
+        let focus = match cx.consume_context::<WindowFocus>() {
+            Some(focus) => focus,
+            None => cx.provide_root_context(WindowFocus::default()),
+        };
+        FocusListener {
+            focus,
+            key: None,
+            refocused: Rc::new(Cell::new(false)),
+            debounce_ms: Rc::new(Cell::new(0)),
+            last_focus: Rc::new(Cell::new(None)),
+            update: cx.schedule_update(),
+        }
+    });
+
+    match debounce_ms {
+        Some(debounce_ms) => {
+            listener.debounce_ms.set(debounce_ms);
+            if listener.key.is_none() {
+                listener.subscribe();
+            }
+        }
+        None => listener.unsubscribe(),
+    }
+
+    listener.refocused.take()
+}
+
+struct FocusListener {
+    focus: WindowFocus,
+    key: Option<usize>,
+    refocused: Rc<Cell<bool>>,
+    debounce_ms: Rc<Cell<u32>>,
+    last_focus: Rc<Cell<Option<f64>>>,
+    update: std::sync::Arc<dyn Fn()>,
+}
+
+impl FocusListener {
+    fn subscribe(&mut self) {
+        let refocused = self.refocused.clone();
+        let debounce_ms = self.debounce_ms.clone();
+        let last_focus = self.last_focus.clone();
+        let update = self.update.clone();
+        let key = self.focus.subscribe(Rc::new(move |timestamp| {
+            let settled = match last_focus.replace(Some(timestamp)) {
+                Some(last) => timestamp - last >= debounce_ms.get() as f64,
+                None => true,
+            };
+            if settled {
+                refocused.set(true);
+                update();
+            }
+        }));
+        self.key = Some(key);
+    }
+
+    fn unsubscribe(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.focus.unsubscribe(key);
+        }
+    }
+}
+
+impl Drop for FocusListener {
+    fn drop(&mut self) {
+        self.unsubscribe();
+    }
+}
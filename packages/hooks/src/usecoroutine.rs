@@ -2,7 +2,7 @@ use dioxus_core::{ScopeState, TaskId};
 pub use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use std::future::Future;
 
-/// Maintain a handle over a future that can be paused, resumed, and canceled.
+/// Maintain a handle over a long-lived future that can be sent messages and canceled.
 ///
 /// This is an upgraded form of [`use_future`] with an integrated channel system.
 /// Specifically, the coroutine generated here comes with an [`UnboundedChannel`]
@@ -107,9 +107,24 @@ impl<T> Coroutine<T> {
     }
 
     /// Send a message to the coroutine
+    ///
+    /// Messages sent after the coroutine finished or was cancelled are dropped.
     pub fn send(&self, msg: T) {
         let _ = self.tx.unbounded_send(msg);
     }
+
+    /// Stop the coroutine, dropping its future and the receiving end of its channel
+    ///
+    /// The coroutine is also cancelled when the component that created it is dropped.
+    pub fn cancel(&self, cx: &ScopeState) {
+        cx.cancel_task(self.task);
+        self.tx.close_channel();
+    }
+
+    /// Check if the coroutine was cancelled or its receiver was dropped
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
 }
 
 impl<T> PartialEq for Coroutine<T> {
@@ -1,5 +1,10 @@
 use dioxus_core::{ScopeState, TaskId};
-use std::{any::Any, cell::Cell, future::Future};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    future::Future,
+    rc::Rc,
+};
 
 use crate::UseFutureDep;
 
@@ -9,6 +14,8 @@ use crate::UseFutureDep;
 /// If a future is pending when the dependencies change, the previous future
 /// will be allowed to continue
 ///
+/// To undo what an effect did before it runs again, use [`use_effect_with_cleanup`].
+///
 /// - dependencies: a tuple of references to values that are `PartialEq` + `Clone`
 ///
 /// ## Examples
@@ -69,6 +76,81 @@ where
     }
 }
 
+/// A hook that runs a callback after the render, and the cleanup it returns before the next run and on unmount
+///
+/// The callback runs as a task, so it runs once the mutations of the render have been handed to the renderer, not
+/// while diffing. Whenever the dependencies change, the cleanup of the last run is called and then the callback runs
+/// again. If the dependencies change again before the callback got to run, only the newest callback runs.
+///
+/// - dependencies: a tuple of references to values that are `PartialEq` + `Clone`
+///
+/// ## Examples
+///
+/// ```rust, ignore
+/// #[inline_props]
+/// fn Ticker(cx: Scope, interval: u32) -> Element {
+///     use_effect_with_cleanup(cx, (interval,), |(interval,)| {
+///         let handle = start_interval(interval);
+///         move || stop_interval(handle)
+///     });
+///
+///     render!(p { "Ticking every {interval}ms" })
+/// }
+/// ```
+pub fn use_effect_with_cleanup<D, C>(
+    cx: &ScopeState,
+    dependencies: D,
+    effect: impl FnOnce(D::Out) -> C + 'static,
+) where
+    D: UseFutureDep,
+    D::Out: 'static,
+    C: FnOnce() + 'static,
+{
+    struct UseEffectWithCleanup {
+        needs_regen: bool,
+        task: Cell<Option<TaskId>>,
+        dependencies: Vec<Box<dyn Any>>,
+        cleanup: Rc<RefCell<Option<Box<dyn FnOnce()>>>>,
+    }
+
+    impl Drop for UseEffectWithCleanup {
+        fn drop(&mut self) {
+            let cleanup = self.cleanup.borrow_mut().take();
+            if let Some(cleanup) = cleanup {
+                cleanup();
+            }
+        }
+    }
+
+    let state = cx.use_hook(move || UseEffectWithCleanup {
+        needs_regen: true,
+        task: Cell::new(None),
+        dependencies: Vec::new(),
+        cleanup: Rc::new(RefCell::new(None)),
+    });
+
+    if dependencies.clone().apply(&mut state.dependencies) || state.needs_regen {
+        state.needs_regen = false;
+
+        // A callback that hasn't run yet is out of date
+        if let Some(task) = state.task.take() {
+            cx.remove_future(task);
+        }
+
+        let cleanup = state.cleanup.clone();
+        let dependencies = dependencies.out();
+        state.task.set(Some(cx.push_future(async move {
+            let previous = cleanup.borrow_mut().take();
+            if let Some(previous) = previous {
+                previous();
+            }
+
+            let next = effect(dependencies);
+            *cleanup.borrow_mut() = Some(Box::new(next));
+        })));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,14 @@
 #![allow(missing_docs)]
 use dioxus_core::{ScopeState, TaskId};
-use std::{any::Any, cell::Cell, future::Future, rc::Rc, sync::Arc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
 
 use crate::{use_state, UseState};
 
@@ -12,9 +20,12 @@ use crate::{use_state, UseState};
 /// This is commonly used for components that cannot be rendered until some
 /// asynchronous operation has completed.
 ///
+/// Unlike suspense, the component keeps rendering while the future runs, so
+/// it can show its own loading state with [`UseFuture::state`].
+///
 /// Whenever the hooks dependencies change, the future will be re-evaluated.
 /// If a future is pending when the dependencies change, the previous future
-/// will be allowed to continue
+/// is dropped, and the last value stays readable until the new one resolves.
 ///
 /// - dependencies: a tuple of references to values that are PartialEq + Clone
 pub fn use_future<T, F, D>(
@@ -22,6 +33,34 @@ pub fn use_future<T, F, D>(
     dependencies: D,
     future: impl FnOnce(D::Out) -> F,
 ) -> &UseFuture<T>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+    D: UseFutureDep,
+{
+    use_cancellable_future(cx, dependencies, move |deps, _| future(deps))
+}
+
+/// A [`use_future`] that can observe its own cancellation.
+///
+/// The future is given a [`CancellationToken`] that fires when the future is
+/// superseded (because the dependencies changed or it was restarted), when it
+/// is cancelled with [`UseFuture::cancel`], or when the component unmounts.
+/// Use it to abort work the future started outside of itself, like a network
+/// request.
+///
+/// ```rust, ignore
+/// let user = use_cancellable_future(cx, (id,), |(id,), token| async move {
+///     let request = start_request(id);
+///     token.on_cancel(move || request.abort());
+///     request.await
+/// });
+/// ```
+pub fn use_cancellable_future<T, F, D>(
+    cx: &ScopeState,
+    dependencies: D,
+    future: impl FnOnce(D::Out, CancellationToken) -> F,
+) -> &UseFuture<T>
 where
     T: 'static,
     F: Future<Output = T> + 'static,
@@ -34,6 +73,7 @@ where
         needs_regen: Cell::new(true),
         state: val.clone(),
         task: Default::default(),
+        token: Default::default(),
         dependencies: Vec::new(),
     });
 
@@ -43,8 +83,12 @@ where
             cx.remove_future(task);
         }
 
+        // let the old future know it was superseded
+        let token = CancellationToken::default();
+        state.token.replace(token.clone()).cancel();
+
         // Create the new future
-        let fut = future(dependencies.out());
+        let fut = future(dependencies.out(), token);
         let val = val.clone();
         let task = state.task.clone();
 
@@ -73,10 +117,17 @@ pub struct UseFuture<T: 'static> {
     update: Arc<dyn Fn()>,
     needs_regen: Cell<bool>,
     task: Rc<Cell<Option<TaskId>>>,
+    token: RefCell<CancellationToken>,
     dependencies: Vec<Box<dyn Any>>,
     state: UseState<Option<T>>,
 }
 
+impl<T> Drop for UseFuture<T> {
+    fn drop(&mut self) {
+        self.token.borrow().cancel();
+    }
+}
+
 pub enum UseFutureState<'a, T> {
     Pending,
     Complete(&'a T),
@@ -86,8 +137,8 @@ pub enum UseFutureState<'a, T> {
 impl<T> UseFuture<T> {
     /// Restart the future with new dependencies.
     ///
-    /// Will not cancel the previous future, but will ignore any values that it
-    /// generates.
+    /// The previous future is dropped when the component renders again, so
+    /// any value it would have generated is never seen.
     pub fn restart(&self) {
         self.needs_regen.set(true);
         (self.update)();
@@ -98,6 +149,7 @@ impl<T> UseFuture<T> {
         if let Some(task) = self.task.take() {
             cx.remove_future(task);
         }
+        self.token.borrow().cancel();
     }
 
     // Manually set the value in the future slot without starting the future over
@@ -135,6 +187,81 @@ impl<T> UseFuture<T> {
     }
 }
 
+/// A handle a future can use to find out that it was cancelled.
+///
+/// Cloning the token gives another handle to the same cancellation.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Rc<CancellationInner>,
+}
+
+#[derive(Default)]
+struct CancellationInner {
+    cancelled: Cell<bool>,
+    wakers: RefCell<Vec<Waker>>,
+    callbacks: RefCell<Vec<Box<dyn FnOnce()>>>,
+}
+
+impl CancellationToken {
+    /// Check if the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.get()
+    }
+
+    /// Cancel the token, waking everything waiting on it. Cancelling twice does nothing.
+    pub fn cancel(&self) {
+        if self.inner.cancelled.replace(true) {
+            return;
+        }
+
+        for waker in self.inner.wakers.take() {
+            waker.wake();
+        }
+
+        for callback in self.inner.callbacks.take() {
+            callback();
+        }
+    }
+
+    /// Run a callback when the token is cancelled, or immediately if it already was
+    pub fn on_cancel(&self, callback: impl FnOnce() + 'static) {
+        match self.is_cancelled() {
+            true => callback(),
+            false => self.inner.callbacks.borrow_mut().push(Box::new(callback)),
+        }
+    }
+
+    /// A future that completes once the token is cancelled
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+        }
+    }
+}
+
+/// The future returned by [`CancellationToken::cancelled`]
+pub struct Cancelled {
+    token: CancellationToken,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+
+        self.token
+            .inner
+            .wakers
+            .borrow_mut()
+            .push(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
 pub trait UseFutureDep: Sized + Clone {
     type Out;
     fn out(&self) -> Self::Out;
@@ -283,4 +410,45 @@ mod tests {
             todo!()
         }
     }
+
+    #[test]
+    fn token_cancelled_when_superseded_or_unmounted() {
+        use dioxus_core::prelude::*;
+        use futures_util::FutureExt;
+
+        thread_local! {
+            static DEP: Cell<usize> = Cell::new(0);
+            static TOKENS: RefCell<Vec<CancellationToken>> = RefCell::new(Vec::new());
+        }
+
+        fn app(cx: Scope) -> Element {
+            let dep = DEP.with(|d| d.get());
+
+            use_cancellable_future(cx, (&dep,), |_, token| {
+                TOKENS.with(|t| t.borrow_mut().push(token));
+                std::future::pending::<()>()
+            });
+
+            None
+        }
+
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+
+        // Changing the dependencies supersedes the pending future
+        DEP.with(|d| d.set(1));
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+
+        let tokens = TOKENS.with(|t| t.take());
+        assert_eq!(tokens.len(), 2);
+        assert!(tokens[0].is_cancelled());
+        assert!(tokens[0].cancelled().now_or_never().is_some());
+        assert!(!tokens[1].is_cancelled());
+        assert!(tokens[1].cancelled().now_or_never().is_none());
+
+        // Unmounting cancels the current future too
+        drop(dom);
+        assert!(tokens[1].is_cancelled());
+    }
 }
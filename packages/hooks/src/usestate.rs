@@ -342,6 +342,24 @@ impl<T: Debug> Debug for UseState<T> {
     }
 }
 
+#[cfg(feature = "devtools")]
+impl<T> dioxus_core::DevInspect for UseState<T>
+where
+    T: Debug + std::str::FromStr + 'static,
+    T::Err: Display,
+{
+    fn inspect(&self) -> String {
+        // Read the slot so values set since the last render show up
+        format!("{:?}", self.slot.borrow())
+    }
+
+    fn set_value(&self, value: &str) -> Result<(), String> {
+        let new = value.parse().map_err(|err: T::Err| err.to_string())?;
+        (self.setter)(new);
+        Ok(())
+    }
+}
+
 impl<T> std::ops::Deref for UseState<T> {
     type Target = T;
 
@@ -0,0 +1,51 @@
+//! Developer tools can read and edit use_state values
+use dioxus::prelude::*;
+use dioxus_core::{HookStateError, HookStateView, Mutation};
+use std::cell::Cell;
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let count = use_state(cx, || 0);
+    RENDERS.with(|r| r.set(r.get() + 1));
+
+    render! { "{count}" }
+}
+
+#[test]
+fn edit_use_state_from_devtools() {
+    let mut dom = VirtualDom::new(app);
+    dom.register_dev_inspect::<UseState<i32>>();
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dom.scope_hook_state(ScopeId(0)),
+        [HookStateView {
+            index: 0,
+            type_name: std::any::type_name::<UseState<i32>>(),
+            value: "0".to_string(),
+        }]
+    );
+
+    dom.set_hook_value(ScopeId(0), 0, "5").unwrap();
+    assert_eq!(dom.scope_hook_state(ScopeId(0))[0].value, "5");
+
+    let edits = dom.render_immediate();
+    assert_eq!(RENDERS.with(Cell::get), 2);
+    assert!(edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, Mutation::SetText { value: "5", .. })));
+    drop(edits);
+
+    assert!(matches!(
+        dom.set_hook_value(ScopeId(0), 0, "five"),
+        Err(HookStateError::InvalidValue(_))
+    ));
+    assert_eq!(
+        dom.set_hook_value(ScopeId(0), 1, "5"),
+        Err(HookStateError::HookNotFound(1))
+    );
+}
@@ -0,0 +1,78 @@
+//! Tests for forwarding a node ref through a wrapper component
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::html::RenderedElementBacking;
+use dioxus::prelude::*;
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+thread_local! {
+    static SEEN: RefCell<Vec<Option<&'static str>>> = RefCell::new(Vec::new());
+}
+
+/// A fake renderer element that knows its own name
+struct Named(&'static str);
+
+impl RenderedElementBacking for Named {
+    fn get_raw_element(&self) -> dioxus::html::MountedResult<&dyn Any> {
+        Ok(&self.0)
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    let input = use_node_ref::<MountedData>(cx);
+
+    let name = input.get().map(|node| {
+        *node
+            .get_raw_element()
+            .unwrap()
+            .downcast_ref::<&'static str>()
+            .unwrap()
+    });
+    SEEN.with(|s| s.borrow_mut().push(name));
+
+    render! {
+        fancy_input { node_ref: input.clone() }
+    }
+}
+
+#[inline_props]
+fn fancy_input(cx: Scope, node_ref: NodeRef<MountedData>) -> Element {
+    render! {
+        div { class: "fancy",
+            input { onmounted: node_ref.attach() }
+        }
+    }
+}
+
+#[test]
+fn parent_gets_node_through_wrapper() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+
+    let mounted: Vec<ElementId> = edits
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            NewEventListener {
+                name: "mounted",
+                id,
+                ..
+            } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(mounted.len(), 1);
+    drop(edits);
+
+    // The renderer mounts the inner input
+    dom.handle_event(
+        "mounted",
+        Rc::new(MountedData::new(Named("inner input"))),
+        mounted[0],
+        false,
+    );
+
+    // Filling in the ref re-renders the parent, which can now see the inner node
+    _ = dom.render_immediate();
+
+    assert_eq!(SEEN.with(|s| s.take()), [None, Some("inner input")]);
+}
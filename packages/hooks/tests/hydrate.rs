@@ -0,0 +1,44 @@
+//! Apps mounted with state from the server start with it instead of fetching it again
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static FETCHES: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let user = use_hydrated_query(cx, 7, |id: u32| async move {
+        FETCHES.with(|f| f.set(f.get() + 1));
+        format!("user {id}")
+    });
+    let count = use_hydrated_state(cx, "count", || 0);
+
+    match user.value() {
+        Some(user) => render! { p { "{user}" } p { "{count}" } },
+        None => render! { p { "Loading..." } p { "{count}" } },
+    }
+}
+
+#[test]
+fn hydrated_query_renders_without_fetching() {
+    let mut dom = VirtualDom::new_with_state(app, r#"{ "7": "user from the server", "count": 5 }"#);
+    _ = dom.rebuild();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<p>user from the server</p><p>5</p>"
+    );
+
+    _ = dom.render_immediate();
+    assert_eq!(FETCHES.with(|f| f.get()), 0);
+}
+
+#[test]
+fn missing_state_falls_back_to_fetching() {
+    let mut dom = VirtualDom::new_with_state(app, "{}");
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "<p>Loading...</p><p>0</p>");
+
+    _ = dom.render_immediate();
+    assert_eq!(FETCHES.with(|f| f.get()), 1);
+    assert_eq!(dioxus_ssr::render(&dom), "<p>user 7</p><p>0</p>");
+}
@@ -0,0 +1,53 @@
+//! Long lists mount a chunk per pass of the scheduler
+use dioxus::prelude::*;
+
+const ROWS: usize = 1000;
+
+fn incremental(cx: Scope) -> Element {
+    let mounted = use_incremental_list(cx, ROWS, 100);
+
+    render! {
+        ul {
+            for i in 0..mounted {
+                li { key: "{i}", "Row {i}" }
+            }
+        }
+    }
+}
+
+fn synchronous(cx: Scope) -> Element {
+    render! {
+        ul {
+            for i in 0..ROWS {
+                li { key: "{i}", "Row {i}" }
+            }
+        }
+    }
+}
+
+fn rows(dom: &VirtualDom) -> usize {
+    dioxus_ssr::render(dom).matches("<li").count()
+}
+
+#[test]
+fn large_list_mounts_in_chunks() {
+    let mut dom = VirtualDom::new(incremental);
+    _ = dom.rebuild();
+    assert_eq!(rows(&dom), 100);
+
+    let mut cycles = 0;
+    while rows(&dom) < ROWS {
+        _ = dom.render_immediate();
+        cycles += 1;
+        assert_eq!(rows(&dom), (100 * (cycles + 1)).min(ROWS));
+    }
+    assert_eq!(cycles, 9);
+
+    // Nothing else is scheduled once the list is complete
+    _ = dom.render_immediate();
+    assert_eq!(rows(&dom), ROWS);
+
+    let mut expected = VirtualDom::new(synchronous);
+    _ = expected.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), dioxus_ssr::render(&expected));
+}
@@ -0,0 +1,98 @@
+#![allow(non_snake_case)]
+
+//! Navigation keeps every component using the location up to date
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Default)]
+struct MockHistory {
+    calls: RefCell<Vec<String>>,
+}
+
+impl NavigationBackend for MockHistory {
+    fn push(&self, path: &str) {
+        self.calls.borrow_mut().push(format!("push {path}"));
+    }
+
+    fn replace(&self, path: &str) {
+        self.calls.borrow_mut().push(format!("replace {path}"));
+    }
+
+    fn back(&self) {
+        self.calls.borrow_mut().push("back".to_string());
+    }
+
+    fn listen(&self, _navigation: &Navigation) {
+        self.calls.borrow_mut().push("listen".to_string());
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        title_bar {}
+        Page {}
+    }
+}
+
+fn title_bar(cx: Scope) -> Element {
+    let navigation = use_navigation(cx);
+    let location = navigation.location();
+
+    render! { nav { "{location}" } }
+}
+
+fn Page(cx: Scope) -> Element {
+    let navigation = use_navigation(cx);
+    let location = navigation.location();
+
+    render! { main { "{location}" } }
+}
+
+#[test]
+fn push_rerenders_subscribers() {
+    let history = Rc::new(MockHistory::default());
+    let mut dom = VirtualDom::new(app);
+    let navigation = dom
+        .base_scope()
+        .provide_context(Navigation::new("/", Some(history.clone())));
+
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "<nav>/</nav><main>/</main>");
+
+    navigation.push("/about");
+    assert_eq!(navigation.location(), "/about");
+    _ = dom.render_immediate();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<nav>/about</nav><main>/about</main>"
+    );
+
+    // Going back waits for the browser to report where it ended up
+    navigation.back();
+    assert_eq!(navigation.location(), "/about");
+    navigation.popped("/");
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "<nav>/</nav><main>/</main>");
+
+    // Two components use the navigation, but the history is only followed once
+    assert_eq!(*history.calls.borrow(), ["listen", "push /about", "back"]);
+}
+
+#[test]
+fn memory_navigation() {
+    let navigation = Navigation::default();
+
+    navigation.push("/a");
+    navigation.push("/b");
+    navigation.replace("/c");
+    assert_eq!(navigation.location(), "/c");
+
+    navigation.back();
+    assert_eq!(navigation.location(), "/a");
+    navigation.back();
+    assert_eq!(navigation.location(), "/");
+
+    // There is nothing before the first entry
+    navigation.back();
+    assert_eq!(navigation.location(), "/");
+}
@@ -0,0 +1,56 @@
+//! Restarting a suspended future drops the old one, so only the latest result is shown
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use std::cell::RefCell;
+
+thread_local! {
+    static SENDERS: RefCell<Vec<oneshot::Sender<&'static str>>> = RefCell::new(Vec::new());
+    static HANDLE: RefCell<Option<SuspenseHandle>> = RefCell::new(None);
+    static RENDERED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    let (content, handle) = use_suspense_with_handle(
+        cx,
+        SuspenseOptions::default(),
+        || {
+            let (tx, rx) = oneshot::channel();
+            SENDERS.with(|s| s.borrow_mut().push(tx));
+            async move { rx.await.unwrap_or("cancelled") }
+        },
+        |value| {
+            RENDERED.with(|r| r.borrow_mut().push(value));
+            render! { p { "{value}" } }
+        },
+    );
+    HANDLE.with(|h| *h.borrow_mut() = Some(handle.clone()));
+    content
+}
+
+#[test]
+fn restarting_mid_flight_only_commits_the_latest_result() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    _ = dom.render_immediate();
+
+    // Restart while the first future is still waiting
+    HANDLE.with(|h| h.borrow().as_ref().unwrap().restart());
+    _ = dom.render_immediate();
+    assert_eq!(SENDERS.with(|s| s.borrow().len()), 2);
+
+    // The first future was dropped, so its value goes nowhere
+    let (stale, fresh) = SENDERS.with(|s| {
+        let mut senders = s.borrow_mut();
+        let fresh = senders.pop().unwrap();
+        (senders.pop().unwrap(), fresh)
+    });
+    assert!(stale.send("stale").is_err());
+    _ = dom.render_immediate();
+    assert!(RENDERED.with(|r| r.borrow().is_empty()));
+
+    fresh.send("fresh").unwrap();
+    for _ in 0..2 {
+        _ = dom.render_immediate();
+    }
+    assert_eq!(RENDERED.with(|r| r.borrow().clone()), ["fresh"]);
+}
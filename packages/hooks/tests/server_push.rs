@@ -0,0 +1,107 @@
+//! Subtrees pushed from the server merge into the live tree without touching local state
+use dioxus::prelude::*;
+use dioxus_core::Mutation;
+use std::cell::RefCell;
+
+thread_local! {
+    static COUNT: RefCell<Option<UseState<i32>>> = RefCell::new(None);
+}
+
+fn app(cx: Scope) -> Element {
+    let count = use_state(cx, || 0);
+    COUNT.with(|c| *c.borrow_mut() = Some(count.clone()));
+
+    render! {
+        p { "count: {count}" }
+        stats_panel {}
+    }
+}
+
+fn stats_panel(cx: Scope) -> Element {
+    let stats = use_server_push(cx, "stats");
+
+    render! { div { stats } }
+}
+
+fn stats(version: u64, visitors: &str) -> PushedUpdate {
+    PushedUpdate {
+        target: "stats".to_string(),
+        version,
+        nodes: vec![PushedNode::Element {
+            tag: "span".to_string(),
+            namespace: None,
+            attrs: vec![("class".to_string(), "visitors".to_string())],
+            children: vec![PushedNode::Text(visitors.to_string())],
+        }],
+    }
+}
+
+fn set_count(value: i32) {
+    COUNT.with(|c| c.borrow().as_ref().unwrap().set(value));
+}
+
+#[test]
+fn pushed_subtree_merges_with_local_state() {
+    let push = ServerPush::default();
+    let mut dom = VirtualDom::new(app).with_root_context(push.clone());
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "<p>count: 0</p><div></div>");
+
+    // Change some local state before anything is pushed
+    set_count(5);
+    _ = dom.render_immediate();
+
+    push.push(stats(1, "42")).unwrap();
+    _ = dom.render_immediate();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<p>count: 5</p><div><span class=\"visitors\">42</span></div>"
+    );
+
+    // The structure didn't change, so the new contents only change the text
+    push.push(stats(2, "43")).unwrap();
+    let edits = dom.render_immediate();
+    assert!(edits.templates.is_empty());
+    assert!(matches!(
+        edits.edits.as_slice(),
+        [Mutation::SetText { value: "43", .. }]
+    ));
+    drop(edits);
+
+    // Updates that arrive late are dropped
+    assert_eq!(
+        push.push(stats(1, "42")),
+        Err(PushError::Outdated { current: 2 })
+    );
+    assert_eq!(push.version("stats"), Some(2));
+
+    // Local renders keep the pushed contents
+    set_count(6);
+    _ = dom.render_immediate();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<p>count: 6</p><div><span class=\"visitors\">43</span></div>"
+    );
+}
+
+#[test]
+fn contents_that_dont_fit_a_template_are_rejected() {
+    let push = ServerPush::default();
+    let mut dom = VirtualDom::new(app).with_root_context(push.clone());
+    _ = dom.rebuild();
+
+    // Template paths index children with a byte, so the 257th root can't be rendered
+    let update = PushedUpdate {
+        target: "stats".to_string(),
+        version: 1,
+        nodes: vec![PushedNode::Text("visitors".to_string()); 257],
+    };
+    assert_eq!(
+        push.push(update),
+        Err(PushError::TooManyChildren { count: 257 })
+    );
+    assert_eq!(push.version("stats"), None);
+
+    let edits = dom.render_immediate();
+    assert!(edits.edits.is_empty());
+}
@@ -0,0 +1,96 @@
+#![allow(non_snake_case)]
+
+//! A suspense boundary can cancel the futures of the components below it and restart them
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use std::cell::RefCell;
+
+thread_local! {
+    static SENDERS: RefCell<Vec<oneshot::Sender<&'static str>>> = RefCell::new(Vec::new());
+    static BOUNDARY: RefCell<Option<SuspenseContext>> = RefCell::new(None);
+    static RENDERED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        SuspenseBoundary {
+            Leaf {}
+            Leaf {}
+            Controls {}
+        }
+    }
+}
+
+fn Leaf(cx: Scope) -> Element {
+    use_suspense(
+        cx,
+        SuspenseOptions::default(),
+        || {
+            let (tx, rx) = oneshot::channel();
+            SENDERS.with(|s| s.borrow_mut().push(tx));
+            async move { rx.await.unwrap_or("cancelled") }
+        },
+        |value| {
+            RENDERED.with(|r| r.borrow_mut().push(value));
+            render! { p { "{value}" } }
+        },
+    )
+}
+
+fn Controls(cx: Scope) -> Element {
+    let boundary = use_suspense_boundary(cx).cloned();
+    BOUNDARY.with(|b| *b.borrow_mut() = boundary);
+    None
+}
+
+fn boundary() -> SuspenseContext {
+    BOUNDARY.with(|b| b.borrow().clone().unwrap())
+}
+
+fn states() -> Vec<SuspenseState> {
+    boundary()
+        .leaves()
+        .into_iter()
+        .map(|(_, state)| state)
+        .collect()
+}
+
+#[test]
+fn cancel_and_restart_the_leaves() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(states(), [SuspenseState::Pending, SuspenseState::Pending]);
+    assert!(boundary().is_pending());
+    assert_eq!(dom.pending_suspense_count(), 2);
+
+    // Cancelling drops the futures, and the renderer no longer waits on them
+    boundary().cancel_all();
+    _ = dom.render_immediate();
+    assert_eq!(
+        states(),
+        [SuspenseState::Cancelled, SuspenseState::Cancelled]
+    );
+    assert!(!boundary().is_pending());
+    assert_eq!(dom.pending_suspense_count(), 0);
+    let cancelled = SENDERS.with(|s| s.take());
+    assert!(cancelled.into_iter().all(|tx| tx.send("late").is_err()));
+
+    // Restarting starts new futures that render once they resolve
+    boundary().restart_all();
+    _ = dom.render_immediate();
+    assert_eq!(states(), [SuspenseState::Pending, SuspenseState::Pending]);
+    for tx in SENDERS.with(|s| s.take()) {
+        tx.send("loaded").unwrap();
+    }
+    _ = dom.render_immediate();
+    _ = dom.render_immediate();
+
+    assert_eq!(states(), [SuspenseState::Resolved, SuspenseState::Resolved]);
+    assert_eq!(RENDERED.with(|r| r.take()), ["loaded", "loaded"]);
+
+    // Resolved leaves can't be cancelled
+    boundary().cancel_all();
+    _ = dom.render_immediate();
+    assert_eq!(states(), [SuspenseState::Resolved, SuspenseState::Resolved]);
+}
@@ -0,0 +1,76 @@
+//! A component that loads its data with use_suspense_with_deps starts over when its props change
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static ID: Cell<u32> = Cell::new(1);
+    static SENDERS: RefCell<Vec<Option<oneshot::Sender<&'static str>>>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    let id = ID.with(Cell::get);
+    render! { user_card { id: id } }
+}
+
+#[inline_props]
+fn user_card(cx: Scope, id: u32) -> Element {
+    use_suspense_with_deps(
+        cx,
+        (id,),
+        |(id,)| {
+            let (tx, rx) = oneshot::channel();
+            SENDERS.with(|s| s.borrow_mut().push(Some(tx)));
+            async move { format!("user {id}: {}", rx.await.unwrap()) }
+        },
+        |name| render! { h2 { "{name}" } },
+    )
+}
+
+fn rerender(dom: &mut VirtualDom) {
+    for _ in 0..2 {
+        _ = dom.render_immediate();
+    }
+}
+
+/// Resolve one of the fetches, failing if it was dropped
+fn send(idx: usize, name: &'static str) -> Result<(), &'static str> {
+    SENDERS.with(|s| s.borrow_mut()[idx].take().unwrap().send(name))
+}
+
+fn fetches() -> usize {
+    SENDERS.with(|s| s.borrow().len())
+}
+
+#[test]
+fn fetches_then_renders_and_restarts_on_new_props() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    rerender(&mut dom);
+    assert_eq!(fetches(), 1);
+    assert_eq!(dom.pending_suspense_count(), 1);
+
+    send(0, "ada").unwrap();
+    rerender(&mut dom);
+    assert_eq!(dom.pending_suspense_count(), 0);
+    assert_eq!(dioxus_ssr::render(&dom), "<h2>user 1: ada</h2>");
+
+    // Rendering again with the same props keeps the value
+    dom.mark_dirty(ScopeId(0));
+    rerender(&mut dom);
+    assert_eq!(fetches(), 1);
+
+    // New props suspend the component and start a new fetch, and props that change again drop that one
+    for id in [2, 3] {
+        ID.with(|i| i.set(id));
+        dom.mark_dirty(ScopeId(0));
+        rerender(&mut dom);
+    }
+    assert_eq!(fetches(), 3);
+    assert_eq!(dom.pending_suspense_count(), 1);
+    assert!(send(1, "stale").is_err());
+
+    send(2, "grace").unwrap();
+    rerender(&mut dom);
+    assert_eq!(dioxus_ssr::render(&dom), "<h2>user 3: grace</h2>");
+}
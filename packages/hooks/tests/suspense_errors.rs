@@ -0,0 +1,123 @@
+#![allow(non_snake_case)]
+
+//! Error boundaries catch suspense futures that panic or resolve to an error, and can retry them
+use dioxus::prelude::*;
+use dioxus_core::{ErrorBoundary, TaskPanic};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    static FAIL: Cell<bool> = Cell::new(true);
+    static CAUGHT: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    static RENDERED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    static BOUNDARY: RefCell<Option<Rc<ErrorBoundary>>> = RefCell::new(None);
+}
+
+fn boundary<'a>(cx: &'a ScopeState, children: Element<'a>) -> Element<'a> {
+    let boundary = use_error_boundary(cx);
+    BOUNDARY.with(|b| *b.borrow_mut() = Some(boundary.clone()));
+
+    if let Some(error) = boundary.error() {
+        let message = match (error.downcast::<TaskPanic>(), error.downcast::<String>()) {
+            (Some(panic), _) => panic.message.clone(),
+            (_, Some(error)) => error.clone(),
+            _ => format!("{:?}", error.error),
+        };
+        CAUGHT.with(|c| c.borrow_mut().push(message));
+        return render! { "Something went wrong" };
+    }
+
+    children
+}
+
+fn panicking_app(cx: Scope) -> Element {
+    boundary(cx, render! { Panicking {} })
+}
+
+fn Panicking(cx: Scope) -> Element {
+    use_suspense(
+        cx,
+        SuspenseOptions::default(),
+        || async {
+            if FAIL.with(Cell::get) {
+                panic!("the future panicked");
+            }
+            "loaded"
+        },
+        |value| {
+            RENDERED.with(|r| r.borrow_mut().push(value));
+            render! { p { "{value}" } }
+        },
+    )
+}
+
+fn erroring_app(cx: Scope) -> Element {
+    boundary(cx, render! { Erroring {} })
+}
+
+fn Erroring(cx: Scope) -> Element {
+    use_suspense(
+        cx,
+        SuspenseOptions::default(),
+        || async {
+            match FAIL.with(Cell::get) {
+                true => Err("not found".to_string()),
+                false => Ok("loaded"),
+            }
+        },
+        |result| {
+            let value = *result.throw(cx)?;
+            RENDERED.with(|r| r.borrow_mut().push(value));
+            render! { p { "{value}" } }
+        },
+    )
+}
+
+fn reset() {
+    FAIL.with(|f| f.set(true));
+    CAUGHT.with(|c| c.borrow_mut().clear());
+    RENDERED.with(|r| r.borrow_mut().clear());
+}
+
+/// Render until the futures, the errors they throw, and the boundary have all settled
+fn settle(dom: &mut VirtualDom) {
+    for _ in 0..3 {
+        _ = dom.render_immediate();
+    }
+}
+
+fn retry(dom: &mut VirtualDom) {
+    FAIL.with(|f| f.set(false));
+    BOUNDARY.with(|b| b.borrow().as_ref().unwrap().reset());
+    settle(dom);
+}
+
+#[test]
+fn panicking_future_is_caught_and_retried() {
+    reset();
+    let mut dom = VirtualDom::new(panicking_app);
+    _ = dom.rebuild();
+    settle(&mut dom);
+
+    assert_eq!(CAUGHT.with(|c| c.borrow().clone()), ["the future panicked"]);
+    assert!(RENDERED.with(|r| r.borrow().is_empty()));
+
+    retry(&mut dom);
+    assert_eq!(RENDERED.with(|r| r.borrow().clone()), ["loaded"]);
+}
+
+#[test]
+fn erroring_future_is_caught_and_retried() {
+    reset();
+    let mut dom = VirtualDom::new(erroring_app);
+    _ = dom.rebuild();
+    settle(&mut dom);
+
+    assert_eq!(CAUGHT.with(|c| c.borrow().clone()), ["not found"]);
+    assert!(RENDERED.with(|r| r.borrow().is_empty()));
+
+    retry(&mut dom);
+    assert_eq!(RENDERED.with(|r| r.borrow().clone()), ["loaded"]);
+}
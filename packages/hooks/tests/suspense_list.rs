@@ -0,0 +1,98 @@
+#![allow(non_snake_case)]
+
+//! Suspense boundaries in a list reveal in the order of the list, not the order they resolve in
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static ORDER: Cell<RevealOrder> = Cell::new(RevealOrder::Forwards);
+    static SENDERS: RefCell<Vec<Option<oneshot::Sender<&'static str>>>> = RefCell::new(Vec::new());
+    static RECEIVERS: RefCell<Vec<Option<oneshot::Receiver<&'static str>>>> = RefCell::new(Vec::new());
+    static REVEALED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        SuspenseList { reveal_order: ORDER.with(Cell::get),
+            Boundary { idx: 0 }
+            Boundary { idx: 1 }
+            Boundary { idx: 2 }
+        }
+    }
+}
+
+#[inline_props]
+fn Boundary(cx: Scope, idx: usize) -> Element {
+    use_suspense(
+        cx,
+        SuspenseOptions::default(),
+        || {
+            let rx = RECEIVERS.with(|r| r.borrow_mut()[*idx].take().unwrap());
+            async move { rx.await.unwrap() }
+        },
+        |value| {
+            REVEALED.with(|r| {
+                let mut revealed = r.borrow_mut();
+                if !revealed.contains(value) {
+                    revealed.push(value);
+                }
+            });
+            render! { p { "{value}" } }
+        },
+    )
+}
+
+fn mount(order: RevealOrder) -> VirtualDom {
+    ORDER.with(|o| o.set(order));
+    let (senders, receivers): (Vec<_>, Vec<_>) = (0..3)
+        .map(|_| oneshot::channel())
+        .map(|(tx, rx)| (Some(tx), Some(rx)))
+        .unzip();
+    SENDERS.with(|s| *s.borrow_mut() = senders);
+    RECEIVERS.with(|r| *r.borrow_mut() = receivers);
+    REVEALED.with(|r| r.borrow_mut().clear());
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    dom
+}
+
+/// Resolve a boundary and render until every boundary it lets through has revealed
+fn resolve(dom: &mut VirtualDom, idx: usize, value: &'static str) -> Vec<&'static str> {
+    SENDERS.with(|s| s.borrow_mut()[idx].take().unwrap().send(value).unwrap());
+    for _ in 0..3 {
+        _ = dom.render_immediate();
+    }
+    REVEALED.with(|r| r.borrow().clone())
+}
+
+#[test]
+fn forwards_reveals_top_to_bottom() {
+    let mut dom = mount(RevealOrder::Forwards);
+
+    assert!(resolve(&mut dom, 2, "third").is_empty());
+    assert_eq!(resolve(&mut dom, 0, "first"), ["first"]);
+    assert_eq!(resolve(&mut dom, 1, "second"), ["first", "second", "third"]);
+}
+
+#[test]
+fn backwards_reveals_bottom_to_top() {
+    let mut dom = mount(RevealOrder::Backwards);
+
+    assert!(resolve(&mut dom, 0, "first").is_empty());
+    assert_eq!(resolve(&mut dom, 2, "third"), ["third"]);
+    assert_eq!(resolve(&mut dom, 1, "second"), ["third", "second", "first"]);
+}
+
+#[test]
+fn together_reveals_all_at_once() {
+    let mut dom = mount(RevealOrder::Together);
+
+    assert!(resolve(&mut dom, 1, "second").is_empty());
+    assert!(resolve(&mut dom, 2, "third").is_empty());
+
+    let mut revealed = resolve(&mut dom, 0, "first");
+    revealed.sort_unstable();
+    assert_eq!(revealed, ["first", "second", "third"]);
+}
@@ -0,0 +1,80 @@
+//! Suspense shows a fallback when its future outlives the timeout
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use std::{cell::RefCell, future::Future, pin::Pin, time::Duration};
+
+thread_local! {
+    static DATA: RefCell<Option<oneshot::Sender<&'static str>>> = RefCell::new(None);
+    static TIMEOUT: RefCell<Option<oneshot::Sender<()>>> = RefCell::new(None);
+    static RENDERED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+/// A clock that only moves when the test fires the timeout
+struct TestTimer;
+
+impl TimerBackend for TestTimer {
+    fn sleep(&self, _: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        let (tx, rx) = oneshot::channel();
+        TIMEOUT.with(|t| *t.borrow_mut() = Some(tx));
+        Box::pin(async move {
+            _ = rx.await;
+        })
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    use_suspense_with_timeout(
+        cx,
+        Duration::from_millis(500),
+        || {
+            let (tx, rx) = oneshot::channel();
+            DATA.with(|d| *d.borrow_mut() = Some(tx));
+            async move { rx.await.unwrap() }
+        },
+        || {
+            RENDERED.with(|r| r.borrow_mut().push("fallback"));
+            render! { "Still loading..." }
+        },
+        |value| {
+            RENDERED.with(|r| r.borrow_mut().push(value));
+            render! { p { "{value}" } }
+        },
+    )
+}
+
+fn mount() -> VirtualDom {
+    RENDERED.with(|r| r.borrow_mut().clear());
+    let mut dom = VirtualDom::new(app).with_root_context(Timer::new(TestTimer));
+    _ = dom.rebuild();
+    _ = dom.render_immediate();
+    dom
+}
+
+#[test]
+fn fallback_shows_after_the_timeout() {
+    let mut dom = mount();
+    assert!(RENDERED.with(|r| r.borrow().is_empty()));
+
+    TIMEOUT.with(|t| t.borrow_mut().take().unwrap().send(()).unwrap());
+    _ = dom.render_immediate();
+    assert_eq!(RENDERED.with(|r| r.borrow().clone()), ["fallback"]);
+
+    DATA.with(|d| d.borrow_mut().take().unwrap().send("done").unwrap());
+    _ = dom.render_immediate();
+    assert_eq!(RENDERED.with(|r| r.borrow().clone()), ["fallback", "done"]);
+}
+
+#[test]
+fn resolving_first_cancels_the_timeout() {
+    let mut dom = mount();
+
+    DATA.with(|d| d.borrow_mut().take().unwrap().send("done").unwrap());
+    _ = dom.render_immediate();
+    assert_eq!(RENDERED.with(|r| r.borrow().clone()), ["done"]);
+
+    // The timeout task was dropped along with its receiver
+    let timeout = TIMEOUT.with(|t| t.borrow_mut().take().unwrap());
+    assert!(timeout.send(()).is_err());
+    _ = dom.render_immediate();
+    assert_eq!(RENDERED.with(|r| r.borrow().clone()), ["done"]);
+}
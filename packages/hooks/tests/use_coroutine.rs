@@ -0,0 +1,58 @@
+#![allow(non_snake_case)]
+
+//! use_coroutine runs one task for the life of the component, and components below it can send it messages
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static RECEIVED: RefCell<Vec<i32>> = RefCell::new(Vec::new());
+    static HANDLE: RefCell<Option<Coroutine<i32>>> = RefCell::new(None);
+    static CANCEL: Cell<bool> = Cell::new(false);
+}
+
+fn app(cx: Scope) -> Element {
+    let counter = use_coroutine(cx, |mut rx: UnboundedReceiver<i32>| async move {
+        while let Some(msg) = rx.next().await {
+            RECEIVED.with(|r| r.borrow_mut().push(msg));
+        }
+    });
+
+    if CANCEL.with(Cell::get) {
+        counter.cancel(cx);
+    }
+
+    render! { Child {} }
+}
+
+fn Child(cx: Scope) -> Element {
+    let handle = use_coroutine_handle::<i32>(cx).cloned();
+    HANDLE.with(|h| *h.borrow_mut() = handle);
+    None
+}
+
+fn handle() -> Coroutine<i32> {
+    HANDLE.with(|h| h.borrow().clone().unwrap())
+}
+
+#[test]
+fn children_send_messages_until_cancelled() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    handle().send(1);
+    handle().send(2);
+    _ = dom.render_immediate();
+    assert_eq!(RECEIVED.with(|r| r.take()), [1, 2]);
+    assert!(!handle().is_closed());
+
+    // Cancelling drops the task, and later messages go nowhere
+    CANCEL.with(|c| c.set(true));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert!(handle().is_closed());
+
+    handle().send(3);
+    _ = dom.render_immediate();
+    assert!(RECEIVED.with(|r| r.take()).is_empty());
+}
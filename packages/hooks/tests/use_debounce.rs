@@ -0,0 +1,139 @@
+//! Debounced and throttled callbacks only run some of the calls made in quick succession
+use dioxus::core::{ElementId, Mutation::NewEventListener};
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc, time::Duration};
+
+thread_local! {
+    static TIMEOUT: RefCell<Option<oneshot::Sender<()>>> = RefCell::new(None);
+    static RAN: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+/// A clock that only moves when the test fires the last timeout
+struct TestTimer;
+
+impl TimerBackend for TestTimer {
+    fn sleep(&self, _: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        let (tx, rx) = oneshot::channel();
+        TIMEOUT.with(|t| *t.borrow_mut() = Some(tx));
+        Box::pin(async move {
+            _ = rx.await;
+        })
+    }
+}
+
+fn ran(value: String) {
+    RAN.with(|r| r.borrow_mut().push(value));
+}
+
+fn take_ran() -> Vec<String> {
+    RAN.with(|r| r.take())
+}
+
+/// Fire the timeout the last sleep is waiting on, returning false if that sleep was cancelled
+fn fire_timeout() -> bool {
+    let timeout = TIMEOUT.with(|t| t.borrow_mut().take());
+    timeout.map_or(false, |timeout| timeout.send(()).is_ok())
+}
+
+fn mount(app: fn(Scope) -> Element) -> (VirtualDom, ElementId) {
+    RAN.with(|r| r.borrow_mut().clear());
+    let mut dom = VirtualDom::new(app).with_root_context(Timer::new(TestTimer));
+    let edits = dom.rebuild();
+    let input = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    drop(edits);
+    (dom, input)
+}
+
+fn type_into(dom: &mut VirtualDom, id: ElementId, value: &str) {
+    let data = FormData {
+        value: value.to_string(),
+        values: Default::default(),
+        files: None,
+    };
+    dom.handle_event("input", Rc::new(data), id, true);
+    dom.process_events();
+}
+
+#[test]
+fn debounce_runs_the_last_of_rapid_calls() {
+    fn app(cx: Scope) -> Element {
+        let search = use_debounce(cx, Duration::from_millis(300), ran);
+        render! {
+            input { oninput: move |evt| search.call(evt.value.clone()) }
+        }
+    }
+
+    let (mut dom, input) = mount(app);
+    for value in ["d", "di", "dio", "diox", "dioxus"] {
+        type_into(&mut dom, input, value);
+    }
+    assert!(take_ran().is_empty());
+
+    // Every call cancelled the one before it, so only the last timeout is still waited on
+    assert!(fire_timeout());
+    dom.process_events();
+    assert_eq!(take_ran(), ["dioxus"]);
+
+    assert!(!fire_timeout());
+    dom.process_events();
+    assert!(take_ran().is_empty());
+}
+
+#[test]
+fn throttle_runs_the_first_call_and_the_last_held_back_one() {
+    fn app(cx: Scope) -> Element {
+        let resize = use_throttle(cx, Duration::from_millis(100), ran);
+        render! {
+            input { oninput: move |evt| resize.call(evt.value.clone()) }
+        }
+    }
+
+    let (mut dom, input) = mount(app);
+    for value in ["1", "2", "3", "4", "5"] {
+        type_into(&mut dom, input, value);
+    }
+    assert_eq!(take_ran(), ["1"]);
+
+    assert!(fire_timeout());
+    dom.process_events();
+    assert_eq!(take_ran(), ["5"]);
+
+    // An interval without calls ends the throttle, so the next call runs right away
+    assert!(fire_timeout());
+    dom.process_events();
+    assert!(take_ran().is_empty());
+    type_into(&mut dom, input, "6");
+    assert_eq!(take_ran(), ["6"]);
+}
+
+#[test]
+#[should_panic(expected = "Timer")]
+fn calling_without_a_timer_panics() {
+    fn app(cx: Scope) -> Element {
+        let search = use_debounce(cx, Duration::from_millis(300), ran);
+        render! {
+            input { oninput: move |evt| search.call(evt.value.clone()) }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+    let input = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener { id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    drop(edits);
+    type_into(&mut dom, input, "dioxus");
+}
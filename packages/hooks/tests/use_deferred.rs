@@ -0,0 +1,47 @@
+//! Deferred values trail fast-changing values and catch up once they settle
+use dioxus::prelude::*;
+use std::cell::RefCell;
+
+thread_local! {
+    static QUERY: RefCell<&'static str> = RefCell::new("");
+    static RENDERED: RefCell<Vec<(&'static str, &'static str)>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    let query = QUERY.with(|q| *q.borrow());
+    let deferred = use_deferred(cx, query);
+    RENDERED.with(|r| r.borrow_mut().push((query, deferred)));
+
+    render! { "{query} {deferred}" }
+}
+
+fn type_query(dom: &mut VirtualDom, query: &'static str) {
+    QUERY.with(|q| *q.borrow_mut() = query);
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+}
+
+#[test]
+fn deferred_value_trails() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    type_query(&mut dom, "d");
+    type_query(&mut dom, "di");
+    type_query(&mut dom, "dio");
+
+    // Once typing stops, the deferred value catches up
+    _ = dom.render_immediate();
+    _ = dom.render_immediate();
+
+    assert_eq!(
+        RENDERED.with(|r| r.take()),
+        [
+            ("", ""),
+            ("d", ""),
+            ("di", "d"),
+            ("dio", "di"),
+            ("dio", "dio"),
+        ]
+    );
+}
@@ -0,0 +1,67 @@
+#![allow(non_snake_case)]
+
+//! Effects with a cleanup run after the render, and clean up before they run again and when they unmount
+use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    static SHOW: Cell<bool> = Cell::new(true);
+    static VALUE: Cell<usize> = Cell::new(0);
+}
+
+fn log(entry: String) {
+    LOG.with(|l| l.borrow_mut().push(entry));
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|l| l.take())
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW.with(Cell::get);
+    render! { show.then(|| rsx!(Child {})) }
+}
+
+fn Child(cx: Scope) -> Element {
+    let value = VALUE.with(Cell::get);
+    use_effect_with_cleanup(cx, (&value,), |(value,)| {
+        log(format!("effect {value}"));
+        move || log(format!("cleanup {value}"))
+    });
+    render! { "{value}" }
+}
+
+fn rerender(dom: &mut VirtualDom) {
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    _ = dom.render_immediate();
+}
+
+#[test]
+fn effects_clean_up_in_order() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Nothing runs while rendering, the effect is a task that runs once the mutations are out
+    assert!(take_log().is_empty());
+    _ = dom.render_immediate();
+    assert_eq!(take_log(), ["effect 0"]);
+
+    // Stable dependencies don't run the effect again
+    rerender(&mut dom);
+    rerender(&mut dom);
+    assert!(take_log().is_empty());
+
+    // New dependencies clean up the last run before running again
+    VALUE.with(|v| v.set(1));
+    dom.mark_dirty(ScopeId(1));
+    _ = dom.render_immediate();
+    _ = dom.render_immediate();
+    assert_eq!(take_log(), ["cleanup 0", "effect 1"]);
+
+    // Unmounting cleans up the last run
+    SHOW.with(|s| s.set(false));
+    rerender(&mut dom);
+    assert_eq!(take_log(), ["cleanup 1"]);
+}
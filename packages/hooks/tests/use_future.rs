@@ -0,0 +1,68 @@
+//! use_future runs in the background without suspending, and starts over when its dependencies change
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static USER: Cell<usize> = Cell::new(0);
+    static SENDERS: RefCell<Vec<Option<oneshot::Sender<&'static str>>>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    let user = USER.with(Cell::get);
+    let name = use_future(cx, (&user,), |(_,)| {
+        let (tx, rx) = oneshot::channel();
+        SENDERS.with(|s| s.borrow_mut().push(Some(tx)));
+        async move { rx.await.unwrap() }
+    });
+
+    let state = match name.state() {
+        UseFutureState::Pending => "loading".to_string(),
+        UseFutureState::Complete(name) => format!("hello {name}"),
+        UseFutureState::Reloading(name) => format!("hello {name}, reloading"),
+    };
+
+    render! { p { "{state}" } }
+}
+
+fn rerender(dom: &mut VirtualDom) {
+    for _ in 0..2 {
+        _ = dom.render_immediate();
+    }
+}
+
+/// Resolve one of the futures, failing if it was dropped
+fn send(idx: usize, name: &'static str) -> Result<(), &'static str> {
+    SENDERS.with(|s| s.borrow_mut()[idx].take().unwrap().send(name))
+}
+
+#[test]
+fn dependency_changes_cancel_and_restart() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    rerender(&mut dom);
+
+    // The component renders its own loading state instead of suspending
+    assert_eq!(dioxus_ssr::render(&dom), "<p>loading</p>");
+
+    // New dependencies drop the future that was still running and start another one
+    USER.with(|u| u.set(1));
+    dom.mark_dirty(ScopeId(0));
+    rerender(&mut dom);
+    assert_eq!(SENDERS.with(|s| s.borrow().len()), 2);
+    assert!(send(0, "stale").is_err());
+
+    send(1, "ferris").unwrap();
+    rerender(&mut dom);
+    assert_eq!(dioxus_ssr::render(&dom), "<p>hello ferris</p>");
+
+    // The last value stays readable while the next future runs
+    USER.with(|u| u.set(2));
+    dom.mark_dirty(ScopeId(0));
+    rerender(&mut dom);
+    assert_eq!(dioxus_ssr::render(&dom), "<p>hello ferris, reloading</p>");
+
+    send(2, "crab").unwrap();
+    rerender(&mut dom);
+    assert_eq!(dioxus_ssr::render(&dom), "<p>hello crab</p>");
+}
@@ -0,0 +1,70 @@
+//! Layout effects run once the renderer has applied the mutations, before regular effects
+use dioxus::prelude::*;
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn log(entry: &'static str) {
+    LOG.with(|l| l.borrow_mut().push(entry));
+}
+
+fn app(cx: Scope) -> Element {
+    use_layout_effect(cx, (), |_| log("layout"));
+    use_effect(cx, (), |_| async { log("effect") });
+
+    render! { div { "hello" } }
+}
+
+#[test]
+fn layout_effect_runs_after_mutations_are_applied() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+    assert!(!edits.edits.is_empty());
+    drop(edits);
+
+    // Nothing runs while rendering
+    assert!(LOG.with(|l| l.borrow().is_empty()));
+
+    // The renderer applies the mutations, then runs the layout effects
+    log("applied");
+    dom.run_layout_effects();
+    assert_eq!(LOG.with(|l| l.borrow().clone()), ["applied", "layout"]);
+
+    // Regular effects run later, as a task
+    _ = dom.render_immediate();
+    assert_eq!(LOG.with(|l| l.take()), ["applied", "layout", "effect"]);
+}
+
+#[test]
+fn layout_effect_reruns_when_dependencies_change() {
+    thread_local! {
+        static COUNT: RefCell<usize> = RefCell::new(0);
+    }
+
+    fn counter(cx: Scope) -> Element {
+        let count = COUNT.with(|c| *c.borrow());
+        use_layout_effect(cx, (&count,), |(count,)| {
+            log(if count == 0 { "zero" } else { "one" })
+        });
+        render! { "{count}" }
+    }
+
+    let mut dom = VirtualDom::new(counter);
+    _ = dom.rebuild();
+    dom.run_layout_effects();
+    assert_eq!(LOG.with(|l| l.take()), ["zero"]);
+
+    // Same dependencies, so nothing is queued
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    dom.run_layout_effects();
+    assert!(LOG.with(|l| l.borrow().is_empty()));
+
+    COUNT.with(|c| *c.borrow_mut() = 1);
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    dom.run_layout_effects();
+    assert_eq!(LOG.with(|l| l.take()), ["one"]);
+}
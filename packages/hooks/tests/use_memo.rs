@@ -0,0 +1,37 @@
+//! Memoized values are only computed again when their dependencies change
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static INPUT: Cell<usize> = Cell::new(1);
+    static RUNS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let input = INPUT.with(Cell::get);
+    let doubled = use_memo(cx, (&input,), |(input,)| {
+        RUNS.with(|r| r.set(r.get() + 1));
+        input * 2
+    });
+
+    render! { "{doubled}" }
+}
+
+#[test]
+fn memo_runs_once_for_the_same_dependencies() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    for _ in 0..2 {
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+    }
+    assert_eq!(RUNS.with(Cell::get), 1);
+    assert_eq!(dioxus_ssr::render(&dom), "2");
+
+    INPUT.with(|i| i.set(5));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(RUNS.with(Cell::get), 2);
+    assert_eq!(dioxus_ssr::render(&dom), "10");
+}
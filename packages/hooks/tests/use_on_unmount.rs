@@ -0,0 +1,58 @@
+#![allow(non_snake_case)]
+
+//! Unmount callbacks run once when their component is removed
+use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static SHOW: Cell<bool> = Cell::new(true);
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn log(event: &'static str) {
+    LOG.with(|l| l.borrow_mut().push(event));
+}
+
+fn take_log() -> Vec<&'static str> {
+    LOG.with(|l| l.take())
+}
+
+fn app(cx: Scope) -> Element {
+    let show = SHOW.with(Cell::get);
+    render! {
+        if show {
+            rsx! { Child {} }
+        }
+    }
+}
+
+fn Child(cx: Scope) -> Element {
+    use_on_unmount(cx, || log("child: first"));
+    use_on_unmount(cx, || log("child: second"));
+    render! { Grandchild {} }
+}
+
+fn Grandchild(cx: Scope) -> Element {
+    use_on_unmount(cx, || log("grandchild"));
+    render! { "hello" }
+}
+
+#[test]
+fn callbacks_run_once_in_reverse_order() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Rendering again doesn't register the callbacks again, or run them
+    dom.mark_dirty(ScopeId(0));
+    dom.mark_dirty(ScopeId(1));
+    _ = dom.render_immediate();
+    assert!(take_log().is_empty());
+
+    SHOW.with(|s| s.set(false));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(take_log(), ["grandchild", "child: second", "child: first"]);
+
+    drop(dom);
+    assert!(take_log().is_empty());
+}
@@ -0,0 +1,113 @@
+//! Components that query the same key share one fetch
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use futures_channel::oneshot;
+use futures_util::FutureExt;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static FETCHES: Cell<usize> = Cell::new(0);
+    static RENDERED: RefCell<Vec<(&'static str, Option<String>)>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        user_name { name: "first" }
+        user_name { name: "second" }
+    }
+}
+
+#[inline_props]
+fn user_name(cx: Scope, name: &'static str) -> Element {
+    let user = use_query(cx, 7, |id: u32| async move {
+        FETCHES.with(|f| f.set(f.get() + 1));
+        format!("user {id}")
+    });
+
+    let value = user.value();
+    RENDERED.with(|r| r.borrow_mut().push((*name, value.clone())));
+
+    match value {
+        Some(value) => render! { "{value}" },
+        None => render! { "Loading..." },
+    }
+}
+
+#[test]
+fn same_key_fetches_once() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        RENDERED.with(|r| r.take()),
+        [("first", None), ("second", None)]
+    );
+
+    // Run the fetch and re-render everyone waiting on it
+    _ = dom.render_immediate();
+
+    assert_eq!(FETCHES.with(|f| f.get()), 1);
+    let mut rendered = RENDERED.with(|r| r.take());
+    rendered.sort();
+    assert_eq!(
+        rendered,
+        [
+            ("first", Some("user 7".to_string())),
+            ("second", Some("user 7".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn fetch_restarts_when_its_component_unmounts() {
+    thread_local! {
+        static SHOW_FIRST: Cell<bool> = Cell::new(true);
+        static STARTED: Cell<usize> = Cell::new(0);
+        static RESPONSE: RefCell<Option<oneshot::Receiver<String>>> = RefCell::new(None);
+    }
+
+    fn app(cx: Scope) -> Element {
+        render! {
+            if SHOW_FIRST.with(|s| s.get()) {
+                rsx! { Reader {} }
+            }
+            Reader {}
+        }
+    }
+
+    fn Reader(cx: Scope) -> Element {
+        let user = use_query(cx, 1, |_: u32| {
+            STARTED.with(|s| s.set(s.get() + 1));
+            let response = RESPONSE.with(|r| r.borrow_mut().take()).unwrap();
+            async move { response.await.unwrap() }
+        });
+
+        match user.value() {
+            Some(value) => render! { "{value}" },
+            None => render! { "Loading..." },
+        }
+    }
+
+    let (first_tx, first_rx) = oneshot::channel();
+    RESPONSE.with(|r| *r.borrow_mut() = Some(first_rx));
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(STARTED.with(|s| s.get()), 1);
+
+    // Unmounting the first reader cancels its fetch, and the second reader starts it again
+    let (second_tx, second_rx) = oneshot::channel();
+    RESPONSE.with(|r| *r.borrow_mut() = Some(second_rx));
+    SHOW_FIRST.with(|s| s.set(false));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    _ = dom.render_immediate();
+    assert_eq!(STARTED.with(|s| s.get()), 2);
+    assert!(first_tx.send("stale".to_string()).is_err());
+
+    second_tx.send("user 1".to_string()).unwrap();
+    dom.wait_for_work().now_or_never();
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "user 1");
+}
@@ -0,0 +1,61 @@
+//! Actions dispatched in one frame are reduced in order before the next render
+use dioxus::core::ElementId;
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+enum Action {
+    Add(&'static str),
+    Remove(usize),
+    Clear,
+}
+
+fn app(cx: Scope) -> Element {
+    RENDERS.with(|r| r.set(r.get() + 1));
+    let (todos, dispatch) = use_reducer(cx, Vec::new, |todos, action| {
+        let mut next = todos.clone();
+        match action {
+            Action::Add(todo) => next.push(todo),
+            Action::Remove(idx) => {
+                next.remove(idx);
+            }
+            Action::Clear => next.clear(),
+        }
+        next
+    });
+    let summary = todos.join(", ");
+
+    render! {
+        button {
+            onclick: move |_| {
+                dispatch(Action::Add("wash"));
+                dispatch(Action::Add("dry"));
+                dispatch(Action::Add("fold"));
+                dispatch(Action::Remove(0));
+            },
+            "{summary}"
+        }
+        button { onclick: move |_| dispatch(Action::Clear), "clear" }
+    }
+}
+
+#[test]
+fn batched_dispatches_reduce_in_order() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(RENDERS.with(Cell::get), 1);
+
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    _ = dom.render_immediate();
+
+    // Every action was applied, with a single render for all of them
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<button>dry, fold</button><button>clear</button>"
+    );
+    assert_eq!(RENDERS.with(Cell::get), 2);
+}
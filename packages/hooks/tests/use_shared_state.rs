@@ -0,0 +1,58 @@
+#![allow(non_snake_case)]
+
+//! Consumers of shared state only render again when the value they see changes
+use dioxus::prelude::*;
+use std::cell::RefCell;
+
+thread_local! {
+    static RENDERED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    static THEME: RefCell<Option<UseSharedState<&'static str>>> = RefCell::new(None);
+}
+
+fn rendered(name: &'static str) {
+    RENDERED.with(|r| r.borrow_mut().push(name));
+}
+
+fn app(cx: Scope) -> Element {
+    use_shared_state_provider(cx, || "light");
+    rendered("app");
+    render! {
+        Consumer {}
+        Bystander {}
+    }
+}
+
+fn Consumer(cx: Scope) -> Element {
+    let theme = use_shared_state::<&'static str>(cx).unwrap();
+    THEME.with(|t| *t.borrow_mut() = Some(theme.clone()));
+    rendered("consumer");
+    let theme = *theme.read();
+    render! { "{theme}" }
+}
+
+fn Bystander(cx: Scope) -> Element {
+    rendered("bystander");
+    None
+}
+
+fn theme() -> UseSharedState<&'static str> {
+    THEME.with(|t| t.borrow().clone().unwrap())
+}
+
+#[test]
+fn set_only_renders_consumers_on_change() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    RENDERED.with(|r| r.take());
+
+    // Setting the value the consumers already see doesn't render anything
+    theme().set("light");
+    _ = dom.render_immediate();
+    assert!(RENDERED.with(|r| r.take()).is_empty());
+
+    // A new value only renders the consumers
+    theme().set("dark");
+    _ = dom.render_immediate();
+    assert_eq!(RENDERED.with(|r| r.take()), ["consumer"]);
+    assert_eq!(dioxus_ssr::render(&dom), "dark");
+}
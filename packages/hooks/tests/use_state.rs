@@ -0,0 +1,59 @@
+//! Updates based on the previous state see the updates queued before them in the same frame
+use dioxus::core::ElementId;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+fn click(dom: &mut VirtualDom) {
+    dom.handle_event("click", Rc::new(MouseData::default()), ElementId(1), true);
+    _ = dom.render_immediate();
+}
+
+#[test]
+fn queued_increments_add_up() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 0);
+
+        render! {
+            button {
+                onclick: move |_| {
+                    count.modify(|c| c + 1);
+                    count.modify(|c| c + 1);
+                    count.modify(|c| c + 1);
+                },
+                "{count}"
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    click(&mut dom);
+    assert_eq!(dioxus_ssr::render(&dom), "<button>3</button>");
+
+    click(&mut dom);
+    assert_eq!(dioxus_ssr::render(&dom), "<button>6</button>");
+}
+
+#[test]
+fn modify_sees_a_value_set_in_the_same_frame() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 1);
+
+        render! {
+            button {
+                onclick: move |_| {
+                    count.set(10);
+                    count.modify(|c| c * 2);
+                },
+                "{count}"
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    click(&mut dom);
+    assert_eq!(dioxus_ssr::render(&dom), "<button>20</button>");
+}
@@ -0,0 +1,111 @@
+#![allow(non_snake_case)]
+
+//! Suspense skeletons have the shape of the content they stand in for
+use dioxus::prelude::*;
+use dioxus_hooks::SKELETON_STYLE;
+use std::cell::Cell;
+
+thread_local! {
+    static SHOW: Cell<bool> = Cell::new(true);
+}
+
+fn app(cx: Scope) -> Element {
+    if SHOW.with(Cell::get) {
+        render! { Profile {} }
+    } else {
+        render! { "hidden" }
+    }
+}
+
+fn Profile(cx: Scope) -> Element {
+    use_suspense(
+        cx,
+        SuspenseOptions {
+            skeleton: true,
+            ..Default::default()
+        },
+        || async { "Ferris" },
+        |name| {
+            render! {
+                div { class: "card",
+                    h1 { "{name}" }
+                    p { "A crab" }
+                }
+            }
+        },
+    )
+}
+
+/// The tags of every element in a template node, nested like the node
+fn shape(node: &TemplateNode) -> String {
+    match node {
+        TemplateNode::Element { tag, children, .. } => {
+            let children: String = children.iter().map(shape).collect();
+            format!("<{tag}>{children}</{tag}>")
+        }
+        _ => String::new(),
+    }
+}
+
+fn find_template<'a>(templates: &[Template<'a>], tag: &str) -> Option<Template<'a>> {
+    templates.iter().copied().find(
+        |template| matches!(template.roots, [TemplateNode::Element { tag: t, .. }] if *t == tag),
+    )
+}
+
+#[test]
+fn skeleton_matches_resolved_content() {
+    let mut dom = VirtualDom::new(app);
+
+    // Nothing is known about the content yet, so the first load has no skeleton
+    let edits = dom.rebuild();
+    assert!(!edits
+        .templates
+        .iter()
+        .any(|template| template.name.contains(":skeleton:")));
+    drop(edits);
+
+    // Resolve the future and render the content
+    let edits = dom.render_immediate();
+    let resolved = find_template(&edits.templates, "div").expect("the content should render");
+    let resolved_shape: Vec<_> = resolved.roots.iter().map(shape).collect();
+    drop(edits);
+
+    // Unmount the profile and mount it again
+    SHOW.with(|s| s.set(false));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+
+    SHOW.with(|s| s.set(true));
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+    let skeleton = edits
+        .templates
+        .iter()
+        .copied()
+        .find(|template| template.name.contains(":skeleton:"))
+        .expect("the reload should render a skeleton");
+
+    assert_eq!(
+        skeleton.roots.iter().map(shape).collect::<Vec<_>>(),
+        resolved_shape,
+    );
+    assert_eq!(shape(&skeleton.roots[0]), "<div><h1></h1><p></p></div>");
+    assert!(skeleton.node_paths.is_empty());
+
+    match skeleton.roots[0] {
+        TemplateNode::Element { attrs, .. } => {
+            assert!(attrs.contains(&TemplateAttribute::Static {
+                name: "class",
+                value: "card",
+                namespace: None,
+            }));
+            assert!(attrs.contains(&TemplateAttribute::Static {
+                name: "style",
+                value: SKELETON_STYLE,
+                namespace: None,
+            }));
+        }
+        _ => panic!("the skeleton root should be an element"),
+    }
+}
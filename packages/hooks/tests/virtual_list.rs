@@ -0,0 +1,83 @@
+//! A virtual list only renders the items scrolled into view, and only renders again when other items come into view
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    RENDERS.with(|r| r.set(r.get() + 1));
+    let list = use_virtual_list(cx, 10_000, 20.0, 100.0);
+    let (top, bottom) = (list.offset_top(), list.offset_bottom());
+
+    render! {
+        div {
+            onscroll: move |evt| list.set_scroll_top(evt.scroll_top),
+            div { style: "height: {top}px;" }
+            for i in list.range() {
+                p { key: "{i}", "{i}" }
+            }
+            div { style: "height: {bottom}px;" }
+        }
+    }
+}
+
+fn scroll(dom: &mut VirtualDom, id: ElementId, scroll_top: f64) {
+    let data = ScrollData {
+        scroll_top,
+        scroll_left: 0.0,
+    };
+    dom.handle_event("scroll", Rc::new(data), id, false);
+    _ = dom.render_immediate();
+}
+
+fn items(dom: &VirtualDom) -> Vec<usize> {
+    let html = dioxus_ssr::render(dom);
+    html.split("<p>")
+        .skip(1)
+        .map(|item| item.split('<').next().unwrap().parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn only_visible_items_are_rendered() {
+    let mut dom = VirtualDom::new(app);
+    let edits = dom.rebuild();
+    let list = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            NewEventListener {
+                name: "scroll", id, ..
+            } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    drop(edits);
+
+    // The five visible items and the overscan below them
+    assert_eq!(items(&dom), (0..8).collect::<Vec<_>>());
+    assert!(dioxus_ssr::render(&dom).contains("height: 199840px;"));
+
+    scroll(&mut dom, list, 1000.0);
+    assert_eq!(RENDERS.with(Cell::get), 2);
+    assert_eq!(items(&dom), (47..58).collect::<Vec<_>>());
+    let html = dioxus_ssr::render(&dom);
+    assert!(html.contains("height: 940px;"));
+    assert!(html.contains("height: 198840px;"));
+
+    // Scrolling down 5px brings the top of item 55 into view
+    scroll(&mut dom, list, 1005.0);
+    assert_eq!(RENDERS.with(Cell::get), 3);
+    assert_eq!(items(&dom), (47..59).collect::<Vec<_>>());
+
+    // Scrolling that doesn't bring other items into view doesn't render the component again
+    scroll(&mut dom, list, 1010.0);
+    assert_eq!(RENDERS.with(Cell::get), 3);
+
+    // Scrolling past the end keeps the last items in the window
+    scroll(&mut dom, list, 1_000_000.0);
+    assert_eq!(items(&dom), (9992..10_000).collect::<Vec<_>>());
+}
@@ -0,0 +1,96 @@
+//! Queries can revalidate when the window regains focus
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+thread_local! {
+    static FETCHES: Cell<usize> = Cell::new(0);
+}
+
+fn app(cx: Scope) -> Element {
+    let user = use_query_with_options(
+        cx,
+        7,
+        QueryOptions {
+            revalidate_on_focus: true,
+            focus_debounce_ms: 500,
+        },
+        |id: u32| async move {
+            FETCHES.with(|f| f.set(f.get() + 1));
+            format!("user {id}")
+        },
+    );
+
+    match user.value() {
+        Some(value) => render! { "{value}" },
+        None => render! { "Loading..." },
+    }
+}
+
+/// Let the component react to the focus, then run the fetch it started
+fn settle(dom: &mut VirtualDom) {
+    _ = dom.render_immediate();
+    _ = dom.render_immediate();
+}
+
+#[test]
+fn focus_revalidates_query() {
+    let mut dom = VirtualDom::new(app);
+
+    // Stand in for the renderer that would forward the window's focus events
+    let focus = dom.base_scope().provide_context(WindowFocus::default());
+
+    _ = dom.rebuild();
+    settle(&mut dom);
+    assert_eq!(FETCHES.with(Cell::get), 1);
+
+    focus.focused(1000.0);
+    settle(&mut dom);
+    assert_eq!(FETCHES.with(Cell::get), 2);
+
+    // Focus bouncing back within the debounce window doesn't fetch again
+    focus.focused(1100.0);
+    settle(&mut dom);
+    assert_eq!(FETCHES.with(Cell::get), 2);
+
+    focus.focused(2000.0);
+    settle(&mut dom);
+    assert_eq!(FETCHES.with(Cell::get), 3);
+}
+
+thread_local! {
+    static LISTENS: Cell<usize> = Cell::new(0);
+}
+
+fn no_listeners(cx: Scope) -> Element {
+    render! { "no focus hooks" }
+}
+
+fn two_listeners(cx: Scope) -> Element {
+    render! {
+        focus_listener {}
+        focus_listener {}
+    }
+}
+
+fn focus_listener(cx: Scope) -> Element {
+    use_window_focus(cx, 0);
+    render! { "listening" }
+}
+
+#[test]
+fn first_listener_starts_listening() {
+    let count_listens = |_: WindowFocus| LISTENS.with(|l| l.set(l.get() + 1));
+
+    // Nothing listens for focus, so the renderer doesn't have to either
+    let mut dom = VirtualDom::new(no_listeners);
+    dom.base_scope()
+        .provide_context(WindowFocus::new(count_listens));
+    _ = dom.rebuild();
+    assert_eq!(LISTENS.with(Cell::get), 0);
+
+    let mut dom = VirtualDom::new(two_listeners);
+    dom.base_scope()
+        .provide_context(WindowFocus::new(count_listens));
+    _ = dom.rebuild();
+    assert_eq!(LISTENS.with(Cell::get), 1);
+}
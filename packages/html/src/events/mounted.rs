@@ -69,6 +69,9 @@ impl MountedData {
     }
 
     /// Get the renderer specific element for the given id
+    ///
+    /// Downcast it to the element type of the renderer: a `web_sys::Element` on the web, a `DesktopElement` on desktop
+    /// and a `LiveviewElement` in liveview. The last two carry the ID scripts can find the element by.
     pub fn get_raw_element(&self) -> MountedResult<&dyn Any> {
         self.inner.get_raw_element()
     }
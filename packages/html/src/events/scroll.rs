@@ -2,12 +2,22 @@ use dioxus_core::Event;
 
 pub type ScrollEvent = Event<ScrollData>;
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ScrollData {}
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScrollData {
+    /// How far the element's content is scrolled from the top, in pixels
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub scroll_top: f64,
+
+    /// How far the element's content is scrolled from the left, in pixels
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub scroll_left: f64,
+}
 
 impl_event! {
     ScrollData;
 
     /// onscroll
+    ///
+    /// Scroll events fire rapidly, so consider throttling the handler: `onscroll: throttle(100) move |evt| {}`
     onscroll
 }
@@ -2320,6 +2320,9 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/x2>
     x2: "x2";
 
+    /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xlink:href>
+    xlink_href: "xlink:href", "http://www.w3.org/1999/xlink";
+
     /// <https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/xmlns>
     xmlns: "xmlns";
 
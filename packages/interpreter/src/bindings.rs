@@ -24,6 +24,9 @@ extern "C" {
     #[wasm_bindgen(method)]
     pub fn AssignId(this: &Interpreter, path: &[u8], id: u32);
 
+    #[wasm_bindgen(method)]
+    pub fn AssignExternalRoot(this: &Interpreter, selector: &str, id: u32);
+
     #[wasm_bindgen(method)]
     pub fn CreatePlaceholder(this: &Interpreter, id: u32);
 
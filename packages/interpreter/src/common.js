@@ -1,4 +1,4 @@
-const bool_attrs = {
+export const bool_attrs = {
   allowfullscreen: true,
   allowpaymentrequest: true,
   async: true,
@@ -1,4 +1,4 @@
-import { setAttributeInner } from "./common.js";
+import { bool_attrs, setAttributeInner } from "./common.js";
 
 class ListenerMap {
   constructor(root) {
@@ -7,45 +7,106 @@ class ListenerMap {
     // non bubbling events listen at the element the listener was created at
     this.local = {};
     this.root = root;
+    // elements outside of the root that portals mount to also need the bubbling listeners
+    this.external = [];
   }
 
-  create(event_name, element, handler, bubbles) {
+  create(event_name, element, handler, bubbles, passive, capture) {
     if (bubbles) {
       if (this.global[event_name] === undefined) {
-        this.global[event_name] = {};
-        this.global[event_name].active = 1;
-        this.global[event_name].callback = handler;
-        this.root.addEventListener(event_name, handler);
-      } else {
-        this.global[event_name].active++;
+        this.global[event_name] = {
+          active: 0,
+          blocking: 0,
+          passive: null,
+          callback: handler,
+          // events from inside the root already reached the listener at the root
+          externalCallback: (event) => {
+            if (!this.root.contains(event.target)) {
+              handler(event);
+            }
+          },
+        };
+      }
+      this.global[event_name].active++;
+      if (!passive) {
+        this.global[event_name].blocking++;
       }
+      this.listenAtRoot(event_name);
     } else {
       const id = element.getAttribute("data-dioxus-id");
       if (!this.local[id]) {
         this.local[id] = {};
       }
       this.local[id][event_name] = handler;
-      element.addEventListener(event_name, handler);
+      element.addEventListener(event_name, handler, { passive, capture });
     }
   }
 
-  remove(element, event_name, bubbles) {
+  remove(element, event_name, bubbles, passive, capture) {
     if (bubbles) {
       this.global[event_name].active--;
+      if (!passive) {
+        this.global[event_name].blocking--;
+      }
       if (this.global[event_name].active === 0) {
         this.root.removeEventListener(
           event_name,
           this.global[event_name].callback
         );
+        for (const external of this.external) {
+          external.removeEventListener(
+            event_name,
+            this.global[event_name].externalCallback
+          );
+        }
         delete this.global[event_name];
+      } else {
+        this.listenAtRoot(event_name);
       }
     } else {
       const id = element.getAttribute("data-dioxus-id");
+      const handler = this.local[id][event_name];
       delete this.local[id][event_name];
-      if (this.local[id].length === 0) {
+      if (Object.keys(this.local[id]).length === 0) {
         delete this.local[id];
       }
-      element.removeEventListener(event_name, handler);
+      // removing a listener only matches the capture option it was added with
+      element.removeEventListener(event_name, handler, { capture });
+    }
+  }
+
+  // bubbling events share one listener at the root, which can only be passive while every listener it serves is
+  listenAtRoot(event_name) {
+    const global = this.global[event_name];
+    const passive = global.blocking === 0;
+    if (global.passive !== passive) {
+      if (global.passive !== null) {
+        this.root.removeEventListener(event_name, global.callback);
+        for (const external of this.external) {
+          external.removeEventListener(event_name, global.externalCallback);
+        }
+      }
+      this.root.addEventListener(event_name, global.callback, { passive });
+      for (const external of this.external) {
+        external.addEventListener(event_name, global.externalCallback, {
+          passive,
+        });
+      }
+      global.passive = passive;
+    }
+  }
+
+  // start listening for bubbling events at an element outside of the root
+  addExternalRoot(element) {
+    if (this.root.contains(element) || this.external.includes(element)) {
+      return;
+    }
+    this.external.push(element);
+    for (const event_name in this.global) {
+      const global = this.global[event_name];
+      element.addEventListener(event_name, global.externalCallback, {
+        passive: global.passive,
+      });
     }
   }
 
@@ -117,6 +178,15 @@ class Interpreter {
     let new_nodes = this.stack.splice(this.stack.length - n);
     old.before(...new_nodes);
   }
+  MoveNode(root, target, before) {
+    let node = this.nodes[root];
+    let old = this.nodes[target];
+    if (before) {
+      old.parentNode.insertBefore(node, old);
+    } else {
+      old.parentNode.insertBefore(node, old.nextSibling);
+    }
+  }
   Remove(root) {
     let node = this.nodes[root];
     if (node !== undefined) {
@@ -137,21 +207,44 @@ class Interpreter {
     this.stack.push(el);
     this.nodes[root] = el;
   }
-  NewEventListener(event_name, root, bubbles, handler) {
+  NewEventListener(event_name, root, bubbles, handler, flags = {}) {
     const element = this.nodes[root];
     element.setAttribute("data-dioxus-id", `${root}`);
-    this.listeners.create(event_name, element, handler, bubbles);
+    element.listening = (element.listening || 0) + 1;
+    this.listeners.create(
+      event_name,
+      element,
+      handler,
+      bubbles,
+      !!flags.passive,
+      !!flags.capture
+    );
   }
-  RemoveEventListener(root, event_name, bubbles) {
+  RemoveEventListener(root, event_name, bubbles, flags = {}) {
     const element = this.nodes[root];
-    element.removeAttribute(`data-dioxus-id`);
-    this.listeners.remove(element, event_name, bubbles);
+    element.listening -= 1;
+    this.listeners.remove(
+      element,
+      event_name,
+      bubbles,
+      !!flags.passive,
+      !!flags.capture
+    );
+    if (element.listening === 0) {
+      element.removeAttribute(`data-dioxus-id`);
+    }
   }
   SetText(root, text) {
     this.nodes[root].textContent = text;
   }
+  SetMultiText(values) {
+    for (const [root, text] of values) {
+      this.nodes[root].textContent = text;
+    }
+  }
   SetAttribute(id, field, value, ns) {
-    if (value === null) {
+    // A false boolean attribute is turned off by leaving it out, other attributes spell out "false"
+    if (value === null || (value === false && bool_attrs.hasOwnProperty(field))) {
       this.RemoveAttribute(id, field, ns);
     } else {
       const node = this.nodes[id];
@@ -275,6 +368,15 @@ class Interpreter {
         return el;
     }
   }
+  AssignExternalRoot(selector, id) {
+    let node = document.querySelector(selector);
+    if (node === null) {
+      node = this.root;
+    } else {
+      this.listeners.addExternalRoot(node);
+    }
+    this.nodes[id] = node;
+  }
   AssignId(path, id) {
     this.nodes[id] = this.LoadChild(path);
   }
@@ -320,6 +422,9 @@ class Interpreter {
       case "AssignId":
         this.AssignId(edit.path, edit.id);
         break;
+      case "AssignExternalRoot":
+        this.AssignExternalRoot(edit.selector, edit.id);
+        break;
       case "CreatePlaceholder":
         this.CreatePlaceholder(edit.id);
         break;
@@ -347,12 +452,18 @@ class Interpreter {
       case "InsertBefore":
         this.InsertBefore(edit.id, edit.m);
         break;
+      case "MoveNode":
+        this.MoveNode(edit.id, edit.target, edit.before);
+        break;
       case "Remove":
         this.Remove(edit.id);
         break;
       case "SetText":
         this.SetText(edit.id, edit.value);
         break;
+      case "SetMultiText":
+        this.SetMultiText(edit.values);
+        break;
       case "SetAttribute":
         this.SetAttribute(edit.id, edit.name, edit.value, edit.ns);
         break;
@@ -360,7 +471,14 @@ class Interpreter {
         this.RemoveAttribute(edit.id, edit.name, edit.ns);
         break;
       case "RemoveEventListener":
-        this.RemoveEventListener(edit.id, edit.name);
+        if (edit.name !== "mounted") {
+          this.RemoveEventListener(
+            edit.id,
+            edit.name,
+            event_bubbles(edit.name),
+            edit.flags
+          );
+        }
         break;
       case "NewEventListener":
         let bubbles = event_bubbles(edit.name);
@@ -376,9 +494,15 @@ class Interpreter {
             })
           );
         } else {
-          this.NewEventListener(edit.name, edit.id, bubbles, (event) => {
-            handler(event, edit.name, bubbles, this.config);
-          });
+          this.NewEventListener(
+            edit.name,
+            edit.id,
+            bubbles,
+            (event) => {
+              handler(event, edit.name, bubbles, this.config);
+            },
+            edit.flags
+          );
         }
         break;
     }
@@ -702,7 +826,11 @@ function serialize_event(event) {
       };
     }
     case "scroll": {
-      return {};
+      const { scrollTop, scrollLeft } = event.target;
+      return {
+        scroll_top: scrollTop,
+        scroll_left: scrollLeft,
+      };
     }
     case "wheel": {
       const { deltaX, deltaY, deltaZ, deltaMode } = event;
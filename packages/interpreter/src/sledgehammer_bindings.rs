@@ -13,42 +13,92 @@ mod js {
             this.local = {};
             this.root = null;
             this.handler = null;
+            // elements outside of the root that portals mount to also need the bubbling listeners
+            this.external = [];
+            this.externalHandler = (event) => {
+                // events from inside the root already reached the listener at the root
+                if (!this.root.contains(event.target)) {
+                    this.handler(event);
+                }
+            };
         }
 
-        create(event_name, element, bubbles) {
+        create(event_name, element, bubbles, passive, capture) {
             if (bubbles) {
                 if (this.global[event_name] === undefined) {
-                    this.global[event_name] = {};
-                    this.global[event_name].active = 1;
-                    this.root.addEventListener(event_name, this.handler);
-                } else {
-                    this.global[event_name].active++;
+                    this.global[event_name] = { active: 0, blocking: 0, passive: null };
+                }
+                this.global[event_name].active++;
+                if (!passive) {
+                    this.global[event_name].blocking++;
                 }
+                this.listenAtRoot(event_name);
             }
             else {
                 const id = element.getAttribute("data-dioxus-id");
                 if (!this.local[id]) {
                     this.local[id] = {};
                 }
-                element.addEventListener(event_name, this.handler);
+                this.local[id][event_name] = capture;
+                element.addEventListener(event_name, this.handler, { passive, capture });
             }
         }
 
-        remove(element, event_name, bubbles) {
+        remove(element, event_name, bubbles, passive, capture) {
             if (bubbles) {
                 this.global[event_name].active--;
+                if (!passive) {
+                    this.global[event_name].blocking--;
+                }
                 if (this.global[event_name].active === 0) {
-                    this.root.removeEventListener(event_name, this.global[event_name].callback);
+                    this.root.removeEventListener(event_name, this.handler);
+                    for (const external of this.external) {
+                        external.removeEventListener(event_name, this.externalHandler);
+                    }
                     delete this.global[event_name];
+                } else {
+                    this.listenAtRoot(event_name);
                 }
             }
             else {
                 const id = element.getAttribute("data-dioxus-id");
                 delete this.local[id][event_name];
-                if (this.local[id].length === 0) {
+                if (Object.keys(this.local[id]).length === 0) {
                     delete this.local[id];
                 }
-                element.removeEventListener(event_name, this.handler);
+                // removing a listener only matches the capture option it was added with
+                element.removeEventListener(event_name, this.handler, { capture });
+            }
+        }
+
+        // bubbling events share one listener at the root, which can only be passive while every listener it serves is
+        listenAtRoot(event_name) {
+            const global = this.global[event_name];
+            const passive = global.blocking === 0;
+            if (global.passive !== passive) {
+                if (global.passive !== null) {
+                    this.root.removeEventListener(event_name, this.handler);
+                    for (const external of this.external) {
+                        external.removeEventListener(event_name, this.externalHandler);
+                    }
+                }
+                this.root.addEventListener(event_name, this.handler, { passive });
+                for (const external of this.external) {
+                    external.addEventListener(event_name, this.externalHandler, { passive });
+                }
+                global.passive = passive;
+            }
+        }
+
+        // start listening for bubbling events at an element outside of the root
+        addExternalRoot(element) {
+            if (this.root.contains(element) || this.external.includes(element)) {
+                return;
+            }
+            this.external.push(element);
+            for (const event_name in this.global) {
+                const passive = this.global[event_name].passive;
+                element.addEventListener(event_name, this.externalHandler, { passive });
             }
         }
 
@@ -70,6 +120,7 @@ mod js {
         } else {
             switch (name) {
                 case "value":
+                    SetControlled(node, name, value);
                     if (value !== node.value) {
                         node.value = value;
                     }
@@ -78,9 +129,11 @@ mod js {
                     node.defaultValue = value;
                     break;
                 case "checked":
+                    SetControlled(node, name, value);
                     node.checked = truthy(value);
                     break;
                 case "selected":
+                    SetControlled(node, name, value);
                     node.selected = truthy(value);
                     break;
                 case "dangerous_inner_html":
@@ -96,6 +149,34 @@ mod js {
             }
         }
     }
+    // remember the state the VirtualDom rendered, so it can be put back if the user changes it without the state changing
+    function SetControlled(node, name, value) {
+        if (node.controlled === undefined) {
+            node.controlled = {};
+        }
+        node.controlled[name] = value;
+    }
+    function RestoreControlled(node) {
+        if (node === undefined || node.controlled === undefined) {
+            return;
+        }
+        for (const name in node.controlled) {
+            const value = node.controlled[name];
+            switch (name) {
+                case "value":
+                    if (value !== node.value) {
+                        node.value = value;
+                    }
+                    break;
+                case "checked":
+                    node.checked = truthy(value);
+                    break;
+                case "selected":
+                    node.selected = truthy(value);
+                    break;
+            }
+        }
+    }
     function LoadChild(ptr, len) {
         // iterate through each number and get that child
         node = stack[stack.length - 1];
@@ -187,6 +268,9 @@ mod js {
     fn mount_to_root() {
         "{AppendChildren(root, stack.length-1);}"
     }
+    fn assign_external_root(selector: &str, id: u32) {
+        "{node = document.querySelector($selector$); if (node === null) { node = nodes[0]; } else { listeners.addExternalRoot(node); } nodes[$id$] = node;}"
+    }
     fn push_root(root: u32) {
         "{stack.push(nodes[$root$]);}"
     }
@@ -217,11 +301,11 @@ mod js {
     fn create_placeholder(id: u32) {
         "{node = document.createElement('pre'); node.hidden = true; stack.push(node); nodes[$id$] = node;}"
     }
-    fn new_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        r#"node = nodes[id]; if(node.listening){node.listening += 1;}else{node.listening = 1;} node.setAttribute('data-dioxus-id', `\${id}`); listeners.create($event_name$, node, $bubbles$);"#
+    fn new_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8, passive: u8, capture: u8) {
+        r#"node = nodes[id]; if(node.listening){node.listening += 1;}else{node.listening = 1;} node.setAttribute('data-dioxus-id', `\${id}`); listeners.create($event_name$, node, $bubbles$, !!$passive$, !!$capture$);"#
     }
-    fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        "{node = nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); listeners.remove(node, $event_name$, $bubbles$);}"
+    fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8, passive: u8, capture: u8) {
+        "{node = nodes[$id$]; node.listening -= 1; listeners.remove(node, $event_name$, $bubbles$, !!$passive$, !!$capture$); if (node.listening === 0) { node.removeAttribute('data-dioxus-id'); }}"
     }
     fn set_text(id: u32, text: &str) {
         "{nodes[$id$].textContent = $text$;}"
@@ -233,14 +317,18 @@ mod js {
         r#"{
             node = nodes[$id$];
             if (!ns) {
+                // a removed value, checked or selected is still controlled, it is controlled to be off
                 switch (field) {
                     case "value":
+                        SetControlled(node, field, "");
                         node.value = "";
                         break;
                     case "checked":
+                        SetControlled(node, field, false);
                         node.checked = false;
                         break;
                     case "selected":
+                        SetControlled(node, field, false);
                         node.selected = false;
                         break;
                     case "dangerous_inner_html":
@@ -257,6 +345,12 @@ mod js {
             }
         }"#
     }
+    fn autofocus(id: u32) {
+        "{node = nodes[$id$]; if (node && !node.hasAttribute('autofocus')) { node = node.querySelector('[autofocus]'); } if (node) { node.focus(); }}"
+    }
+    fn restore_controlled(id: u32) {
+        "{RestoreControlled(nodes[$id$]);}"
+    }
     fn assign_id(ptr: u32, len: u8, id: u32) {
         "{nodes[$id$] = LoadChild($ptr$, $len$);}"
     }
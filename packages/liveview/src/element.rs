@@ -4,6 +4,8 @@ use dioxus_html::{geometry::euclid::Rect, MountedResult, RenderedElementBacking}
 use crate::query::QueryEngine;
 
 /// A mounted element passed to onmounted events
+///
+/// Get it from the event with [`dioxus_html::MountedData::get_raw_element`] and `downcast_ref`.
 pub struct LiveviewElement {
     id: ElementId,
     query: QueryEngine,
@@ -13,6 +15,11 @@ impl LiveviewElement {
     pub(crate) fn new(id: ElementId, query: QueryEngine) -> Self {
         Self { id, query }
     }
+
+    /// The ID of the element. Scripts running in the browser can find the element with `window.interpreter.nodes[id]`.
+    pub fn id(&self) -> ElementId {
+        self.id
+    }
 }
 
 impl RenderedElementBacking for LiveviewElement {
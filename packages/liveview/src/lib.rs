@@ -19,6 +19,7 @@ pub mod adapters {
 pub use adapters::*;
 
 mod element;
+pub use element::LiveviewElement;
 pub mod pool;
 mod query;
 use futures_util::{SinkExt, StreamExt};
@@ -80,7 +81,10 @@ static INTERPRETER_JS: Lazy<String> = Lazy::new(|| {
     }"#;
 
     let interpreter = interpreter.replace("/*POST_EVENT_SERIALIZATION*/", serialize_file_uploads);
-    interpreter.replace("import { setAttributeInner } from \"./common.js\";", "")
+    interpreter.replace(
+        "import { bool_attrs, setAttributeInner } from \"./common.js\";",
+        "",
+    )
 });
 
 static COMMON_JS: Lazy<String> = Lazy::new(|| {
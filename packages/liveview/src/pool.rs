@@ -8,7 +8,7 @@ use dioxus_core::{prelude::*, Mutations};
 use dioxus_html::{EventData, HtmlEvent, MountedData};
 use futures_util::{pin_mut, SinkExt, StreamExt};
 use serde::Serialize;
-use std::{rc::Rc, time::Duration};
+use std::{future::Future, pin::Pin, rc::Rc, time::Duration};
 use tokio_util::task::LocalPoolHandle;
 
 #[derive(Clone)]
@@ -124,6 +124,8 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
     let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
     let query_engine = QueryEngine::new(query_tx);
     vdom.base_scope().provide_context(query_engine.clone());
+    vdom.base_scope()
+        .provide_context(dioxus_hooks::Timer::new(TokioTimer));
     init_eval(vdom.base_scope());
 
     // todo: use an efficient binary packed format for this
@@ -134,17 +136,7 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
 
     // send the initial render to the client
     ws.send(edits.into_bytes()).await?;
-
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(HtmlEvent),
-        #[serde(rename = "query")]
-        Query(QueryResult),
-    }
+    vdom.run_layout_effects();
 
     loop {
         #[cfg(all(feature = "hot-reload", debug_assertions))]
@@ -163,8 +155,8 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
                         ws.send(b"__pong__".to_vec()).await?;
                     }
                     Some(Ok(evt)) => {
-                        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
-                            match message {
+                        match serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
+                            Ok(message) => match message {
                                 IpcMessage::Event(evt) => {
                                     // Intercept the mounted event and insert a custom element type
                                     if let EventData::Mounted = &evt.data {
@@ -188,7 +180,9 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
                                 IpcMessage::Query(result) => {
                                     query_engine.send(result);
                                 },
-                            }
+                                IpcMessage::Initialize {} => {}
+                            },
+                            Err(err) => log::warn!("Ignoring a message from the client that isn't an event or a query: {err}"),
                         }
                     }
                     // log this I guess? when would we get an error here?
@@ -221,15 +215,43 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
             .render_with_deadline(tokio::time::sleep(Duration::from_millis(10)))
             .await;
 
-        ws.send(
-            serde_json::to_string(&ClientUpdate::Edits(edits))
-                .unwrap()
-                .into_bytes(),
-        )
-        .await?;
+        // Pings, queries and events that didn't change anything don't need a message
+        if !edits.edits.is_empty() || !edits.templates.is_empty() {
+            ws.send(
+                serde_json::to_string(&ClientUpdate::Edits(edits))
+                    .unwrap()
+                    .into_bytes(),
+            )
+            .await?;
+        }
+
+        vdom.run_layout_effects();
+    }
+}
+
+/// Backs the timeouts of hooks with tokio's timer
+struct TokioTimer;
+
+impl dioxus_hooks::TimerBackend for TokioTimer {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        Box::pin(tokio::time::sleep(duration))
     }
 }
 
+// desktop uses this wrapper struct thing around the actual event itself
+// this is sorta driven by tao/wry
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "method", content = "params")]
+enum IpcMessage {
+    #[serde(rename = "user_event")]
+    Event(HtmlEvent),
+    #[serde(rename = "query")]
+    Query(QueryResult),
+    // Sent with empty params once the socket opens, the initial render has already been sent by then
+    #[serde(rename = "initialize")]
+    Initialize {},
+}
+
 #[derive(Serialize)]
 #[serde(tag = "type", content = "data")]
 enum ClientUpdate<'a> {
@@ -238,3 +260,15 @@ enum ClientUpdate<'a> {
     #[serde(rename = "query")]
     Query(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_initialize_message() {
+        // The exact message the client sends when the socket opens
+        let message = serde_json::from_str(r#"{"method":"initialize","params":{}}"#);
+        assert!(matches!(message, Ok(IpcMessage::Initialize {})));
+    }
+}
@@ -0,0 +1,73 @@
+//! The client only gets a batch of edits when something changed
+use dioxus::prelude::*;
+use dioxus_liveview::LiveViewError;
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{Sink, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A socket whose client is a pair of channels
+struct MockSocket {
+    incoming: UnboundedReceiver<Vec<u8>>,
+    outgoing: UnboundedSender<Vec<u8>>,
+}
+
+impl Stream for MockSocket {
+    type Item = Result<Vec<u8>, LiveViewError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.incoming
+            .poll_next_unpin(cx)
+            .map(|message| message.map(Ok))
+    }
+}
+
+impl Sink<Vec<u8>> for MockSocket {
+    type Error = LiveViewError;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, message: Vec<u8>) -> Result<(), Self::Error> {
+        self.outgoing
+            .unbounded_send(message)
+            .map_err(|_| LiveViewError::SendingFailed)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn app(cx: Scope) -> Element {
+    render! { div { "hello" } }
+}
+
+#[tokio::test]
+async fn messages_that_change_nothing_get_no_edits() {
+    let (client, incoming) = unbounded();
+    let (outgoing, mut received) = unbounded();
+
+    // The messages the client sends when it connects and to keep the socket alive, then it disconnects
+    client
+        .unbounded_send(br#"{"method":"initialize","params":{}}"#.to_vec())
+        .unwrap();
+    client.unbounded_send(b"__ping__".to_vec()).unwrap();
+    drop(client);
+
+    dioxus_liveview::run(VirtualDom::new(app), MockSocket { incoming, outgoing })
+        .await
+        .unwrap();
+
+    let initial_render = String::from_utf8(received.next().await.unwrap()).unwrap();
+    assert!(initial_render.starts_with(r#"{"type":"edits""#));
+    assert_eq!(received.next().await.unwrap(), b"__pong__");
+    assert_eq!(received.next().await, None);
+}
@@ -100,6 +100,13 @@ impl DioxusState {
                     let node_id = self.load_child(rdom, path);
                     self.set_element_id(rdom.get_mut(node_id).unwrap(), id);
                 }
+                // There is no document to search, so portals mount to the root
+                AssignExternalRoot { id, .. } => {
+                    if self.node_id_mapping.len() <= id.0 {
+                        self.node_id_mapping.resize(id.0 + 1, None);
+                    }
+                    self.node_id_mapping[id.0] = Some(rdom.root_id());
+                }
                 CreatePlaceholder { id } => {
                     let node = NodeType::Placeholder;
                     let node = rdom.create_node(node);
@@ -173,6 +180,15 @@ impl DioxusState {
                         rdom.tree_mut().insert_before(old_node_id, new);
                     }
                 }
+                MoveNode { id, target, before } => {
+                    let node_id = self.element_to_node_id(id);
+                    let target_id = self.element_to_node_id(target);
+                    // Moves the node the same way as pushing it and inserting it
+                    match before {
+                        true => rdom.tree_mut().insert_before(target_id, node_id),
+                        false => rdom.get_mut(node_id).unwrap().insert_after(target_id),
+                    }
+                }
                 SetAttribute {
                     name,
                     value,
@@ -207,12 +223,22 @@ impl DioxusState {
                         *text.text_mut() = value.to_string();
                     }
                 }
-                NewEventListener { name, id } => {
+                SetMultiText { values } => {
+                    for (id, value) in values {
+                        let node_id = self.element_to_node_id(id);
+                        let mut node = rdom.get_mut(node_id).unwrap();
+                        let node_type_mut = node.node_type_mut();
+                        if let NodeTypeMut::Text(mut text) = node_type_mut {
+                            *text.text_mut() = value.to_string();
+                        }
+                    }
+                }
+                NewEventListener { name, id, .. } => {
                     let node_id = self.element_to_node_id(id);
                     let mut node = rdom.get_mut(node_id).unwrap();
                     node.add_event_listener(name);
                 }
-                RemoveEventListener { id, name } => {
+                RemoveEventListener { id, name, .. } => {
                     let node_id = self.element_to_node_id(id);
                     let mut node = rdom.get_mut(node_id).unwrap();
                     node.remove_event_listener(name);
@@ -5,10 +5,10 @@ use super::*;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
-    parse::{Parse, ParseBuffer, ParseStream},
+    parse::{discouraged::Speculative, Parse, ParseBuffer, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
-    Error, Expr, Ident, LitStr, Result, Token,
+    Error, Expr, Ident, LitInt, LitStr, Result, Token,
 };
 
 // =======================================
@@ -84,10 +84,12 @@ impl Parse for Element {
                 let span = content.span();
 
                 if name_str.starts_with("on") {
+                    let modifiers = parse_listener_modifiers(&content)?;
                     attributes.push(ElementAttrNamed {
                         el_name: el_name.clone(),
                         attr: ElementAttr::EventTokens {
                             name,
+                            modifiers,
                             tokens: content.parse()?,
                         },
                     });
@@ -101,6 +103,15 @@ impl Parse for Element {
                         "node_ref" => {
                             _el_ref = Some(content.parse::<Expr>()?);
                         }
+                        "bind_value" | "bind_checked" => {
+                            attributes.push(ElementAttrNamed {
+                                el_name: el_name.clone(),
+                                attr: ElementAttr::Bind {
+                                    name,
+                                    value: content.parse()?,
+                                },
+                            });
+                        }
                         _ => {
                             if content.peek(LitStr) {
                                 attributes.push(ElementAttrNamed {
@@ -154,6 +165,26 @@ impl Parse for Element {
             }
         }
 
+        check_bindings(&el_name, &attributes)?;
+
+        // The inner HTML replaces the children, so there would be nothing left of them to diff
+        if !children.is_empty() {
+            let inner_html = attributes.iter().find_map(|attr| match &attr.attr {
+                ElementAttr::AttrText { name, .. } | ElementAttr::AttrExpression { name, .. }
+                    if name == "dangerous_inner_html" =>
+                {
+                    Some(name)
+                }
+                _ => None,
+            });
+            if let Some(name) = inner_html {
+                return Err(Error::new(
+                    name.span(),
+                    "an element with `dangerous_inner_html` can't have children",
+                ));
+            }
+        }
+
         Ok(Self {
             key,
             name: el_name,
@@ -164,6 +195,240 @@ impl Parse for Element {
     }
 }
 
+/// The elements each binding can be placed on
+///
+/// `bind_value` reads the value of text inputs, text areas and selects, and `bind_checked` reads whether a checkbox is
+/// checked. Both update their state from `oninput`.
+const BINDINGS: &[(&str, &[&str])] = &[
+    ("bind_value", &["input", "textarea", "select"]),
+    ("bind_checked", &["input"]),
+];
+
+/// Make sure every binding is on an element it supports, and that nothing else sets the attribute or listener it
+/// expands to
+fn check_bindings(el_name: &ElementName, attributes: &[ElementAttrNamed]) -> Result<()> {
+    let mut bound = None;
+
+    for attr in attributes {
+        if let ElementAttr::Bind { name, .. } = &attr.attr {
+            let (_, elements) = BINDINGS
+                .iter()
+                .find(|(binding, _)| name == binding)
+                .unwrap();
+            if !elements.iter().any(|element| el_name == element) {
+                return Err(Error::new(
+                    name.span(),
+                    format!(
+                        "`{name}` can only be used on {}",
+                        elements
+                            .iter()
+                            .map(|element| format!("`{element}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ));
+            }
+            if bound.is_some() {
+                return Err(Error::new(
+                    name.span(),
+                    "an element can only have one binding",
+                ));
+            }
+            bound = Some(name);
+        }
+    }
+
+    let binding = match bound {
+        Some(binding) => binding,
+        None => return Ok(()),
+    };
+    let (bound_attr, _) = bind_names(binding);
+
+    for attr in attributes {
+        let conflict = match &attr.attr {
+            ElementAttr::AttrText { name, .. }
+            | ElementAttr::AttrExpression { name, .. }
+            | ElementAttr::EventTokens { name, .. } => *name == bound_attr || name == "oninput",
+            _ => false,
+        };
+        if conflict {
+            return Err(Error::new(
+                attr.attr.start(),
+                format!("`{binding}` already sets `{bound_attr}` and `oninput`"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The attribute and the listener a binding expands to
+fn bind_names(binding: &Ident) -> (Ident, Ident) {
+    let attr = binding.to_string().trim_start_matches("bind_").to_string();
+    (
+        Ident::new(&attr, binding.span()),
+        Ident::new("oninput", binding.span()),
+    )
+}
+
+/// Expand a binding into the attribute that shows the state and the listener that updates it
+///
+/// The binding is either a `UseState`, or a tuple of the current value and a function that sets a new one.
+fn expand_binding(el_name: &ElementName, binding: &Ident, state: &Expr) -> [ElementAttrNamed; 2] {
+    let (attr, listener) = bind_names(binding);
+    let checked = binding == "bind_checked";
+
+    let (current, set): (Expr, Expr) = match state {
+        Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+            (tuple.elems[0].clone(), tuple.elems[1].clone())
+        }
+        state => (
+            syn::parse_quote! { *#state.get() },
+            syn::parse_quote! {{
+                let __state = #state.clone();
+                move |value| __state.set(value)
+            }},
+        ),
+    };
+
+    let (value, read): (Expr, Expr) = match checked {
+        true => (current, syn::parse_quote! { evt.value == "true" }),
+        false => (
+            syn::parse_quote! { ::core::format_args!("{}", #current) },
+            syn::parse_quote! { evt.value.clone() },
+        ),
+    };
+
+    [
+        ElementAttrNamed {
+            el_name: el_name.clone(),
+            attr: ElementAttr::AttrExpression { name: attr, value },
+        },
+        ElementAttrNamed {
+            el_name: el_name.clone(),
+            attr: ElementAttr::EventTokens {
+                name: listener,
+                modifiers: Vec::new(),
+                tokens: syn::parse_quote! {{
+                    let __set = #set;
+                    move |evt| __set(#read)
+                }},
+            },
+        },
+    ]
+}
+
+/// The modifiers that can be placed between a listener's name and its handler
+///
+/// `onsubmit: prevent_default move |_| {}`
+const LISTENER_MODIFIERS: &[&str] = &[
+    "prevent_default",
+    "stop_propagation",
+    "passive",
+    "capture",
+    "once",
+];
+
+/// The modifiers that take a number of milliseconds
+///
+/// `onscroll: throttle(100) move |_| {}`
+const LISTENER_MODIFIERS_WITH_ARG: &[&str] = &["throttle"];
+
+/// A modifier in front of a listener's handler that sets one of its `ListenerFlags`
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct ListenerModifier {
+    pub name: Ident,
+    pub arg: Option<LitInt>,
+}
+
+impl Display for ListenerModifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.arg {
+            Some(arg) => write!(f, "{}({})", self.name, arg),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl ToTokens for ListenerModifier {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let name = &self.name;
+        tokens.append_all(match &self.arg {
+            Some(arg) => quote! { #name: Some(#arg) },
+            None => quote! { #name: true },
+        });
+    }
+}
+
+/// Parse any modifiers in front of a listener's handler
+///
+/// A modifier name followed directly by a comma (or nothing) is the handler itself, not a modifier
+fn parse_listener_modifiers(content: ParseStream) -> Result<Vec<ListenerModifier>> {
+    let mut modifiers: Vec<ListenerModifier> = vec![];
+
+    while content.peek(Ident) {
+        let fork = content.fork();
+        let name = fork.parse::<Ident>()?;
+
+        let arg = if LISTENER_MODIFIERS_WITH_ARG.iter().any(|m| name == m) {
+            match parse_modifier_arg(&fork)? {
+                Some(arg) => Some(arg),
+                None => break,
+            }
+        } else if LISTENER_MODIFIERS.iter().any(|m| name == m) {
+            None
+        } else {
+            break;
+        };
+
+        let is_modifier = !fork.is_empty()
+            && !fork.peek(Token![,])
+            && !fork.peek(Token![.])
+            && !fork.peek(Token![::])
+            && !fork.peek(syn::token::Paren);
+
+        if !is_modifier {
+            break;
+        }
+
+        if modifiers.iter().any(|m| m.name == name) {
+            return Err(Error::new(
+                name.span(),
+                format!("the `{name}` modifier is applied more than once"),
+            ));
+        }
+
+        if let Some(arg) = &arg {
+            arg.base10_parse::<u32>()?;
+        }
+
+        content.advance_to(&fork);
+        modifiers.push(ListenerModifier { name, arg });
+    }
+
+    // Browsers ignore `preventDefault` in passive listeners, so the combination would silently do nothing
+    let has = |modifier: &str| modifiers.iter().find(|m| m.name == modifier);
+    if let (Some(_), Some(passive)) = (has("prevent_default"), has("passive")) {
+        return Err(Error::new(
+            passive.name.span(),
+            "a `passive` listener can't `prevent_default`",
+        ));
+    }
+
+    Ok(modifiers)
+}
+
+/// Parse the `(100)` after a modifier that takes a number, or nothing if it isn't there
+fn parse_modifier_arg(input: ParseStream) -> Result<Option<LitInt>> {
+    if !input.peek(syn::token::Paren) {
+        return Ok(None);
+    }
+
+    let args;
+    syn::parenthesized!(args in input);
+    Ok(args.parse::<LitInt>().ok().filter(|_| args.is_empty()))
+}
+
 impl ToTokens for Element {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let name = &self.name;
@@ -174,13 +439,22 @@ impl ToTokens for Element {
             None => quote! { None },
         };
 
-        let listeners = self
+        let attributes: Vec<_> = self
             .attributes
+            .iter()
+            .flat_map(|attr| match &attr.attr {
+                ElementAttr::Bind { name, value } => {
+                    expand_binding(&attr.el_name, name, value).to_vec()
+                }
+                _ => vec![attr.clone()],
+            })
+            .collect();
+
+        let listeners = attributes
             .iter()
             .filter(|f| matches!(f.attr, ElementAttr::EventTokens { .. }));
 
-        let attr = self
-            .attributes
+        let attr = attributes
             .iter()
             .filter(|f| !matches!(f.attr, ElementAttr::EventTokens { .. }));
 
@@ -282,7 +556,18 @@ pub enum ElementAttr {
     // /// onclick: move |_| {}
     // EventClosure { name: Ident, closure: ExprClosure },
     /// onclick: {}
-    EventTokens { name: Ident, tokens: Expr },
+    ///
+    /// onclick: stop_propagation {}
+    EventTokens {
+        name: Ident,
+        modifiers: Vec<ListenerModifier>,
+        tokens: Expr,
+    },
+
+    /// `bind_value: text`
+    ///
+    /// `bind_checked: (done, set_done)`
+    Bind { name: Ident, value: Expr },
 }
 
 impl ElementAttr {
@@ -293,6 +578,7 @@ impl ElementAttr {
             ElementAttr::CustomAttrText { name, .. } => name.span(),
             ElementAttr::CustomAttrExpression { name, .. } => name.span(),
             ElementAttr::EventTokens { name, .. } => name.span(),
+            ElementAttr::Bind { name, .. } => name.span(),
         }
     }
 
@@ -302,6 +588,7 @@ impl ElementAttr {
             ElementAttr::AttrExpression { .. }
                 | ElementAttr::CustomAttrExpression { .. }
                 | ElementAttr::EventTokens { .. }
+                | ElementAttr::Bind { .. }
         )
     }
 }
@@ -379,11 +666,31 @@ impl ToTokens for ElementAttrNamed {
                     )
                 }
             }
-            ElementAttr::EventTokens { name, tokens } => {
-                quote! {
-                    dioxus_elements::events::#name(__cx, #tokens)
+            ElementAttr::EventTokens {
+                name,
+                modifiers,
+                tokens,
+            } => {
+                if modifiers.is_empty() {
+                    quote! {
+                        dioxus_elements::events::#name(__cx, #tokens)
+                    }
+                } else {
+                    quote! {
+                        dioxus_elements::events::#name(__cx, #tokens).with_listener_flags(
+                            ::dioxus::core::ListenerFlags {
+                                #(#modifiers,)*
+                                ..::core::default::Default::default()
+                            }
+                        )
+                    }
                 }
             }
+            // A binding takes up two dynamic attributes
+            ElementAttr::Bind { name, value } => {
+                let [attr, listener] = expand_binding(el_name, name, value);
+                quote! { #attr, #listener }
+            }
         };
 
         tokens.append_all(attribute);
@@ -188,6 +188,13 @@ impl<'a> TemplateRenderer<'a> {
 
 impl<'a> ToTokens for TemplateRenderer<'a> {
     fn to_tokens(&self, out_tokens: &mut TokenStream2) {
+        out_tokens.append_all(self.render(true));
+    }
+}
+
+impl<'a> TemplateRenderer<'a> {
+    /// Render the template, encoding it as a compact template if it's fully static and `compact` is set
+    fn render(&self, compact: bool) -> TokenStream2 {
         let mut context = DynamicContext::default();
 
         let key = match self.roots.get(0) {
@@ -236,23 +243,127 @@ impl<'a> ToTokens for TemplateRenderer<'a> {
         let node_paths = context.node_paths.iter().map(|it| quote!(&[#(#it),*]));
         let attr_paths = context.attr_paths.iter().map(|it| quote!(&[#(#it),*]));
 
-        out_tokens.append_all(quote! {
-            static TEMPLATE: ::dioxus::core::Template = ::dioxus::core::Template {
-                name: #name,
-                roots: &[ #roots ],
-                node_paths: &[ #(#node_paths),* ],
-                attr_paths: &[ #(#attr_paths),* ],
-            };
+        // Fully static templates are encoded into a string of bytes, which is much smaller than a tree of nodes
+        let compact_parts = match compact && context.is_static() {
+            true => compact_parts(self.roots),
+            false => None,
+        };
+        let (template_items, template) = match compact_parts {
+            Some(parts) => (
+                quote! {
+                    const PARTS: &[&str] = &[ #(#parts),* ];
+                    static COMPACT: [u8; ::dioxus::core::compact_template_len(PARTS)] =
+                        ::dioxus::core::compact_template(PARTS);
+                    // Decoded on the first render, so later renders don't look it up again
+                    ::std::thread_local! {
+                        static TEMPLATE: ::dioxus::core::Template<'static> =
+                            ::dioxus::core::Template::from_compact(#name, &COMPACT);
+                    }
+                },
+                quote! { TEMPLATE.with(|template| *template) },
+            ),
+            None => (
+                quote! {
+                    static TEMPLATE: ::dioxus::core::Template = ::dioxus::core::Template {
+                        name: #name,
+                        roots: &[ #roots ],
+                        node_paths: &[ #(#node_paths),* ],
+                        attr_paths: &[ #(#attr_paths),* ],
+                    };
+                },
+                quote! { TEMPLATE },
+            ),
+        };
+
+        quote! {
+            #template_items
             ::dioxus::core::VNode {
-                parent: None,
+                parent: Default::default(),
                 key: #key_tokens,
-                template: std::cell::Cell::new(TEMPLATE),
+                template: std::cell::Cell::new(#template),
                 root_ids: Default::default(),
                 dynamic_nodes: __cx.bump().alloc([ #( #node_printer ),* ]),
                 dynamic_attrs: __cx.bump().alloc([ #( #dyn_attr_printer ),* ]),
             }
-        });
+        }
+    }
+}
+
+/// The parts of the compact template for static nodes, or `None` if some text can't be encoded
+///
+/// The markers must match the ones `dioxus_core` decodes compact templates with.
+fn compact_parts(roots: &[BodyNode]) -> Option<Vec<TokenStream2>> {
+    const ELEMENT: &str = "\u{1}";
+    const SEPARATOR: &str = "\u{2}";
+    const ATTRIBUTE: &str = "\u{3}";
+    const TEXT: &str = "\u{4}";
+    const CLOSE: &str = "\u{5}";
+
+    fn encodable(text: String) -> Option<String> {
+        match text.contains(|c| ('\u{1}'..='\u{5}').contains(&c)) {
+            true => None,
+            false => Some(text),
+        }
     }
+
+    fn push_node(node: &BodyNode, parts: &mut Vec<TokenStream2>) -> Option<()> {
+        match node {
+            BodyNode::Element(el) => {
+                let (tag, namespace) = match &el.name {
+                    ElementName::Ident(i) => (
+                        quote! { dioxus_elements::#i::TAG_NAME },
+                        quote! { ::dioxus::core::compact_namespace(dioxus_elements::#i::NAME_SPACE) },
+                    ),
+                    ElementName::Custom(s) => (quote! { #s }, quote! { "" }),
+                };
+                parts.extend([quote!(#ELEMENT), tag, quote!(#SEPARATOR), namespace]);
+
+                for attr in &el.attributes {
+                    let (name, namespace, value) = match (&attr.attr, &el.name) {
+                        (ElementAttr::AttrText { name, value }, ElementName::Ident(i)) => (
+                            quote! { dioxus_elements::#i::#name.0 },
+                            quote! { ::dioxus::core::compact_namespace(dioxus_elements::#i::#name.1) },
+                            value.to_static()?,
+                        ),
+                        (ElementAttr::AttrText { name, value }, ElementName::Custom(_)) => {
+                            let name = name.to_string();
+                            (quote! { #name }, quote! { "" }, value.to_static()?)
+                        }
+                        (ElementAttr::CustomAttrText { name, value }, _) => {
+                            (quote! { #name }, quote! { "" }, value.to_static()?)
+                        }
+                        _ => return None,
+                    };
+                    let value = encodable(value)?;
+                    parts.extend([
+                        quote!(#ATTRIBUTE),
+                        name,
+                        quote!(#SEPARATOR),
+                        namespace,
+                        quote!(#SEPARATOR),
+                        quote!(#value),
+                    ]);
+                }
+
+                for child in &el.children {
+                    push_node(child, parts)?;
+                }
+                parts.push(quote!(#CLOSE));
+            }
+            BodyNode::Text(text) => {
+                let text = encodable(text.to_static()?)?;
+                parts.extend([quote!(#TEXT), quote!(#text)]);
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    let mut parts = Vec::new();
+    for root in roots {
+        push_node(root, &mut parts)?;
+    }
+    Some(parts)
 }
 
 #[cfg(feature = "hot_reload")]
@@ -324,6 +435,11 @@ impl DynamicMapping {
                         | ElementAttr::EventTokens { .. } => {
                             self.insert_attribute(attr.attr);
                         }
+
+                        ElementAttr::Bind { .. } => {
+                            self.insert_attribute(attr.attr.clone());
+                            self.insert_attribute(attr.attr);
+                        }
                     }
                 }
 
@@ -358,6 +474,11 @@ pub struct DynamicContext<'a> {
 }
 
 impl<'a> DynamicContext<'a> {
+    /// Check if the template has no dynamic parts at all
+    fn is_static(&self) -> bool {
+        self.dynamic_attributes.is_empty() && self.dynamic_nodes.is_empty()
+    }
+
     #[cfg(feature = "hot_reload")]
     fn update_node<Ctx: HotReloadingContext>(
         &mut self,
@@ -400,7 +521,7 @@ impl<'a> DynamicContext<'a> {
                         | ElementAttr::EventTokens { .. } => {
                             let idx = match mapping {
                                 Some(mapping) => mapping.get_attribute_idx(&attr.attr)?,
-                                None => self.dynamic_attributes.len(),
+                                None => self.attr_paths.len(),
                             };
                             self.dynamic_attributes.push(attr);
 
@@ -410,6 +531,26 @@ impl<'a> DynamicContext<'a> {
                             self.attr_paths[idx] = self.current_path.clone();
                             static_attrs.push(TemplateAttribute::Dynamic { id: idx })
                         }
+
+                        ElementAttr::Bind { .. } => {
+                            let mut idxs = match mapping {
+                                Some(mapping) => [
+                                    mapping.get_attribute_idx(&attr.attr)?,
+                                    mapping.get_attribute_idx(&attr.attr)?,
+                                ],
+                                None => [self.attr_paths.len(), self.attr_paths.len() + 1],
+                            };
+                            idxs.sort_unstable();
+                            self.dynamic_attributes.push(attr);
+
+                            for idx in idxs {
+                                if self.attr_paths.len() <= idx {
+                                    self.attr_paths.resize_with(idx + 1, Vec::new);
+                                }
+                                self.attr_paths[idx] = self.current_path.clone();
+                                static_attrs.push(TemplateAttribute::Dynamic { id: idx })
+                            }
+                        }
                     }
                 }
 
@@ -511,11 +652,24 @@ impl<'a> DynamicContext<'a> {
                     | ElementAttr::CustomAttrText { .. }
                     | ElementAttr::CustomAttrExpression { .. }
                     | ElementAttr::EventTokens { .. } => {
-                        let ct = self.dynamic_attributes.len();
+                        let ct = self.attr_paths.len();
                         self.dynamic_attributes.push(attr);
                         self.attr_paths.push(self.current_path.clone());
                         quote! { ::dioxus::core::TemplateAttribute::Dynamic { id: #ct } }
                     }
+
+                    // The attribute and the listener of a binding are two dynamic attributes on the same element
+                    ElementAttr::Bind { .. } => {
+                        let (attr_id, listener_id) =
+                            (self.attr_paths.len(), self.attr_paths.len() + 1);
+                        self.dynamic_attributes.push(attr);
+                        self.attr_paths.push(self.current_path.clone());
+                        self.attr_paths.push(self.current_path.clone());
+                        quote! {
+                            ::dioxus::core::TemplateAttribute::Dynamic { id: #attr_id },
+                            ::dioxus::core::TemplateAttribute::Dynamic { id: #listener_id }
+                        }
+                    }
                 });
 
                 let attrs = quote! { #(#static_attrs),*};
@@ -771,7 +925,99 @@ fn diff_template() {
                 ],
             }],
             node_paths: &[&[0, 3], &[0, 2], &[0, 1], &[0, 0]],
-            attr_paths: &[&[0], &[0]]
+            attr_paths: &[&[0], &[0]],
         },
     )
 }
+
+#[test]
+fn compact_static_template() {
+    fn count_tokens(tokens: TokenStream2) -> usize {
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                proc_macro2::TokenTree::Group(group) => 1 + count_tokens(group.stream()),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    let input = quote! {
+        article {
+            class: "post",
+            h1 { "Static content" }
+            p { "A paragraph of text" }
+            ul {
+                li { "one" }
+                li { "two" }
+                li { "three" }
+                li { "four" }
+            }
+            footer { "data-kind": "footer", "The end" }
+        }
+    };
+    let call_body: CallBody = syn::parse2(input).unwrap();
+    let renderer = TemplateRenderer {
+        roots: &call_body.roots,
+        location: None,
+    };
+
+    let before = count_tokens(renderer.render(false));
+    let after = count_tokens(renderer.render(true));
+    assert!(
+        after * 10 < before * 9,
+        "the compact template should be smaller ({} tokens before, {} after)",
+        before,
+        after
+    );
+
+    // Templates with dynamic parts are left alone
+    let input = quote! { div { class: "{class}", "hello" } };
+    let call_body: CallBody = syn::parse2(input).unwrap();
+    let renderer = TemplateRenderer {
+        roots: &call_body.roots,
+        location: None,
+    };
+    assert_eq!(
+        renderer.render(true).to_string(),
+        renderer.render(false).to_string()
+    );
+}
+
+#[test]
+fn bind_expands_to_an_attribute_and_a_listener() {
+    let input = quote! { input { class: "{class}", bind_value: text } };
+    let call_body: CallBody = syn::parse2(input).unwrap();
+    let renderer = TemplateRenderer {
+        roots: &call_body.roots,
+        location: None,
+    };
+
+    let tokens = renderer.render(false).to_string();
+    let expected = quote! {
+        attrs: &[
+            ::dioxus::core::TemplateAttribute::Dynamic { id: 0usize },
+            ::dioxus::core::TemplateAttribute::Dynamic { id: 1usize },
+            ::dioxus::core::TemplateAttribute::Dynamic { id: 2usize }
+        ]
+    };
+    assert!(tokens.contains(&expected.to_string()));
+    assert!(tokens.contains(&quote! { attr_paths: &[&[0u8], &[0u8], &[0u8]] }.to_string()));
+    assert!(tokens.contains(&quote! { dioxus_elements::input::value.0 }.to_string()));
+    assert!(tokens.contains(&quote! { dioxus_elements::events::oninput }.to_string()));
+
+    // Bindings only go on the elements that can read them back
+    let input = quote! { div { bind_checked: done } };
+    let err = syn::parse2::<CallBody>(input).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`bind_checked` can only be used on `input`"
+    );
+
+    let input = quote! { input { bind_value: text, oninput: move |_| {} } };
+    let err = syn::parse2::<CallBody>(input).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`bind_value` already sets `value` and `oninput`"
+    );
+}
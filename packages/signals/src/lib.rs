@@ -24,8 +24,8 @@ pub fn use_signal<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> T) -> Signal<
             None => cx.provide_context(claim_rt(cx.schedule_update_any())),
         };
 
+        // Components subscribe to the signal when they read it, not when they create it
         let id = rt.init(f());
-        rt.subscribe(id, cx.scope_id());
 
         struct SignalHook<T> {
             signal: Signal<T>,
@@ -72,9 +72,11 @@ impl<T: 'static> Signal<T> {
         f(&*write)
     }
 
-    pub fn update<O>(&self, _f: impl FnOnce(&mut T) -> O) -> O {
-        let mut write = self.write();
-        _f(&mut *write)
+    /// Mutate the value in place and re-render every component that has read this signal
+    pub fn update<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
+        let out = f(&mut *self.write());
+        self.rt.notify(self.id);
+        out
     }
 }
 
@@ -100,6 +102,12 @@ impl<T> std::clone::Clone for Signal<T> {
 
 impl<T> Copy for Signal<T> {}
 
+impl<T> PartialEq for Signal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && std::ptr::eq(self.rt, other.rt)
+    }
+}
+
 impl<T: Display + 'static> Display for Signal<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.rt.with::<T, _>(self.id, |v| T::fmt(v, f))
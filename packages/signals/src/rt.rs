@@ -1,6 +1,6 @@
-use std::{any::Any, cell::RefCell, sync::Arc};
+use std::{any::Any, cell::RefCell, rc::Rc, sync::Arc};
 
-use dioxus_core::ScopeId;
+use dioxus_core::{with_current_scope, ScopeId};
 use slab::Slab;
 
 thread_local! {
@@ -46,10 +46,52 @@ impl SignalRt {
     }
 
     pub fn subscribe(&self, id: usize, subscriber: ScopeId) {
-        self.signals.borrow_mut()[id].subscribers.push(subscriber);
+        let mut signals = self.signals.borrow_mut();
+        let subscribers = &mut signals[id].subscribers;
+        if !subscribers.contains(&subscriber) {
+            subscribers.push(subscriber);
+        }
+    }
+
+    /// Subscribe the currently rendering component (if any) to this signal
+    ///
+    /// Reads outside of a render (in event handlers, tasks, etc) don't create subscriptions
+    pub(crate) fn track(&'static self, id: usize) {
+        with_current_scope(|cx| {
+            // The subscriptions of each component live in its own context, so they are dropped with it
+            let subscriptions = match cx.has_context::<Rc<Subscriptions>>() {
+                Some(subscriptions) => subscriptions,
+                None => cx.provide_context(Rc::new(Subscriptions {
+                    rt: self,
+                    scope: cx.scope_id(),
+                    signals: Default::default(),
+                })),
+            };
+            let mut signals = subscriptions.signals.borrow_mut();
+            if !signals.contains(&id) {
+                signals.push(id);
+            }
+            self.subscribe(id, cx.scope_id());
+        });
+    }
+
+    /// Stop re-rendering a component when this signal changes
+    pub fn unsubscribe(&self, id: usize, subscriber: ScopeId) {
+        if let Some(inner) = self.signals.borrow_mut().get_mut(id) {
+            inner.subscribers.retain(|scope| *scope != subscriber);
+        }
+    }
+
+    /// Mark every component that has read this signal as dirty
+    pub(crate) fn notify(&self, id: usize) {
+        let signals = self.signals.borrow();
+        for subscriber in signals[id].subscribers.iter() {
+            (self.update_any)(*subscriber);
+        }
     }
 
-    pub fn get<T: Clone + 'static>(&self, id: usize) -> T {
+    pub fn get<T: Clone + 'static>(&'static self, id: usize) -> T {
+        self.track(id);
         self.signals.borrow()[id]
             .value
             .downcast_ref::<T>()
@@ -58,27 +100,24 @@ impl SignalRt {
     }
 
     pub fn set<T: 'static>(&self, id: usize, value: T) {
-        let mut signals = self.signals.borrow_mut();
-        let inner = &mut signals[id];
-        inner.value = Box::new(value);
-
-        for subscriber in inner.subscribers.iter() {
-            (self.update_any)(*subscriber);
-        }
+        self.signals.borrow_mut()[id].value = Box::new(value);
+        self.notify(id);
     }
 
     pub fn remove(&self, id: usize) {
         self.signals.borrow_mut().remove(id);
     }
 
-    pub fn with<T: 'static, O>(&self, id: usize, f: impl FnOnce(&T) -> O) -> O {
+    pub fn with<T: 'static, O>(&'static self, id: usize, f: impl FnOnce(&T) -> O) -> O {
+        self.track(id);
         let signals = self.signals.borrow();
         let inner = &signals[id];
         let inner = inner.value.downcast_ref::<T>().unwrap();
         f(inner)
     }
 
-    pub(crate) fn read<T: 'static>(&self, id: usize) -> std::cell::Ref<T> {
+    pub(crate) fn read<T: 'static>(&'static self, id: usize) -> std::cell::Ref<T> {
+        self.track(id);
         let signals = self.signals.borrow();
         std::cell::Ref::map(signals, |signals| {
             signals[id].value.downcast_ref::<T>().unwrap()
@@ -92,7 +131,7 @@ impl SignalRt {
         })
     }
 
-    pub(crate) fn getter<T: 'static + Clone>(&self, id: usize) -> &dyn Fn() -> T {
+    pub(crate) fn getter<T: 'static + Clone>(&'static self, id: usize) -> &dyn Fn() -> T {
         let mut signals = self.signals.borrow_mut();
         let inner = &mut signals[id];
         let r = inner.getter.as_mut();
@@ -112,6 +151,23 @@ impl SignalRt {
     }
 }
 
+/// The signals a component has read, which it unsubscribes from when it is dropped
+///
+/// Scope ids are reused, so a stale subscription would re-render whatever component gets the id next.
+struct Subscriptions {
+    rt: &'static SignalRt,
+    scope: ScopeId,
+    signals: RefCell<Vec<usize>>,
+}
+
+impl Drop for Subscriptions {
+    fn drop(&mut self) {
+        for id in self.signals.get_mut().drain(..) {
+            self.rt.unsubscribe(id, self.scope);
+        }
+    }
+}
+
 pub(crate) struct Inner {
     pub value: Box<dyn Any>,
     pub subscribers: Vec<ScopeId>,
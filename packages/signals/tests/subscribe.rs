@@ -0,0 +1,107 @@
+//! Signals should only re-render the components that actually read them
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_signals::{use_signal, Signal};
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static SIGNAL: Cell<Option<Signal<i32>>> = Cell::new(None);
+    static RENDERS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn rendered(name: &'static str) {
+    RENDERS.with(|r| r.borrow_mut().push(name));
+}
+
+fn take_renders() -> Vec<&'static str> {
+    RENDERS.with(|r| std::mem::take(&mut *r.borrow_mut()))
+}
+
+#[test]
+fn only_subscribers_rerender() {
+    fn app(cx: Scope) -> Element {
+        rendered("app");
+        let signal = use_signal(cx, || 0);
+        SIGNAL.with(|s| s.set(Some(signal)));
+
+        render! {
+            Reader { signal: signal }
+            Reader { signal: signal }
+            Bystander {}
+        }
+    }
+
+    #[inline_props]
+    fn Reader(cx: Scope, signal: Signal<i32>) -> Element {
+        rendered("reader");
+        let value = signal.get();
+        render! { div { "{value}" } }
+    }
+
+    fn Bystander(cx: Scope) -> Element {
+        rendered("bystander");
+        render! { div { "static" } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(take_renders(), ["app", "reader", "reader", "bystander"]);
+
+    // Writing outside of a render should only touch the two readers
+    let mut signal = SIGNAL.with(|s| s.get()).unwrap();
+    signal.set(1);
+    _ = dom.render_immediate();
+    assert_eq!(take_renders(), ["reader", "reader"]);
+
+    signal.update(|value| *value += 1);
+    _ = dom.render_immediate();
+    assert_eq!(take_renders(), ["reader", "reader"]);
+    assert_eq!(signal.get(), 2);
+}
+
+#[test]
+fn dropped_subscribers_are_forgotten() {
+    thread_local! {
+        static STAGE: Cell<u8> = Cell::new(0);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let signal = use_signal(cx, || 0);
+        SIGNAL.with(|s| s.set(Some(signal)));
+
+        match STAGE.with(Cell::get) {
+            0 => render! { Reader { signal: signal } },
+            1 => render! { "nothing" },
+            _ => render! { Bystander {} },
+        }
+    }
+
+    #[inline_props]
+    fn Reader(cx: Scope, signal: Signal<i32>) -> Element {
+        rendered("reader");
+        let value = signal.get();
+        render! { div { "{value}" } }
+    }
+
+    fn Bystander(cx: Scope) -> Element {
+        rendered("bystander");
+        render! { div { "static" } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // Drop the reader, then mount a component that takes over its scope id
+    for stage in [1, 2] {
+        STAGE.with(|s| s.set(stage));
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+    }
+    assert_eq!(take_renders(), ["reader", "bystander"]);
+
+    let mut signal = SIGNAL.with(|s| s.get()).unwrap();
+    signal.set(1);
+    _ = dom.render_immediate();
+    assert!(take_renders().is_empty());
+}
@@ -147,7 +147,7 @@ impl StringCache {
     }
 }
 
-fn tag_is_self_closing(tag: &str) -> bool {
+pub(crate) fn tag_is_self_closing(tag: &str) -> bool {
     matches!(
         tag,
         "area"
@@ -5,18 +5,34 @@ pub mod config;
 mod fs_cache;
 pub mod incremental;
 mod incremental_cfg;
+pub mod mutations;
 pub mod renderer;
+pub mod streaming;
 pub mod template;
 
-use dioxus_core::{Element, LazyNodes, Scope, VirtualDom};
+use dioxus_core::{Element, LazyNodes, RenderTarget, Scope, VirtualDom};
 use std::cell::Cell;
 
+pub use crate::mutations::{render_to_string, MutationRenderer};
 pub use crate::renderer::Renderer;
+pub use crate::streaming::{render_streaming, StreamingRenderer};
 
 /// A convenience function to render an `rsx!` call to a string
 ///
 /// For advanced rendering, create a new `SsrRender`.
 pub fn render_lazy(f: LazyNodes<'_, '_>) -> String {
+    render_lazy_for(RenderTarget::Screen, f)
+}
+
+/// A convenience function to render an `rsx!` call to a string for print media
+///
+/// Components will see [`RenderTarget::Print`] from `cx.render_target()`. To render an existing VirtualDom for print,
+/// build it with [`VirtualDom::with_render_target`].
+pub fn render_lazy_print(f: LazyNodes<'_, '_>) -> String {
+    render_lazy_for(RenderTarget::Print, f)
+}
+
+fn render_lazy_for(target: RenderTarget, f: LazyNodes<'_, '_>) -> String {
     // We need to somehow get the lazy call into the virtualdom even with the lifetime
     // Since the lazy lifetime is valid for this function, we can just transmute it to static temporarily
     // This is okay since we're returning an owned value
@@ -36,7 +52,7 @@ pub fn render_lazy(f: LazyNodes<'_, '_>) -> String {
         })
     };
 
-    let mut dom = VirtualDom::new_with_props(lazy_app, props);
+    let mut dom = VirtualDom::new_with_props(lazy_app, props).with_render_target(target);
     _ = dom.rebuild();
 
     Renderer::new().render(&dom)
@@ -0,0 +1,420 @@
+//! Render to a string by applying the mutations of a VirtualDom to an in-memory tree
+//!
+//! This is slower than the template cache of [`crate::Renderer`], but it only relies on the [`Mutation`]s a VirtualDom
+//! sends to every other renderer. Since it implements [`dioxus_core::Renderer`], it can also follow a VirtualDom through
+//! later diffs and serialize the tree at any point.
+
+use crate::cache::tag_is_self_closing;
+use dioxus_core::{
+    BorrowedAttributeValue, ElementId, Mutation, Mutations, Template, TemplateAttribute,
+    TemplateNode, VirtualDom,
+};
+use rustc_hash::FxHashMap;
+use std::fmt::Write;
+
+/// A [`dioxus_core::Renderer`] that builds a tree of nodes from mutations and serializes it to HTML
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// let mut renderer = MutationRenderer::new();
+/// renderer.apply(dom.rebuild());
+/// let html = renderer.to_html();
+/// ```
+pub struct MutationRenderer {
+    templates: FxHashMap<&'static str, Template<'static>>,
+    nodes: Vec<Node>,
+    ids: FxHashMap<ElementId, usize>,
+    stack: Vec<usize>,
+}
+
+struct Node {
+    parent: Option<usize>,
+    children: Vec<usize>,
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    Element {
+        tag: &'static str,
+        namespace: Option<&'static str>,
+        attrs: Vec<(String, String)>,
+        styles: Vec<(String, String)>,
+        inner_html: Option<String>,
+    },
+    Text(String),
+    Placeholder,
+}
+
+impl Default for MutationRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MutationRenderer {
+    /// Create a renderer with an empty root, which is the element with the [`ElementId`] 0
+    pub fn new() -> Self {
+        let root = Node {
+            parent: None,
+            children: Vec::new(),
+            kind: NodeKind::Placeholder,
+        };
+        let mut ids = FxHashMap::default();
+        ids.insert(ElementId(0), 0);
+
+        Self {
+            templates: FxHashMap::default(),
+            nodes: vec![root],
+            ids,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Serialize the children of the root to HTML
+    pub fn to_html(&self) -> String {
+        let mut buf = String::new();
+        self.render_to(&mut buf).unwrap();
+        buf
+    }
+
+    /// Write the children of the root to `buf` as HTML
+    pub fn render_to(&self, buf: &mut impl Write) -> std::fmt::Result {
+        for child in &self.nodes[0].children {
+            self.write_node(buf, *child, None)?;
+        }
+        Ok(())
+    }
+
+    fn write_node(
+        &self,
+        buf: &mut impl Write,
+        id: usize,
+        parent_namespace: Option<&str>,
+    ) -> std::fmt::Result {
+        let node = &self.nodes[id];
+        match &node.kind {
+            NodeKind::Element {
+                tag,
+                namespace,
+                attrs,
+                styles,
+                inner_html,
+            } => {
+                write!(buf, "<{tag}")?;
+
+                // Elements that switch namespace, like an svg inside of html, need to say so
+                if let Some(namespace) = namespace {
+                    if parent_namespace != Some(*namespace)
+                        && !attrs.iter().any(|(name, _)| name == "xmlns")
+                    {
+                        write!(buf, " xmlns=\"{}\"", escape(namespace))?;
+                    }
+                }
+                for (name, value) in attrs {
+                    write!(buf, " {}=\"{}\"", name, escape(value))?;
+                }
+                if !styles.is_empty() {
+                    write!(buf, " style=\"")?;
+                    for (name, value) in styles {
+                        write!(buf, "{}:{};", name, escape(value))?;
+                    }
+                    write!(buf, "\"")?;
+                }
+
+                if node.children.is_empty() && inner_html.is_none() && tag_is_self_closing(tag) {
+                    return write!(buf, "/>");
+                }
+
+                write!(buf, ">")?;
+                if let Some(inner_html) = inner_html {
+                    write!(buf, "{inner_html}")?;
+                }
+                for child in &node.children {
+                    self.write_node(buf, *child, *namespace)?;
+                }
+                write!(buf, "</{tag}>")
+            }
+            NodeKind::Text(text) => write!(buf, "{}", escape(text)),
+            NodeKind::Placeholder => Ok(()),
+        }
+    }
+
+    fn insert(&mut self, kind: NodeKind) -> usize {
+        self.nodes.push(Node {
+            parent: None,
+            children: Vec::new(),
+            kind,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn create(&mut self, template: &TemplateNode<'static>) -> usize {
+        match template {
+            TemplateNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                let node = self.insert(NodeKind::Element {
+                    tag: *tag,
+                    namespace: *namespace,
+                    attrs: Vec::new(),
+                    styles: Vec::new(),
+                    inner_html: None,
+                });
+                for attr in *attrs {
+                    if let TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace,
+                    } = attr
+                    {
+                        self.set_attribute(node, name, *namespace, Some(value.to_string()));
+                    }
+                }
+                for child in *children {
+                    let child = self.create(child);
+                    self.append(node, child);
+                }
+                node
+            }
+            TemplateNode::Text { text } => self.insert(NodeKind::Text(text.to_string())),
+            TemplateNode::DynamicText { .. } => self.insert(NodeKind::Text(String::new())),
+            TemplateNode::Dynamic { .. } => self.insert(NodeKind::Placeholder),
+        }
+    }
+
+    fn set_attribute(&mut self, node: usize, name: &str, ns: Option<&str>, value: Option<String>) {
+        let (attrs, styles, inner_html) = match &mut self.nodes[node].kind {
+            NodeKind::Element {
+                attrs,
+                styles,
+                inner_html,
+                ..
+            } => (attrs, styles, inner_html),
+            _ => {
+                log::warn!("Can't set the attribute {:?} on a text node", name);
+                return;
+            }
+        };
+
+        if name == "dangerous_inner_html" {
+            *inner_html = value;
+            return;
+        }
+
+        let attrs = match ns {
+            Some("style") => styles,
+            _ => attrs,
+        };
+        let existing = attrs.iter().position(|(existing, _)| existing == name);
+        match (existing, value) {
+            (Some(idx), Some(value)) => attrs[idx].1 = value,
+            (None, Some(value)) => attrs.push((name.to_string(), value)),
+            (Some(idx), None) => {
+                attrs.remove(idx);
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn node(&self, id: ElementId) -> usize {
+        *self
+            .ids
+            .get(&id)
+            .unwrap_or_else(|| panic!("{:?} is not mounted", id))
+    }
+
+    fn at_path(&self, path: &[u8]) -> usize {
+        path.iter().fold(*self.stack.last().unwrap(), |node, idx| {
+            self.nodes[node].children[*idx as usize]
+        })
+    }
+
+    fn pop(&mut self, m: usize) -> Vec<usize> {
+        self.stack.split_off(self.stack.len() - m)
+    }
+
+    /// Take a node out of its parent, if it has one
+    fn detach(&mut self, node: usize) {
+        if let Some(parent) = self.nodes[node].parent.take() {
+            self.nodes[parent].children.retain(|child| *child != node);
+        }
+    }
+
+    fn append(&mut self, parent: usize, child: usize) {
+        self.detach(child);
+        self.nodes[child].parent = Some(parent);
+        self.nodes[parent].children.push(child);
+    }
+
+    /// Insert nodes next to `target`, right before it or right after it
+    fn insert_next_to(&mut self, target: usize, nodes: Vec<usize>, before: bool) {
+        for node in &nodes {
+            self.detach(*node);
+        }
+
+        let parent = self.nodes[target]
+            .parent
+            .expect("only mounted nodes have siblings");
+        let idx = self.nodes[parent]
+            .children
+            .iter()
+            .position(|child| *child == target)
+            .unwrap();
+        let idx = if before { idx } else { idx + 1 };
+
+        for node in &nodes {
+            self.nodes[*node].parent = Some(parent);
+        }
+        self.nodes[parent].children.splice(idx..idx, nodes);
+    }
+
+    fn replace(&mut self, target: usize, nodes: Vec<usize>) {
+        self.insert_next_to(target, nodes, true);
+        self.detach(target);
+    }
+
+    fn set_text(&mut self, node: usize, value: &str) {
+        self.nodes[node].kind = NodeKind::Text(value.to_string());
+    }
+
+    fn apply_edit(&mut self, edit: Mutation) {
+        match edit {
+            Mutation::AppendChildren { id, m } => {
+                let parent = self.node(id);
+                for child in self.pop(m) {
+                    self.append(parent, child);
+                }
+            }
+            Mutation::AssignId { path, id } => {
+                let node = self.at_path(path);
+                self.ids.insert(id, node);
+            }
+            // There is no document to search, so portals mount to the root
+            Mutation::AssignExternalRoot { id, .. } => {
+                let root = self.node(ElementId(0));
+                self.ids.insert(id, root);
+            }
+            Mutation::CreatePlaceholder { id } => {
+                let node = self.insert(NodeKind::Placeholder);
+                self.ids.insert(id, node);
+                self.stack.push(node);
+            }
+            Mutation::CreateTextNode { value, id } => {
+                let node = self.insert(NodeKind::Text(value.to_string()));
+                self.ids.insert(id, node);
+                self.stack.push(node);
+            }
+            Mutation::HydrateText { path, value, id } => {
+                let node = self.at_path(path);
+                self.set_text(node, value);
+                self.ids.insert(id, node);
+            }
+            Mutation::LoadTemplate { name, index, id } => {
+                let template = self
+                    .templates
+                    .get(name)
+                    .unwrap_or_else(|| panic!("the template {:?} was never registered", name));
+                let root = template.roots[index];
+                let node = self.create(&root);
+                self.ids.insert(id, node);
+                self.stack.push(node);
+            }
+            Mutation::ReplaceWith { id, m } => {
+                let nodes = self.pop(m);
+                self.replace(self.node(id), nodes);
+            }
+            Mutation::ReplacePlaceholder { path, m } => {
+                let nodes = self.pop(m);
+                self.replace(self.at_path(path), nodes);
+            }
+            Mutation::InsertAfter { id, m } => {
+                let nodes = self.pop(m);
+                self.insert_next_to(self.node(id), nodes, false);
+            }
+            Mutation::InsertBefore { id, m } => {
+                let nodes = self.pop(m);
+                self.insert_next_to(self.node(id), nodes, true);
+            }
+            Mutation::MoveNode { id, target, before } => {
+                self.insert_next_to(self.node(target), vec![self.node(id)], before);
+            }
+            Mutation::SetAttribute {
+                name,
+                value,
+                id,
+                ns,
+            } => {
+                let value = match value {
+                    value if value.removes_attribute(name) => None,
+                    BorrowedAttributeValue::Text(value) => Some(value.to_string()),
+                    BorrowedAttributeValue::Float(value) => Some(value.to_string()),
+                    BorrowedAttributeValue::Int(value) => Some(value.to_string()),
+                    BorrowedAttributeValue::Bool(value) => Some(value.to_string()),
+                    BorrowedAttributeValue::Any(_) => {
+                        log::warn!("Can't render the attribute {:?} to a string", name);
+                        return;
+                    }
+                    BorrowedAttributeValue::None => None,
+                };
+                self.set_attribute(self.node(id), name, ns, value);
+            }
+            Mutation::SetText { value, id } => self.set_text(self.node(id), value),
+            Mutation::SetMultiText { values } => {
+                for (id, value) in values {
+                    self.set_text(self.node(id), value);
+                }
+            }
+            Mutation::Remove { id } => {
+                let node = self.node(id);
+                self.detach(node);
+            }
+            Mutation::PushRoot { id } => self.stack.push(self.node(id)),
+
+            // Strings don't have listeners
+            Mutation::NewEventListener { .. } | Mutation::RemoveEventListener { .. } => {}
+        }
+    }
+}
+
+impl dioxus_core::Renderer for MutationRenderer {
+    fn apply(&mut self, mutations: Mutations) {
+        for template in mutations.templates {
+            self.templates.insert(template.name, template);
+        }
+        for edit in mutations.edits {
+            self.apply_edit(edit);
+        }
+    }
+
+    fn set_multi_text(&mut self, values: &[(ElementId, &str)]) {
+        for (id, value) in values {
+            self.set_text(self.node(*id), value);
+        }
+    }
+
+    fn move_node(&mut self, root: ElementId, target: ElementId, before: bool) {
+        self.apply_edit(Mutation::MoveNode {
+            id: root,
+            target,
+            before,
+        });
+    }
+}
+
+/// Build a VirtualDom and render the mutations of its first render to a string with a [`MutationRenderer`]
+///
+/// The VirtualDom must not have been rebuilt yet, since this rebuilds it to get the mutations.
+pub fn render_to_string(dom: &mut VirtualDom) -> String {
+    use dioxus_core::Renderer;
+
+    let mut renderer = MutationRenderer::new();
+    renderer.apply(dom.rebuild());
+    renderer.to_html()
+}
+
+fn escape(text: &str) -> askama_escape::Escaped<'_, askama_escape::Html> {
+    askama_escape::escape(text, askama_escape::Html)
+}
@@ -1,6 +1,8 @@
 use super::cache::Segment;
 use crate::cache::StringCache;
-use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn};
+use dioxus_core::{
+    prelude::*, AttributeValue, BorrowedAttributeValue, DynamicNode, ElementId, RenderReturn,
+};
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::Arc;
@@ -26,6 +28,10 @@ pub struct Renderer {
 
     /// A cache of templates that have been rendered
     template_cache: HashMap<&'static str, Arc<StringCache>>,
+
+    /// Write markers for suspended components instead of skipping them, collecting them in `suspended`
+    pub(crate) suspense_markers: bool,
+    pub(crate) suspended: Vec<ScopeId>,
 }
 
 impl Renderer {
@@ -43,6 +49,29 @@ impl Renderer {
         self.render_scope(buf, dom, ScopeId(0))
     }
 
+    /// Write the HTML of the VirtualDom to `out` as it is rendered, without building the whole page in memory first
+    ///
+    /// Each piece of HTML is written as soon as it is ready, so wrap unbuffered writers like sockets in a
+    /// [`std::io::BufWriter`].
+    ///
+    /// ```rust, ignore
+    /// let mut out = std::io::BufWriter::new(stream);
+    /// dioxus_ssr::Renderer::new().render_to_io(&mut out, &dom)?;
+    /// ```
+    pub fn render_to_io(
+        &mut self,
+        out: &mut impl std::io::Write,
+        dom: &VirtualDom,
+    ) -> std::io::Result<()> {
+        let mut writer = IoWriter { out, error: None };
+        match self.render_to(&mut writer, dom) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(writer.error.unwrap_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::Other, "failed to render the html")
+            })),
+        }
+    }
+
     pub fn render_scope(
         &mut self,
         buf: &mut impl Write,
@@ -52,12 +81,49 @@ impl Renderer {
         // We should never ever run into async or errored nodes in SSR
         // Error boundaries and suspense boundaries will convert these to sync
         if let RenderReturn::Ready(node) = dom.get_scope(scope).unwrap().root_node() {
+            self.render_root_portals(buf, dom, node)?;
             self.render_template(buf, dom, node)?
         };
 
         Ok(())
     }
 
+    /// Write the children of the portals under a node that mount to the root of the app
+    ///
+    /// The client creates the children of a portal before the portal's parent, and appends them to the root before the
+    /// rest of the app. Writing them in the same order lets the page be hydrated.
+    fn render_root_portals(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        template: &VNode,
+    ) -> std::fmt::Result {
+        // Written in the order the client mounts them
+        for node in template.dynamic_nodes_in_creation_order() {
+            match node {
+                DynamicNode::Component(node) => {
+                    let scope = dom.get_scope(node.mounted_scope().unwrap()).unwrap();
+                    if let RenderReturn::Ready(node) = scope.root_node() {
+                        self.render_root_portals(buf, dom, node)?;
+                        if let Some(mount) = scope.portal_mount() {
+                            if mount.target == ElementId(0) {
+                                self.render_template(buf, dom, node)?;
+                            }
+                        }
+                    }
+                }
+                DynamicNode::Fragment(nodes) => {
+                    for node in *nodes {
+                        self.render_root_portals(buf, dom, node)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
     fn render_template(
         &mut self,
         buf: &mut impl Write,
@@ -88,6 +154,10 @@ impl Renderer {
                             AttributeValue::Text(value) => {
                                 write!(buf, " {}=\"{}\"", attr.name, value)?
                             }
+                            // Boolean attributes are turned off by leaving them out
+                            AttributeValue::Bool(false)
+                                if BorrowedAttributeValue::Bool(false)
+                                    .removes_attribute(attr.name) => {}
                             AttributeValue::Bool(value) => write!(buf, " {}={}", attr.name, value)?,
                             AttributeValue::Int(value) => write!(buf, " {}={}", attr.name, value)?,
                             AttributeValue::Float(value) => {
@@ -106,12 +176,25 @@ impl Renderer {
                             let scope = dom.get_scope(id).unwrap();
                             let node = scope.root_node();
                             match node {
+                                // The children of a portal aren't under it, so only its placeholder is written here
+                                _ if scope.portal_mount().is_some() => {
+                                    if self.pre_render {
+                                        write!(buf, "<pre></pre>")?;
+                                    }
+                                }
                                 RenderReturn::Ready(node) => {
                                     self.render_template(buf, dom, node)?
                                 }
-                                _ => todo!(
-                                    "generally, scopes should be sync, only if being traversed"
-                                ),
+                                RenderReturn::Aborted(_) if self.suspense_markers => {
+                                    write!(buf, "<!--ds:{}--><!--/ds:{}-->", id.0, id.0)?;
+                                    self.suspended.push(id);
+                                }
+                                // A scope that is still suspended renders like a placeholder
+                                RenderReturn::Aborted(_) => {
+                                    if self.pre_render {
+                                        write!(buf, "<pre></pre>")?;
+                                    }
+                                }
                             }
                         }
                     }
@@ -121,11 +204,16 @@ impl Renderer {
                             write!(buf, "<!--#-->")?;
                         }
 
-                        write!(
-                            buf,
-                            "{}",
-                            askama_escape::escape(text.value, askama_escape::Html)
-                        )?;
+                        // Unescaped text has promised us it's already safe to write out as-is
+                        if text.is_unescaped() {
+                            write!(buf, "{}", text.value)?;
+                        } else {
+                            write!(
+                                buf,
+                                "{}",
+                                askama_escape::escape(text.value, askama_escape::Html)
+                            )?;
+                        }
 
                         if self.pre_render {
                             write!(buf, "<!--#-->")?;
@@ -193,6 +281,21 @@ impl Renderer {
     }
 }
 
+/// Forwards the HTML to an [`std::io::Write`], keeping the io error that [`std::fmt::Error`] can't carry
+struct IoWriter<'a, W> {
+    out: &'a mut W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write> Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.out.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            std::fmt::Error
+        })
+    }
+}
+
 #[test]
 fn to_string_works() {
     use dioxus::prelude::*;
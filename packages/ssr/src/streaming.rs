@@ -0,0 +1,142 @@
+//! Stream the HTML of a VirtualDom, sending suspended components as they resolve
+//!
+//! The first chunk is the shell: everything that is ready, with a pair of comment markers in place of each suspended
+//! component. The markers are named after the [`ScopeId`] of the component:
+//!
+//! ```html
+//! <!--ds:3--><!--/ds:3-->
+//! ```
+//!
+//! When the shell has any markers, it ends with a script that defines `__dxSwap`. Each later chunk is the HTML of one
+//! component that resolved, in the order they resolve, followed by a call that swaps it in between its markers and
+//! removes the markers:
+//!
+//! ```html
+//! <template id="ds-3"><p>loaded</p></template><script>__dxSwap(3)</script>
+//! ```
+//!
+//! The HTML of a chunk can have markers of its own, for components under it that suspended in turn. Their chunks
+//! always come after it.
+
+use crate::Renderer;
+use dioxus_core::{RenderReturn, ScopeId, VirtualDom};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Moves the content of a chunk in between the markers of its component
+const SWAP_SCRIPT: &str = r#"function __dxSwap(id){var w=document.createTreeWalker(document,NodeFilter.SHOW_COMMENT),s=null,e=null;while(w.nextNode()){var d=w.currentNode.data;if(d==="ds:"+id)s=w.currentNode;else if(d==="/ds:"+id){e=w.currentNode;break}}var t=document.getElementById("ds-"+id);if(!s||!e||!t)return;while(s.nextSibling!==e)s.parentNode.removeChild(s.nextSibling);e.parentNode.insertBefore(t.content,e);s.remove();e.remove();t.remove()}"#;
+
+/// Renders a VirtualDom to a shell and a chunk for each suspended component once it resolves
+#[derive(Default)]
+pub struct StreamingRenderer {
+    renderer: Renderer,
+    // The suspended components that were sent as markers, with the instance of the scope that rendered them
+    pending: Vec<(ScopeId, usize)>,
+}
+
+impl StreamingRenderer {
+    /// Create a new streaming renderer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the inner renderer.
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+
+    /// Get the inner renderer mutably.
+    pub fn renderer_mut(&mut self) -> &mut Renderer {
+        &mut self.renderer
+    }
+
+    /// Check if every suspended component has been sent
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Render everything that is ready, with markers for the suspended components
+    ///
+    /// The VirtualDom must have been rebuilt.
+    pub fn render_shell(&mut self, dom: &VirtualDom) -> String {
+        let mut html = self.render_with_markers(dom, ScopeId(0));
+        if !self.pending.is_empty() {
+            html.push_str("<script>");
+            html.push_str(SWAP_SCRIPT);
+            html.push_str("</script>");
+        }
+        html
+    }
+
+    /// Wait for the next suspended component to resolve and render its chunk
+    ///
+    /// Returns `None` once every suspended component has been sent.
+    pub async fn next_chunk(&mut self, dom: &mut VirtualDom) -> Option<String> {
+        loop {
+            // Components that were unmounted while they were suspended will never resolve. Their ScopeId can be reused
+            // by another component, which doesn't belong between their markers.
+            self.pending.retain(|(id, instance)| {
+                dom.get_scope(*id).map(|scope| scope.instance()) == Some(*instance)
+            });
+
+            if self.pending.is_empty() {
+                return None;
+            }
+
+            let resolved = self.pending.iter().position(|(id, _)| {
+                let root = dom.get_scope(*id).and_then(|scope| scope.try_root_node());
+                matches!(root, Some(RenderReturn::Ready(_)))
+            });
+
+            if let Some(idx) = resolved {
+                let (id, _) = self.pending.remove(idx);
+                let html = self.render_with_markers(dom, id);
+                return Some(format!(
+                    r#"<template id="ds-{}">{}</template><script>__dxSwap({})</script>"#,
+                    id.0, html, id.0
+                ));
+            }
+
+            _ = dom.wait_for_suspense_step().await;
+        }
+    }
+
+    fn render_with_markers(&mut self, dom: &VirtualDom, scope: ScopeId) -> String {
+        let mut html = String::new();
+        self.renderer.suspense_markers = true;
+        self.renderer.render_scope(&mut html, dom, scope).unwrap();
+        self.renderer.suspense_markers = false;
+        self.pending.extend(
+            self.renderer
+                .suspended
+                .drain(..)
+                .filter_map(|id| dom.get_scope(id).map(|scope| (id, scope.instance()))),
+        );
+        html
+    }
+}
+
+/// Write the HTML of a VirtualDom to `out`, writing each suspended component as soon as it resolves
+///
+/// The VirtualDom must have been rebuilt. See the [module docs](self) for the format of the chunks.
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// _ = dom.rebuild();
+/// dioxus_ssr::streaming::render_streaming(&mut dom, &mut response).await?;
+/// ```
+pub async fn render_streaming(
+    dom: &mut VirtualDom,
+    out: &mut (impl AsyncWrite + Unpin),
+) -> std::io::Result<()> {
+    let mut renderer = StreamingRenderer::new();
+
+    out.write_all(renderer.render_shell(dom).as_bytes()).await?;
+    out.flush().await?;
+
+    while let Some(chunk) = renderer.next_chunk(dom).await {
+        out.write_all(chunk.as_bytes()).await?;
+        out.flush().await?;
+    }
+
+    Ok(())
+}
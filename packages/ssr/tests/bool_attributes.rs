@@ -0,0 +1,65 @@
+//! A `false` boolean attribute is left out instead of being written as "false"
+//!
+//! Enumerated attributes like `spellcheck` and ARIA attributes keep their "false", since that is what turns them off.
+use dioxus::prelude::*;
+use dioxus_core::Renderer;
+use dioxus_ssr::MutationRenderer;
+use std::cell::Cell;
+
+thread_local! {
+    static PRESSED: Cell<bool> = Cell::new(true);
+}
+
+fn app(cx: Scope) -> Element {
+    let pressed = PRESSED.with(Cell::get);
+
+    render! {
+        button { disabled: pressed, spellcheck: pressed, aria_pressed: pressed, "Press" }
+    }
+}
+
+#[test]
+fn false_removes_the_attribute() {
+    PRESSED.with(|p| p.set(false));
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<button spellcheck=false aria-pressed=false>Press</button>"
+    );
+}
+
+#[test]
+fn setting_false_removes_the_attribute() {
+    PRESSED.with(|p| p.set(true));
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MutationRenderer::new();
+    renderer.apply(dom.rebuild());
+    assert_eq!(
+        renderer.to_html(),
+        "<button disabled=\"true\" spellcheck=\"true\" aria-pressed=\"true\">Press</button>"
+    );
+
+    PRESSED.with(|p| p.set(false));
+    dom.mark_dirty(ScopeId(0));
+    renderer.apply(dom.render_immediate());
+    assert_eq!(
+        renderer.to_html(),
+        "<button spellcheck=\"false\" aria-pressed=\"false\">Press</button>"
+    );
+}
+
+#[test]
+fn listeners_are_left_out() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            button { onclick: |_| {}, disabled: false, "Press" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dioxus_ssr::render(&dom), "<button>Press</button>");
+}
@@ -0,0 +1,125 @@
+//! Rendering a VirtualDom to a string by applying its mutations
+use dioxus::prelude::*;
+use dioxus_core::{BorrowedAttributeValue, ElementId, Mutation, Mutations, Renderer};
+use dioxus_ssr::{render_to_string, MutationRenderer};
+use std::cell::Cell;
+
+fn jack_and_jill(cx: Scope) -> Element {
+    let lines = ["Jack and Jill went up the hill", "To fetch a pail of water"];
+    let title = "Jack & Jill";
+
+    render! {
+        div { class: "rhyme", "data-title": "{title}",
+            h1 { "{title}" }
+            lines.iter().map(|line| rsx! {
+                p { key: "{line}", "{line}" br {} }
+            })
+            img { src: "hill.png", alt: "A \"big\" hill" }
+            input { value: "<pail>" }
+            svg { width: "10", height: "10",
+                circle { cx: "5", cy: "5", r: "4" }
+            }
+        }
+    }
+}
+
+#[test]
+fn renders_jack_and_jill() {
+    let mut dom = VirtualDom::new(jack_and_jill);
+
+    assert_eq!(
+        render_to_string(&mut dom),
+        concat!(
+            r#"<div class="rhyme" data-title="Jack &amp; Jill">"#,
+            "<h1>Jack &amp; Jill</h1>",
+            "<p>Jack and Jill went up the hill<br/></p>",
+            "<p>To fetch a pail of water<br/></p>",
+            r#"<img src="hill.png" alt="A &quot;big&quot; hill"/>"#,
+            r#"<input value="&lt;pail&gt;"/>"#,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">"#,
+            r#"<circle cx="5" cy="5" r="4"></circle>"#,
+            "</svg></div>"
+        )
+    );
+}
+
+#[test]
+fn follows_diffs() {
+    thread_local! {
+        static COUNT: Cell<usize> = Cell::new(0);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let count = COUNT.with(Cell::get);
+
+        render! {
+            p { "{count} items" }
+            ul { (0..count).rev().map(|i| rsx! { li { key: "{i}", "{i}" } }) }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MutationRenderer::new();
+    renderer.apply(dom.rebuild());
+    assert_eq!(renderer.to_html(), "<p>0 items</p><ul></ul>");
+
+    for count in [3, 1] {
+        COUNT.with(|c| c.set(count));
+        dom.mark_dirty(ScopeId(0));
+        renderer.apply(dom.render_immediate());
+    }
+    assert_eq!(renderer.to_html(), "<p>1 items</p><ul><li>0</li></ul>");
+
+    // The tree matches what the template renderer makes of the same VirtualDom
+    assert_eq!(renderer.to_html(), dioxus_ssr::render(&dom));
+}
+
+#[test]
+fn ids_can_be_assigned_along_paths_built_at_runtime() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            ul {
+                li { "first" }
+                li { "second" }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let mut renderer = MutationRenderer::new();
+    let edits = dom.rebuild();
+    let list = edits
+        .edits
+        .iter()
+        .find_map(|edit| match edit {
+            Mutation::LoadTemplate { id, .. } => Some(*id),
+            _ => None,
+        })
+        .unwrap();
+    renderer.apply(edits);
+
+    // The path doesn't come from a template, it only lives as long as the mutations
+    let second = vec![1];
+    renderer.apply(Mutations {
+        edits: vec![
+            Mutation::PushRoot { id: list },
+            Mutation::AssignId {
+                path: &second,
+                id: ElementId(100),
+            },
+            Mutation::SetAttribute {
+                name: "class",
+                value: BorrowedAttributeValue::Text("selected"),
+                id: ElementId(100),
+                ns: None,
+            },
+        ],
+        ..Default::default()
+    });
+    drop(second);
+
+    assert_eq!(
+        renderer.to_html(),
+        "<ul><li>first</li><li class=\"selected\">second</li></ul>"
+    );
+}
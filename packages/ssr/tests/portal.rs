@@ -0,0 +1,70 @@
+//! Rendering portals where the client creates their children
+use dioxus::prelude::*;
+use dioxus_ssr::render_to_string;
+
+fn modal(cx: Scope) -> Element {
+    render! {
+        div { "content"
+            Portal {
+                p { "modal" }
+                Portal { p { "nested" } }
+            }
+        }
+        p { "footer" }
+    }
+}
+
+#[test]
+fn root_portals_are_written_where_the_client_mounts_them() {
+    let mut dom = VirtualDom::new(modal);
+    _ = dom.rebuild();
+
+    let html = dioxus_ssr::render(&dom);
+    assert_eq!(
+        html,
+        "<p>nested</p><p>modal</p><div>content</div><p>footer</p>"
+    );
+
+    // The client appends the same nodes to the root
+    assert_eq!(html, render_to_string(&mut VirtualDom::new(modal)));
+}
+
+#[test]
+fn portals_outside_of_the_app_are_left_to_the_client() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div { "content"
+                Portal { selector: "body", p { "tooltip" } }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dioxus_ssr::render(&dom), "<div>content</div>");
+    assert_eq!(
+        dioxus_ssr::pre_render(&dom),
+        "<div>content<pre></pre></div>"
+    );
+}
+
+#[test]
+fn sibling_portals_are_written_in_the_order_the_client_mounts_them() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            main {
+                Portal { p { "first" } }
+                Portal { p { "second" } }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // The portals under an element are created last to first
+    let html = dioxus_ssr::render(&dom);
+    assert_eq!(html, "<p>second</p><p>first</p><main></main>");
+    assert_eq!(html, render_to_string(&mut VirtualDom::new(app)));
+}
@@ -0,0 +1,37 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    match cx.render_target() {
+        RenderTarget::Screen => render! { button { "Share" } },
+        RenderTarget::Print => render! { p { "Printed from example.com" } },
+    }
+}
+
+#[test]
+fn renders_for_screen_by_default() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(dioxus_ssr::render(&dom), "<button>Share</button>");
+}
+
+#[test]
+fn renders_for_print() {
+    let mut dom = VirtualDom::new(app).with_render_target(RenderTarget::Print);
+    _ = dom.rebuild();
+
+    assert_eq!(dioxus_ssr::render(&dom), "<p>Printed from example.com</p>");
+}
+
+#[test]
+fn lazy_print_reaches_children() {
+    fn Footer(cx: Scope) -> Element {
+        let target = cx.render_target();
+        render! { "{target:?}" }
+    }
+
+    assert_eq!(dioxus_ssr::render_lazy(rsx! { Footer {} }), "Screen");
+    assert_eq!(dioxus_ssr::render_lazy_print(rsx! { Footer {} }), "Print");
+}
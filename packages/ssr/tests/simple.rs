@@ -74,3 +74,57 @@ fn fragments() {
         "<div></div>"
     );
 }
+
+#[test]
+fn render_to_io() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            ul {
+                (0..3).map(|i| rsx! {
+                    li { class: "item-{i}", "item {i}" }
+                })
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // The html is written piece by piece, and adds up to the same page
+    struct Chunks(Vec<Vec<u8>>);
+    impl std::io::Write for Chunks {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.push(buf.to_vec());
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut chunks = Chunks(Vec::new());
+    dioxus_ssr::Renderer::new()
+        .render_to_io(&mut chunks, &dom)
+        .unwrap();
+    assert!(chunks.0.len() > 1);
+    assert_eq!(
+        String::from_utf8(chunks.0.concat()).unwrap(),
+        dioxus_ssr::render(&dom)
+    );
+
+    // Errors from the writer are passed through
+    struct Closed;
+    impl std::io::Write for Closed {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = dioxus_ssr::Renderer::new()
+        .render_to_io(&mut Closed, &dom)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+}
@@ -0,0 +1,129 @@
+#![allow(non_snake_case)]
+
+//! Streaming sends the shell first, then each suspended component as it resolves
+use dioxus::prelude::*;
+use dioxus_ssr::StreamingRenderer;
+use std::cell::{Cell, RefCell};
+use tokio::sync::oneshot;
+
+thread_local! {
+    static GATES: RefCell<Vec<Option<oneshot::Sender<()>>>> = RefCell::new(Vec::new());
+    static SHOWN: Cell<Option<usize>> = Cell::new(Some(0));
+}
+
+fn open_gate(idx: usize) {
+    GATES.with(|g| g.borrow_mut()[idx].take().unwrap().send(()).unwrap());
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        main {
+            "shell"
+            (0..3).map(|idx| rsx! { Slow { key: "{idx}", idx: idx } })
+        }
+    }
+}
+
+/// Suspends until its gate opens
+#[inline_props]
+fn Slow(cx: Scope, idx: usize) -> Element {
+    let open = use_state(cx, || false);
+    cx.use_hook(|| {
+        let (tx, rx) = oneshot::channel();
+        GATES.with(|g| g.borrow_mut().push(Some(tx)));
+        let open = open.clone();
+        cx.spawn(async move {
+            if rx.await.is_ok() {
+                open.set(true);
+            }
+        });
+    });
+
+    if !**open {
+        return cx.suspend()?;
+    }
+
+    render! { p { "slow {idx}" } }
+}
+
+#[tokio::test]
+async fn boundaries_stream_in_the_order_they_resolve() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = StreamingRenderer::new();
+    let shell = renderer.render_shell(&dom);
+    assert!(shell.starts_with(
+        "<main>shell<!--ds:1--><!--/ds:1--><!--ds:2--><!--/ds:2--><!--ds:3--><!--/ds:3--></main><script>"
+    ));
+    assert!(shell.contains("function __dxSwap(id)"));
+
+    for idx in (0..3).rev() {
+        open_gate(idx);
+        let chunk = renderer.next_chunk(&mut dom).await.unwrap();
+        let id = idx + 1;
+        assert_eq!(
+            chunk,
+            format!(
+                r#"<template id="ds-{id}"><p>slow {idx}</p></template><script>__dxSwap({id})</script>"#
+            )
+        );
+    }
+
+    assert!(renderer.is_finished());
+    assert!(renderer.next_chunk(&mut dom).await.is_none());
+}
+
+#[tokio::test]
+async fn writes_chunks_to_an_async_sink() {
+    fn app(cx: Scope) -> Element {
+        render! { div { Slow { idx: 0 } } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut out = Vec::new();
+    let render = dioxus_ssr::render_streaming(&mut dom, &mut out);
+    let open = async {
+        tokio::task::yield_now().await;
+        open_gate(0);
+    };
+    let (written, _) = tokio::join!(render, open);
+    written.unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("<div><!--ds:1--><!--/ds:1--></div><script>"));
+    assert!(out
+        .ends_with(r#"<template id="ds-1"><p>slow 0</p></template><script>__dxSwap(1)</script>"#));
+}
+
+#[tokio::test]
+async fn reused_scope_ids_are_not_swapped_in() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                SHOWN.with(Cell::get).map(|idx| rsx! { Slow { key: "{idx}", idx: idx } })
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = StreamingRenderer::new();
+    let shell = renderer.render_shell(&dom);
+    assert!(shell.starts_with("<div><!--ds:1--><!--/ds:1--></div><script>"));
+
+    // Unmount the suspended component, then mount another one that gets its ScopeId
+    SHOWN.with(|s| s.set(None));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    SHOWN.with(|s| s.set(Some(1)));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(dom.get_scope(ScopeId(1)).unwrap().name(), "Slow");
+
+    open_gate(1);
+    assert!(renderer.next_chunk(&mut dom).await.is_none());
+}
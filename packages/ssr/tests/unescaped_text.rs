@@ -0,0 +1,39 @@
+use dioxus::prelude::*;
+
+#[test]
+fn unescaped_text_skips_escaping() {
+    fn app(cx: Scope) -> Element {
+        let escaped = "Fish &amp; Chips &lt;3";
+        let plain = "Fish & Chips <3";
+
+        render! {
+            p { cx.unescaped_text_node(format_args!("{escaped}")) }
+            p { "{plain}" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<p>Fish &amp; Chips &lt;3</p><p>Fish &amp; Chips &lt;3</p>"
+    );
+}
+
+#[test]
+fn unescaped_text_pre_renders_with_markers() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div { cx.unescaped_text_node(format_args!("&copy; 2023")) }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::pre_render(&dom),
+        "<div><!--#-->&copy; 2023<!--#--></div>"
+    );
+}
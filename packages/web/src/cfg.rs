@@ -1,3 +1,5 @@
+use crate::ExternalAttributePolicy;
+
 ///  Configuration for the WebSys renderer for the Dioxus VirtualDOM.
 ///
 /// This struct helps configure the specifics of hydration and render destination for WebSys.
@@ -13,6 +15,8 @@ pub struct Config {
     pub(crate) rootname: String,
     pub(crate) cached_strings: Vec<String>,
     pub(crate) default_panic_hook: bool,
+    pub(crate) external_attributes: Option<ExternalAttributePolicy>,
+    pub(crate) mutation_budget: Option<usize>,
 }
 
 impl Default for Config {
@@ -23,6 +27,8 @@ impl Default for Config {
             rootname: "main".to_string(),
             cached_strings: Vec::new(),
             default_panic_hook: true,
+            external_attributes: None,
+            mutation_budget: None,
         }
     }
 }
@@ -71,4 +77,24 @@ impl Config {
         self.default_panic_hook = f;
         self
     }
+
+    /// Watch for attributes that code outside of Dioxus changes on elements Dioxus manages.
+    ///
+    /// This is useful when a JavaScript library modifies elements rendered by Dioxus. The policy decides if the
+    /// VirtualDom leaves those attributes alone or overwrites them with a warning. By default external changes are not
+    /// tracked.
+    pub fn with_external_attributes(mut self, policy: ExternalAttributePolicy) -> Self {
+        self.external_attributes = Some(policy);
+        self
+    }
+
+    /// Spread large renders over several frames, applying about `budget` mutations per frame.
+    ///
+    /// The browser gets to paint and handle input between the frames, so a render that touches thousands of nodes
+    /// doesn't freeze the page. Work is only split between components, so render big lists as a list of components.
+    /// By default every render is applied in a single frame.
+    pub fn with_mutation_budget(mut self, budget: usize) -> Self {
+        self.mutation_budget = Some(budget);
+        self
+    }
 }
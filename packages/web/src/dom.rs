@@ -8,29 +8,50 @@
 //! - Partial delegation?>
 
 use dioxus_core::{
-    BorrowedAttributeValue, ElementId, Mutation, Template, TemplateAttribute, TemplateNode,
+    BorrowedAttributeValue, ElementId, EventThrottle, ListenerFlags, Mutation, Template,
+    TemplateAttribute, TemplateNode,
 };
+use dioxus_hooks::{Navigation, NavigationBackend, TimerBackend};
 use dioxus_html::{event_bubbles, CompositionData, FormData, MountedData};
 use dioxus_interpreter_js::{get_node, minimal_bindings, save_template, Channel};
 use futures_channel::mpsc;
 use js_sys::Array;
-use rustc_hash::FxHashMap;
-use std::{any::Any, rc::Rc};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{any::Any, cell::RefCell, future::Future, pin::Pin, rc::Rc, time::Duration};
 use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsCast, JsValue};
 use web_sys::{Document, Element, Event};
 
-use crate::Config;
+use crate::{external::ExternalAttributeObserver, Config};
 
 pub struct WebsysDom {
     document: Document,
     #[allow(dead_code)]
     pub(crate) root: Element,
     templates: FxHashMap<String, u32>,
+    // The roots of each template, by index, that have an element with a static `autofocus` attribute
+    autofocus_roots: FxHashSet<(u32, usize)>,
     max_template_id: u32,
     pub(crate) interpreter: Channel,
     event_channel: mpsc::UnboundedSender<UiEvent>,
+    pub(crate) listener_flags: ListenerFlagsMap,
+    throttled: ThrottledEventsRef,
+    external: Option<ExternalAttributeObserver>,
+    // Elements that got an input or change event since the last edits were applied
+    controlled: Vec<ElementId>,
 }
 
+/// The modifiers of every mounted listener, keyed by element and event name (without the "on" prefix)
+pub(crate) type ListenerFlagsMap = Rc<RefCell<FxHashMap<(ElementId, String), ListenerFlags>>>;
+
+/// Events held back by listeners with the `throttle` modifier
+#[derive(Default)]
+struct ThrottledEvents {
+    events: EventThrottle<UiEvent>,
+    flush_scheduled: bool,
+}
+
+type ThrottledEventsRef = Rc<RefCell<ThrottledEvents>>;
+
 pub struct UiEvent {
     pub name: String,
     pub bubbles: bool,
@@ -48,15 +69,32 @@ impl WebsysDom {
             None => document.create_element("body").ok().unwrap(),
         };
         let interpreter = Channel::default();
+        let listener_flags = ListenerFlagsMap::default();
+        let throttled = ThrottledEventsRef::default();
 
         let handler: Closure<dyn FnMut(&Event)> = Closure::wrap(Box::new({
             let event_channel = event_channel.clone();
+            let listener_flags = listener_flags.clone();
+            let throttled = throttled.clone();
+            let mut last_local_event: Option<Event> = None;
             move |event: &web_sys::Event| {
                 let name = event.type_();
-                let element = walk_event_for_id(event);
                 let bubbles = dioxus_html::event_bubbles(name.as_str());
+
+                // Events that don't bubble are listened to on each element with a listener, so one event reaches the
+                // capture listeners of its ancestors before its target. The VirtualDom calls all of them at once.
+                if !bubbles {
+                    if last_local_event.as_ref() == Some(event) {
+                        return;
+                    }
+                    last_local_event = Some(event.clone());
+                }
+
+                let element = walk_event_for_id(event);
                 if let Some((element, target)) = element {
-                    let prevent_event;
+                    let flags = path_flags(&listener_flags.borrow(), &target, &name);
+
+                    let mut prevent_event;
                     if let Some(prevent_requests) = target
                         .get_attribute("dioxus-prevent-default")
                         .as_deref()
@@ -68,6 +106,7 @@ impl WebsysDom {
                     } else {
                         prevent_event = false;
                     }
+                    prevent_event |= flags.prevent_default;
 
                     // Prevent forms from submitting and redirecting
                     if name == "submit" {
@@ -80,12 +119,40 @@ impl WebsysDom {
                     }
 
                     let data = virtual_event_from_websys_event(event.clone(), target);
-                    let _ = event_channel.unbounded_send(UiEvent {
-                        name,
+                    let ui_event = UiEvent {
+                        name: name.clone(),
                         bubbles,
                         element,
                         data,
-                    });
+                    };
+
+                    match flags.throttle {
+                        // Throttled listeners only get the event right away if their window is over
+                        Some((listener, window)) => {
+                            let ready = throttled.borrow_mut().events.offer(
+                                listener,
+                                &name,
+                                window,
+                                event.time_stamp(),
+                                ui_event,
+                            );
+                            match ready {
+                                Some(ui_event) => {
+                                    let _ = event_channel.unbounded_send(ui_event);
+                                }
+                                None => schedule_throttle_flush(&throttled, &event_channel),
+                            }
+                        }
+                        None => {
+                            let _ = event_channel.unbounded_send(ui_event);
+                        }
+                    }
+
+                    // The VirtualDom stops bubbling between dioxus listeners, this keeps the native event from reaching
+                    // any listeners outside of the root
+                    if flags.stop_propagation {
+                        event.stop_propagation();
+                    }
                 }
             }
         }));
@@ -95,13 +162,23 @@ impl WebsysDom {
             handler.as_ref().unchecked_ref(),
         );
         handler.forget();
+
+        let external = cfg
+            .external_attributes
+            .map(|policy| ExternalAttributeObserver::new(&root, policy));
+
         Self {
             document,
             root,
             interpreter,
             templates: FxHashMap::default(),
+            autofocus_roots: FxHashSet::default(),
             max_template_id: 0,
             event_channel,
+            listener_flags,
+            throttled,
+            external,
+            controlled: Vec::new(),
         }
     }
 
@@ -113,7 +190,10 @@ impl WebsysDom {
         for template in templates {
             let mut roots = vec![];
 
-            for root in template.roots {
+            for (index, root) in template.roots.iter().enumerate() {
+                if has_autofocus(root) {
+                    self.autofocus_roots.insert((self.max_template_id, index));
+                }
                 roots.push(self.create_template_node(root))
             }
 
@@ -168,17 +248,72 @@ impl WebsysDom {
         }
     }
 
+    /// Keep the element of an input or change event at the value, checked and selected state the VirtualDom renders
+    ///
+    /// The element is put back once the edits of the next render are applied, even if the render has nothing to change.
+    pub(crate) fn restore_controlled_after(&mut self, event: &UiEvent) {
+        if matches!(event.name.as_str(), "input" | "change") {
+            self.controlled.push(event.element);
+        }
+    }
+
+    /// Forget the listeners of a node that is about to be removed and its children, since their ids will be reused
+    fn forget_listeners(&self, id: ElementId) {
+        let mut ids = vec![id];
+        if let Some(element) = get_node(id.0 as u32).dyn_ref::<Element>() {
+            if let Ok(children) = element.query_selector_all("[data-dioxus-id]") {
+                for index in 0..children.length() {
+                    let child = children
+                        .get(index)
+                        .and_then(|child| child.dyn_into::<Element>().ok())
+                        .and_then(|child| child.get_attribute("data-dioxus-id"))
+                        .and_then(|id| id.parse().ok());
+                    ids.extend(child.map(ElementId));
+                }
+            }
+        }
+
+        let mut throttled = self.throttled.borrow_mut();
+        self.listener_flags.borrow_mut().retain(|(id, name), _| {
+            let removed = ids.contains(id);
+            if removed {
+                throttled.events.remove(*id, name);
+            }
+            !removed
+        });
+    }
+
     pub fn apply_edits(&mut self, mut edits: Vec<Mutation>) {
         use Mutation::*;
         let i = &mut self.interpreter;
         // we need to apply the mount events last, so we collect them here
         let mut to_mount = Vec::new();
+        // Browsers only focus autofocus elements that are in the page when it loads, so the renderer focuses the
+        // elements it creates after they are in the page
+        let mut to_focus = Vec::new();
+        if let Some(external) = &self.external {
+            external.collect_external();
+        }
         for edit in &edits {
+            if let SetAttribute {
+                name: "autofocus",
+                value,
+                id,
+                ..
+            } = edit
+            {
+                if !value.removes_attribute("autofocus") {
+                    to_focus.push(*id);
+                }
+            }
             match edit {
                 AppendChildren { id, m } => i.append_children(id.0 as u32, *m as u32),
                 AssignId { path, id } => {
                     i.assign_id(path.as_ptr() as u32, path.len() as u8, id.0 as u32)
                 }
+                AssignExternalRoot { selector, id } => {
+                    i.assign_external_root(selector, id.0 as u32)
+                }
                 CreatePlaceholder { id } => i.create_placeholder(id.0 as u32),
                 CreateTextNode { value, id } => i.create_text_node(value, id.0 as u32),
                 HydrateText { path, value, id } => {
@@ -186,15 +321,39 @@ impl WebsysDom {
                 }
                 LoadTemplate { name, index, id } => {
                     if let Some(tmpl_id) = self.templates.get(*name) {
-                        i.load_template(*tmpl_id, *index as u32, id.0 as u32)
+                        i.load_template(*tmpl_id, *index as u32, id.0 as u32);
+                        if self.autofocus_roots.contains(&(*tmpl_id, *index)) {
+                            to_focus.push(*id);
+                        }
                     }
                 }
-                ReplaceWith { id, m } => i.replace_with(id.0 as u32, *m as u32),
+                ReplaceWith { id, m } => {
+                    forget_external(&self.external, *id);
+                    self.forget_listeners(*id);
+                    i.replace_with(id.0 as u32, *m as u32)
+                }
                 ReplacePlaceholder { path, m } => {
                     i.replace_placeholder(path.as_ptr() as u32, path.len() as u8, *m as u32)
                 }
                 InsertAfter { id, m } => i.insert_after(id.0 as u32, *m as u32),
                 InsertBefore { id, m } => i.insert_before(id.0 as u32, *m as u32),
+                MoveNode { id, target, before } => {
+                    i.push_root(id.0 as u32);
+                    match before {
+                        true => i.insert_before(target.0 as u32, 1),
+                        false => i.insert_after(target.0 as u32, 1),
+                    }
+                }
+                SetAttribute { name, id, .. }
+                    if !should_set_attribute(&self.external, *id, name) => {}
+                SetAttribute {
+                    name,
+                    value,
+                    id,
+                    ns,
+                } if value.removes_attribute(name) => {
+                    i.remove_attribute(id.0 as u32, name, ns.unwrap_or_default())
+                }
                 SetAttribute {
                     name,
                     value,
@@ -216,36 +375,76 @@ impl WebsysDom {
                         if *b { "true" } else { "false" },
                         ns.unwrap_or_default(),
                     ),
-                    BorrowedAttributeValue::None => {
-                        i.remove_attribute(id.0 as u32, name, ns.unwrap_or_default())
-                    }
                     _ => unreachable!(),
                 },
                 SetText { value, id } => i.set_text(id.0 as u32, value),
-                NewEventListener { name, id, .. } => {
+                SetMultiText { values } => {
+                    for (id, value) in values {
+                        i.set_text(id.0 as u32, value)
+                    }
+                }
+                NewEventListener { name, id, flags } => {
+                    self.listener_flags
+                        .borrow_mut()
+                        .insert((*id, name.to_string()), *flags);
                     match *name {
                         // mounted events are fired immediately after the element is mounted.
                         "mounted" => {
                             to_mount.push(*id);
                         }
                         _ => {
-                            i.new_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+                            i.new_event_listener(
+                                name,
+                                id.0 as u32,
+                                event_bubbles(name) as u8,
+                                flags.passive as u8,
+                                flags.capture as u8,
+                            );
                         }
                     }
                 }
-                RemoveEventListener { name, id } => match *name {
-                    "mounted" => {}
-                    _ => {
-                        i.remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
+                RemoveEventListener { name, id, flags } => {
+                    self.listener_flags
+                        .borrow_mut()
+                        .remove(&(*id, name.to_string()));
+                    self.throttled.borrow_mut().events.remove(*id, name);
+                    match *name {
+                        "mounted" => {}
+                        _ => {
+                            i.remove_event_listener(
+                                name,
+                                id.0 as u32,
+                                event_bubbles(name) as u8,
+                                flags.passive as u8,
+                                flags.capture as u8,
+                            );
+                        }
                     }
-                },
-                Remove { id } => i.remove(id.0 as u32),
+                }
+                Remove { id } => {
+                    forget_external(&self.external, *id);
+                    self.forget_listeners(*id);
+                    i.remove(id.0 as u32)
+                }
                 PushRoot { id } => i.push_root(id.0 as u32),
             }
         }
+        // The user may have changed an element in a way the state didn't follow, like typing into an input whose value
+        // is only set when the text is valid. Put the element back to what the VirtualDom rendered, like React does.
+        for id in self.controlled.drain(..) {
+            i.restore_controlled(id.0 as u32);
+        }
+        for id in to_focus {
+            i.autofocus(id.0 as u32);
+        }
         edits.clear();
         i.flush();
 
+        // Everything the observer saw while the edits were applied was caused by the renderer
+        if let Some(external) = &self.external {
+            external.discard_own();
+        }
+
         for id in to_mount {
             let node = get_node(id.0 as u32);
             if let Some(element) = node.dyn_ref::<Element>() {
@@ -263,6 +462,93 @@ impl WebsysDom {
     }
 }
 
+/// Check if a template node or any node under it has a static `autofocus` attribute
+fn has_autofocus(node: &TemplateNode) -> bool {
+    match node {
+        TemplateNode::Element {
+            attrs, children, ..
+        } => {
+            attrs.iter().any(|attr| {
+                matches!(
+                    attr,
+                    TemplateAttribute::Static {
+                        name: "autofocus",
+                        ..
+                    }
+                )
+            }) || children.iter().any(has_autofocus)
+        }
+        _ => false,
+    }
+}
+
+/// Check if an attribute update from the VirtualDom should be written to the DOM
+///
+/// This reads the node before the pending edits are flushed, which is fine because any node created in the same batch
+/// can't have been changed externally yet.
+fn should_set_attribute(
+    external: &Option<ExternalAttributeObserver>,
+    id: ElementId,
+    name: &str,
+) -> bool {
+    match external {
+        Some(external) => external
+            .attributes
+            .borrow_mut()
+            .should_set(&get_node(id.0 as u32), name),
+        None => true,
+    }
+}
+
+/// The modifiers of the listeners an event reaches on its way from its target to the root
+///
+/// Any of the listeners can prevent the default action or stop the native event. Throttling applies to the whole
+/// dispatch, so it is decided by the nearest listener for the event and keyed by the element it is on.
+#[derive(Default)]
+struct PathFlags {
+    prevent_default: bool,
+    stop_propagation: bool,
+    throttle: Option<(ElementId, u32)>,
+}
+
+fn path_flags(
+    listener_flags: &FxHashMap<(ElementId, String), ListenerFlags>,
+    target: &Element,
+    name: &str,
+) -> PathFlags {
+    let mut path = PathFlags::default();
+    let mut nearest = true;
+    let mut current = Some(target.clone());
+    while let Some(element) = current {
+        let id = element
+            .get_attribute("data-dioxus-id")
+            .and_then(|id| id.parse().ok())
+            .map(ElementId);
+        let listener = id.and_then(|id| Some(id).zip(listener_flags.get(&(id, name.to_string()))));
+        if let Some((id, flags)) = listener {
+            path.prevent_default |= flags.prevent_default;
+            path.stop_propagation |= flags.stop_propagation;
+            if nearest {
+                path.throttle = flags.throttle.map(|window| (id, window));
+                nearest = false;
+            }
+        }
+        current = element.parent_element();
+    }
+    path
+}
+
+/// Forget the external changes of a node that is about to be removed, and of everything inside of it
+fn forget_external(external: &Option<ExternalAttributeObserver>, id: ElementId) {
+    if let Some(external) = external {
+        let removed = get_node(id.0 as u32);
+        external
+            .attributes
+            .borrow_mut()
+            .forget(|node| removed.contains(Some(node)));
+    }
+}
+
 // todo: some of these events are being casted to the wrong event type.
 // We need tests that simulate clicks/etc and make sure every event type works.
 pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -> Rc<dyn Any> {
@@ -295,7 +581,10 @@ pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -
         "select" => Rc::new(SelectionData {}),
         "touchcancel" | "touchend" | "touchmove" | "touchstart" => Rc::new(TouchData::from(event)),
 
-        "scroll" => Rc::new(()),
+        "scroll" => Rc::new(ScrollData {
+            scroll_top: target.scroll_top() as f64,
+            scroll_left: target.scroll_left() as f64,
+        }),
         "wheel" => Rc::new(WheelData::from(event)),
         "animationstart" | "animationend" | "animationiteration" => {
             Rc::new(AnimationData::from(event))
@@ -313,6 +602,136 @@ pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -
     }
 }
 
+/// Forward the focus events of the window to the hooks listening for them
+pub(crate) fn listen_for_window_focus(focus: dioxus_hooks::WindowFocus) {
+    let on_focus: Closure<dyn FnMut(&Event)> = Closure::wrap(Box::new(move |event: &Event| {
+        focus.focused(event.time_stamp())
+    }));
+    web_sys::window()
+        .expect("should have access to the Window")
+        .add_event_listener_with_callback("focus", on_focus.as_ref().unchecked_ref())
+        .expect("should be able to listen for focus events");
+    on_focus.forget();
+}
+
+/// Backs the navigation hooks with `window.history`
+struct BrowserHistory;
+
+impl NavigationBackend for BrowserHistory {
+    fn push(&self, path: &str) {
+        let _ = history().push_state_with_url(&JsValue::NULL, "", Some(path));
+    }
+
+    fn replace(&self, path: &str) {
+        let _ = history().replace_state_with_url(&JsValue::NULL, "", Some(path));
+    }
+
+    fn back(&self) {
+        let _ = history().back();
+    }
+
+    fn listen(&self, navigation: &Navigation) {
+        let on_popstate: Closure<dyn FnMut(&Event)> = Closure::wrap(Box::new({
+            let navigation = navigation.clone();
+            move |_: &Event| navigation.popped(browser_location())
+        }));
+        web_sys::window()
+            .expect("should have access to the Window")
+            .add_event_listener_with_callback("popstate", on_popstate.as_ref().unchecked_ref())
+            .expect("should be able to listen for popstate events");
+        on_popstate.forget();
+    }
+}
+
+fn history() -> web_sys::History {
+    web_sys::window()
+        .expect("should have access to the Window")
+        .history()
+        .expect("should have access to the History")
+}
+
+/// The path, query and fragment of the current page
+fn browser_location() -> String {
+    let location = web_sys::window()
+        .expect("should have access to the Window")
+        .location();
+    format!(
+        "{}{}{}",
+        location.pathname().unwrap_or_default(),
+        location.search().unwrap_or_default(),
+        location.hash().unwrap_or_default()
+    )
+}
+
+/// Create the navigation context for the app, updated when the user moves through the browser history
+pub(crate) fn browser_navigation() -> Navigation {
+    Navigation::new(browser_location(), Some(Rc::new(BrowserHistory)))
+}
+
+/// Backs the timeouts of hooks with `setTimeout`
+pub(crate) struct BrowserTimer;
+
+impl TimerBackend for BrowserTimer {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        let timeout = duration.as_millis().min(i32::MAX as u128) as i32;
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            let _ = web_sys::window()
+                .expect("should have access to the Window")
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, timeout);
+        });
+        Box::pin(async move {
+            let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+        })
+    }
+}
+
+/// Make sure a timer is set to dispatch the next event held back by a throttled listener
+fn schedule_throttle_flush(
+    throttled: &ThrottledEventsRef,
+    event_channel: &mpsc::UnboundedSender<UiEvent>,
+) {
+    let mut state = throttled.borrow_mut();
+    if state.flush_scheduled {
+        return;
+    }
+    let deadline = match state.events.next_deadline() {
+        Some(deadline) => deadline,
+        None => return,
+    };
+
+    let flush = Closure::once_into_js({
+        let throttled = throttled.clone();
+        let event_channel = event_channel.clone();
+        move || {
+            let ready = {
+                let mut state = throttled.borrow_mut();
+                state.flush_scheduled = false;
+                state.events.flush(now())
+            };
+            for (_, _, ui_event) in ready {
+                let _ = event_channel.unbounded_send(ui_event);
+            }
+
+            // Other listeners might still be holding events back
+            schedule_throttle_flush(&throttled, &event_channel);
+        }
+    });
+
+    let timeout = (deadline - now()).max(0.0).ceil() as i32;
+    state.flush_scheduled = web_sys::window()
+        .expect("should have access to the Window")
+        .set_timeout_with_callback_and_timeout_and_arguments_0(flush.unchecked_ref(), timeout)
+        .is_ok();
+}
+
+/// The current time in milliseconds, on the same clock as `Event.timeStamp`
+fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or(f64::INFINITY)
+}
+
 fn make_composition_event(event: &Event) -> Rc<CompositionData> {
     let evt: &web_sys::CompositionEvent = event.dyn_ref().unwrap();
     Rc::new(CompositionData {
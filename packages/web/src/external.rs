@@ -0,0 +1,171 @@
+//! Tracking for attributes that code outside of Dioxus changed on nodes the renderer manages.
+//!
+//! Libraries like animation frameworks or date pickers often write attributes directly to the DOM. When the
+//! VirtualDom later diffs that attribute it will overwrite whatever the library wrote. With an
+//! [`ExternalAttributePolicy`] set in the [`crate::Config`], the renderer watches the root with a `MutationObserver`
+//! and either leaves those attributes alone or warns about the conflict.
+
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, MutationObserver, MutationObserverInit, MutationRecord, Node};
+
+use std::{cell::RefCell, rc::Rc};
+
+/// What the renderer should do when the VirtualDom sets an attribute that was changed outside of Dioxus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalAttributePolicy {
+    /// Keep the value the external code wrote and skip the update from the VirtualDom
+    Respect,
+
+    /// Apply the update from the VirtualDom, but log a warning about the conflicting write
+    Warn,
+}
+
+/// The attributes that were changed outside of the renderer, keyed by the node they were changed on
+pub(crate) struct ExternalAttributes<N> {
+    policy: ExternalAttributePolicy,
+    changed: Vec<(N, String)>,
+}
+
+impl<N: PartialEq> ExternalAttributes<N> {
+    pub(crate) fn new(policy: ExternalAttributePolicy) -> Self {
+        Self {
+            policy,
+            changed: Vec::new(),
+        }
+    }
+
+    /// Remember that something other than the renderer changed an attribute
+    pub(crate) fn record(&mut self, node: N, name: String) {
+        if !self.is_changed(&node, &name) {
+            self.changed.push((node, name));
+        }
+    }
+
+    /// Check if an attribute was changed outside of the renderer
+    pub(crate) fn is_changed(&self, node: &N, name: &str) -> bool {
+        self.changed.iter().any(|(n, a)| n == node && a == name)
+    }
+
+    /// Decide if the renderer should apply an attribute update from the VirtualDom
+    pub(crate) fn should_set(&mut self, node: &N, name: &str) -> bool {
+        if !self.is_changed(node, name) {
+            return true;
+        }
+        match self.policy {
+            ExternalAttributePolicy::Respect => false,
+            ExternalAttributePolicy::Warn => {
+                log::warn!(
+                    "The attribute `{}` was changed outside of Dioxus and is being overwritten by the VirtualDom",
+                    name
+                );
+                // The VirtualDom owns the attribute again until something else changes it
+                self.changed.retain(|(n, a)| !(n == node && a == name));
+                true
+            }
+        }
+    }
+
+    /// Drop everything recorded for the nodes of a subtree that was removed from the DOM
+    pub(crate) fn forget(&mut self, removed: impl Fn(&N) -> bool) {
+        self.changed.retain(|(n, _)| !removed(n));
+    }
+}
+
+pub(crate) type ExternalAttributesRef = Rc<RefCell<ExternalAttributes<Node>>>;
+
+/// Watches the root of the app for attribute changes
+pub(crate) struct ExternalAttributeObserver {
+    pub(crate) attributes: ExternalAttributesRef,
+    observer: MutationObserver,
+}
+
+impl ExternalAttributeObserver {
+    pub(crate) fn new(root: &Element, policy: ExternalAttributePolicy) -> Self {
+        let attributes: ExternalAttributesRef =
+            Rc::new(RefCell::new(ExternalAttributes::new(policy)));
+
+        let callback: Closure<dyn FnMut(js_sys::Array)> = Closure::wrap(Box::new({
+            let attributes = attributes.clone();
+            move |records: js_sys::Array| record_all(&attributes, records)
+        }));
+        let observer = MutationObserver::new(callback.as_ref().unchecked_ref())
+            .expect("MutationObserver should be supported");
+        callback.forget();
+
+        let mut init = MutationObserverInit::new();
+        init.attributes(true).subtree(true);
+        observer
+            .observe_with_options(root, &init)
+            .expect("the root should be observable");
+
+        Self {
+            attributes,
+            observer,
+        }
+    }
+
+    /// Record any external changes the observer has queued up but not delivered yet
+    ///
+    /// This must be called before the renderer writes to the DOM so external changes aren't mistaken for our own
+    pub(crate) fn collect_external(&self) {
+        record_all(&self.attributes, self.observer.take_records());
+    }
+
+    /// Throw away the records caused by the renderer's own writes
+    pub(crate) fn discard_own(&self) {
+        self.observer.take_records();
+    }
+}
+
+fn record_all(attributes: &ExternalAttributesRef, records: js_sys::Array) {
+    let mut attributes = attributes.borrow_mut();
+    for record in records.iter() {
+        let record: MutationRecord = record.unchecked_into();
+        if let (Some(target), Some(name)) = (record.target(), record.attribute_name()) {
+            attributes.record(target, name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Nodes are mocked with plain ids so the bookkeeping can be tested outside of a browser
+    fn external(policy: ExternalAttributePolicy) -> ExternalAttributes<u32> {
+        let mut attributes = ExternalAttributes::new(policy);
+        attributes.record(1, "class".to_string());
+        attributes
+    }
+
+    #[test]
+    fn respect_skips_externally_changed_attributes() {
+        let mut attributes = external(ExternalAttributePolicy::Respect);
+
+        assert!(!attributes.should_set(&1, "class"));
+        assert!(!attributes.should_set(&1, "class"));
+        assert!(attributes.should_set(&1, "style"));
+        assert!(attributes.should_set(&2, "class"));
+    }
+
+    #[test]
+    fn warn_overwrites_externally_changed_attributes() {
+        let mut attributes = external(ExternalAttributePolicy::Warn);
+
+        assert!(attributes.should_set(&1, "class"));
+        assert!(!attributes.is_changed(&1, "class"));
+    }
+
+    #[test]
+    fn removed_nodes_are_forgotten() {
+        let mut attributes = external(ExternalAttributePolicy::Respect);
+        attributes.record(2, "style".to_string());
+        attributes.record(3, "style".to_string());
+
+        // Nodes 1 and 2 are removed together
+        attributes.forget(|node| *node <= 2);
+        assert!(attributes.should_set(&1, "class"));
+        assert!(attributes.should_set(&2, "style"));
+        assert!(!attributes.should_set(&3, "style"));
+    }
+}
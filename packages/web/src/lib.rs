@@ -54,28 +54,27 @@
 //     - Do DOM work in the next requestAnimationFrame callback
 
 pub use crate::cfg::Config;
-use dioxus_core::{Element, Scope, VirtualDom};
+pub use crate::external::ExternalAttributePolicy;
+use dioxus_core::{Element, RenderTarget, Scope, VirtualDom, WorkStatus};
 use futures_util::{
     future::{select, Either},
     pin_mut, FutureExt, StreamExt,
 };
+use std::time::Duration;
 
 mod cache;
 mod cfg;
 mod dom;
 #[cfg(feature = "eval")]
 mod eval;
+mod external;
 #[cfg(feature = "file_engine")]
 mod file_engine;
 #[cfg(all(feature = "hot_reload", debug_assertions))]
 mod hot_reload;
 #[cfg(feature = "hydrate")]
 mod rehydrate;
-
-// Currently disabled since it actually slows down immediate rendering
-// todo: only schedule non-immediate renders through ric/raf
-// mod ric_raf;
-// mod rehydrate;
+mod ric_raf;
 
 /// Launch the VirtualDOM given a root component and a configuration.
 ///
@@ -171,7 +170,9 @@ pub fn launch_with_props<T: 'static>(
 pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_props: T, cfg: Config) {
     log::info!("Starting up");
 
-    let mut dom = VirtualDom::new_with_props(root, root_props);
+    let mut dom = VirtualDom::new_with_props(root, root_props)
+        .with_render_target(render_target())
+        .with_deferred_idle_tasks(true);
 
     #[cfg(feature = "eval")]
     {
@@ -180,6 +181,17 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         eval::init_eval(cx);
     }
 
+    // Let hooks that revalidate on focus know when the window regains focus, once the first one is used
+    dom.base_scope()
+        .provide_context(dioxus_hooks::WindowFocus::new(dom::listen_for_window_focus));
+
+    // Keep the navigation hooks in sync with the browser history
+    dom.base_scope().provide_context(dom::browser_navigation());
+
+    // Let hooks with timeouts wait on the browser's clock
+    dom.base_scope()
+        .provide_context(dioxus_hooks::Timer::new(dom::BrowserTimer));
+
     #[cfg(feature = "panic_hook")]
     if cfg.default_panic_hook {
         console_error_panic_hook::set_once();
@@ -202,6 +214,7 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     #[cfg(not(feature = "hydrate"))]
     let should_hydrate = false;
 
+    let mutation_budget = cfg.mutation_budget;
     let mut websys_dom = dom::WebsysDom::new(cfg, tx);
 
     log::info!("rebuilding app");
@@ -215,7 +228,7 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
             let templates = dom.rebuild().templates;
             websys_dom.load_templates(&templates);
 
-            if let Err(err) = websys_dom.rehydrate(&dom) {
+            if let Err(err) = websys_dom.rehydrate(&mut dom) {
                 log::error!(
                     "Rehydration failed {:?}. Rebuild DOM into element from scratch",
                     &err
@@ -237,16 +250,30 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
 
     // the mutations come back with nothing - we need to actually mount them
     websys_dom.mount();
+    dom.run_layout_effects();
+
+    let mut work_loop = ric_raf::RafLoop::new();
 
     loop {
         log::trace!("waiting for work");
 
         // if virtualdom has nothing, wait for it to have something before requesting idle time
         // if there is work then this future resolves immediately.
-        let (mut res, template) = {
+        let idle_tasks = dom.has_idle_tasks();
+        let (mut res, template, idle) = {
             let work = dom.wait_for_work().fuse();
             pin_mut!(work);
 
+            // Idle tasks run when the browser has spare time, unless other work or an event comes first
+            let idle_time = async {
+                match idle_tasks {
+                    true => _ = work_loop.wait_for_idle_time().await,
+                    false => futures_util::future::pending().await,
+                }
+            };
+            pin_mut!(idle_time);
+            let work = select(work, idle_time).map(|res| matches!(res, Either::Right(_)));
+
             #[cfg(all(feature = "hot_reload", debug_assertions))]
             // futures_util::select! {
             //     _ = work => (None, None),
@@ -256,17 +283,21 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
             //     evt = rx.next() =>
             // }
             match select(work, select(hotreload_rx.next(), rx.next())).await {
-                Either::Left((_, _)) => (None, None),
-                Either::Right((Either::Left((new_template, _)), _)) => (None, new_template),
-                Either::Right((Either::Right((evt, _)), _)) => (evt, None),
+                Either::Left((idle, _)) => (None, None, idle),
+                Either::Right((Either::Left((new_template, _)), _)) => (None, new_template, false),
+                Either::Right((Either::Right((evt, _)), _)) => (evt, None, false),
             }
             #[cfg(not(all(feature = "hot_reload", debug_assertions)))]
             match select(work, rx.next()).await {
-                Either::Left((_, _)) => (None, None),
-                Either::Right((evt, _)) => (evt, None),
+                Either::Left((idle, _)) => (None, None, idle),
+                Either::Right((evt, _)) => (evt, None, false),
             }
         };
 
+        if idle {
+            dom.process_idle_tasks();
+        }
+
         if let Some(template) = template {
             dom.replace_template(template);
         }
@@ -274,28 +305,58 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         // Dequeue all of the events from the channel in send order
         // todo: we should re-order these if possible
         while let Some(evt) = res {
+            websys_dom.restore_controlled_after(&evt);
             dom.handle_event(evt.name.as_str(), evt.data, evt.element, evt.bubbles);
             res = rx.try_next().transpose().unwrap().ok();
         }
 
-        // Todo: This is currently disabled because it has a negative impact on response times for events but it could be re-enabled for tasks
-        // Jank free rendering
-        //
-        // 1. wait for the browser to give us "idle" time
-        // 2. During idle time, diff the dom
-        // 3. Stop diffing if the deadline is exceded
-        // 4. Wait for the animation frame to patch the dom
+        // Batch everything that happens before the next animation frame into a single flush, unless a task in the
+        // immediate lane needs its changes on screen right away
+        dom.process_events();
+        if !dom.has_immediate_work() {
+            work_loop.wait_for_visible_raf().await;
+
+            while let Ok(Some(evt)) = rx.try_next() {
+                websys_dom.restore_controlled_after(&evt);
+                dom.handle_event(evt.name.as_str(), evt.data, evt.element, evt.bubbles);
+            }
+        }
+
+        match mutation_budget {
+            // Apply a slice of the work each frame, so the browser can paint and handle input in between
+            Some(budget) => loop {
+                let (edits, status) = dom.process_with_budget(budget, Duration::MAX);
 
-        // wait for the mainthread to schedule us in
-        // let deadline = work_loop.wait_for_idle_time().await;
+                websys_dom.load_templates(&edits.templates);
+                websys_dom.apply_edits(edits.edits);
+                dom.run_layout_effects();
+
+                if status == WorkStatus::Finished {
+                    break;
+                }
+                work_loop.wait_for_visible_raf().await;
+            },
+            None => {
+                let edits = dom.render_immediate();
 
-        // run the virtualdom work phase until the frame deadline is reached
-        let edits = dom.render_immediate();
+                websys_dom.load_templates(&edits.templates);
+                websys_dom.apply_edits(edits.edits);
+                dom.run_layout_effects();
+            }
+        }
+    }
+}
 
-        // wait for the animation frame to fire so we can apply our changes
-        // work_loop.wait_for_raf().await;
+/// Pick the [`RenderTarget`] from the media the page is currently being displayed on
+fn render_target() -> RenderTarget {
+    let print = web_sys::window()
+        .and_then(|window| window.match_media("print").ok().flatten())
+        .map(|query| query.matches())
+        .unwrap_or(false);
 
-        websys_dom.load_templates(&edits.templates);
-        websys_dom.apply_edits(edits.edits);
+    if print {
+        RenderTarget::Print
+    } else {
+        RenderTarget::Screen
     }
 }
@@ -1,10 +1,11 @@
 use crate::dom::WebsysDom;
 use dioxus_core::{
-    AttributeValue, DynamicNode, ElementId, ScopeState, TemplateNode, VNode, VirtualDom,
+    AttributeValue, DynamicNode, ElementId, RenderReturn, ScopeId, ScopeState, TemplateNode, VNode,
+    VirtualDom,
 };
 use dioxus_html::event_bubbles;
 use wasm_bindgen::JsCast;
-use web_sys::{Comment, Node};
+use web_sys::{Comment, Element, Node};
 
 #[derive(Debug, Copy, Clone)]
 pub enum RehydrationError {
@@ -14,6 +15,37 @@ pub enum RehydrationError {
 }
 use RehydrationError::*;
 
+/// An element root of a template whose server HTML didn't match the client render
+struct RehydrationMismatch {
+    scope: ScopeId,
+    root: ElementId,
+}
+
+/// Collect the portals under a node in the order the client created their children, which is the order the server
+/// writes the children of portals that mount to the root in
+fn collect_portals<'a>(dom: &'a VirtualDom, vnode: &VNode, portals: &mut Vec<&'a ScopeState>) {
+    for node in vnode.dynamic_nodes_in_creation_order() {
+        match node {
+            DynamicNode::Component(comp) => {
+                if let Some(scope) = comp.mounted_scope().and_then(|id| dom.get_scope(id)) {
+                    if let RenderReturn::Ready(node) = scope.root_node() {
+                        collect_portals(dom, node, portals);
+                    }
+                    if scope.portal_mount().is_some() {
+                        portals.push(scope);
+                    }
+                }
+            }
+            DynamicNode::Fragment(nodes) => {
+                for node in *nodes {
+                    collect_portals(dom, node, portals);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 fn set_node(hydrated: &mut Vec<bool>, id: ElementId, node: Node) {
     let idx = id.0;
     if idx >= hydrated.len() {
@@ -28,7 +60,9 @@ fn set_node(hydrated: &mut Vec<bool>, id: ElementId, node: Node) {
 impl WebsysDom {
     // we're streaming in patches, but the nodes already exist
     // so we're just going to write the correct IDs to the node and load them in
-    pub fn rehydrate(&mut self, dom: &VirtualDom) -> Result<(), RehydrationError> {
+    //
+    // If part of the server HTML doesn't match the client render, only the template roots that diverged are recreated
+    pub fn rehydrate(&mut self, dom: &mut VirtualDom) -> Result<(), RehydrationError> {
         let mut root = self
             .root
             .clone()
@@ -43,6 +77,34 @@ impl WebsysDom {
 
         let mut last_node_was_static_text = false;
 
+        let mut mismatches = Vec::new();
+
+        let mut portals = Vec::new();
+        if let RenderReturn::Ready(node) = root_scope.root_node() {
+            collect_portals(dom, node, &mut portals);
+        }
+
+        // The server leaves out the children of portals that don't mount to the root, so the client has to create them
+        if portals
+            .iter()
+            .any(|scope| scope.portal_mount().map(|mount| mount.target) != Some(ElementId(0)))
+        {
+            return Err(NodeNotFound);
+        }
+
+        // The children of portals come first, since the client appends them to the root before the rest of the app
+        for scope in portals {
+            self.rehydrate_scope(
+                scope,
+                &mut root,
+                &mut hydrated,
+                dom,
+                &mut last_node_was_static_text,
+                &mut mismatches,
+            )?;
+            last_node_was_static_text = false;
+        }
+
         // Recursively rehydrate the dom from the VirtualDom
         self.rehydrate_scope(
             root_scope,
@@ -50,9 +112,19 @@ impl WebsysDom {
             &mut hydrated,
             dom,
             &mut last_node_was_static_text,
+            &mut mismatches,
         )?;
 
         self.interpreter.flush();
+
+        // Now that everything else is hydrated, recreate the roots that didn't match
+        for RehydrationMismatch { scope, root } in mismatches {
+            if let Some(edits) = dom.replace_template_root(scope, root) {
+                self.load_templates(&edits.templates);
+                self.apply_edits(edits.edits);
+            }
+        }
+
         Ok(())
     }
 
@@ -63,6 +135,7 @@ impl WebsysDom {
         hydrated: &mut Vec<bool>,
         dom: &VirtualDom,
         last_node_was_static_text: &mut bool,
+        mismatches: &mut Vec<RehydrationMismatch>,
     ) -> Result<(), RehydrationError> {
         let vnode = match scope.root_node() {
             dioxus_core::RenderReturn::Ready(ready) => ready,
@@ -72,47 +145,75 @@ impl WebsysDom {
             current_child,
             hydrated,
             dom,
+            scope,
             vnode,
             last_node_was_static_text,
+            mismatches,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn rehydrate_vnode(
         &mut self,
         current_child: &mut Result<Node, RehydrationError>,
         hydrated: &mut Vec<bool>,
         dom: &VirtualDom,
+        scope: &ScopeState,
         vnode: &VNode,
         last_node_was_static_text: &mut bool,
+        mismatches: &mut Vec<RehydrationMismatch>,
     ) -> Result<(), RehydrationError> {
         for (i, root) in vnode.template.get().roots.iter().enumerate() {
+            let root_id = *vnode.root_ids.borrow().get(i).ok_or(VNodeNotInitialized)?;
+            let root_node = current_child.clone()?;
+
             // make sure we set the root node ids even if the node is not dynamic
-            set_node(
-                hydrated,
-                *vnode.root_ids.borrow().get(i).ok_or(VNodeNotInitialized)?,
-                current_child.clone()?,
-            );
+            set_node(hydrated, root_id, root_node.clone());
 
-            self.rehydrate_template_node(
+            let result = self.rehydrate_template_node(
                 current_child,
                 hydrated,
                 dom,
+                scope,
                 vnode,
                 root,
                 last_node_was_static_text,
-            )?;
+                mismatches,
+            );
+
+            match (result, root) {
+                // If an element root diverged from the server HTML, recreate just that root once we're done hydrating
+                (Err(NodeTypeMismatch | NodeNotFound), TemplateNode::Element { tag, .. }) => {
+                    log::warn!(
+                        "Hydration mismatch in <{}> rendered by {} ({:?}). Recreating it from scratch",
+                        tag,
+                        scope.name(),
+                        scope.scope_id()
+                    );
+                    mismatches.push(RehydrationMismatch {
+                        scope: scope.scope_id(),
+                        root: root_id,
+                    });
+                    *current_child = root_node.next_sibling().ok_or(NodeNotFound);
+                    *last_node_was_static_text = false;
+                }
+                (result, _) => result?,
+            }
         }
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn rehydrate_template_node(
         &mut self,
         current_child: &mut Result<Node, RehydrationError>,
         hydrated: &mut Vec<bool>,
         dom: &VirtualDom,
+        scope: &ScopeState,
         vnode: &VNode,
         node: &TemplateNode,
         last_node_was_static_text: &mut bool,
+        mismatches: &mut Vec<RehydrationMismatch>,
     ) -> Result<(), RehydrationError> {
         log::trace!("rehydrate template node: {:?}", node);
         if let Ok(current_child) = current_child {
@@ -122,8 +223,22 @@ impl WebsysDom {
         }
         match node {
             TemplateNode::Element {
-                children, attrs, ..
+                tag,
+                children,
+                attrs,
+                ..
             } => {
+                // Make sure the server rendered the same element we did
+                let matches_tag = current_child
+                    .as_ref()
+                    .map_err(|e| *e)?
+                    .dyn_ref::<Element>()
+                    .map(|el| el.tag_name().eq_ignore_ascii_case(tag))
+                    .unwrap_or(false);
+                if !matches_tag {
+                    return Err(NodeTypeMismatch);
+                }
+
                 let mut mounted_id = None;
                 for attr in *attrs {
                     if let dioxus_core::TemplateAttribute::Dynamic { id } = attr {
@@ -132,12 +247,17 @@ impl WebsysDom {
                         let id = attribute.mounted_element();
                         mounted_id = Some(id);
                         let name = attribute.name;
-                        if let AttributeValue::Listener(_) = value {
+                        if let AttributeValue::Listener(_, flags) = value {
                             let event_name = &name[2..];
+                            self.listener_flags
+                                .borrow_mut()
+                                .insert((id, event_name.to_string()), *flags);
                             self.interpreter.new_event_listener(
                                 event_name,
                                 id.0 as u32,
                                 event_bubbles(event_name) as u8,
+                                flags.passive as u8,
+                                flags.capture as u8,
                             );
                         }
                     }
@@ -158,9 +278,11 @@ impl WebsysDom {
                             &mut children_current_child,
                             hydrated,
                             dom,
+                            scope,
                             vnode,
                             child,
                             last_node_was_static_text,
+                            mismatches,
                         )?;
                     }
                 }
@@ -187,21 +309,26 @@ impl WebsysDom {
                     current_child,
                     hydrated,
                     dom,
+                    scope,
                     &vnode.dynamic_nodes[*id],
                     last_node_was_static_text,
+                    mismatches,
                 )?;
             }
         }
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn rehydrate_dynamic_node(
         &mut self,
         current_child: &mut Result<Node, RehydrationError>,
         hydrated: &mut Vec<bool>,
         dom: &VirtualDom,
+        scope: &ScopeState,
         dynamic: &DynamicNode,
         last_node_was_static_text: &mut bool,
+        mismatches: &mut Vec<RehydrationMismatch>,
     ) -> Result<(), RehydrationError> {
         log::trace!("rehydrate dynamic node: {:?}", dynamic);
         if let Ok(current_child) = current_child {
@@ -225,16 +352,36 @@ impl WebsysDom {
                     .next_sibling()
                     .ok_or(NodeNotFound);
 
-                set_node(
-                    hydrated,
-                    id.ok_or(VNodeNotInitialized)?,
-                    current_child.clone()?,
-                );
-                *current_child = current_child
-                    .as_mut()
-                    .map_err(|e| *e)?
-                    .next_sibling()
-                    .ok_or(NodeNotFound);
+                let id = id.ok_or(VNodeNotInitialized)?;
+                let server_text = current_child.clone()?;
+                if server_text.has_type::<Comment>() {
+                    // The server rendered empty text, which the browser doesn't create a node for
+                    let client_text: Node = server_text
+                        .owner_document()
+                        .ok_or(NodeNotFound)?
+                        .create_text_node(text.value)
+                        .into();
+                    server_text
+                        .parent_node()
+                        .ok_or(NodeNotFound)?
+                        .insert_before(&client_text, Some(&server_text))
+                        .map_err(|_| NodeNotFound)?;
+                    set_node(hydrated, id, client_text);
+                } else {
+                    // Text can differ without changing the structure, so fix it in place
+                    if server_text.text_content().as_deref() != Some(text.value) {
+                        log::warn!(
+                            "Hydration mismatch in text rendered by {} ({:?}). Replacing {:?} with {:?}",
+                            scope.name(),
+                            scope.scope_id(),
+                            server_text.text_content().unwrap_or_default(),
+                            text.value
+                        );
+                        server_text.set_text_content(Some(text.value));
+                    }
+                    set_node(hydrated, id, server_text.clone());
+                    *current_child = server_text.next_sibling().ok_or(NodeNotFound);
+                }
 
                 // skip comment separator after node
                 if cfg!(debug_assertions) {
@@ -266,13 +413,27 @@ impl WebsysDom {
             }
             dioxus_core::DynamicNode::Component(comp) => {
                 let scope = comp.mounted_scope().ok_or(VNodeNotInitialized)?;
-                self.rehydrate_scope(
-                    dom.get_scope(scope).unwrap(),
-                    current_child,
-                    hydrated,
-                    dom,
-                    last_node_was_static_text,
-                )?;
+                let scope = dom.get_scope(scope).unwrap();
+                match scope.portal_mount() {
+                    // The children of a portal were hydrated at its target, only its placeholder is here
+                    Some(mount) => {
+                        set_node(hydrated, mount.anchor, current_child.clone()?);
+                        *current_child = current_child
+                            .as_mut()
+                            .map_err(|e| *e)?
+                            .next_sibling()
+                            .ok_or(NodeNotFound);
+                        *last_node_was_static_text = false;
+                    }
+                    None => self.rehydrate_scope(
+                        scope,
+                        current_child,
+                        hydrated,
+                        dom,
+                        last_node_was_static_text,
+                        mismatches,
+                    )?,
+                }
             }
             dioxus_core::DynamicNode::Fragment(fragment) => {
                 for vnode in *fragment {
@@ -280,8 +441,10 @@ impl WebsysDom {
                         current_child,
                         hydrated,
                         dom,
+                        scope,
                         vnode,
                         last_node_was_static_text,
+                        mismatches,
                     )?;
                 }
             }
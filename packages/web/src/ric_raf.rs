@@ -8,7 +8,6 @@
 //! if RIC is available.
 
 use futures_util::StreamExt;
-use gloo_timers::future::TimeoutFuture;
 use js_sys::Function;
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 use web_sys::{window, Window};
@@ -64,12 +63,16 @@ impl RafLoop {
             ric_closure,
         }
     }
-    /// waits for some idle time and returns a timeout future that expires after the idle time has passed
-    pub async fn wait_for_idle_time(&mut self) -> TimeoutFuture {
+    /// waits for some idle time and returns how many milliseconds of it are left
+    ///
+    /// Browsers without requestIdleCallback get a timer from the polyfill instead
+    pub async fn wait_for_idle_time(&mut self) -> u32 {
+        // a wait that was cancelled by other work can leave its callback behind
+        while let Ok(Some(_)) = self.ric_receiver.try_next() {}
+
         let ric_fn = self.ric_closure.as_ref().dyn_ref::<Function>().unwrap();
         let _cb_id: u32 = self.window.request_idle_callback(ric_fn).unwrap();
-        let deadline = self.ric_receiver.next().await.unwrap();
-        TimeoutFuture::new(deadline)
+        self.ric_receiver.next().await.unwrap()
     }
 
     pub async fn wait_for_raf(&mut self) {
@@ -77,4 +80,18 @@ impl RafLoop {
         let _id: i32 = self.window.request_animation_frame(raf_fn).unwrap();
         self.raf_receiver.next().await.unwrap();
     }
+
+    /// waits for the next animation frame, unless the page is hidden
+    ///
+    /// Browsers stop running animation frames in background tabs, which would hold back every render until the tab is
+    /// shown again
+    pub async fn wait_for_visible_raf(&mut self) {
+        let hidden = self
+            .window
+            .document()
+            .map_or(false, |document| document.hidden());
+        if !hidden {
+            self.wait_for_raf().await;
+        }
+    }
 }
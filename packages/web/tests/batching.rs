@@ -0,0 +1,70 @@
+//! Changes made before the next animation frame are flushed together
+use dioxus::prelude::*;
+use dioxus_web::Config;
+use std::cell::Cell;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::window;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static RENDERS: Cell<usize> = Cell::new(0);
+}
+
+#[wasm_bindgen_test]
+async fn state_changes_in_one_frame_flush_once() {
+    fn app(cx: Scope) -> Element {
+        RENDERS.with(|renders| renders.set(renders.get() + 1));
+        let count = use_state(cx, || 0);
+
+        cx.render(rsx! {
+            p { "{count}" }
+            button { onclick: move |_| count += 1, "increment" }
+        })
+    }
+
+    let document = window().unwrap().document().unwrap();
+    document
+        .body()
+        .unwrap()
+        .set_inner_html("<div id='main'></div>");
+    wasm_bindgen_futures::spawn_local(dioxus_web::run_with_props(app, (), Config::new()));
+    sleep(50).await;
+    assert_eq!(RENDERS.with(Cell::get), 1);
+
+    let main = document.get_element_by_id("main").unwrap();
+    let button = main
+        .last_element_child()
+        .unwrap()
+        .dyn_into::<web_sys::HtmlElement>()
+        .unwrap();
+
+    // Let the app handle each click before the next one, without giving the browser a chance to paint
+    for _ in 0..3 {
+        button.click();
+        next_tick().await;
+    }
+    sleep(50).await;
+
+    assert_eq!(RENDERS.with(Cell::get), 2);
+    let paragraph = main.first_element_child().unwrap();
+    assert_eq!(paragraph.text_content().as_deref(), Some("3"));
+}
+
+/// Resolve after the microtasks that are already queued, which is where the app handles its events
+async fn next_tick() {
+    let tick = js_sys::Promise::resolve(&wasm_bindgen::JsValue::NULL);
+    wasm_bindgen_futures::JsFuture::from(tick).await.unwrap();
+}
+
+/// Resolve after `ms` milliseconds, so the app can process its work
+async fn sleep(ms: i32) {
+    let sleep = js_sys::Promise::new(&mut |resolve, _| {
+        window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(sleep).await.unwrap();
+}
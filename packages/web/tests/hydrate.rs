@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 use dioxus_web::Config;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::wasm_bindgen_test;
 use web_sys::window;
 
@@ -62,3 +63,150 @@ fn rehydrates() {
 
     dioxus_web::launch_cfg(app, Config::new().hydrate(true));
 }
+
+#[wasm_bindgen_test]
+async fn recovers_from_mismatched_roots() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            h1 { "title" }
+            div { "client" }
+        })
+    }
+
+    // The server rendered a different element for the second root
+    let document = window().unwrap().document().unwrap();
+    document
+        .body()
+        .unwrap()
+        .set_inner_html("<div id='main'><h1>title</h1><span>server</span></div>");
+    let main = document.get_element_by_id("main").unwrap();
+    let server_title = main.first_child().unwrap();
+
+    wasm_bindgen_futures::spawn_local(dioxus_web::run_with_props(
+        app,
+        (),
+        Config::new().hydrate(true),
+    ));
+
+    // Give the app a chance to hydrate
+    sleep(50).await;
+
+    // The mismatched root is recreated, but the matching one is still the node from the server
+    let recreated = main.last_element_child().unwrap();
+    assert_eq!(recreated.tag_name(), "DIV");
+    assert_eq!(recreated.text_content().as_deref(), Some("client"));
+    assert_eq!(main.child_element_count(), 2);
+    assert!(main
+        .first_child()
+        .unwrap()
+        .is_same_node(Some(&server_title)));
+}
+
+#[wasm_bindgen_test]
+async fn hydration_reuses_server_nodes() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 0);
+        cx.render(rsx! {
+            h1 { "title" }
+            p { "{count}" }
+            button { onclick: move |_| count += 1, "increment" }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let _ = dom.rebuild();
+    let out = dioxus_ssr::pre_render(&dom);
+
+    let document = window().unwrap().document().unwrap();
+    document
+        .body()
+        .unwrap()
+        .set_inner_html(&format!("<div id='main'>{out}</div>"));
+    let main = document.get_element_by_id("main").unwrap();
+    let server_nodes: Vec<_> =
+        std::iter::successors(main.first_element_child(), |el| el.next_element_sibling()).collect();
+    assert_eq!(server_nodes.len(), 3);
+
+    wasm_bindgen_futures::spawn_local(dioxus_web::run_with_props(
+        app,
+        (),
+        Config::new().hydrate(true),
+    ));
+    sleep(50).await;
+
+    // Nothing was created, every element is still the one from the server
+    assert_eq!(main.child_element_count(), 3);
+    let mut hydrated = main.first_element_child();
+    for server_node in &server_nodes {
+        let node = hydrated.unwrap();
+        assert!(node.is_same_node(Some(server_node)));
+        hydrated = node.next_element_sibling();
+    }
+
+    // The listener was attached to the server's button, and the server's text node is updated in place
+    server_nodes[2]
+        .clone()
+        .dyn_into::<web_sys::HtmlElement>()
+        .unwrap()
+        .click();
+    sleep(50).await;
+    assert_eq!(server_nodes[1].text_content().as_deref(), Some("1"));
+    let paragraph = main.first_element_child().unwrap().next_element_sibling();
+    assert!(paragraph.unwrap().is_same_node(Some(&server_nodes[1])));
+}
+
+#[wasm_bindgen_test]
+async fn hydration_fixes_dynamic_text() {
+    thread_local! {
+        static ON_SERVER: std::cell::Cell<bool> = std::cell::Cell::new(true);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let (label, note) = match ON_SERVER.with(|s| s.get()) {
+            true => ("server", ""),
+            false => ("client", "note"),
+        };
+        cx.render(rsx! {
+            p { "{label}" }
+            span { "{note}" }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let _ = dom.rebuild();
+    let out = dioxus_ssr::pre_render(&dom);
+    ON_SERVER.with(|s| s.set(false));
+
+    let document = window().unwrap().document().unwrap();
+    document
+        .body()
+        .unwrap()
+        .set_inner_html(&format!("<div id='main'>{out}</div>"));
+    let main = document.get_element_by_id("main").unwrap();
+    let server_paragraph = main.first_element_child().unwrap();
+
+    wasm_bindgen_futures::spawn_local(dioxus_web::run_with_props(
+        app,
+        (),
+        Config::new().hydrate(true),
+    ));
+    sleep(50).await;
+
+    // The text is fixed in place, and the empty text from the server gets a node
+    let paragraph = main.first_element_child().unwrap();
+    assert!(paragraph.is_same_node(Some(&server_paragraph)));
+    assert_eq!(paragraph.text_content().as_deref(), Some("client"));
+    let span = paragraph.next_element_sibling().unwrap();
+    assert_eq!(span.text_content().as_deref(), Some("note"));
+}
+
+/// Resolve after `ms` milliseconds, so the app can process its work
+async fn sleep(ms: i32) {
+    let sleep = js_sys::Promise::new(&mut |resolve, _| {
+        window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(sleep).await.unwrap();
+}
@@ -59,10 +59,36 @@ test('input value', async ({ page }) => {
   await page.goto('http://localhost:8080');
 
   // Expect the page to contain the input with the value.
-  const input = page.locator('input');
+  const input = page.locator('input.hello-input');
   await expect(input).toHaveValue('hello input');
 });
 
+test('controlled value', async ({ page }) => {
+  await page.goto('http://localhost:8080');
+
+  // The textarea only takes digits, so the letters are removed after they are typed.
+  const textarea = page.locator('textarea.digits-textarea');
+  await textarea.type('1a2');
+  await expect(textarea).toHaveValue('12');
+});
+
+test('controlled checkbox', async ({ page }) => {
+  await page.goto('http://localhost:8080');
+
+  // The checkbox is rendered unchecked and its state never changes, so it is unchecked again after a click.
+  const checkbox = page.locator('input.locked-checkbox');
+  await checkbox.click();
+  await expect(checkbox).not.toBeChecked();
+});
+
+test('autofocus', async ({ page }) => {
+  await page.goto('http://localhost:8080');
+
+  // The input is created after the page loaded, and still gets focus.
+  await page.locator('button.show-search-button').click();
+  await expect(page.locator('input.search-input')).toBeFocused();
+});
+
 test('style', async ({ page }) => {
   await page.goto('http://localhost:8080');
 
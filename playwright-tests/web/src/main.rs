@@ -5,6 +5,8 @@ use dioxus::prelude::*;
 fn app(cx: Scope) -> Element {
     let mut num = use_state(cx, || 0);
     let eval_result = use_state(cx, String::new);
+    let digits = use_state(cx, String::new);
+    let show_search = use_state(cx, || false);
 
     let eval_provider = dioxus_html::prelude::use_eval(cx);
 
@@ -32,8 +34,33 @@ fn app(cx: Scope) -> Element {
             dangerous_inner_html: "<p>hello dangerous inner html</p>",
         }
         input {
+            class: "hello-input",
             value: "hello input",
         }
+        textarea {
+            class: "digits-textarea",
+            value: "{digits}",
+            oninput: move |evt| {
+                if evt.value.chars().all(|c| c.is_ascii_digit()) {
+                    digits.set(evt.value.clone());
+                }
+            },
+        }
+        input {
+            class: "locked-checkbox",
+            r#type: "checkbox",
+            checked: false,
+            // The checkbox never changes state, so clicking it has no effect
+            onchange: move |_| {},
+        }
+        button {
+            class: "show-search-button",
+            onclick: move |_| show_search.set(true),
+            "Search"
+        }
+        show_search.then(|| rsx! {
+            input { class: "search-input", autofocus: "true" }
+        })
         div {
             class: "style-div",
             color: "red",